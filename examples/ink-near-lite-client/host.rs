@@ -0,0 +1,29 @@
+//! # Contract host functions
+//!
+//! Contracts can't call the host functions of the runtime: SHA-256 goes through the hashing the
+//! contracts pallet exposes, and signatures are verified in WASM.
+
+use ink_env::hash::Sha2x256;
+use near_primitives_wasm::{HostFunctions, PublicKey, Signature};
+
+pub struct ContractHostFunctions;
+
+impl HostFunctions for ContractHostFunctions {
+	fn sha256(data: &[u8]) -> [u8; 32] {
+		let mut output = [0; 32];
+		ink_env::hash_bytes::<Sha2x256>(data, &mut output);
+		output
+	}
+
+	/// `verify_strict` rejects the non canonical encodings that `Signature::verify` rejects.
+	fn verify(signature: Signature, data: impl AsRef<[u8]>, public_key: PublicKey) -> bool {
+		let public_key = match ed25519_dalek::PublicKey::from_bytes(&public_key.0) {
+			Ok(public_key) => public_key,
+			Err(_) => return false,
+		};
+		match ed25519_dalek::Signature::from_bytes(signature.as_bytes()) {
+			Ok(signature) => public_key.verify_strict(data.as_ref(), &signature).is_ok(),
+			Err(_) => false,
+		}
+	}
+}
@@ -0,0 +1,269 @@
+//! # Near lite client contract
+//!
+//! An ink! contract embedding the light client, to show it runs in contracts pallet environments:
+//! `update` moves the head forward with a NEAR header and `verify` checks outcome proofs against
+//! it, while the owner sets the checkpoint the client starts from. The state of the client is
+//! kept in the storage of the contract, see [`storage::ContractStorage`].
+//!
+//! Headers and proofs are passed borsh encoded, as the NEAR RPC returns them once converted, and
+//! decoded within the default `DecodeLimits`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod host;
+mod storage;
+
+pub use host::ContractHostFunctions;
+
+#[ink_lang::contract]
+mod near_lite_client_contract {
+	use ink_prelude::vec::Vec;
+	use near_lite_client::{
+		DecodeLimits, LightClient, LightClientBuilder, NearLiteClientError, TrustedCheckpoint,
+		MIN_RETAINED_EPOCHS,
+	};
+	use near_primitives_wasm::CryptoHash;
+
+	use crate::{
+		host::ContractHostFunctions,
+		storage::{self, ContractStorage},
+	};
+
+	#[ink(storage)]
+	pub struct NearLiteClient {
+		/// Allowed to reset the client to a new checkpoint.
+		owner: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct HeadUpdated {
+		height: u64,
+		block_hash: [u8; 32],
+	}
+
+	#[ink(event)]
+	pub struct CheckpointSet {
+		height: u64,
+		block_hash: [u8; 32],
+	}
+
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+	pub enum Error {
+		/// Only the owner can reset the client.
+		NotOwner,
+		/// An argument could not be decoded, or is over the decoding limits.
+		Malformed,
+		/// The header is not ahead of the head.
+		StaleHeader,
+		/// The header or the outcome proof failed verification.
+		VerificationFailed,
+		/// The state of the client is invalid.
+		InvalidState,
+	}
+
+	impl From<NearLiteClientError> for Error {
+		// the string errors of `near-merkle-proofs` still convert into `ProofVerificationError`
+		#[allow(deprecated)]
+		fn from(err: NearLiteClientError) -> Self {
+			match err {
+				NearLiteClientError::Borsh(_) |
+				NearLiteClientError::Conversion(_) |
+				NearLiteClientError::Serialization { .. } |
+				NearLiteClientError::CapacityExceeded(_) => Self::Malformed,
+				NearLiteClientError::StaleHeader { .. } => Self::StaleHeader,
+				NearLiteClientError::Verification(_) |
+				NearLiteClientError::ProofVerificationError(_) |
+				NearLiteClientError::InvalidLiteBlock(_) |
+				NearLiteClientError::SignatureVerification(_) |
+				NearLiteClientError::MerkleRootMismatch { .. } => Self::VerificationFailed,
+				_ => Self::InvalidState,
+			}
+		}
+	}
+
+	pub type Result<T> = core::result::Result<T, Error>;
+
+	impl NearLiteClient {
+		/// Starts the client from a borsh encoded `LightClientBlockView`, trusted as is.
+		#[ink(constructor)]
+		pub fn new(checkpoint: Vec<u8>) -> Self {
+			let contract = Self { owner: Self::env().caller() };
+			contract.set_checkpoint(&checkpoint).expect("the checkpoint is a valid header");
+			contract
+		}
+
+		/// Drops the state of the client and restarts it from a borsh encoded
+		/// `LightClientBlockView`.
+		#[ink(message)]
+		pub fn reset(&mut self, checkpoint: Vec<u8>) -> Result<()> {
+			if self.env().caller() != self.owner {
+				return Err(Error::NotOwner)
+			}
+			self.set_checkpoint(&checkpoint)
+		}
+
+		/// Validates a borsh encoded `LightClientBlockView` and makes it the head. Submitting the
+		/// current head again succeeds without an event.
+		#[ink(message)]
+		pub fn update(&mut self, block_view: Vec<u8>) -> Result<()> {
+			let block_view = DecodeLimits::default().decode_block_view(&block_view)?;
+			let mut client = Self::client()?;
+			let summary = client.validate_and_update_head(block_view)?;
+			if !summary.already_validated {
+				self.env().emit_event(HeadUpdated {
+					height: summary.new_height.0,
+					block_hash: client.head_hash().0,
+				});
+			}
+			Ok(())
+		}
+
+		/// Verifies a borsh encoded `OutcomeProof` and its borsh encoded outcome root proof
+		/// against `expected_block_outcome_root`.
+		#[ink(message)]
+		pub fn verify(
+			&self,
+			outcome_proof: Vec<u8>,
+			outcome_root_proof: Vec<u8>,
+			expected_block_outcome_root: [u8; 32],
+		) -> Result<()> {
+			let limits = DecodeLimits::default();
+			let outcome_proof = limits.decode_outcome_proof(&outcome_proof)?;
+			let outcome_root_proof = limits.decode_merkle_path(&outcome_root_proof)?;
+			Self::client()?.validate_transaction(
+				&outcome_proof,
+				&outcome_root_proof,
+				CryptoHash(expected_block_outcome_root),
+			)?;
+			Ok(())
+		}
+
+		/// Height and hash of the head.
+		#[ink(message)]
+		pub fn head(&self) -> Result<(u64, [u8; 32])> {
+			let client = Self::client()?;
+			Ok((client.head_height().0, client.head_hash().0))
+		}
+
+		fn set_checkpoint(&self, checkpoint: &[u8]) -> Result<()> {
+			let checkpoint = DecodeLimits::default().decode_block_view(checkpoint)?;
+			storage::clear()?;
+			let client = Self::builder().checkpoint(TrustedCheckpoint(checkpoint)).build()?;
+			self.env().emit_event(CheckpointSet {
+				height: client.head_height().0,
+				block_hash: client.head_hash().0,
+			});
+			Ok(())
+		}
+
+		/// The client, resumed from the storage of the contract.
+		fn client() -> Result<LightClient<ContractHostFunctions>> {
+			Ok(Self::builder().build()?)
+		}
+
+		fn builder() -> LightClientBuilder<ContractHostFunctions> {
+			LightClientBuilder::new()
+				.storage(ContractStorage)
+				.retained_epochs(MIN_RETAINED_EPOCHS)
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use borsh::BorshSerialize;
+		use ink_lang as ink;
+		use near_lite_client::Sha256HostFunctions;
+		use near_lite_client_test_utils::{OutcomeProofBuilder, SignedHeader, ValidatorSet};
+		use near_primitives_wasm::{
+			BlockHeight, Direction, EpochId, ExecutionOutcomeView, MerklePathItem,
+		};
+
+		fn signed_header() -> SignedHeader {
+			let validators = ValidatorSet::new(4);
+			let epochs = [1, 2, 3].map(|epoch| EpochId(CryptoHash([epoch; 32])));
+			SignedHeader::new::<Sha256HostFunctions>(
+				BlockHeight(10),
+				epochs,
+				&validators,
+				&validators,
+			)
+			.unwrap()
+		}
+
+		fn encode<V: BorshSerialize>(value: &V) -> Vec<u8> {
+			value.try_to_vec().unwrap()
+		}
+
+		#[ink::test]
+		fn test_update() {
+			let chain = signed_header();
+			let mut contract = NearLiteClient::new(encode(&chain.head));
+			assert_eq!(contract.head().unwrap().0, 10);
+
+			assert_eq!(contract.update(encode(&chain.block_view)), Ok(()));
+			assert_eq!(
+				contract.head(),
+				Ok((11, chain.block_view.current_block_hash::<Sha256HostFunctions>().0))
+			);
+			assert_eq!(contract.update(encode(&chain.head)), Err(Error::StaleHeader));
+			assert_eq!(contract.update(vec![0; 3]), Err(Error::Malformed));
+			assert_eq!(ink_env::test::recorded_events().count(), 2);
+
+			let mut forged = chain.block_view.clone();
+			forged.inner_lite.height = BlockHeight(12);
+			assert_eq!(contract.update(encode(&forged)), Err(Error::VerificationFailed));
+		}
+
+		#[ink::test]
+		fn test_verify() {
+			let contract = NearLiteClient::new(encode(&signed_header().head));
+			let path = (0..4)
+				.map(|index| MerklePathItem {
+					hash: CryptoHash([index; 32]),
+					direction: if index % 2 == 0 { Direction::Left } else { Direction::Right },
+				})
+				.collect::<Vec<_>>();
+			let outcome = ExecutionOutcomeView {
+				logs: Vec::new(),
+				receipt_ids: vec![CryptoHash([5; 32])],
+				gas_burnt: 1,
+				tokens_burnt: 1,
+				executor_id: "bridge.near".into(),
+				status: Vec::new(),
+			};
+			let (outcome_proof, outcome_root_proof, block_outcome_root) =
+				OutcomeProofBuilder::new(CryptoHash([7; 32]), outcome)
+					.proof(path.clone())
+					.outcome_root_proof(path)
+					.build::<Sha256HostFunctions>()
+					.unwrap();
+			let outcome = &outcome_proof.outcome;
+			// `OutcomeProof` is only decoded by the client, its fields are encoded in order here
+			let encoded_outcome_proof = encode(&(
+				&outcome_proof.proof,
+				outcome_proof.block_hash,
+				outcome_proof.id,
+				&outcome.logs,
+				&outcome.receipt_ids,
+				outcome.gas_burnt,
+				outcome.tokens_burnt,
+				&outcome.executor_id,
+				&outcome.status,
+			));
+
+			assert_eq!(
+				contract.verify(
+					encoded_outcome_proof.clone(),
+					encode(&outcome_root_proof),
+					block_outcome_root.0
+				),
+				Ok(())
+			);
+			assert_eq!(
+				contract.verify(encoded_outcome_proof, encode(&outcome_root_proof), [0; 32]),
+				Err(Error::VerificationFailed)
+			);
+		}
+	}
+}
@@ -0,0 +1,111 @@
+//! # Contract storage
+//!
+//! The state of the light client lives in the storage of the contract, under fixed keys: the head
+//! and the block producers of every retained epoch borsh encoded, and the epoch ids in the order
+//! they were inserted, which decides which epoch is dropped first. The client keeps
+//! `MIN_RETAINED_EPOCHS` epochs, which bounds the storage of the contract.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use ink_env::hash::{Blake2x256, CryptoHash as _};
+use ink_prelude::{format, vec::Vec};
+use ink_primitives::Key;
+use near_lite_client::{
+	LightClientStorage, LiteClientResult, NearLiteClientError, ValidatorStakeView,
+};
+use near_primitives_wasm::{EpochId, LightClientBlockView};
+
+const HEAD: &[u8] = b"near-lite-client:head";
+const EPOCHS: &[u8] = b"near-lite-client:epochs";
+const BLOCK_PRODUCERS: &[u8] = b"near-lite-client:block-producers";
+
+#[derive(Default)]
+pub struct ContractStorage;
+
+impl LightClientStorage for ContractStorage {
+	fn head(&self) -> LiteClientResult<Option<LightClientBlockView>> {
+		read::<Vec<u8>>(&key(HEAD, &[]))?.map(|encoded| decode(&encoded)).transpose()
+	}
+
+	fn set_head(&mut self, head: &LightClientBlockView) -> LiteClientResult<()> {
+		ink_env::set_contract_storage(&key(HEAD, &[]), &encode(head)?);
+		Ok(())
+	}
+
+	fn epoch_block_producers(&self) -> LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>> {
+		epochs()?
+			.into_iter()
+			.map(|epoch_id| {
+				let encoded =
+					read::<Vec<u8>>(&block_producers_key(&epoch_id))?.ok_or_else(|| {
+						NearLiteClientError::Serialization {
+							type_name: core::any::type_name::<Vec<ValidatorStakeView>>(),
+							reason: "missing from storage".into(),
+						}
+					})?;
+				Ok((EpochId(near_primitives_wasm::CryptoHash(epoch_id)), decode(&encoded)?))
+			})
+			.collect()
+	}
+
+	fn insert_epoch_block_producers(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<()> {
+		let epoch_id = epoch_id.0 .0;
+		ink_env::set_contract_storage(&block_producers_key(&epoch_id), &encode(block_producers)?);
+		let mut epochs = epochs()?;
+		epochs.retain(|known| *known != epoch_id);
+		epochs.push(epoch_id);
+		ink_env::set_contract_storage(&key(EPOCHS, &[]), &epochs);
+		Ok(())
+	}
+
+	fn remove_epoch_block_producers(&mut self, epoch_id: &EpochId) -> LiteClientResult<()> {
+		let epoch_id = epoch_id.0 .0;
+		ink_env::clear_contract_storage(&block_producers_key(&epoch_id));
+		let mut epochs = epochs()?;
+		epochs.retain(|known| *known != epoch_id);
+		ink_env::set_contract_storage(&key(EPOCHS, &[]), &epochs);
+		Ok(())
+	}
+}
+
+/// Empties the storage of the client, to start over from a new checkpoint.
+pub fn clear() -> LiteClientResult<()> {
+	for epoch_id in epochs()? {
+		ink_env::clear_contract_storage(&block_producers_key(&epoch_id));
+	}
+	ink_env::clear_contract_storage(&key(EPOCHS, &[]));
+	ink_env::clear_contract_storage(&key(HEAD, &[]));
+	Ok(())
+}
+
+fn epochs() -> LiteClientResult<Vec<[u8; 32]>> {
+	Ok(read(&key(EPOCHS, &[]))?.unwrap_or_default())
+}
+
+fn block_producers_key(epoch_id: &[u8; 32]) -> Key {
+	key(BLOCK_PRODUCERS, epoch_id)
+}
+
+fn key(prefix: &[u8], suffix: &[u8]) -> Key {
+	let mut output = [0; 32];
+	Blake2x256::hash(&[prefix, suffix].concat(), &mut output);
+	Key::from(output)
+}
+
+fn read<V: scale::Decode>(key: &Key) -> LiteClientResult<Option<V>> {
+	ink_env::get_contract_storage(key).map_err(|err| NearLiteClientError::Serialization {
+		type_name: core::any::type_name::<V>(),
+		reason: format!("{:?}", err),
+	})
+}
+
+fn encode<V: BorshSerialize + ?Sized>(value: &V) -> LiteClientResult<Vec<u8>> {
+	value.try_to_vec().map_err(NearLiteClientError::Borsh)
+}
+
+fn decode<V: BorshDeserialize>(encoded: &[u8]) -> LiteClientResult<V> {
+	V::try_from_slice(encoded).map_err(NearLiteClientError::Borsh)
+}
@@ -0,0 +1,50 @@
+//! Relays what the [`NearSource`] serves to the [`MockParachain`].
+
+use borsh::BorshSerialize;
+use near_lite_client::{LiteClientResult, OutcomeRoots};
+
+use crate::{MockParachain, NearSource, OutcomeId};
+
+pub struct Bridge<S> {
+	source: S,
+	parachain: MockParachain,
+}
+
+impl<S: NearSource> Bridge<S> {
+	pub fn new(source: S, parachain: MockParachain) -> Self {
+		Self { source, parachain }
+	}
+
+	pub fn source(&self) -> &S {
+		&self.source
+	}
+
+	pub fn parachain(&self) -> &MockParachain {
+		&self.parachain
+	}
+
+	/// Submits the light client blocks following the head of the parachain, one by one, until
+	/// the source has nothing newer. Returns how many blocks became the head; the first rejected
+	/// block stops the relay with its error.
+	pub async fn relay_headers(&mut self) -> LiteClientResult<usize> {
+		let mut relayed = 0;
+		while let Some(block_view) =
+			self.source.next_light_client_block(self.parachain.head_hash()).await?
+		{
+			if block_view.inner_lite.height <= self.parachain.head_height() {
+				break
+			}
+			log::info!("relaying block at height={}", block_view.inner_lite.height);
+			self.parachain.submit_header(&block_view.try_to_vec()?)?;
+			relayed += 1;
+		}
+		Ok(relayed)
+	}
+
+	/// Fetches the proof of `outcome` up to the head of the parachain and submits it. The outcome
+	/// must be in a block before the head, [`Bridge::relay_headers`] first.
+	pub async fn relay_outcome(&mut self, outcome: &OutcomeId) -> LiteClientResult<OutcomeRoots> {
+		let proof = self.source.outcome_proof(outcome, self.parachain.head_hash()).await?;
+		self.parachain.submit_outcome_proof(&proof)
+	}
+}
@@ -0,0 +1,24 @@
+//! # End-to-end bridge
+//!
+//! The pieces of a NEAR to parachain bridge, wired together through the public API of
+//! `near-lite-client` only:
+//! 1. a [`NearSource`] serves light client blocks and outcome proofs, e.g. a
+//!    [`NearRpcClient`](near_lite_client::rpc::NearRpcClient) over JSON-RPC
+//! 2. the [`Bridge`] relays the blocks to the parachain, borsh encoded like the relayer does, then
+//!    the proofs of the outcomes it is asked for, up to the head of the parachain
+//! 3. the [`MockParachain`] validates both with its own light client, like the pallet does, and
+//!    deposits an event for every successful call
+//!
+//! `tests/bridge.rs` runs it against a `MockNearChain`, the binary against a NEAR node:
+//!
+//! ```text
+//! cargo run -p near-bridge-example -- <checkpoint hash> <transaction hash> <sender> [rpc url]
+//! ```
+
+pub mod bridge;
+pub mod parachain;
+pub mod source;
+
+pub use bridge::Bridge;
+pub use parachain::{Event, MockParachain};
+pub use source::{NearSource, OutcomeId};
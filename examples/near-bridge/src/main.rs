@@ -0,0 +1,50 @@
+//! `near-bridge-example <checkpoint hash> <transaction hash> <sender> [rpc url]`: trusts the light
+//! client block following the checkpoint hash, relays every block after it to a mock parachain and
+//! proves the outcome of the transaction there.
+
+use std::{env, io, process, str::FromStr};
+
+use near_bridge_example::{Bridge, MockParachain, OutcomeId};
+use near_lite_client::{
+	rpc::{NearRpcClient, MAINNET_RPC_URL},
+	CryptoHash, TrustedCheckpoint,
+};
+
+const USAGE: &str =
+	"usage: near-bridge-example <checkpoint hash> <transaction hash> <sender> [rpc url]";
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+	env_logger::init();
+	let args = env::args().skip(1).collect::<Vec<_>>();
+	if !(3..=4).contains(&args.len()) {
+		eprintln!("{}", USAGE);
+		process::exit(2);
+	}
+	let checkpoint_hash = CryptoHash::from_str(&args[0]).map_err(other)?;
+	let outcome = OutcomeId::Transaction {
+		transaction_hash: CryptoHash::from_str(&args[1]).map_err(other)?,
+		sender_id: args[2].clone(),
+	};
+	let rpc = NearRpcClient::new(args.get(3).map_or(MAINNET_RPC_URL, String::as_str));
+
+	let checkpoint = rpc
+		.next_light_client_block(checkpoint_hash)
+		.await
+		.map_err(other)?
+		.ok_or_else(|| other(format!("no light client block follows {}", checkpoint_hash)))?;
+	let parachain = MockParachain::new(TrustedCheckpoint(checkpoint)).map_err(other)?;
+	let mut bridge = Bridge::new(rpc, parachain);
+	let relayed = bridge.relay_headers().await.map_err(other)?;
+	log::info!("relayed {} blocks, head at height={}", relayed, bridge.parachain().head_height());
+	bridge.relay_outcome(&outcome).await.map_err(other)?;
+
+	for event in bridge.parachain().events() {
+		println!("{:?}", event);
+	}
+	Ok(())
+}
+
+fn other(err: impl std::fmt::Debug) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, format!("{:?}", err))
+}
@@ -0,0 +1,79 @@
+//! A parachain reduced to what the bridge needs from it: a light client fed by extrinsics.
+
+use near_lite_client::{
+	rainbow::FullOutcomeProof, BlockHeight, CryptoHash, DecodeLimits, LightClient,
+	LiteClientResult, OutcomeRoots, TrustedCheckpoint,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+	HeadUpdated {
+		height: BlockHeight,
+		block_hash: CryptoHash,
+		epoch_changed: bool,
+	},
+	/// The outcome `id` of the block `block_hash` is final, the bridge can act on it.
+	OutcomeVerified {
+		id: CryptoHash,
+		block_hash: CryptoHash,
+	},
+}
+
+pub struct MockParachain {
+	client: LightClient,
+	decode_limits: DecodeLimits,
+	events: Vec<Event>,
+}
+
+impl MockParachain {
+	/// A parachain whose light client was initialized with `checkpoint` by governance.
+	pub fn new(checkpoint: TrustedCheckpoint) -> LiteClientResult<Self> {
+		Ok(Self {
+			client: LightClient::with_checkpoint(checkpoint)?,
+			decode_limits: DecodeLimits::default(),
+			events: Vec::new(),
+		})
+	}
+
+	pub fn head_hash(&self) -> CryptoHash {
+		self.client.head_hash()
+	}
+
+	pub fn head_height(&self) -> BlockHeight {
+		self.client.head_height()
+	}
+
+	/// Events deposited so far, oldest first.
+	pub fn events(&self) -> &[Event] {
+		&self.events
+	}
+
+	/// Validates a borsh encoded `LightClientBlockView` and makes it the head, as the pallet's
+	/// `submit_header` does.
+	pub fn submit_header(&mut self, block_view: &[u8]) -> LiteClientResult<()> {
+		let block_view = self.decode_limits.decode_block_view(block_view)?;
+		let summary = self.client.validate_and_update_head(block_view)?;
+		if !summary.already_validated {
+			self.events.push(Event::HeadUpdated {
+				height: summary.new_height,
+				block_hash: self.client.head_hash(),
+				epoch_changed: summary.epoch_changed,
+			});
+		}
+		Ok(())
+	}
+
+	/// Verifies `proof` against the `block_merkle_root` of the head, so that only outcomes of
+	/// blocks before a validated header are accepted.
+	pub fn submit_outcome_proof(
+		&mut self,
+		proof: &FullOutcomeProof,
+	) -> LiteClientResult<OutcomeRoots> {
+		let roots = self.client.validate_full_outcome_proof(proof)?;
+		self.events.push(Event::OutcomeVerified {
+			id: proof.outcome_proof.id,
+			block_hash: proof.outcome_proof.block_hash,
+		});
+		Ok(roots)
+	}
+}
@@ -0,0 +1,65 @@
+//! Where the bridge gets its data from.
+
+use futures::{future::LocalBoxFuture, FutureExt};
+use near_lite_client::{
+	rainbow::FullOutcomeProof,
+	rpc::{LightClientProofRequest, NearRpcClient},
+	CryptoHash, LightClientBlockView, LiteClientResult,
+};
+
+/// An outcome to prove, identified the way `EXPERIMENTAL_light_client_proof` expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutcomeId {
+	Transaction { transaction_hash: CryptoHash, sender_id: String },
+	Receipt { receipt_id: CryptoHash, receiver_id: String },
+}
+
+impl OutcomeId {
+	/// The id of the outcome, as in its proof.
+	pub fn id(&self) -> CryptoHash {
+		match self {
+			Self::Transaction { transaction_hash, .. } => *transaction_hash,
+			Self::Receipt { receipt_id, .. } => *receipt_id,
+		}
+	}
+}
+
+pub trait NearSource {
+	/// The light client block following `last_known_hash`, or `None` if there is nothing newer
+	/// yet.
+	fn next_light_client_block(
+		&self,
+		last_known_hash: CryptoHash,
+	) -> LocalBoxFuture<'_, LiteClientResult<Option<LightClientBlockView>>>;
+
+	/// The proof of `outcome`, up to the `block_merkle_root` of `light_client_head`.
+	fn outcome_proof<'a>(
+		&'a self,
+		outcome: &'a OutcomeId,
+		light_client_head: CryptoHash,
+	) -> LocalBoxFuture<'a, LiteClientResult<FullOutcomeProof>>;
+}
+
+impl NearSource for NearRpcClient {
+	fn next_light_client_block(
+		&self,
+		last_known_hash: CryptoHash,
+	) -> LocalBoxFuture<'_, LiteClientResult<Option<LightClientBlockView>>> {
+		NearRpcClient::next_light_client_block(self, last_known_hash).boxed_local()
+	}
+
+	fn outcome_proof<'a>(
+		&'a self,
+		outcome: &'a OutcomeId,
+		light_client_head: CryptoHash,
+	) -> LocalBoxFuture<'a, LiteClientResult<FullOutcomeProof>> {
+		let request = match outcome {
+			OutcomeId::Transaction { transaction_hash, sender_id } =>
+				LightClientProofRequest::transaction(*transaction_hash, sender_id.as_str()),
+			OutcomeId::Receipt { receipt_id, receiver_id } =>
+				LightClientProofRequest::receipt(*receipt_id, receiver_id.as_str()),
+		}
+		.light_client_head(light_client_head);
+		async move { Ok(self.light_client_proof(&request).await?.into()) }.boxed_local()
+	}
+}
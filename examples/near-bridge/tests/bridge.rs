@@ -0,0 +1,165 @@
+//! Runs the bridge end to end against a [`MockNearChain`], through the public API only.
+
+use std::collections::HashMap;
+
+use borsh::BorshSerialize;
+use futures::{
+	future::{self, LocalBoxFuture},
+	FutureExt,
+};
+use near_bridge_example::{Bridge, Event, MockParachain, NearSource, OutcomeId};
+use near_lite_client::{
+	rainbow::FullOutcomeProof, BlockHeight, CryptoHash, LightClientBlockView, LiteClientResult,
+	NearLiteClientError, Sha256HostFunctions,
+};
+use near_lite_client_test_utils::{Fault, MockNearChain};
+use near_primitives_wasm::ExecutionOutcomeView;
+
+type H = Sha256HostFunctions;
+
+/// Serves the headers of a [`MockNearChain`] as a NEAR node would, and the proofs of the
+/// outcomes registered with [`MockSource::outcome`].
+struct MockSource {
+	chain: MockNearChain,
+	/// height and index of every outcome
+	outcomes: HashMap<CryptoHash, (BlockHeight, usize)>,
+}
+
+impl MockSource {
+	/// Appends a block with one outcome per id.
+	fn block_with_outcomes(&mut self, ids: &[CryptoHash]) -> LightClientBlockView {
+		let block_view =
+			self.chain.block_with_outcomes(ids.iter().map(|id| (*id, transfer())).collect());
+		for (index, id) in ids.iter().enumerate() {
+			self.outcomes.insert(*id, (block_view.inner_lite.height, index));
+		}
+		block_view
+	}
+
+	fn height_of(&self, block_hash: CryptoHash) -> Option<BlockHeight> {
+		let checkpoint = self.chain.checkpoint().0;
+		std::iter::once(&checkpoint)
+			.chain(self.chain.headers())
+			.find(|header| header.current_block_hash::<H>() == block_hash)
+			.map(|header| header.inner_lite.height)
+	}
+}
+
+impl NearSource for MockSource {
+	fn next_light_client_block(
+		&self,
+		last_known_hash: CryptoHash,
+	) -> LocalBoxFuture<'_, LiteClientResult<Option<LightClientBlockView>>> {
+		let next = self.height_of(last_known_hash).and_then(|height| {
+			self.chain
+				.headers()
+				.iter()
+				.find(|header| header.inner_lite.height > height)
+				.cloned()
+		});
+		future::ready(Ok(next)).boxed_local()
+	}
+
+	fn outcome_proof<'a>(
+		&'a self,
+		outcome: &'a OutcomeId,
+		light_client_head: CryptoHash,
+	) -> LocalBoxFuture<'a, LiteClientResult<FullOutcomeProof>> {
+		let proof = self.outcomes.get(&outcome.id()).and_then(|(height, index)| {
+			self.chain
+				.full_outcome_proof(*height, *index, self.height_of(light_client_head)?)
+		});
+		future::ready(proof.ok_or_else(|| NearLiteClientError::Rpc("unknown outcome".into())))
+			.boxed_local()
+	}
+}
+
+fn transfer() -> ExecutionOutcomeView {
+	ExecutionOutcomeView {
+		logs: vec![],
+		receipt_ids: vec![CryptoHash([9; 32])],
+		gas_burnt: 223_182_562_500,
+		tokens_burnt: 22_318_256_250_000_000_000,
+		executor_id: "alice.near".into(),
+		status: vec![3, 9, 9, 9],
+	}
+}
+
+fn transaction(transaction_hash: CryptoHash) -> OutcomeId {
+	OutcomeId::Transaction { transaction_hash, sender_id: "alice.near".into() }
+}
+
+/// A chain with a block of outcomes in its second epoch, and the bridge at its checkpoint.
+fn setup() -> (Bridge<MockSource>, [CryptoHash; 2]) {
+	let ids = [CryptoHash([1; 32]), CryptoHash([2; 32])];
+	let mut source = MockSource { chain: MockNearChain::new(4), outcomes: HashMap::new() };
+	source.chain.epoch_change();
+	source.chain.skip_heights(3);
+	source.block_with_outcomes(&ids);
+	source.chain.block();
+	source.chain.epoch_change();
+	let parachain = MockParachain::new(source.chain.checkpoint()).unwrap();
+	(Bridge::new(source, parachain), ids)
+}
+
+#[tokio::test]
+async fn relays_headers_then_outcomes() {
+	let (mut bridge, ids) = setup();
+	assert_eq!(bridge.relay_headers().await.unwrap(), 4);
+	assert_eq!(bridge.relay_headers().await.unwrap(), 0);
+	let head = bridge.source().chain.head().clone();
+	assert_eq!(bridge.parachain().head_hash(), head.current_block_hash::<H>());
+
+	for id in ids {
+		let roots = bridge.relay_outcome(&transaction(id)).await.unwrap();
+		assert_eq!(
+			roots.block_outcome_root,
+			bridge.source().chain.headers()[1].inner_lite.outcome_root
+		);
+	}
+
+	let events = bridge.parachain().events();
+	let epoch_changes = events
+		.iter()
+		.filter(|event| matches!(event, Event::HeadUpdated { epoch_changed: true, .. }))
+		.count();
+	assert_eq!(epoch_changes, 2);
+	assert_eq!(
+		events[4..],
+		ids.map(|id| Event::OutcomeVerified {
+			id,
+			block_hash: bridge.source().chain.headers()[1].current_block_hash::<H>(),
+		})
+	);
+	assert!(bridge.relay_outcome(&transaction(CryptoHash([3; 32]))).await.is_err());
+}
+
+#[test]
+fn parachain_only_trusts_what_it_validated() {
+	let (bridge, _) = setup();
+	let chain = &bridge.source().chain;
+	let outcome_height = chain.headers()[1].inner_lite.height;
+	let proof = chain
+		.full_outcome_proof(outcome_height, 0, chain.head().inner_lite.height)
+		.unwrap();
+
+	// the checkpoint commits to none of the blocks after it
+	let mut parachain = MockParachain::new(chain.checkpoint()).unwrap();
+	assert!(matches!(
+		parachain.submit_outcome_proof(&proof),
+		Err(NearLiteClientError::MerkleRootMismatch { .. })
+	));
+
+	for header in chain.headers() {
+		parachain.submit_header(&header.try_to_vec().unwrap()).unwrap();
+	}
+	let forged_header = chain.faulty_block(Fault::InsufficientStake);
+	assert!(parachain.submit_header(&forged_header.try_to_vec().unwrap()).is_err());
+	assert!(parachain.submit_header(&[0; 8]).is_err());
+	let mut forged_proof = proof.clone();
+	forged_proof.outcome_proof.outcome.tokens_burnt += 1;
+	assert!(parachain.submit_outcome_proof(&forged_proof).is_err());
+
+	parachain.submit_outcome_proof(&proof).unwrap();
+	assert_eq!(parachain.events().len(), chain.headers().len() + 1);
+}
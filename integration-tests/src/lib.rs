@@ -4,7 +4,8 @@ extern crate alloc;
 use alloc::collections::BTreeMap;
 
 use near_lite_client::{
-	CryptoHash, LightClientBlockView, NearLiteClientTrait, TrustedCheckpoint, ValidatorStakeView,
+	EpochId, LightClientBlockView, LiteClientResult, NearLiteClientTrait, TrustedCheckpoint,
+	ValidatorStakeView,
 };
 use near_primitives_wasm::HostFunctions;
 
@@ -19,7 +20,7 @@ impl HostFunctions for NearHostFunctions {
 
 pub struct LightClient {
 	pub head: LightClientBlockView,
-	pub epoch_block_producers: BTreeMap<CryptoHash, Vec<ValidatorStakeView>>,
+	pub epoch_block_producers: BTreeMap<EpochId, Vec<ValidatorStakeView>>,
 }
 
 impl LightClient {
@@ -32,9 +33,12 @@ impl LightClient {
 	}
 }
 impl NearLiteClientTrait for LightClient {
-	fn new_from_checkpoint(checkpoint: TrustedCheckpoint, _heights_to_track: usize) -> Self {
+	fn new_from_checkpoint(
+		checkpoint: TrustedCheckpoint,
+		_heights_to_track: usize,
+	) -> LiteClientResult<Self> {
 		let block_view = checkpoint.0;
-		Self {
+		Ok(Self {
 			epoch_block_producers: [(
 				block_view.inner_lite.next_epoch_id,
 				block_view.next_bps.clone().unwrap(),
@@ -42,10 +46,10 @@ impl NearLiteClientTrait for LightClient {
 			.into_iter()
 			.collect::<BTreeMap<_, _>>(),
 			head: block_view,
-		}
+		})
 	}
 
 	fn current_block_height(&self) -> u64 {
-		self.head.inner_lite.height
+		self.head.inner_lite.height.0
 	}
 }
@@ -0,0 +1,93 @@
+//! ## Header replay
+//!
+//! Replays a recording of consecutive light client blocks, as returned by
+//! `next_light_client_block`, through `LightClient::validate_and_update_head`. The first block is
+//! trusted as the checkpoint, every following one has to be accepted, and the client has to
+//! follow every epoch change of the recording.
+//!
+//! The recording is `fixtures/headers.jsonl`, one block per line, or the file `HEADERS_RECORDING`
+//! points to, so that long recordings (e.g. of mainnet, across many epochs) can be replayed
+//! without being committed.
+
+use std::{env, fs::File, io::BufReader};
+
+use near_lite_client::{
+	replay::{HeaderFormat, HeaderReader},
+	LightClient, LightClientBlockView, Sha256HostFunctions, TrustedCheckpoint,
+};
+
+type H = Sha256HostFunctions;
+
+fn recording() -> Vec<LightClientBlockView> {
+	let path = env::var("HEADERS_RECORDING").unwrap_or_else(|_| {
+		concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/headers.jsonl").to_owned()
+	});
+	let file = File::open(&path).unwrap_or_else(|err| panic!("cannot open {}: {}", path, err));
+	let headers = HeaderReader::new(BufReader::new(file), HeaderFormat::Json)
+		.collect::<Result<Vec<_>, _>>()
+		.unwrap();
+	assert!(headers.len() >= 2, "{} needs a checkpoint and a header to replay", path);
+	headers
+}
+
+fn client(checkpoint: &LightClientBlockView) -> LightClient<H> {
+	LightClient::with_checkpoint(TrustedCheckpoint(checkpoint.clone())).unwrap()
+}
+
+#[test]
+fn replay_recorded_headers() {
+	let headers = recording();
+	let mut client = client(&headers[0]);
+	let mut epoch_changes = 0;
+	for pair in headers.windows(2) {
+		let (previous, block_view) = (&pair[0], &pair[1]);
+		let height = block_view.inner_lite.height;
+		let summary = client
+			.validate_and_update_head(block_view.clone())
+			.unwrap_or_else(|err| panic!("header at height {} was rejected: {:?}", height, err));
+
+		let epoch_changed = block_view.inner_lite.epoch_id != previous.inner_lite.epoch_id;
+		assert_eq!(summary.new_height, height);
+		assert_eq!(summary.epoch_changed, epoch_changed, "at height {}", height);
+		if epoch_changed {
+			assert_eq!(
+				block_view.inner_lite.epoch_id, previous.inner_lite.next_epoch_id,
+				"the recording skips an epoch at height {}",
+				height
+			);
+			epoch_changes += 1;
+		}
+		assert_eq!(client.head_hash(), block_view.current_block_hash::<H>());
+		assert!(
+			client.epoch_block_producers(&block_view.inner_lite.next_epoch_id).is_some(),
+			"the block producers of the next epoch are unknown at height {}",
+			height
+		);
+	}
+	assert!(epoch_changes > 0, "the recording never changes epoch");
+}
+
+#[test]
+fn tampered_epoch_change_is_rejected() {
+	let headers = recording();
+	let change = headers
+		.windows(2)
+		.position(|pair| pair[0].inner_lite.epoch_id != pair[1].inner_lite.epoch_id)
+		.expect("the recording never changes epoch") +
+		1;
+	let mut client = client(&headers[0]);
+	for block_view in &headers[1..change] {
+		client.validate_and_update_head(block_view.clone()).unwrap();
+	}
+
+	let mut tampered = headers[change].clone();
+	match tampered.next_bps.as_mut().and_then(|next_bps| next_bps.first_mut()) {
+		Some(block_producer) => *block_producer.stake_mut() += 1,
+		None => panic!("the first header of an epoch announces the block producers of the next"),
+	}
+	let head_hash = client.head_hash();
+	assert!(client.validate_and_update_head(tampered).is_err());
+	assert_eq!(client.head_hash(), head_hash);
+
+	assert!(client.validate_and_update_head(headers[change].clone()).unwrap().epoch_changed);
+}
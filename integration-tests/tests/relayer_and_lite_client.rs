@@ -9,7 +9,6 @@ use near_lite_relayer::{
 	state::LightClientState,
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
 use near_lite_client::prelude::*;
 
 /// ## Both Relayer and Lite Client
@@ -43,15 +42,14 @@ async fn both_relayer_and_lite_client() {
 	let light_client_block_view =
 		blockchain_connector.get_light_client_block_view(block_hash_past_epoch).unwrap();
 
-	let serialized_block_view = light_client_block_view.try_to_vec().unwrap();
 	let block_view_for_lite_client =
-		BorshDeserialize::try_from_slice(&serialized_block_view).unwrap();
+		LightClientBlockView::try_from(light_client_block_view.clone()).unwrap();
 
 	// TODO: assert that we're on a past epoch
 	let _lite_client_relayer = LightClientState::new_from_checkpoint(light_client_block_view);
 
 	let trusted_checkpoint = TrustedCheckpoint(block_view_for_lite_client);
-	let mut lite_client = LightClient::new_from_checkpoint(trusted_checkpoint, 10);
+	let mut lite_client = LightClient::new_from_checkpoint(trusted_checkpoint, 10).unwrap();
 
 	let (tx, mut rx) = tokio::sync::mpsc::channel(1);
 
@@ -76,11 +74,10 @@ async fn both_relayer_and_lite_client() {
 					near_light_client_block_view.inner_lite.height
 				);
 
-				let light_client_block_view = LightClientBlockView::try_from_slice(
-					near_light_client_block_view.try_to_vec().unwrap().as_ref(),
-				)
-				.unwrap();
-				if light_client_block_view.inner_lite.height <= lite_client.current_block_height() {
+				let light_client_block_view =
+					LightClientBlockView::try_from(near_light_client_block_view).unwrap();
+				if light_client_block_view.inner_lite.height.0 <= lite_client.current_block_height()
+				{
 					log::info!("block has not yet been updated");
 					continue;
 				}
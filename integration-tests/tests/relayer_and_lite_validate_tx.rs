@@ -3,9 +3,10 @@ use near_lite_relayer::blockchain_connector::{BlockchainConnector, NearNetwork};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use near_lite_client::{
-	validate_transaction, CryptoHash, MerklePath, NearLiteClientTrait, OutcomeProof,
-	TrustedCheckpoint,
+	validate_transaction, CryptoHash, LightClientBlockView, MerklePath, NearLiteClientTrait,
+	OutcomeProof, TrustedCheckpoint,
 };
+use near_primitives_wasm::MerklePathItem;
 
 /// ## Both Relayer and Lite Client - testing tx validation
 ///
@@ -26,12 +27,11 @@ async fn both_relayer_and_lite_client_validate_tx() {
 		.get_light_client_block_view(almost_last_block_hash)
 		.unwrap();
 
-	let serialized_block_view = light_client_block_view.try_to_vec().unwrap();
 	let block_view_for_lite_client =
-		BorshDeserialize::try_from_slice(&serialized_block_view).unwrap();
+		LightClientBlockView::try_from(light_client_block_view).unwrap();
 
 	let trusted_checkpoint = TrustedCheckpoint(block_view_for_lite_client);
-	let _ = LightClient::new_from_checkpoint(trusted_checkpoint, 10);
+	let _ = LightClient::new_from_checkpoint(trusted_checkpoint, 10).unwrap();
 	// find a transaction in a block that has been validated
 	let mut height = almost_latest_height - 500;
 
@@ -46,21 +46,15 @@ async fn both_relayer_and_lite_client_validate_tx() {
 					.get_light_client_proof_transaction(almost_last_block_hash, tx_hash, sender_id)
 					.unwrap();
 
-				let expected_block_outcome_root = CryptoHash::try_from_slice(
-					tx_light_client_proof
-						.block_header_lite
-						.inner_lite
-						.outcome_root
-						.try_to_vec()
-						.unwrap()
-						.as_ref(),
-				)
-				.unwrap();
+				let expected_block_outcome_root = CryptoHash::from(
+					tx_light_client_proof.block_header_lite.inner_lite.outcome_root,
+				);
 
-				let outcome_root_proof = MerklePath::try_from_slice(
-					tx_light_client_proof.outcome_root_proof.try_to_vec().unwrap().as_ref(),
-				)
-				.unwrap();
+				let outcome_root_proof: MerklePath = tx_light_client_proof
+					.outcome_root_proof
+					.into_iter()
+					.map(MerklePathItem::from)
+					.collect();
 
 				let outcome_proof = OutcomeProof::try_from_slice(
 					tx_light_client_proof.outcome_proof.try_to_vec().unwrap().as_ref(),
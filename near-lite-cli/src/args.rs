@@ -0,0 +1,177 @@
+//! Command line of `near-lite verify`.
+
+use std::{path::PathBuf, str::FromStr};
+
+use near_lite_client::{rpc::MAINNET_RPC_URL, CryptoHash};
+
+pub const USAGE: &str = "\
+usage: near-lite verify (--checkpoint <FILE> | --checkpoint-hash <HASH>)
+                        [--block <FILE> | --fetch-block]
+                        [--proof <FILE> | --transaction <HASH> --sender <ACCOUNT>
+                                        | --receipt <ID> --receiver <ACCOUNT>]
+                        [--rpc <URL>]
+
+Files hold the JSON of the RPC results, or whole JSON-RPC responses:
+  --checkpoint       a `next_light_client_block` result, trusted as it is
+  --checkpoint-hash  trusts the light client block following this block, fetched over RPC
+  --block            a `next_light_client_block` result, validated against the checkpoint
+  --fetch-block      fetches the light client block following the checkpoint
+  --proof            an `EXPERIMENTAL_light_client_proof` result
+  --transaction      fetches the proof of a transaction, up to the head
+  --receipt          fetches the proof of a receipt, up to the head
+  --rpc              NEAR JSON-RPC endpoint, mainnet by default";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointSource {
+	File(PathBuf),
+	/// the light client block following this one
+	Hash(CryptoHash),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockSource {
+	File(PathBuf),
+	/// the light client block following the checkpoint
+	Rpc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofSource {
+	File(PathBuf),
+	Transaction { transaction_hash: CryptoHash, sender_id: String },
+	Receipt { receipt_id: CryptoHash, receiver_id: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyArgs {
+	pub checkpoint: CheckpointSource,
+	pub block: Option<BlockSource>,
+	pub proof: Option<ProofSource>,
+	pub rpc_url: String,
+}
+
+impl VerifyArgs {
+	/// Parses the arguments following the program name.
+	pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+		let mut args = args.into_iter();
+		match args.next().as_deref() {
+			Some("verify") => {},
+			Some(command) => return Err(format!("unknown command `{}`", command)),
+			None => return Err("missing command".into()),
+		}
+
+		let mut checkpoint = None;
+		let mut block = None;
+		let mut fetch_block = false;
+		let mut proof_file = None;
+		let mut transaction = None;
+		let mut sender = None;
+		let mut receipt = None;
+		let mut receiver = None;
+		let mut rpc_url = None;
+		while let Some(flag) = args.next() {
+			let mut value = || args.next().ok_or_else(|| format!("missing value of `{}`", flag));
+			match flag.as_str() {
+				"--checkpoint" => checkpoint = Some(CheckpointSource::File(value()?.into())),
+				"--checkpoint-hash" =>
+					checkpoint = Some(CheckpointSource::Hash(parse_hash(&flag, &value()?)?)),
+				"--block" => block = Some(BlockSource::File(value()?.into())),
+				"--fetch-block" => fetch_block = true,
+				"--proof" => proof_file = Some(PathBuf::from(value()?)),
+				"--transaction" => transaction = Some(parse_hash(&flag, &value()?)?),
+				"--sender" => sender = Some(value()?),
+				"--receipt" => receipt = Some(parse_hash(&flag, &value()?)?),
+				"--receiver" => receiver = Some(value()?),
+				"--rpc" => rpc_url = Some(value()?),
+				_ => return Err(format!("unknown argument `{}`", flag)),
+			}
+		}
+
+		let checkpoint =
+			checkpoint.ok_or("one of `--checkpoint` or `--checkpoint-hash` is required")?;
+		let block = match (block, fetch_block) {
+			(Some(_), true) => return Err("`--block` and `--fetch-block` are exclusive".into()),
+			(block, false) => block,
+			(None, true) => Some(BlockSource::Rpc),
+		};
+		let proof = match (proof_file, transaction, sender, receipt, receiver) {
+			(None, None, None, None, None) => None,
+			(Some(path), None, None, None, None) => Some(ProofSource::File(path)),
+			(None, Some(transaction_hash), Some(sender_id), None, None) =>
+				Some(ProofSource::Transaction { transaction_hash, sender_id }),
+			(None, None, None, Some(receipt_id), Some(receiver_id)) =>
+				Some(ProofSource::Receipt { receipt_id, receiver_id }),
+			_ =>
+				return Err("a proof is either `--proof`, `--transaction` with `--sender` or \
+				            `--receipt` with `--receiver`"
+					.into()),
+		};
+		Ok(Self {
+			checkpoint,
+			block,
+			proof,
+			rpc_url: rpc_url.unwrap_or_else(|| MAINNET_RPC_URL.into()),
+		})
+	}
+}
+
+fn parse_hash(flag: &str, value: &str) -> Result<CryptoHash, String> {
+	CryptoHash::from_str(value).map_err(|err| format!("invalid `{}`: {:?}", flag, err))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &str) -> Result<VerifyArgs, String> {
+		VerifyArgs::parse(args.split_whitespace().map(String::from))
+	}
+
+	#[test]
+	fn test_parse_files() {
+		let args =
+			parse("verify --checkpoint head.json --block block.json --proof proof.json").unwrap();
+		assert_eq!(
+			args,
+			VerifyArgs {
+				checkpoint: CheckpointSource::File("head.json".into()),
+				block: Some(BlockSource::File("block.json".into())),
+				proof: Some(ProofSource::File("proof.json".into())),
+				rpc_url: MAINNET_RPC_URL.into(),
+			}
+		);
+	}
+
+	#[test]
+	fn test_parse_rpc_sources() {
+		let hash = CryptoHash([1; 32]).to_string();
+		let args = parse(&format!(
+			"verify --checkpoint-hash {0} --fetch-block --receipt {0} --receiver bob.near \
+			 --rpc http://127.0.0.1:3030",
+			hash
+		))
+		.unwrap();
+		assert_eq!(args.checkpoint, CheckpointSource::Hash(CryptoHash([1; 32])));
+		assert_eq!(args.block, Some(BlockSource::Rpc));
+		assert_eq!(
+			args.proof,
+			Some(ProofSource::Receipt {
+				receipt_id: CryptoHash([1; 32]),
+				receiver_id: "bob.near".into()
+			})
+		);
+		assert_eq!(args.rpc_url, "http://127.0.0.1:3030");
+
+		assert!(parse("verify").is_err());
+		assert!(parse("prove --checkpoint head.json").is_err());
+		assert!(parse("verify --checkpoint").is_err());
+		assert!(parse("verify --checkpoint-hash 0x00").is_err());
+		assert!(parse("verify --checkpoint head.json --block block.json --fetch-block").is_err());
+		assert!(parse(&format!("verify --checkpoint head.json --transaction {}", hash)).is_err());
+		assert!(parse(&format!(
+			"verify --checkpoint head.json --proof proof.json --receipt {} --receiver bob.near",
+			hash
+		))
+		.is_err());
+	}
+}
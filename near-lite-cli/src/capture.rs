@@ -0,0 +1,303 @@
+//! `near-lite capture`: snapshots RPC responses into the fixture files the test suites include.
+//!
+//! A fixture is the JSON-RPC response as the node sent it, plus a `fixture` object recording how
+//! to request it again:
+//!
+//! ```text
+//! {
+//!   "fixture": { "version": 1, "network": "testnet", "method": "...", "params": ... },
+//!   "id": "dontcare",
+//!   "jsonrpc": "2.0",
+//!   "result": { ... }
+//! }
+//! ```
+//!
+//! so that the code parsing responses reads fixtures unchanged. Fixtures are written to
+//! `<out>/<network>/<method>/<name>.json`, named after the height of the block, the id of the
+//! outcome or the hash of the block the validators were requested for.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+
+use near_lite_client::{
+	rpc::{
+		light_client_block_from_json, LightClientProofRequest, NearRpcClient, MAINNET_RPC_URL,
+		TESTNET_RPC_URL,
+	},
+	CryptoHash, Sha256HostFunctions,
+};
+use serde_json::{json, Value};
+
+use crate::verify::other;
+
+/// Version of the `fixture` object, bumped whenever its layout changes.
+pub const FIXTURE_VERSION: u32 = 1;
+
+pub const USAGE: &str = "\
+usage: near-lite capture [--network <NAME>] [--rpc <URL>] [--out <DIR>]
+                         (--block <HASH> [--follow <N>]
+                          | --transaction <HASH> --sender <ACCOUNT> --head <HASH>
+                          | --receipt <ID> --receiver <ACCOUNT> --head <HASH>
+                          | --validators <HASH>)
+
+  --network      recorded in the fixtures, `mainnet` or `testnet` select their RPC endpoint;
+                 mainnet by default
+  --rpc          NEAR JSON-RPC endpoint, required for other networks
+  --out          directory of the fixtures, `fixtures` by default
+  --block        the light client block following this block
+  --follow       also the N light client blocks following it, one after the other
+  --transaction  the proof of a transaction, up to the light client head `--head`
+  --receipt      the proof of a receipt, up to the light client head `--head`
+  --validators   the block producers of the epoch of this block, in approval order";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+	/// `1 + follow` consecutive light client blocks, the first one following `after`
+	Blocks {
+		after: CryptoHash,
+		follow: usize,
+	},
+	Transaction {
+		transaction_hash: CryptoHash,
+		sender_id: String,
+		light_client_head: CryptoHash,
+	},
+	Receipt {
+		receipt_id: CryptoHash,
+		receiver_id: String,
+		light_client_head: CryptoHash,
+	},
+	Validators {
+		block_hash: CryptoHash,
+	},
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureArgs {
+	pub network: String,
+	pub rpc_url: String,
+	pub out_dir: PathBuf,
+	pub target: Target,
+}
+
+impl CaptureArgs {
+	/// Parses the arguments following the program name.
+	pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+		let mut args = args.into_iter();
+		match args.next().as_deref() {
+			Some("capture") => {},
+			Some(command) => return Err(format!("unknown command `{}`", command)),
+			None => return Err("missing command".into()),
+		}
+
+		let mut network = None;
+		let mut rpc_url = None;
+		let mut out_dir = None;
+		let mut block = None;
+		let mut follow = None;
+		let mut transaction = None;
+		let mut sender = None;
+		let mut receipt = None;
+		let mut receiver = None;
+		let mut head = None;
+		let mut validators = None;
+		while let Some(flag) = args.next() {
+			let mut value = || args.next().ok_or_else(|| format!("missing value of `{}`", flag));
+			match flag.as_str() {
+				"--network" => network = Some(value()?),
+				"--rpc" => rpc_url = Some(value()?),
+				"--out" => out_dir = Some(PathBuf::from(value()?)),
+				"--block" => block = Some(parse_hash(&flag, &value()?)?),
+				"--follow" =>
+					follow = Some(
+						value()?.parse().map_err(|err| format!("invalid `{}`: {}", flag, err))?,
+					),
+				"--transaction" => transaction = Some(parse_hash(&flag, &value()?)?),
+				"--sender" => sender = Some(value()?),
+				"--receipt" => receipt = Some(parse_hash(&flag, &value()?)?),
+				"--receiver" => receiver = Some(value()?),
+				"--head" => head = Some(parse_hash(&flag, &value()?)?),
+				"--validators" => validators = Some(parse_hash(&flag, &value()?)?),
+				_ => return Err(format!("unknown argument `{}`", flag)),
+			}
+		}
+
+		let network = network.unwrap_or_else(|| "mainnet".into());
+		let rpc_url = match (rpc_url, network.as_str()) {
+			(Some(rpc_url), _) => rpc_url,
+			(None, "mainnet") => MAINNET_RPC_URL.into(),
+			(None, "testnet") => TESTNET_RPC_URL.into(),
+			(None, network) => return Err(format!("`--rpc` is required for `{}`", network)),
+		};
+		let target = match (block, follow, transaction, sender, receipt, receiver, head, validators)
+		{
+			(Some(after), follow, None, None, None, None, None, None) =>
+				Target::Blocks { after, follow: follow.unwrap_or(0) },
+			(None, None, Some(transaction_hash), Some(sender_id), None, None, Some(head), None) =>
+				Target::Transaction { transaction_hash, sender_id, light_client_head: head },
+			(None, None, None, None, Some(receipt_id), Some(receiver_id), Some(head), None) =>
+				Target::Receipt { receipt_id, receiver_id, light_client_head: head },
+			(None, None, None, None, None, None, None, Some(block_hash)) =>
+				Target::Validators { block_hash },
+			_ =>
+				return Err("exactly one of `--block`, `--transaction`, `--receipt` or \
+				            `--validators` is required, with its own arguments"
+					.into()),
+		};
+		Ok(Self { network, rpc_url, out_dir: out_dir.unwrap_or_else(|| "fixtures".into()), target })
+	}
+}
+
+fn parse_hash(flag: &str, value: &str) -> Result<CryptoHash, String> {
+	CryptoHash::from_str(value).map_err(|err| format!("invalid `{}`: {:?}", flag, err))
+}
+
+/// The content of a fixture file.
+pub fn fixture(network: &str, method: &str, params: Value, result: Value) -> Value {
+	json!({
+		"fixture": {
+			"version": FIXTURE_VERSION,
+			"network": network,
+			"method": method,
+			"params": params,
+		},
+		"id": "dontcare",
+		"jsonrpc": "2.0",
+		"result": result,
+	})
+}
+
+/// Requests the fixtures of `args` and writes them, returning their paths.
+pub async fn capture(args: &CaptureArgs) -> std::io::Result<Vec<PathBuf>> {
+	let rpc = NearRpcClient::new(args.rpc_url.as_str());
+	let dir = args.out_dir.join(&args.network);
+	let mut written = Vec::new();
+	match &args.target {
+		Target::Blocks { after, follow } => {
+			let mut last_known_hash = *after;
+			for _ in 0..=*follow {
+				let method = "next_light_client_block";
+				let params = json!([last_known_hash.to_string()]);
+				let result: Value = rpc.call(method, params.clone()).await.map_err(other)?;
+				// an up to date node answers with an empty object
+				if result.as_object().map_or(false, |result| result.is_empty()) {
+					break
+				}
+				let block_view = light_client_block_from_json(result.clone()).map_err(other)?;
+				let name = block_view.inner_lite.height.0.to_string();
+				let contents = fixture(&args.network, method, params, result);
+				written.push(write(&dir.join(method), &name, &contents)?);
+				last_known_hash = block_view.current_block_hash::<Sha256HostFunctions>();
+			}
+		},
+		Target::Transaction { transaction_hash, sender_id, light_client_head } => {
+			let request =
+				LightClientProofRequest::transaction(*transaction_hash, sender_id.as_str())
+					.light_client_head(*light_client_head);
+			written.push(capture_proof(&rpc, args, &request, transaction_hash).await?);
+		},
+		Target::Receipt { receipt_id, receiver_id, light_client_head } => {
+			let request = LightClientProofRequest::receipt(*receipt_id, receiver_id.as_str())
+				.light_client_head(*light_client_head);
+			written.push(capture_proof(&rpc, args, &request, receipt_id).await?);
+		},
+		Target::Validators { block_hash } => {
+			let method = "EXPERIMENTAL_validators_ordered";
+			let params = json!([block_hash.to_string()]);
+			let result: Value = rpc.call(method, params.clone()).await.map_err(other)?;
+			let contents = fixture(&args.network, method, params, result);
+			written.push(write(&dir.join(method), &block_hash.to_string(), &contents)?);
+		},
+	}
+	Ok(written)
+}
+
+async fn capture_proof(
+	rpc: &NearRpcClient,
+	args: &CaptureArgs,
+	request: &LightClientProofRequest,
+	id: &CryptoHash,
+) -> std::io::Result<PathBuf> {
+	let method = "EXPERIMENTAL_light_client_proof";
+	let params = request.to_params().map_err(other)?;
+	let result: Value = rpc.call(method, params.clone()).await.map_err(other)?;
+	let contents = fixture(&args.network, method, params, result);
+	write(&args.out_dir.join(&args.network).join(method), &id.to_string(), &contents)
+}
+
+fn write(dir: &Path, name: &str, contents: &Value) -> std::io::Result<PathBuf> {
+	fs::create_dir_all(dir)?;
+	let path = dir.join(format!("{}.json", name));
+	let json = serde_json::to_string_pretty(contents).map_err(other)?;
+	fs::write(&path, json + "\n")?;
+	Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &str) -> Result<CaptureArgs, String> {
+		CaptureArgs::parse(args.split_whitespace().map(String::from))
+	}
+
+	#[test]
+	fn test_parse() {
+		let hash = CryptoHash([1; 32]);
+		let args =
+			parse(&format!("capture --network testnet --block {} --follow 3", hash)).unwrap();
+		assert_eq!(
+			args,
+			CaptureArgs {
+				network: "testnet".into(),
+				rpc_url: TESTNET_RPC_URL.into(),
+				out_dir: "fixtures".into(),
+				target: Target::Blocks { after: hash, follow: 3 },
+			}
+		);
+		let args = parse(&format!(
+			"capture --network localnet --rpc http://127.0.0.1:3030 --out tmp \
+			 --transaction {0} --sender alice.near --head {0}",
+			hash
+		))
+		.unwrap();
+		assert_eq!(args.out_dir, PathBuf::from("tmp"));
+		assert_eq!(
+			args.target,
+			Target::Transaction {
+				transaction_hash: hash,
+				sender_id: "alice.near".into(),
+				light_client_head: hash
+			}
+		);
+
+		assert!(parse("capture").is_err());
+		assert!(parse(&format!("capture --network localnet --validators {}", hash)).is_err());
+		assert!(parse(&format!("capture --receipt {0} --receiver bob.near", hash)).is_err());
+		assert!(parse(&format!("capture --block {0} --validators {0}", hash)).is_err());
+		assert!(parse(&format!("capture --block {} --follow many", hash)).is_err());
+	}
+
+	#[test]
+	fn test_fixtures_read_as_responses() {
+		let fixture = fixture(
+			"testnet",
+			"next_light_client_block",
+			json!(["11111111111111111111111111111111"]),
+			json!({"height": 1}),
+		);
+		assert_eq!(fixture["fixture"]["version"], FIXTURE_VERSION);
+		assert_eq!(fixture["result"]["height"], 1);
+
+		// the fixtures migrated from the test suites
+		let migrated: Value = serde_json::from_str(include_str!(
+			"../../fixtures/testnet/next_light_client_block/86456070.json"
+		))
+		.unwrap();
+		assert_eq!(migrated["fixture"]["version"], FIXTURE_VERSION);
+		light_client_block_from_json(migrated["result"].clone()).unwrap();
+	}
+}
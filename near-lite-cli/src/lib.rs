@@ -0,0 +1,7 @@
+//! `near-lite`: verifies light client blocks and outcome proofs against a checkpoint, from files or
+//! over RPC, without running a relayer or a chain. `near-lite capture` records RPC responses as
+//! test fixtures.
+
+pub mod args;
+pub mod capture;
+pub mod verify;
@@ -0,0 +1,58 @@
+//! `near-lite verify ...`: prints the [`Report`] of the verification as JSON. Exits with 0 if
+//! everything verified, 1 if the block or the proof was rejected, and 2 if they could not be
+//! loaded.
+//!
+//! `near-lite capture ...`: writes the fixtures and prints their paths. Exits with 2 on failure.
+
+use std::{env, process};
+
+use near_lite_cli::{
+	args::{VerifyArgs, USAGE},
+	capture::{self, capture, CaptureArgs},
+	verify::{verify, Report},
+};
+
+#[tokio::main]
+async fn main() {
+	env_logger::init();
+	let args = env::args().skip(1).collect::<Vec<_>>();
+	if args.first().map(String::as_str) == Some("capture") {
+		return run_capture(args).await
+	}
+	let args = match VerifyArgs::parse(args) {
+		Ok(args) => args,
+		Err(err) => {
+			eprintln!("{}\n\n{}\n\n{}", err, USAGE, capture::USAGE);
+			process::exit(2);
+		},
+	};
+	let report: Report = match verify(&args).await {
+		Ok(report) => report,
+		Err(err) => {
+			eprintln!("{}", err);
+			process::exit(2);
+		},
+	};
+	println!("{}", serde_json::to_string_pretty(&report).expect("the report is valid JSON; qed"));
+	process::exit(if report.verified { 0 } else { 1 });
+}
+
+async fn run_capture(args: Vec<String>) {
+	let args = match CaptureArgs::parse(args) {
+		Ok(args) => args,
+		Err(err) => {
+			eprintln!("{}\n\n{}", err, capture::USAGE);
+			process::exit(2);
+		},
+	};
+	match capture(&args).await {
+		Ok(paths) =>
+			for path in paths {
+				println!("{}", path.display());
+			},
+		Err(err) => {
+			eprintln!("{}", err);
+			process::exit(2);
+		},
+	}
+}
@@ -0,0 +1,264 @@
+//! Loads the checkpoint, block and proof of [`VerifyArgs`], from files or over RPC, and verifies
+//! them with a [`LightClient`] the way a bridge would, reporting every step.
+
+use std::{fs, io, path::Path};
+
+use near_lite_client::{
+	rpc::{light_client_block_from_json, LightClientProof, LightClientProofRequest, NearRpcClient},
+	LightClient, LightClientBlockView, NearLiteClientError, Sha256HostFunctions, TrustedCheckpoint,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::args::{BlockSource, CheckpointSource, ProofSource, VerifyArgs};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+	pub checkpoint: HeaderReport,
+	/// `None` if no block was given, or none follows the checkpoint yet
+	pub block: Option<BlockReport>,
+	pub proof: Option<ProofReport>,
+	/// whether the block was accepted and the proof is valid, when given
+	pub verified: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeaderReport {
+	pub height: u64,
+	pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlockReport {
+	#[serde(flatten)]
+	pub header: HeaderReport,
+	#[serde(flatten)]
+	pub status: Status<Approvals>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Approvals {
+	pub approvals_checked: usize,
+	pub stake_approved_bps: u16,
+	pub epoch_changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProofReport {
+	pub outcome_id: String,
+	pub block_hash: String,
+	/// hash of the head the block proof was verified against
+	pub light_client_head: String,
+	#[serde(flatten)]
+	pub status: Status<Roots>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Roots {
+	pub outcome_hash: String,
+	pub shard_outcome_root: String,
+	pub block_outcome_root: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Status<T> {
+	Valid(T),
+	Invalid {
+		/// stable code of the [`NearLiteClientError`]
+		code: u16,
+		error: String,
+	},
+}
+
+impl<T> Status<T> {
+	pub fn is_valid(&self) -> bool {
+		matches!(self, Self::Valid(_))
+	}
+}
+
+impl<T> From<Result<T, NearLiteClientError>> for Status<T> {
+	fn from(result: Result<T, NearLiteClientError>) -> Self {
+		match result {
+			Ok(value) => Self::Valid(value),
+			Err(err) => Self::Invalid { code: err.code(), error: format!("{:?}", err) },
+		}
+	}
+}
+
+/// Validates the block against the checkpoint, then the proof against the head: the block if it
+/// was accepted, the checkpoint otherwise. Failing to load any of them is an error, failing to
+/// verify them is reported.
+pub async fn verify(args: &VerifyArgs) -> io::Result<Report> {
+	let rpc = NearRpcClient::new(args.rpc_url.as_str());
+	let checkpoint = match &args.checkpoint {
+		CheckpointSource::File(path) => read_block_view(path)?,
+		CheckpointSource::Hash(hash) => rpc
+			.next_light_client_block(*hash)
+			.await
+			.map_err(other)?
+			.ok_or_else(|| other(format!("no light client block follows {}", hash)))?,
+	};
+	let mut client =
+		LightClient::<Sha256HostFunctions>::with_checkpoint(TrustedCheckpoint(checkpoint.clone()))
+			.map_err(other)?;
+	let checkpoint = header_report(&checkpoint);
+
+	let block_view = match &args.block {
+		None => None,
+		Some(BlockSource::File(path)) => Some(read_block_view(path)?),
+		Some(BlockSource::Rpc) =>
+			rpc.next_light_client_block(client.head_hash()).await.map_err(other)?,
+	};
+	let block = block_view.map(|block_view| BlockReport {
+		header: header_report(&block_view),
+		status: client
+			.validate_and_update_head(block_view)
+			.map(|summary| Approvals {
+				approvals_checked: summary.approvals_checked,
+				stake_approved_bps: summary.stake_approved_bps,
+				epoch_changed: summary.epoch_changed,
+			})
+			.into(),
+	});
+
+	let light_client_head = client.head_hash();
+	let proof = match &args.proof {
+		None => None,
+		Some(ProofSource::File(path)) =>
+			Some(LightClientProof::from_json(read_json(path)?).map_err(other)?),
+		Some(ProofSource::Transaction { transaction_hash, sender_id }) => Some(
+			rpc.light_client_proof(
+				&LightClientProofRequest::transaction(*transaction_hash, sender_id.as_str())
+					.light_client_head(light_client_head),
+			)
+			.await
+			.map_err(other)?,
+		),
+		Some(ProofSource::Receipt { receipt_id, receiver_id }) => Some(
+			rpc.light_client_proof(
+				&LightClientProofRequest::receipt(*receipt_id, receiver_id.as_str())
+					.light_client_head(light_client_head),
+			)
+			.await
+			.map_err(other)?,
+		),
+	};
+	let proof = proof.map(|proof| ProofReport {
+		outcome_id: proof.outcome_proof.id.to_string(),
+		block_hash: proof.outcome_proof.block_hash.to_string(),
+		light_client_head: light_client_head.to_string(),
+		status: client
+			.validate_full_outcome_proof(&proof.into())
+			.map(|roots| Roots {
+				outcome_hash: roots.outcome_hash.to_string(),
+				shard_outcome_root: roots.shard_outcome_root.to_string(),
+				block_outcome_root: roots.block_outcome_root.to_string(),
+			})
+			.into(),
+	});
+
+	let verified = block.as_ref().map_or(true, |block| block.status.is_valid()) &&
+		proof.as_ref().map_or(true, |proof| proof.status.is_valid());
+	Ok(Report { checkpoint, block, proof, verified })
+}
+
+fn header_report(block_view: &LightClientBlockView) -> HeaderReport {
+	HeaderReport {
+		height: block_view.inner_lite.height.0,
+		hash: block_view.current_block_hash::<Sha256HostFunctions>().to_string(),
+	}
+}
+
+/// The JSON of `path`, unwrapping the `result` of a JSON-RPC response.
+fn read_json(path: &Path) -> io::Result<Value> {
+	let json: Value = serde_json::from_str(&fs::read_to_string(path)?).map_err(other)?;
+	Ok(match json {
+		Value::Object(mut response) if response.contains_key("jsonrpc") =>
+			response.remove("result").ok_or_else(|| {
+				other(format!("{} is a JSON-RPC response without result", path.display()))
+			})?,
+		json => json,
+	})
+}
+
+fn read_block_view(path: &Path) -> io::Result<LightClientBlockView> {
+	light_client_block_from_json(read_json(path)?).map_err(other)
+}
+
+pub(crate) fn other(err: impl std::fmt::Debug) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, format!("{:?}", err))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use near_lite_client::BlockHeight;
+	use near_primitives::views::LightClientBlockView as NearLightClientBlockView;
+	use serde_json::json;
+
+	/// Writes `json` to a file of the temporary directory, removed on drop.
+	struct TempFile(std::path::PathBuf);
+
+	impl TempFile {
+		fn new(name: &str, json: Value) -> Self {
+			let path = std::env::temp_dir().join(format!(
+				"near-lite-cli-{}-{}.json",
+				std::process::id(),
+				name
+			));
+			fs::write(&path, json.to_string()).unwrap();
+			Self(path)
+		}
+	}
+
+	impl Drop for TempFile {
+		fn drop(&mut self) {
+			let _ = fs::remove_file(&self.0);
+		}
+	}
+
+	fn block_view_json(height: u64) -> Value {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(height);
+		serde_json::to_value(NearLightClientBlockView::try_from(block_view).unwrap()).unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_report_a_rejected_block() {
+		let checkpoint = TempFile::new("checkpoint", block_view_json(10));
+		// as saved from a JSON-RPC response
+		let block = TempFile::new(
+			"block",
+			json!({"jsonrpc": "2.0", "result": block_view_json(9), "id": "dontcare"}),
+		);
+		let args = VerifyArgs {
+			checkpoint: CheckpointSource::File(checkpoint.0.clone()),
+			block: Some(BlockSource::File(block.0.clone())),
+			proof: None,
+			rpc_url: String::new(),
+		};
+
+		let report = verify(&args).await.unwrap();
+		assert_eq!(report.checkpoint.height, 10);
+		let block = report.block.unwrap();
+		assert_eq!(block.header.height, 9);
+		match block.status {
+			Status::Invalid { code, .. } => assert_eq!(
+				code,
+				NearLiteClientError::StaleHeader {
+					head_height: BlockHeight(10),
+					block_height: BlockHeight(9)
+				}
+				.code()
+			),
+			status => panic!("unexpected status {:?}", status),
+		}
+		assert!(!report.verified);
+
+		let args = VerifyArgs { block: None, ..args };
+		assert!(verify(&args).await.unwrap().verified);
+		let args = VerifyArgs { checkpoint: CheckpointSource::File("missing.json".into()), ..args };
+		assert!(verify(&args).await.is_err());
+	}
+}
@@ -0,0 +1,16 @@
+//! Validates a chain of headers from a checkpoint and commits the resulting
+//! [`HeaderChainJournal`] to the journal of the receipt. An invalid chain makes the guest panic,
+//! so that no receipt is produced for it.
+#![no_main]
+
+use near_lite_client_risc0::{validate_header_chain, HeaderChainInput};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+	let input: Vec<u8> = env::read();
+	let input = HeaderChainInput::decode(&input).expect("the input of the host is well formed");
+	let journal = validate_header_chain(&input).expect("the headers extend the checkpoint");
+	env::commit(&journal.encode());
+}
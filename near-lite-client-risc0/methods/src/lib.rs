@@ -0,0 +1,4 @@
+//! The header chain guest, built for the RISC Zero zkVM: `HEADER_CHAIN_PATH` is its ELF and
+//! `HEADER_CHAIN_ID` its method id, which receipts are verified against.
+
+include!(concat!(env!("OUT_DIR"), "/methods.rs"));
@@ -0,0 +1,130 @@
+//! # Near lite client in RISC Zero
+//!
+//! Proves that a chain of NEAR headers extends a checkpoint by validating it inside the RISC Zero
+//! zkVM: the receipt attests "header H extends checkpoint C" with a single proof, for chains that
+//! can't afford to verify the approvals of every header themselves.
+//!
+//! The guest (`methods/guest`) runs [`validate_header_chain`], the same [`LightClient`] validation
+//! as everywhere else, and commits a [`HeaderChainJournal`]. With the `prover` feature, the host
+//! side produces and verifies receipts, see [`prover`].
+//!
+//! ```ignore
+//! let input = HeaderChainInput { checkpoint, headers };
+//! let (receipt, journal) = prover::prove(&input)?;
+//! // on the verifying side, trusting `checkpoint_hash`
+//! let journal = prover::verify(&receipt)?;
+//! assert_eq!(journal.checkpoint_hash, checkpoint_hash);
+//! ```
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_lite_client::{LightClient, LiteClientResult, Sha256HostFunctions, TrustedCheckpoint};
+use near_primitives_wasm::{BlockHeight, CryptoHash, EpochId, LightClientBlockView};
+
+#[cfg(feature = "prover")]
+pub mod prover;
+
+/// What the guest is given: the checkpoint it trusts and the headers to validate from it, in
+/// order.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct HeaderChainInput {
+	pub checkpoint: LightClientBlockView,
+	pub headers: Vec<LightClientBlockView>,
+}
+
+impl HeaderChainInput {
+	pub fn encode(&self) -> Vec<u8> {
+		self.try_to_vec().expect("encoding into a Vec can't fail; qed")
+	}
+
+	/// The input comes from the prover, the guest doesn't bound it: a larger input only makes
+	/// proving more expensive.
+	pub fn decode(bytes: &[u8]) -> LiteClientResult<Self> {
+		Ok(Self::try_from_slice(bytes)?)
+	}
+}
+
+/// What a receipt attests: the headers from the checkpoint `checkpoint_hash` up to `head_hash`
+/// are valid.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct HeaderChainJournal {
+	pub checkpoint_hash: CryptoHash,
+	pub head_hash: CryptoHash,
+	pub head_height: BlockHeight,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
+}
+
+impl HeaderChainJournal {
+	pub fn encode(&self) -> Vec<u8> {
+		self.try_to_vec().expect("encoding into a Vec can't fail; qed")
+	}
+
+	pub fn decode(bytes: &[u8]) -> LiteClientResult<Self> {
+		Ok(Self::try_from_slice(bytes)?)
+	}
+}
+
+/// Validates `input.headers` one after the other from `input.checkpoint`, as the guest does.
+pub fn validate_header_chain(input: &HeaderChainInput) -> LiteClientResult<HeaderChainJournal> {
+	let mut client = LightClient::<Sha256HostFunctions>::with_checkpoint(TrustedCheckpoint(
+		input.checkpoint.clone(),
+	))?;
+	let checkpoint_hash = client.head_hash();
+	for header in &input.headers {
+		client.validate_and_update_head(header.clone())?;
+	}
+	Ok(HeaderChainJournal {
+		checkpoint_hash,
+		head_hash: client.head_hash(),
+		head_height: client.head_height(),
+		epoch_id: client.current_epoch_id(),
+		next_epoch_id: client.next_epoch_id(),
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use near_lite_client_test_utils::{SignedHeader, ValidatorSet};
+
+	fn input() -> HeaderChainInput {
+		let validators = ValidatorSet::new(4);
+		let epochs = [1, 2, 3].map(|epoch| EpochId(CryptoHash([epoch; 32])));
+		let chain = SignedHeader::new::<Sha256HostFunctions>(
+			BlockHeight(10),
+			epochs,
+			&validators,
+			&validators,
+		)
+		.unwrap();
+		HeaderChainInput { checkpoint: chain.head, headers: vec![chain.block_view] }
+	}
+
+	#[test]
+	fn test_validate_header_chain() {
+		let input = input();
+		let decoded = HeaderChainInput::decode(&input.encode()).unwrap();
+		let journal = validate_header_chain(&decoded).unwrap();
+		assert_eq!(
+			journal.checkpoint_hash,
+			input.checkpoint.current_block_hash::<Sha256HostFunctions>()
+		);
+		assert_eq!(journal.head_hash, input.headers[0].current_block_hash::<Sha256HostFunctions>());
+		assert_eq!(journal.head_height, BlockHeight(11));
+		assert_eq!(HeaderChainJournal::decode(&journal.encode()).unwrap(), journal);
+	}
+
+	#[test]
+	fn test_invalid_chains_are_rejected() {
+		let mut input = input();
+		input.headers[0]
+			.approvals_after_next
+			.iter_mut()
+			.for_each(|approval| *approval = None);
+		assert!(validate_header_chain(&input).is_err());
+
+		let mut encoded = input.encode();
+		encoded.push(0);
+		assert!(HeaderChainInput::decode(&encoded).is_err());
+	}
+}
@@ -0,0 +1,45 @@
+//! # Receipts
+//!
+//! Runs the header chain guest in the zkVM to produce a receipt, and verifies receipts against
+//! the method id of the guest this crate was built with.
+
+use near_lite_client_risc0_methods::{HEADER_CHAIN_ID, HEADER_CHAIN_PATH};
+use risc0_zkvm::{
+	host::{Prover, Receipt},
+	serde::{from_slice, to_vec},
+};
+
+use crate::{HeaderChainInput, HeaderChainJournal};
+
+#[derive(Debug)]
+pub enum ProverError {
+	/// The guest could not be loaded, or proving failed, e.g. because the guest panicked on
+	/// headers that don't extend the checkpoint.
+	Zkvm(risc0_zkvm::host::Exception),
+	/// The receipt is not for the header chain guest, or it was tampered with.
+	InvalidReceipt(risc0_zkvm::host::Exception),
+	Encoding(String),
+}
+
+/// Validates `input` in the zkVM, returning the receipt together with its decoded journal.
+pub fn prove(input: &HeaderChainInput) -> Result<(Receipt, HeaderChainJournal), ProverError> {
+	let elf = std::fs::read(HEADER_CHAIN_PATH)
+		.map_err(|err| ProverError::Encoding(format!("cannot read the guest: {}", err)))?;
+	let words = to_vec(&input.encode()).map_err(|err| ProverError::Encoding(err.to_string()))?;
+
+	let mut prover = Prover::new(&elf, HEADER_CHAIN_ID).map_err(ProverError::Zkvm)?;
+	prover.add_input_u32_slice(&words);
+	let receipt = prover.run().map_err(ProverError::Zkvm)?;
+	let journal = verify(&receipt)?;
+	Ok((receipt, journal))
+}
+
+/// Checks that `receipt` was produced by the header chain guest and returns what it attests.
+/// Whether `checkpoint_hash` is trusted is for the caller to decide.
+pub fn verify(receipt: &Receipt) -> Result<HeaderChainJournal, ProverError> {
+	receipt.verify(HEADER_CHAIN_ID).map_err(ProverError::InvalidReceipt)?;
+	let journal = receipt.get_journal_vec().map_err(ProverError::InvalidReceipt)?;
+	let encoded: Vec<u8> =
+		from_slice(&journal).map_err(|err| ProverError::Encoding(err.to_string()))?;
+	HeaderChainJournal::decode(&encoded).map_err(|err| ProverError::Encoding(format!("{:?}", err)))
+}
@@ -0,0 +1,272 @@
+//! # Builders
+//!
+//! Data the light client accepts, built from deterministic keys so that tests failing on some
+//! input can rebuild it exactly.
+
+use std::collections::BTreeMap;
+
+use near_lite_client::{
+	calculate_execution_outcome_hash, compute_root_from_path, BlockViewHashes, LiteClientResult,
+	TrustedCheckpoint,
+};
+use near_primitives_wasm::{
+	Balance, BlockHeight, CryptoHash, EpochId, ExecutionOutcomeView, HostFunctions,
+	LightClientBlockView, MerklePath, OutcomeProof, PublicKey, Signature, ValidatorStakeView,
+	ValidatorStakeViewV1,
+};
+use sp_core::{ed25519::Pair, Pair as _};
+
+/// Block producers whose keys are derived from their index.
+#[derive(Clone)]
+pub struct ValidatorSet {
+	/// index of the key of the first block producer
+	first_key: usize,
+	pairs: Vec<Pair>,
+	stakes: Vec<Balance>,
+}
+
+impl ValidatorSet {
+	/// `len` block producers staking `1` each.
+	pub fn new(len: usize) -> Self {
+		Self::with_stakes(vec![1; len])
+	}
+
+	/// One block producer per stake, ordered by decreasing stake as nearcore orders `next_bps`.
+	pub fn with_stakes(stakes: Vec<Balance>) -> Self {
+		Self::with_keys_from(0, stakes)
+	}
+
+	/// [`ValidatorSet::with_stakes`], with the keys derived from the indices from `first_key`
+	/// instead of `0`, so that sets built with disjoint ranges share no block producer.
+	pub fn with_keys_from(first_key: usize, mut stakes: Vec<Balance>) -> Self {
+		stakes.sort_unstable_by(|a, b| b.cmp(a));
+		let pairs = (first_key..first_key + stakes.len()).map(key).collect();
+		Self { first_key, pairs, stakes }
+	}
+
+	pub fn len(&self) -> usize {
+		self.pairs.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pairs.is_empty()
+	}
+
+	pub fn block_producers(&self) -> Vec<ValidatorStakeView> {
+		self.pairs
+			.iter()
+			.zip(&self.stakes)
+			.enumerate()
+			.map(|(index, (pair, stake))| {
+				ValidatorStakeView::V1(ValidatorStakeViewV1 {
+					account_id: format!("node{}.near", self.first_key + index),
+					public_key: PublicKey(pair.public().0),
+					stake: *stake,
+				})
+			})
+			.collect()
+	}
+
+	/// The `next_bp_hash` of a header announcing this set.
+	pub fn hash<H: HostFunctions>(&self) -> CryptoHash {
+		CryptoHash(H::sha256_borsh(&self.block_producers()))
+	}
+
+	/// Approvals of `message` by the block producers at the indices for which `approves` holds,
+	/// `None` for the others.
+	pub fn approvals(
+		&self,
+		message: &[u8],
+		approves: impl Fn(usize) -> bool,
+	) -> Vec<Option<Signature>> {
+		self.pairs
+			.iter()
+			.enumerate()
+			.map(|(index, pair)| approves(index).then(|| Signature::Ed25519(pair.sign(message))))
+			.collect()
+	}
+}
+
+fn key(index: usize) -> Pair {
+	let mut seed = [0u8; 32];
+	seed[..8].copy_from_slice(&(index as u64).to_le_bytes());
+	Pair::from_seed(&seed)
+}
+
+/// A header on top of a head, approved by the block producers of its epoch.
+#[derive(Debug, Clone)]
+pub struct HeaderBuilder {
+	block_view: LightClientBlockView,
+}
+
+impl HeaderBuilder {
+	/// The header at the height after `head`, in the same epochs and without `next_bps`.
+	pub fn child_of(head: &LightClientBlockView) -> Self {
+		let mut block_view = head.clone();
+		block_view.inner_lite.height = BlockHeight(head.inner_lite.height.0 + 1);
+		block_view.next_bps = None;
+		block_view.approvals_after_next = Vec::new();
+		Self { block_view }
+	}
+
+	pub fn height(mut self, height: BlockHeight) -> Self {
+		self.block_view.inner_lite.height = height;
+		self
+	}
+
+	pub fn epochs(mut self, epoch_id: EpochId, next_epoch_id: EpochId) -> Self {
+		self.block_view.inner_lite.epoch_id = epoch_id;
+		self.block_view.inner_lite.next_epoch_id = next_epoch_id;
+		self
+	}
+
+	pub fn prev_block_hash(mut self, prev_block_hash: CryptoHash) -> Self {
+		self.block_view.prev_block_hash = prev_block_hash;
+		self
+	}
+
+	pub fn outcome_root(mut self, outcome_root: CryptoHash) -> Self {
+		self.block_view.inner_lite.outcome_root = outcome_root;
+		self
+	}
+
+	pub fn block_merkle_root(mut self, block_merkle_root: CryptoHash) -> Self {
+		self.block_view.inner_lite.block_merkle_root = block_merkle_root;
+		self
+	}
+
+	/// Sets both `timestamp` and `timestamp_nanosec`, which nearcore keeps equal.
+	pub fn timestamp(mut self, timestamp: u64) -> Self {
+		self.block_view.inner_lite.timestamp = timestamp;
+		self.block_view.inner_lite.timestamp_nanosec = timestamp;
+		self
+	}
+
+	/// Announces `next_bps` as the block producers of the next epoch.
+	pub fn next_bps<H: HostFunctions>(mut self, next_bps: &ValidatorSet) -> Self {
+		self.block_view.inner_lite.next_bp_hash = next_bps.hash::<H>();
+		self.block_view.next_bps = Some(next_bps.block_producers());
+		self
+	}
+
+	/// Overrides the `next_bp_hash`, e.g. with one that does not commit to `next_bps`.
+	pub fn next_bp_hash(mut self, next_bp_hash: CryptoHash) -> Self {
+		self.block_view.inner_lite.next_bp_hash = next_bp_hash;
+		self
+	}
+
+	/// The header, approved by every block producer of `validators`.
+	pub fn sign<H: HostFunctions>(
+		self,
+		validators: &ValidatorSet,
+	) -> LiteClientResult<LightClientBlockView> {
+		self.sign_with::<H>(validators, |_| true)
+	}
+
+	/// The header, approved by the block producers of `validators` at the indices for which
+	/// `approves` holds.
+	pub fn sign_with<H: HostFunctions>(
+		mut self,
+		validators: &ValidatorSet,
+		approves: impl Fn(usize) -> bool,
+	) -> LiteClientResult<LightClientBlockView> {
+		let approval_message = BlockViewHashes::new::<H>(&self.block_view)?.approval_message;
+		self.block_view.approvals_after_next = validators.approvals(&approval_message, approves);
+		Ok(self.block_view)
+	}
+}
+
+/// A head and a header valid on top of it: the header is the first of the head's next epoch,
+/// approved by every block producer the head announced.
+#[derive(Debug, Clone)]
+pub struct SignedHeader {
+	pub head: LightClientBlockView,
+	pub block_view: LightClientBlockView,
+	pub epoch_block_producers: BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+}
+
+impl SignedHeader {
+	/// `epochs` are the current, next and following epochs of `head`; `validators` produce the
+	/// blocks of the next epoch and `next_validators` those of the following one.
+	pub fn new<H: HostFunctions>(
+		height: BlockHeight,
+		epochs: [EpochId; 3],
+		validators: &ValidatorSet,
+		next_validators: &ValidatorSet,
+	) -> LiteClientResult<Self> {
+		let [epoch_id, next_epoch_id, following_epoch_id] = epochs;
+		let head = HeaderBuilder::child_of(&LightClientBlockView::new_for_test())
+			.height(height)
+			.epochs(epoch_id, next_epoch_id)
+			.next_bps::<H>(validators)
+			.sign::<H>(&ValidatorSet::new(0))?;
+		let block_view = HeaderBuilder::child_of(&head)
+			.epochs(next_epoch_id, following_epoch_id)
+			.next_bps::<H>(next_validators)
+			.sign::<H>(validators)?;
+
+		let mut epoch_block_producers = BTreeMap::new();
+		epoch_block_producers.insert(next_epoch_id, validators.block_producers());
+		Ok(Self { head, block_view, epoch_block_producers })
+	}
+
+	/// A checkpoint at the head, from which a client accepts the header.
+	pub fn checkpoint(&self) -> TrustedCheckpoint {
+		TrustedCheckpoint(self.head.clone())
+	}
+}
+
+/// An outcome proof together with its outcome root proof and the outcome root of the block they
+/// lead to.
+#[derive(Debug, Clone)]
+pub struct OutcomeProofBuilder {
+	outcome_proof: OutcomeProof,
+	outcome_root_proof: MerklePath,
+}
+
+impl OutcomeProofBuilder {
+	/// The outcome of `id`, with empty paths.
+	pub fn new(id: CryptoHash, outcome: ExecutionOutcomeView) -> Self {
+		Self {
+			outcome_proof: OutcomeProof {
+				proof: Vec::new(),
+				block_hash: CryptoHash::default(),
+				id,
+				outcome,
+			},
+			outcome_root_proof: Vec::new(),
+		}
+	}
+
+	/// The path from the outcome to the outcome root of its shard.
+	pub fn proof(mut self, proof: MerklePath) -> Self {
+		self.outcome_proof.proof = proof;
+		self
+	}
+
+	/// The path from the outcome root of the shard to the outcome root of the block.
+	pub fn outcome_root_proof(mut self, outcome_root_proof: MerklePath) -> Self {
+		self.outcome_root_proof = outcome_root_proof;
+		self
+	}
+
+	pub fn block_hash(mut self, block_hash: CryptoHash) -> Self {
+		self.outcome_proof.block_hash = block_hash;
+		self
+	}
+
+	/// The outcome proof, the outcome root proof and the outcome root of the block.
+	pub fn build<H: HostFunctions>(
+		self,
+	) -> LiteClientResult<(OutcomeProof, MerklePath, CryptoHash)> {
+		let Self { outcome_proof, outcome_root_proof } = self;
+		let outcome_hash =
+			calculate_execution_outcome_hash::<H>(&outcome_proof.outcome, outcome_proof.id)?;
+		let shard_outcome_root = compute_root_from_path::<H>(&outcome_proof.proof, outcome_hash)?;
+		let block_outcome_root = compute_root_from_path::<H>(
+			&outcome_root_proof,
+			CryptoHash(H::sha256_borsh(&shard_outcome_root)),
+		)?;
+		Ok((outcome_proof, outcome_root_proof, block_outcome_root))
+	}
+}
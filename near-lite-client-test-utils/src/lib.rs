@@ -0,0 +1,35 @@
+//! # Near lite client test utils
+//!
+//! Builders and `proptest` strategies producing data the light client accepts: validator sets
+//! with deterministic keys, headers approved by them and outcome proofs leading to a known root.
+//! [`MockNearChain`] chains such headers over several epochs, with faults on demand, and
+//! [`nearcore`] is a reference verifier built on nearcore's types for differential testing.
+//! Pallets and other integrations can use them to property-test their own handling of the client.
+//!
+//! ```ignore
+//! use near_lite_client_test_utils::prelude::*;
+//!
+//! proptest! {
+//! 	#[test]
+//! 	fn accepts_valid_headers(chain in signed_header(32)) {
+//! 		let mut client = LightClient::<Sha256HostFunctions>::with_checkpoint(chain.checkpoint()).unwrap();
+//! 		prop_assert!(client.validate_and_update_head(chain.block_view).is_ok());
+//! 	}
+//! }
+//! ```
+
+pub mod builders;
+pub mod mock_chain;
+pub mod nearcore;
+pub mod strategies;
+
+pub use builders::{HeaderBuilder, OutcomeProofBuilder, SignedHeader, ValidatorSet};
+pub use mock_chain::{Fault, MockNearChain};
+
+pub mod prelude {
+	pub use super::{builders::*, mock_chain::*, strategies::*};
+	pub use near_lite_client::{
+		prelude::*, BlockHeight, EpochId, LightClient, LightClientBlockView, Sha256HostFunctions,
+	};
+	pub use proptest::prelude::*;
+}
@@ -0,0 +1,702 @@
+//! # Mock chain
+//!
+//! [`MockNearChain`] produces a consistent sequence of light client blocks: every header links to
+//! the previous one, is approved by the block producers of its epoch and commits to the outcomes
+//! it was given, and to every header before it in its `block_merkle_root`. Headers with a [`Fault`]
+//! are built on top of the same head without being appended, so a test can check that the client
+//! rejects them and then carry on with the chain.
+//!
+//! ```ignore
+//! let mut chain = MockNearChain::<Sha256HostFunctions>::new(4);
+//! let mut client = LightClient::with_checkpoint(chain.checkpoint())?;
+//! client.validate_and_update_head(chain.epoch_change())?;
+//! assert!(client.validate_and_update_head(chain.faulty_block(Fault::BadSignature)).is_err());
+//! client.validate_and_update_head(chain.block())?;
+//! ```
+
+use std::marker::PhantomData;
+
+use near_lite_client::{
+	calculate_execution_outcome_hash, merklize, merklize_leaf_hashes, rainbow::FullOutcomeProof,
+	BlockMerkleAccumulator, LiteClientResult, Sha256HostFunctions, TrustedCheckpoint,
+};
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, EpochId, ExecutionOutcomeView, HostFunctions,
+	LightClientBlockLiteView, LightClientBlockView, MerklePath, OutcomeProof, Signature,
+};
+use sp_core::{ed25519::Pair, Pair as _};
+
+use crate::builders::{HeaderBuilder, ValidatorSet};
+
+/// Nanoseconds between two headers.
+pub const BLOCK_TIME: u64 = 1_000_000_000;
+
+/// Timestamp of the checkpoint.
+const GENESIS_TIMESTAMP: u64 = 1_600_000_000_000_000_000;
+
+/// What is wrong with a header built by [`MockNearChain::faulty_block`] or
+/// [`MockNearChain::faulty_epoch_change`]. The client rejects all of them, as long as the epochs
+/// have block producers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+	/// the first approval signs another message
+	BadSignature,
+	/// the approvals are signed by the block producers of another epoch
+	WrongSigners,
+	/// at most 2/3 of the stake approves
+	InsufficientStake,
+	/// the approval of the last block producer is left out of `approvals_after_next`
+	MissingApproval,
+	/// `next_bps` is announced with a `next_bp_hash` that does not commit to it
+	WrongBpHash,
+	/// the header is in the epoch after the next one
+	SkippedEpoch,
+	/// the header is at the height of the head
+	StaleHeight,
+}
+
+/// A chain whose epochs have `block_producers` block producers each, with disjoint keys. Epoch
+/// `i` has the id [`MockNearChain::epoch_id`]`(i)`, the checkpoint is in epoch `0`.
+///
+/// A client bootstrapped from the checkpoint only knows the block producers of epoch `1`, so the
+/// first header it can accept is [`MockNearChain::epoch_change`].
+pub struct MockNearChain<H: HostFunctions = Sha256HostFunctions> {
+	headers: Vec<LightClientBlockView>,
+	/// outcomes of every header, in the order of `headers`
+	outcomes: Vec<Vec<(CryptoHash, ExecutionOutcomeView)>>,
+	/// epoch of the head
+	epoch: usize,
+	/// ids of the epochs up to the next one of the head
+	epoch_ids: Vec<EpochId>,
+	block_producers: usize,
+	/// heights the next header skips
+	skipped_heights: u64,
+	_host_functions: PhantomData<fn() -> H>,
+}
+
+impl<H: HostFunctions> MockNearChain<H> {
+	pub fn new(block_producers: usize) -> Self {
+		let checkpoint = HeaderBuilder::child_of(&LightClientBlockView::new_for_test())
+			.height(BlockHeight(1))
+			.epochs(Self::made_up_epoch_id(0), Self::made_up_epoch_id(1))
+			.timestamp(GENESIS_TIMESTAMP)
+			.next_bps::<H>(&Self::validator_set(1, block_producers))
+			.sign::<H>(&Self::validator_set(0, block_producers))
+			.expect("mock headers serialize; qed");
+		Self {
+			headers: vec![checkpoint],
+			outcomes: vec![Vec::new()],
+			epoch: 0,
+			epoch_ids: vec![Self::made_up_epoch_id(0), Self::made_up_epoch_id(1)],
+			block_producers,
+			skipped_heights: 0,
+			_host_functions: PhantomData,
+		}
+	}
+
+	/// The id of `epoch`. As in NEAR, it is the hash of the last header of the epoch two before,
+	/// the ids of the epochs of the checkpoint and of those the chain didn't reach are made up.
+	pub fn epoch_id(&self, epoch: usize) -> EpochId {
+		self.epoch_ids
+			.get(epoch)
+			.copied()
+			.unwrap_or_else(|| Self::made_up_epoch_id(epoch))
+	}
+
+	fn made_up_epoch_id(epoch: usize) -> EpochId {
+		let mut hash = [0; 32];
+		hash[..8].copy_from_slice(&(epoch as u64).to_le_bytes());
+		EpochId(CryptoHash(hash))
+	}
+
+	fn validator_set(epoch: usize, block_producers: usize) -> ValidatorSet {
+		ValidatorSet::with_keys_from(epoch * block_producers, vec![1; block_producers])
+	}
+
+	/// The block producers of `epoch`.
+	pub fn validators(&self, epoch: usize) -> ValidatorSet {
+		Self::validator_set(epoch, self.block_producers)
+	}
+
+	pub fn checkpoint(&self) -> TrustedCheckpoint {
+		TrustedCheckpoint(self.headers[0].clone())
+	}
+
+	pub fn head(&self) -> &LightClientBlockView {
+		self.headers.last().expect("the chain starts with its checkpoint; qed")
+	}
+
+	/// Every header after the checkpoint, in order.
+	pub fn headers(&self) -> &[LightClientBlockView] {
+		&self.headers[1..]
+	}
+
+	/// Makes the next header `heights` further than the one after the head, as when blocks are
+	/// missed. The client accepts such headers.
+	pub fn skip_heights(&mut self, heights: u64) -> &mut Self {
+		self.skipped_heights += heights;
+		self
+	}
+
+	/// Appends the next header of the epoch of the head.
+	pub fn block(&mut self) -> LightClientBlockView {
+		self.block_with_outcomes(Vec::new())
+	}
+
+	/// Appends the next header of the epoch of the head, whose outcome root commits to
+	/// `outcomes`, each outcome with its id.
+	pub fn block_with_outcomes(
+		&mut self,
+		outcomes: Vec<(CryptoHash, ExecutionOutcomeView)>,
+	) -> LightClientBlockView {
+		self.append(false, outcomes)
+	}
+
+	/// Appends the first header of the next epoch, which announces the block producers of the
+	/// epoch after it.
+	pub fn epoch_change(&mut self) -> LightClientBlockView {
+		self.append(true, Vec::new())
+	}
+
+	/// The header [`MockNearChain::block`] would append, with `fault`.
+	pub fn faulty_block(&self, fault: Fault) -> LightClientBlockView {
+		self.build(false, &[], Some(fault))
+	}
+
+	/// The header [`MockNearChain::epoch_change`] would append, with `fault`.
+	pub fn faulty_epoch_change(&self, fault: Fault) -> LightClientBlockView {
+		self.build(true, &[], Some(fault))
+	}
+
+	/// The proof of the outcome at `index` in the header at `height`, its outcome root proof and
+	/// the outcome root of the header, as expected by `validate_transaction`.
+	pub fn outcome_proof(
+		&self,
+		height: BlockHeight,
+		index: usize,
+	) -> Option<(OutcomeProof, MerklePath, CryptoHash)> {
+		let position = self.position(height)?;
+		let header = &self.headers[position];
+		let (id, outcome) = self.outcomes[position].get(index)?.clone();
+		let (_, outcome_root_proof, mut paths) =
+			outcome_tree::<H>(&self.outcomes[position]).expect("mock outcomes serialize; qed");
+		let outcome_proof = OutcomeProof {
+			proof: paths.swap_remove(index),
+			block_hash: header.current_block_hash::<H>(),
+			id,
+			outcome,
+		};
+		Some((outcome_proof, outcome_root_proof, header.inner_lite.outcome_root))
+	}
+
+	/// The proof of the outcome at `index` in the header at `height`, up to the
+	/// `block_merkle_root` of the later header at `light_client_head`, as returned by
+	/// `EXPERIMENTAL_light_client_proof`.
+	pub fn full_outcome_proof(
+		&self,
+		height: BlockHeight,
+		index: usize,
+		light_client_head: BlockHeight,
+	) -> Option<FullOutcomeProof> {
+		let position = self.position(height)?;
+		let head_position = self.position(light_client_head)?;
+		if position >= head_position {
+			return None
+		}
+		let (outcome_proof, outcome_root_proof, _) = self.outcome_proof(height, index)?;
+		let header = &self.headers[position];
+		let block_proof = block_tree::<H>(&self.headers[..head_position])
+			.and_then(|tree| tree.proof::<H>(position as u64))
+			.expect("mock headers hash; qed");
+		Some(FullOutcomeProof {
+			outcome_proof,
+			outcome_root_proof,
+			block_header_lite: LightClientBlockLiteView {
+				prev_block_hash: header.prev_block_hash,
+				inner_rest_hash: header.inner_rest_hash,
+				inner_lite: header.inner_lite.clone(),
+			},
+			block_proof,
+		})
+	}
+
+	fn position(&self, height: BlockHeight) -> Option<usize> {
+		self.headers.iter().position(|header| header.inner_lite.height == height)
+	}
+
+	fn append(
+		&mut self,
+		next_epoch: bool,
+		outcomes: Vec<(CryptoHash, ExecutionOutcomeView)>,
+	) -> LightClientBlockView {
+		let header = self.build(next_epoch, &outcomes, None);
+		if next_epoch {
+			self.epoch_ids.push(header.inner_lite.next_epoch_id);
+		}
+		self.epoch += next_epoch as usize;
+		self.skipped_heights = 0;
+		self.headers.push(header.clone());
+		self.outcomes.push(outcomes);
+		header
+	}
+
+	fn build(
+		&self,
+		next_epoch: bool,
+		outcomes: &[(CryptoHash, ExecutionOutcomeView)],
+		fault: Option<Fault>,
+	) -> LightClientBlockView {
+		let head = self.head();
+		let mut epoch = self.epoch + next_epoch as usize;
+		if fault == Some(Fault::SkippedEpoch) {
+			epoch += 2;
+		}
+		let height = match fault {
+			Some(Fault::StaleHeight) => head.inner_lite.height,
+			_ => BlockHeight(head.inner_lite.height.0 + 1 + self.skipped_heights),
+		};
+		let (outcome_root, ..) = outcome_tree::<H>(outcomes).expect("mock outcomes serialize; qed");
+		let block_merkle_root = block_tree::<H>(&self.headers)
+			.and_then(|tree| tree.root::<H>())
+			.expect("mock headers hash; qed");
+
+		let next_epoch_id = if epoch + 1 == self.epoch_ids.len() {
+			// the head is the last header of the epoch before the one of the header
+			EpochId(head.current_block_hash::<H>())
+		} else {
+			self.epoch_id(epoch + 1)
+		};
+
+		let mut builder = HeaderBuilder::child_of(head)
+			.height(height)
+			.prev_block_hash(head.current_block_hash::<H>())
+			.epochs(self.epoch_id(epoch), next_epoch_id)
+			.timestamp(head.inner_lite.timestamp + BLOCK_TIME)
+			.outcome_root(outcome_root)
+			.block_merkle_root(block_merkle_root);
+		if next_epoch || fault == Some(Fault::WrongBpHash) {
+			builder = builder.next_bps::<H>(&self.validators(epoch + 1));
+		}
+		if fault == Some(Fault::WrongBpHash) {
+			builder = builder.next_bp_hash(CryptoHash::default());
+		}
+		let mut header = match fault {
+			Some(Fault::WrongSigners) => builder.sign::<H>(&self.validators(epoch + 1)),
+			Some(Fault::InsufficientStake) => builder
+				.sign_with::<H>(&self.validators(epoch), |index| {
+					3 * (index + 1) <= 2 * self.block_producers
+				}),
+			_ => builder.sign::<H>(&self.validators(epoch)),
+		}
+		.expect("mock headers serialize; qed");
+
+		match fault {
+			Some(Fault::BadSignature) =>
+				if let Some(approval) = header.approvals_after_next.first_mut() {
+					*approval = Some(Signature::Ed25519(
+						Pair::from_seed(&[0; 32]).sign(b"not the approval message"),
+					));
+				},
+			Some(Fault::MissingApproval) => {
+				header.approvals_after_next.pop();
+			},
+			_ => {},
+		}
+		header
+	}
+}
+
+/// The block merkle tree of `headers`, whose root is the `block_merkle_root` of the next header.
+fn block_tree<H: HostFunctions>(
+	headers: &[LightClientBlockView],
+) -> LiteClientResult<BlockMerkleAccumulator> {
+	let mut tree = BlockMerkleAccumulator::new();
+	for header in headers {
+		tree.append::<H>(header.current_block_hash::<H>())?;
+	}
+	Ok(tree)
+}
+
+/// The outcome root of a block with a single shard holding `outcomes`, the outcome root proof of
+/// that shard and the proof of every outcome.
+fn outcome_tree<H: HostFunctions>(
+	outcomes: &[(CryptoHash, ExecutionOutcomeView)],
+) -> LiteClientResult<(CryptoHash, MerklePath, Vec<MerklePath>)> {
+	let outcome_hashes = outcomes
+		.iter()
+		.map(|(id, outcome)| calculate_execution_outcome_hash::<H>(outcome, *id))
+		.collect::<LiteClientResult<Vec<_>>>()?;
+	let (shard_outcome_root, paths) = merklize_leaf_hashes::<H>(&outcome_hashes)?;
+	let (outcome_root, mut shard_paths) = merklize::<H, _>(&[shard_outcome_root])?;
+	Ok((outcome_root, shard_paths.remove(0), paths))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use near_lite_client::{
+		AuditEvent, InMemoryKv, InMemoryStorage, KvBackend, KvStorage, LightClient,
+		LightClientBuilder, NearLiteClientError, RetentionPolicy, VerificationOutcome,
+	};
+	use std::{cell::Cell, rc::Rc};
+
+	type Chain = MockNearChain<Sha256HostFunctions>;
+
+	fn outcome(executor_id: &str) -> ExecutionOutcomeView {
+		ExecutionOutcomeView {
+			logs: vec![],
+			receipt_ids: vec![],
+			gas_burnt: 1,
+			tokens_burnt: 2,
+			executor_id: executor_id.into(),
+			status: vec![0],
+		}
+	}
+
+	fn code(result: LiteClientResult<impl std::fmt::Debug>) -> u16 {
+		result.expect_err("the fault was not detected").code()
+	}
+
+	/// In memory, but failing to write while `failing` is set.
+	struct FailingKv {
+		kv: InMemoryKv,
+		failing: Rc<Cell<bool>>,
+	}
+
+	impl FailingKv {
+		fn check(&self) -> LiteClientResult<()> {
+			match self.failing.get() {
+				true => Err(NearLiteClientError::Storage("disk full".into())),
+				false => Ok(()),
+			}
+		}
+	}
+
+	impl KvBackend for FailingKv {
+		fn get(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>> {
+			self.kv.get(key)
+		}
+
+		fn put(&mut self, key: &[u8], value: &[u8]) -> LiteClientResult<()> {
+			self.check()?;
+			self.kv.put(key, value)
+		}
+
+		fn delete(&mut self, key: &[u8]) -> LiteClientResult<()> {
+			self.check()?;
+			self.kv.delete(key)
+		}
+
+		fn iter_prefix(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+			self.kv.iter_prefix(prefix)
+		}
+	}
+
+	#[test]
+	fn test_client_follows_the_chain() {
+		let mut chain = Chain::new(4);
+		let mut client =
+			LightClient::<Sha256HostFunctions>::with_checkpoint(chain.checkpoint()).unwrap();
+		for _ in 0..3 {
+			client.validate_and_update_head(chain.epoch_change()).unwrap();
+			chain.skip_heights(5);
+			client.validate_and_update_head(chain.block()).unwrap();
+			client.validate_and_update_head(chain.block()).unwrap();
+		}
+		assert_eq!(client.head_hash(), chain.head().current_block_hash::<Sha256HostFunctions>());
+		assert_eq!(client.current_epoch_id(), chain.epoch_id(3));
+		assert_eq!(chain.headers().len(), 9);
+		assert_eq!(chain.head().inner_lite.height, BlockHeight(1 + 9 + 3 * 5));
+		for pair in chain.headers().windows(2) {
+			assert_eq!(
+				pair[1].prev_block_hash,
+				pair[0].current_block_hash::<Sha256HostFunctions>()
+			);
+		}
+	}
+
+	#[test]
+	fn test_check_header_leaves_the_client_unchanged() {
+		let mut chain = Chain::new(4);
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.storage(InMemoryStorage::default())
+			.build()
+			.unwrap();
+		let epoch_change = chain.epoch_change();
+		let faulty = chain.faulty_block(Fault::BadSignature);
+
+		assert!(matches!(
+			client.check_header(&epoch_change),
+			VerificationOutcome::Valid { epoch_changed: true, .. }
+		));
+		assert_eq!(client.head_height(), BlockHeight(1));
+		assert!(client.block_hash_at(epoch_change.inner_lite.height).unwrap().is_none());
+		assert!(client.epoch_block_producers(&chain.epoch_id(2)).is_none());
+
+		client.validate_and_update_head(epoch_change.clone()).unwrap();
+		assert!(matches!(
+			client.check_header(&epoch_change),
+			VerificationOutcome::AlreadyValidated
+		));
+		let outcome = client.check_header(&faulty);
+		assert!(!outcome.is_valid());
+		match outcome {
+			VerificationOutcome::Invalid(err) =>
+				assert_eq!(err.code(), code(client.validate_and_update_head(faulty))),
+			outcome => panic!("the bad signature was not detected: {:?}", outcome),
+		}
+	}
+
+	#[test]
+	fn test_failed_commit_leaves_the_client_unchanged() {
+		let mut chain = Chain::new(4);
+		let failing = Rc::new(Cell::new(false));
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.storage(KvStorage::new(FailingKv {
+				kv: InMemoryKv::default(),
+				failing: failing.clone(),
+			}))
+			.header_mmr()
+			.audit_log()
+			.build()
+			.unwrap();
+		let mmr_root = client.header_mmr_root().unwrap();
+		let epoch_change = chain.epoch_change();
+
+		failing.set(true);
+		assert_eq!(code(client.validate_and_update_head(epoch_change.clone())), 18);
+		assert_eq!(client.head_height(), BlockHeight(1));
+		assert!(client.epoch_block_producers(&chain.epoch_id(2)).is_none());
+		assert_eq!(client.header_mmr_root().unwrap(), mmr_root);
+		assert!(client.block_hash_at(epoch_change.inner_lite.height).unwrap().is_none());
+		assert!(client.audit_log().unwrap().entries().is_empty());
+
+		// the header was not taken for one already accepted
+		failing.set(false);
+		let summary = client.validate_and_update_head(epoch_change.clone()).unwrap();
+		assert!(!summary.already_validated);
+		assert_eq!(client.head_hash(), epoch_change.current_block_hash::<Sha256HostFunctions>());
+		assert!(client.epoch_block_producers(&chain.epoch_id(2)).is_some());
+		assert_ne!(client.header_mmr_root().unwrap(), mmr_root);
+		let events = client.audit_log().unwrap().entries().iter().map(|entry| &entry.event);
+		assert!(matches!(
+			events.collect::<Vec<_>>()[..],
+			[AuditEvent::ValidatorSetRotation { .. }, AuditEvent::HeadUpdateAccepted { .. }]
+		));
+	}
+
+	#[test]
+	fn test_sequential_client_takes_every_block() {
+		let mut chain = Chain::new(4);
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.sequential_headers()
+			.build()
+			.unwrap();
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+
+		let next = chain.block();
+		chain.skip_heights(5);
+		let after_next = chain.block();
+		assert_eq!(code(client.validate_and_update_head(after_next.clone())), 122);
+		client.validate_and_update_head(next).unwrap();
+		client.validate_and_update_head(after_next).unwrap();
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+		assert_eq!(client.head_hash(), chain.head().current_block_hash::<Sha256HostFunctions>());
+	}
+
+	#[test]
+	fn test_candidates_become_the_head_once_final() {
+		let mut chain = Chain::new(4);
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.sequential_headers()
+			.build()
+			.unwrap();
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+		let finalized_height = client.head_height();
+
+		let first = chain.block();
+		assert!(client.submit_candidate(first.clone()).unwrap().is_empty());
+		assert!(client.submit_candidate(chain.block()).unwrap().is_empty());
+		assert_eq!(client.latest_head().inner_lite.height, chain.head().inner_lite.height);
+		assert_eq!(client.head_height(), finalized_height);
+
+		// the third block in a row finalizes the first one
+		assert_eq!(client.submit_candidate(chain.block()).unwrap(), [first.inner_lite.height]);
+		assert_eq!(client.head_hash(), first.current_block_hash::<Sha256HostFunctions>());
+		assert_eq!(client.latest_head().inner_lite.height, chain.head().inner_lite.height);
+	}
+
+	#[test]
+	fn test_faults_are_rejected() {
+		let mut chain = Chain::new(4);
+		let mut client =
+			LightClient::<Sha256HostFunctions>::with_checkpoint(chain.checkpoint()).unwrap();
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+
+		for (fault, expected) in [
+			(Fault::BadSignature, 104),
+			(Fault::WrongSigners, 104),
+			(Fault::InsufficientStake, 105),
+			(Fault::MissingApproval, 113),
+			(Fault::WrongBpHash, 106),
+			(Fault::SkippedEpoch, 101),
+		] {
+			assert_eq!(
+				code(client.validate_and_update_head(chain.faulty_block(fault))),
+				expected,
+				"{:?} in a block",
+				fault
+			);
+			assert_eq!(
+				code(client.validate_and_update_head(chain.faulty_epoch_change(fault))),
+				expected,
+				"{:?} in an epoch change",
+				fault
+			);
+		}
+		assert!(matches!(
+			client.validate_and_update_head(chain.faulty_block(Fault::StaleHeight)),
+			Err(NearLiteClientError::StaleHeader { .. })
+		));
+
+		// the chain carries on after the rejected headers
+		client.validate_and_update_head(chain.block()).unwrap();
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+	}
+
+	#[test]
+	fn test_outcome_proofs() {
+		let mut chain = Chain::new(1);
+		let ids = [CryptoHash([1; 32]), CryptoHash([2; 32]), CryptoHash([3; 32])];
+		let block_view =
+			chain.block_with_outcomes(ids.iter().map(|id| (*id, outcome("bridge.near"))).collect());
+		for index in 0..ids.len() {
+			let (outcome_proof, outcome_root_proof, outcome_root) =
+				chain.outcome_proof(block_view.inner_lite.height, index).unwrap();
+			assert_eq!(outcome_proof.id, ids[index]);
+			assert_eq!(outcome_root, block_view.inner_lite.outcome_root);
+			near_lite_client::validate_transaction::<Sha256HostFunctions>(
+				&outcome_proof,
+				outcome_root_proof,
+				outcome_root,
+			)
+			.unwrap();
+		}
+		assert!(chain.outcome_proof(block_view.inner_lite.height, ids.len()).is_none());
+		assert!(chain.outcome_proof(BlockHeight(100), 0).is_none());
+
+		let height = block_view.inner_lite.height;
+		assert!(chain.full_outcome_proof(height, 0, height).is_none());
+		chain.block();
+		let head = chain.epoch_change();
+		let proof = chain.full_outcome_proof(height, 1, head.inner_lite.height).unwrap();
+		let roots = proof.verify::<Sha256HostFunctions>(head.inner_lite.block_merkle_root).unwrap();
+		assert_eq!(roots.block_outcome_root, block_view.inner_lite.outcome_root);
+	}
+
+	#[test]
+	fn test_accepted_heads_are_indexed_by_height() {
+		let mut chain = Chain::new(4);
+		let checkpoint_height = chain.head().inner_lite.height;
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.storage(InMemoryStorage::default())
+			.build()
+			.unwrap();
+		let mut accepted = vec![chain.head().clone()];
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+		accepted.push(chain.head().clone());
+		chain.skip_heights(3);
+		client.validate_and_update_head(chain.block()).unwrap();
+		accepted.push(chain.head().clone());
+
+		for block_view in &accepted {
+			assert_eq!(
+				client.block_hash_at(block_view.inner_lite.height).unwrap(),
+				Some(block_view.current_block_hash::<Sha256HostFunctions>())
+			);
+		}
+		let skipped = BlockHeight(client.head_height().0 - 1);
+		assert!(client.block_hash_at(skipped).unwrap().is_none());
+		assert!(client.block_hash_at(BlockHeight(checkpoint_height.0 - 1)).unwrap().is_none());
+	}
+
+	#[test]
+	fn test_history_beyond_the_retained_heights_is_pruned() {
+		let mut chain = Chain::new(4);
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.storage(InMemoryStorage::default())
+			.retained_epochs(2)
+			.retention(RetentionPolicy::Heights(10))
+			.audit_log()
+			.build()
+			.unwrap();
+		let checkpoint_height = client.head_height();
+		let mut pruned = Vec::new();
+		for _ in 0..3 {
+			pruned.extend(
+				client.validate_and_update_head(chain.epoch_change()).unwrap().pruned_below,
+			);
+			chain.skip_heights(5);
+			pruned.extend(client.validate_and_update_head(chain.block()).unwrap().pruned_below);
+			pruned.extend(client.validate_and_update_head(chain.block()).unwrap().pruned_below);
+		}
+		assert_eq!(client.head_height(), BlockHeight(25));
+		// the last head found nothing left to prune below height 15
+		assert_eq!(pruned.last(), Some(&BlockHeight(14)));
+		let events = client.audit_log().unwrap().entries().iter().map(|entry| &entry.event);
+		assert_eq!(
+			events.filter(|event| matches!(event, AuditEvent::Pruned { .. })).count(),
+			pruned.len()
+		);
+
+		assert!(client.block_hash_at(checkpoint_height).unwrap().is_none());
+		assert!(client.block_hash_at(BlockHeight(15)).unwrap().is_none(), "never a head");
+		assert!(client.block_hash_at(BlockHeight(16)).unwrap().is_some());
+		// the first epoch ended before height 14, the second one only at height 17
+		assert!(client.epoch_validators(&chain.epoch_id(1)).unwrap().is_none());
+		assert!(client.epoch_block_producers(&chain.epoch_id(2)).is_none());
+		assert!(client.epoch_validators(&chain.epoch_id(2)).unwrap().is_some());
+	}
+
+	#[test]
+	fn test_accepted_heads_are_proven_against_the_header_mmr() {
+		let mut chain = Chain::new(4);
+		let mut client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.header_mmr()
+			.build()
+			.unwrap();
+		let mut accepted = vec![chain.head().clone()];
+		client.validate_and_update_head(chain.epoch_change()).unwrap();
+		accepted.push(chain.head().clone());
+		chain.skip_heights(2);
+		client.validate_and_update_head(chain.block()).unwrap();
+		accepted.push(chain.head().clone());
+		// an already accepted head is not appended again
+		client.validate_and_update_head(chain.head().clone()).unwrap();
+
+		let root = client.header_mmr_root().unwrap().unwrap();
+		for (leaf_index, block_view) in accepted.iter().enumerate() {
+			let proof = client.prove_header_inclusion(block_view.inner_lite.height).unwrap();
+			assert_eq!(proof.leaf_index, leaf_index as u64);
+			assert_eq!(proof.size, accepted.len() as u64);
+			assert_eq!(proof.block_hash, block_view.current_block_hash::<Sha256HostFunctions>());
+			proof.verify::<Sha256HostFunctions>(&root).unwrap();
+		}
+		let skipped = BlockHeight(client.head_height().0 - 1);
+		assert!(client.prove_header_inclusion(skipped).is_err());
+
+		let client = LightClientBuilder::<Sha256HostFunctions>::new()
+			.checkpoint(chain.checkpoint())
+			.build()
+			.unwrap();
+		assert!(client.header_mmr_root().unwrap().is_none());
+		assert!(client.prove_header_inclusion(client.head_height()).is_err());
+	}
+}
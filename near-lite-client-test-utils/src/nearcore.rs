@@ -0,0 +1,136 @@
+//! # nearcore reference
+//!
+//! The light client rules of the specification (<https://nomicon.io/ChainSpec/LightClient>),
+//! written against nearcore's own types: block hashes, the approval message and signatures are
+//! all computed by `near-primitives` and `near-crypto`. nearcore only produces light client
+//! blocks, so this is the closest there is to a reference implementation, for
+//! differential tests and fuzz targets to compare the verdicts of the client with.
+
+use std::collections::{BTreeMap, HashMap};
+
+use borsh::BorshSerialize;
+use near_lite_client::{EpochId, LightClientBlockView, ValidatorStakeView};
+use near_primitives::{
+	block_header::{Approval, ApprovalInner},
+	hash::{hash, CryptoHash},
+	merkle::combine_hash,
+	views::{
+		validator_stake_view::ValidatorStakeView as NearValidatorStakeView,
+		LightClientBlockLiteView, LightClientBlockView as NearLightClientBlockView,
+	},
+};
+
+pub type EpochBlockProducers = HashMap<CryptoHash, Vec<NearValidatorStakeView>>;
+
+/// `block_view` as nearcore serves it.
+pub fn block_view(block_view: &LightClientBlockView) -> NearLightClientBlockView {
+	block_view
+		.clone()
+		.try_into()
+		.expect("every block view converts to nearcore's; qed")
+}
+
+/// The block producers of every epoch, as nearcore serves them.
+pub fn epoch_block_producers(
+	epoch_block_producers: &BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+) -> EpochBlockProducers {
+	epoch_block_producers
+		.iter()
+		.map(|(epoch_id, block_producers)| {
+			let block_producers = block_producers
+				.iter()
+				.map(|block_producer| {
+					block_producer.clone().try_into().expect("every stake view converts; qed")
+				})
+				.collect();
+			(epoch_id.0.into(), block_producers)
+		})
+		.collect()
+}
+
+/// The specification's `validate_and_update_head`, without the update: whether `block_view` may
+/// become the head after `head`.
+pub fn accepts_light_block(
+	head: &NearLightClientBlockView,
+	block_view: &NearLightClientBlockView,
+	epoch_block_producers: &EpochBlockProducers,
+) -> bool {
+	let (head_lite, block_lite) = (&head.inner_lite, &block_view.inner_lite);
+	if block_lite.height <= head_lite.height {
+		return false
+	}
+	if ![head_lite.epoch_id, head_lite.next_epoch_id].contains(&block_lite.epoch_id) {
+		return false
+	}
+	if block_lite.epoch_id == head_lite.next_epoch_id && block_view.next_bps.is_none() {
+		return false
+	}
+	let block_producers = match epoch_block_producers.get(&block_lite.epoch_id) {
+		Some(block_producers) => block_producers,
+		None => return false,
+	};
+	if block_view.approvals_after_next.len() < block_producers.len() {
+		return false
+	}
+
+	let current_block_hash = LightClientBlockLiteView {
+		prev_block_hash: block_view.prev_block_hash,
+		inner_rest_hash: block_view.inner_rest_hash,
+		inner_lite: block_lite.clone(),
+	}
+	.hash();
+	let next_block_hash = combine_hash(&block_view.next_block_inner_hash, &current_block_hash);
+	let approval_message = Approval::get_data_for_sig(
+		&ApprovalInner::Endorsement(next_block_hash),
+		block_lite.height + 2,
+	);
+
+	let (mut total_stake, mut approved_stake) = (0u128, 0u128);
+	for (approval, block_producer) in block_view.approvals_after_next.iter().zip(block_producers) {
+		let block_producer = block_producer.clone().into_validator_stake();
+		// the specification doesn't expect the stakes to overflow, a client can only reject them
+		total_stake = match total_stake.checked_add(block_producer.stake()) {
+			Some(total_stake) => total_stake,
+			None => return false,
+		};
+		if let Some(signature) = approval {
+			approved_stake += block_producer.stake();
+			if !signature.verify(&approval_message, block_producer.public_key()) {
+				return false
+			}
+		}
+	}
+	// `total_stake * 2 / 3`, without overflowing
+	let threshold = total_stake / 3 * 2 + total_stake % 3 * 2 / 3;
+	if approved_stake <= threshold {
+		return false
+	}
+
+	match &block_view.next_bps {
+		Some(next_bps) =>
+			hash(&next_bps.try_to_vec().expect("stake views serialize; qed")) ==
+				block_lite.next_bp_hash,
+		None => true,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MockNearChain;
+
+	#[test]
+	fn test_accepts_the_mock_chain() {
+		let mut chain = MockNearChain::<near_lite_client::Sha256HostFunctions>::new(4);
+		let checkpoint = chain.checkpoint().0;
+		let block = chain.epoch_change();
+		let mut known = BTreeMap::new();
+		known.insert(checkpoint.inner_lite.next_epoch_id, checkpoint.next_bps.clone().unwrap());
+		let epoch_block_producers = epoch_block_producers(&known);
+
+		let (head, block) = (super::block_view(&checkpoint), super::block_view(&block));
+		assert!(accepts_light_block(&head, &block, &epoch_block_producers));
+		assert!(!accepts_light_block(&block, &head, &epoch_block_producers));
+		assert!(!accepts_light_block(&head, &block, &HashMap::new()));
+	}
+}
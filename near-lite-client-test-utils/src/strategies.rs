@@ -0,0 +1,153 @@
+//! # Strategies
+//!
+//! `proptest` strategies over the [builders](crate::builders). Hashes are SHA-256, as on NEAR, so
+//! the generated data verifies with [`Sha256HostFunctions`].
+
+use near_lite_client::Sha256HostFunctions;
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, Direction, EpochId, ExecutionOutcomeView, MerklePath, MerklePathItem,
+	OutcomeProof,
+};
+use proptest::{collection::vec, prelude::*};
+
+use crate::builders::{OutcomeProofBuilder, SignedHeader, ValidatorSet};
+
+/// Stakes stay low enough for the total of any generated set to fit in a `u128`.
+const MAX_STAKE: u128 = 1 << 100;
+
+pub fn crypto_hash() -> impl Strategy<Value = CryptoHash> {
+	any::<[u8; 32]>().prop_map(CryptoHash)
+}
+
+pub fn epoch_id() -> impl Strategy<Value = EpochId> {
+	crypto_hash().prop_map(EpochId)
+}
+
+pub fn merkle_path(max_len: usize) -> impl Strategy<Value = MerklePath> {
+	vec(
+		(crypto_hash(), any::<bool>()).prop_map(|(hash, left)| MerklePathItem {
+			hash,
+			direction: if left { Direction::Left } else { Direction::Right },
+		}),
+		0..=max_len,
+	)
+}
+
+/// Between one and `max_len` block producers with non-zero stakes.
+pub fn validator_set(max_len: usize) -> impl Strategy<Value = ValidatorSet> {
+	vec(1..=MAX_STAKE, 1..=max_len).prop_map(ValidatorSet::with_stakes)
+}
+
+/// A head and the first header of its next epoch, each epoch with at most `max_block_producers`
+/// block producers.
+pub fn signed_header(max_block_producers: usize) -> impl Strategy<Value = SignedHeader> {
+	(
+		1..u64::MAX / 2,
+		[epoch_id(), epoch_id(), epoch_id()],
+		validator_set(max_block_producers),
+		validator_set(max_block_producers),
+	)
+		.prop_map(|(height, epochs, validators, next_validators)| {
+			SignedHeader::new::<Sha256HostFunctions>(
+				BlockHeight(height),
+				epochs,
+				&validators,
+				&next_validators,
+			)
+			.expect("generated headers serialize; qed")
+		})
+}
+
+pub fn execution_outcome() -> impl Strategy<Value = ExecutionOutcomeView> {
+	(
+		vec(".{0,32}", 0..4),
+		vec(crypto_hash(), 0..4),
+		any::<u64>(),
+		any::<u128>(),
+		"[a-z0-9_.-]{2,64}",
+		vec(any::<u8>(), 0..64),
+	)
+		.prop_map(|(logs, receipt_ids, gas_burnt, tokens_burnt, executor_id, status)| {
+			ExecutionOutcomeView { logs, receipt_ids, gas_burnt, tokens_burnt, executor_id, status }
+		})
+}
+
+/// An outcome proof with at most `max_depth` items in each of its paths, together with its
+/// outcome root proof and the outcome root of the block they lead to.
+pub fn outcome_proof(
+	max_depth: usize,
+) -> impl Strategy<Value = (OutcomeProof, MerklePath, CryptoHash)> {
+	(crypto_hash(), execution_outcome(), merkle_path(max_depth), merkle_path(max_depth)).prop_map(
+		|(id, outcome, proof, outcome_root_proof)| {
+			OutcomeProofBuilder::new(id, outcome)
+				.proof(proof)
+				.outcome_root_proof(outcome_root_proof)
+				.build::<Sha256HostFunctions>()
+				.expect("generated outcomes serialize; qed")
+		},
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::builders::HeaderBuilder;
+	use near_lite_client::{
+		validate_head, validate_transaction, LightClient, NearLiteClientError, VerificationFailure,
+	};
+
+	proptest! {
+		#![proptest_config(ProptestConfig::with_cases(16))]
+
+		#[test]
+		fn test_signed_headers_are_accepted(chain in signed_header(8)) {
+			validate_head::<Sha256HostFunctions>(
+				&chain.head,
+				&chain.block_view,
+				&chain.epoch_block_producers,
+			)
+			.unwrap();
+
+			let mut client = LightClient::<Sha256HostFunctions>::with_checkpoint(chain.checkpoint()).unwrap();
+			client.validate_and_update_head(chain.block_view.clone()).unwrap();
+			prop_assert_eq!(client.head_height(), chain.block_view.inner_lite.height);
+		}
+
+		#[test]
+		fn test_outcome_proofs_verify((proof, root_proof, root) in outcome_proof(8)) {
+			validate_transaction::<Sha256HostFunctions>(&proof, &root_proof, root).unwrap();
+		}
+	}
+
+	#[test]
+	fn test_missing_approvals_are_rejected() {
+		let validators = ValidatorSet::new(3);
+		let chain = SignedHeader::new::<Sha256HostFunctions>(
+			BlockHeight(1),
+			[
+				EpochId(CryptoHash([1; 32])),
+				EpochId(CryptoHash([2; 32])),
+				EpochId(CryptoHash([3; 32])),
+			],
+			&validators,
+			&validators,
+		)
+		.unwrap();
+		let block_view = HeaderBuilder::child_of(&chain.head)
+			.epochs(chain.block_view.inner_lite.epoch_id, chain.block_view.inner_lite.next_epoch_id)
+			.next_bps::<Sha256HostFunctions>(&validators)
+			.sign_with::<Sha256HostFunctions>(&validators, |index| index == 0)
+			.unwrap();
+
+		let error = validate_head::<Sha256HostFunctions>(
+			&chain.head,
+			&block_view,
+			&chain.epoch_block_producers,
+		)
+		.unwrap_err();
+		assert!(matches!(
+			error,
+			NearLiteClientError::Verification(VerificationFailure::StakeThresholdNotReached { .. })
+		));
+	}
+}
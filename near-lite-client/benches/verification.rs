@@ -0,0 +1,123 @@
+//! Benchmarks of the verification paths over headers and an outcome proof taken from NEAR's RPC:
+//! the headers are the testnet ones of the shared `fixtures`, the outcome proof is in
+//! `benches/fixtures`. Run with `cargo bench -p near-lite-client`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use near_lite_client::{validate_head, validate_transaction, CryptoHash, LightClientBlockView};
+use near_primitives::views::LightClientBlockView as NearLightClientBlockView;
+use near_primitives_wasm::{
+	Direction, ExecutionOutcomeView, HostFunctions, MerklePathItem, OutcomeProof,
+	Sha256HostFunctions,
+};
+use serde_json::Value;
+use std::{collections::BTreeMap, str::FromStr};
+
+type H = Sha256HostFunctions;
+
+/// `next_light_client_block` responses of testnet, named by the height of their block.
+const CHECKPOINT_BLOCK: &str =
+	include_str!("../../fixtures/testnet/next_light_client_block/86441383.json");
+const BLOCK: &str = include_str!("../../fixtures/testnet/next_light_client_block/86455909.json");
+const NEXT_EPOCH_BLOCK: &str =
+	include_str!("../../fixtures/testnet/next_light_client_block/86456070.json");
+
+fn block_view(rpc_response: &str) -> LightClientBlockView {
+	let response: Value = serde_json::from_str(rpc_response).unwrap();
+	serde_json::from_value::<NearLightClientBlockView>(response["result"].clone())
+		.unwrap()
+		.try_into()
+		.unwrap()
+}
+
+fn hash(value: &Value) -> CryptoHash {
+	CryptoHash::from_str(value.as_str().unwrap()).unwrap()
+}
+
+fn merkle_path(value: &Value) -> Vec<MerklePathItem> {
+	value
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|item| MerklePathItem {
+			hash: hash(&item["hash"]),
+			direction: match item["direction"].as_str().unwrap() {
+				"Left" => Direction::Left,
+				_ => Direction::Right,
+			},
+		})
+		.collect()
+}
+
+/// The outcome proof, its outcome root proof and the outcome root of its block.
+fn outcome_proof() -> (OutcomeProof, Vec<MerklePathItem>, CryptoHash) {
+	let fixture: Value = serde_json::from_str(include_str!("fixtures/outcome_proof.json")).unwrap();
+	let outcome = &fixture["outcome"];
+	let outcome_proof = OutcomeProof {
+		block_hash: CryptoHash::default(),
+		id: hash(&fixture["id"]),
+		proof: merkle_path(&fixture["proof"]),
+		outcome: ExecutionOutcomeView {
+			logs: Vec::new(),
+			receipt_ids: outcome["receipt_ids"].as_array().unwrap().iter().map(hash).collect(),
+			gas_burnt: outcome["gas_burnt"].as_u64().unwrap(),
+			tokens_burnt: outcome["tokens_burnt"].as_str().unwrap().parse().unwrap(),
+			executor_id: outcome["executor_id"].as_str().unwrap().into(),
+			status: hex::decode(outcome["status"].as_str().unwrap()).unwrap(),
+		},
+	};
+	(
+		outcome_proof,
+		merkle_path(&fixture["outcome_root_proof"]),
+		hash(&fixture["block_outcome_root"]),
+	)
+}
+
+fn header_validation(c: &mut Criterion) {
+	let checkpoint = block_view(CHECKPOINT_BLOCK);
+	let block_view = block_view(BLOCK);
+	let epoch_block_producers: BTreeMap<_, _> =
+		[(checkpoint.inner_lite.next_epoch_id, checkpoint.next_bps.clone().unwrap())].into();
+	assert!(validate_head::<H>(&checkpoint, &block_view, &epoch_block_producers).is_ok());
+
+	c.bench_function("validate_head", |b| {
+		b.iter(|| validate_head::<H>(&checkpoint, black_box(&block_view), &epoch_block_producers))
+	});
+	c.bench_function("current_block_hash", |b| {
+		b.iter(|| black_box(&block_view).current_block_hash::<H>())
+	});
+	let next_bps = checkpoint.next_bps.as_deref().unwrap();
+	c.bench_function("next_bp_hash", |b| b.iter(|| H::sha256_borsh(black_box(next_bps))));
+}
+
+fn outcome_proof_verification(c: &mut Criterion) {
+	let (outcome_proof, outcome_root_proof, block_outcome_root) = outcome_proof();
+	assert!(
+		validate_transaction::<H>(&outcome_proof, &outcome_root_proof, block_outcome_root).is_ok()
+	);
+
+	c.bench_function("validate_transaction", |b| {
+		b.iter(|| {
+			validate_transaction::<H>(
+				black_box(&outcome_proof),
+				&outcome_root_proof,
+				block_outcome_root,
+			)
+		})
+	});
+}
+
+fn borsh_encoding(c: &mut Criterion) {
+	let block_view = block_view(NEXT_EPOCH_BLOCK);
+	let encoded = block_view.try_to_vec().unwrap();
+
+	c.bench_function("serialize LightClientBlockView", |b| {
+		b.iter(|| black_box(&block_view).try_to_vec().unwrap())
+	});
+	c.bench_function("deserialize LightClientBlockView", |b| {
+		b.iter(|| LightClientBlockView::try_from_slice(black_box(&encoded)).unwrap())
+	});
+}
+
+criterion_group!(benches, header_validation, outcome_proof_verification, borsh_encoding);
+criterion_main!(benches);
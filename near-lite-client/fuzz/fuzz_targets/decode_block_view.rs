@@ -0,0 +1,21 @@
+//! Arbitrary bytes through both borsh decoders of `LightClientBlockView`, then through the hashing
+//! of whatever they accept.
+#![no_main]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use libfuzzer_sys::fuzz_target;
+use near_lite_client::{DecodeLimits, LightClientBlockView, Sha256HostFunctions};
+
+fuzz_target!(|data: &[u8]| {
+	let bounded = DecodeLimits::default().decode_block_view(data);
+	match LightClientBlockView::try_from_slice(data) {
+		Ok(block_view) => {
+			// the bounded decoder can only be stricter
+			if let Ok(bounded) = bounded {
+				assert_eq!(bounded.try_to_vec().unwrap(), block_view.try_to_vec().unwrap());
+			}
+			block_view.current_block_hash::<Sha256HostFunctions>();
+		},
+		Err(_) => assert!(bounded.is_err()),
+	}
+});
@@ -0,0 +1,24 @@
+//! Arbitrary bytes through both borsh decoders of `OutcomeProof`, then through the verification
+//! of whatever they accept.
+#![no_main]
+
+use borsh::BorshDeserialize;
+use libfuzzer_sys::fuzz_target;
+use near_lite_client::{
+	verify_outcome, CryptoHash, DecodeLimits, OutcomeProof, Sha256HostFunctions,
+};
+
+fuzz_target!(|data: &[u8]| {
+	let bounded = DecodeLimits::default().decode_outcome_proof(data);
+	match OutcomeProof::try_from_slice(data) {
+		Ok(outcome_proof) => {
+			let outcome_root_proof = outcome_proof.proof.clone();
+			let _ = verify_outcome::<Sha256HostFunctions>(
+				&outcome_proof,
+				&outcome_root_proof,
+				CryptoHash::default(),
+			);
+		},
+		Err(_) => assert!(bounded.is_err()),
+	}
+});
@@ -0,0 +1,120 @@
+//! Headers generated like `validate_light_block`'s, validated both by this crate and by the
+//! nearcore reference of `near-lite-client-test-utils`. Any header one accepts and the other
+//! rejects is a finding.
+//!
+//! Well-formed `next_bps` (sorted, without duplicates nor zero stakes) are a rule of this crate
+//! only: headers breaking it must be rejected, and are not compared.
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+use near_lite_client::{
+	benchmarking::HeaderSetup, validate_head, validate_next_bps, BlockHeight, CryptoHash, EpochId,
+	Sha256HostFunctions, Signature, ValidatorStakeView,
+};
+use near_lite_client_test_utils::nearcore;
+
+type H = Sha256HostFunctions;
+
+#[derive(Debug, Arbitrary)]
+struct Mutations {
+	block_producers: u8,
+	height: Option<u64>,
+	epoch_id: Option<[u8; 32]>,
+	next_epoch_id: Option<[u8; 32]>,
+	next_bp_hash: Option<[u8; 32]>,
+	prev_block_hash: Option<[u8; 32]>,
+	inner_rest_hash: Option<[u8; 32]>,
+	next_block_inner_hash: Option<[u8; 32]>,
+	timestamp: Option<u64>,
+	drop_next_bps: bool,
+	/// index of a next block producer and its new stake
+	next_stakes: Vec<(u8, u128)>,
+	approvals_len: Option<u8>,
+	rotate_approvals: u8,
+	/// indices of approvals to remove
+	remove_approvals: Vec<u8>,
+	/// index of an approval and the byte to replace its first byte with
+	corrupt_approval: Option<(u8, u8)>,
+	/// index of a block producer and its new stake
+	stakes: Vec<(u8, u128)>,
+}
+
+fuzz_target!(|mutations: Mutations| {
+	let block_producers = u32::from(mutations.block_producers % 16);
+	let mut setup = HeaderSetup::new::<H>(block_producers).expect("the setup is valid; qed");
+	let block_view = &mut setup.block_view;
+
+	if let Some(height) = mutations.height {
+		block_view.inner_lite.height = BlockHeight(height);
+	}
+	if let Some(epoch_id) = mutations.epoch_id {
+		block_view.inner_lite.epoch_id = EpochId(CryptoHash(epoch_id));
+	}
+	if let Some(next_epoch_id) = mutations.next_epoch_id {
+		block_view.inner_lite.next_epoch_id = EpochId(CryptoHash(next_epoch_id));
+	}
+	if let Some(next_bp_hash) = mutations.next_bp_hash {
+		block_view.inner_lite.next_bp_hash = CryptoHash(next_bp_hash);
+	}
+	if let Some(prev_block_hash) = mutations.prev_block_hash {
+		block_view.prev_block_hash = CryptoHash(prev_block_hash);
+	}
+	if let Some(inner_rest_hash) = mutations.inner_rest_hash {
+		block_view.inner_rest_hash = CryptoHash(inner_rest_hash);
+	}
+	if let Some(next_block_inner_hash) = mutations.next_block_inner_hash {
+		block_view.next_block_inner_hash = CryptoHash(next_block_inner_hash);
+	}
+	if let Some(timestamp) = mutations.timestamp {
+		block_view.inner_lite.timestamp = timestamp;
+	}
+	if mutations.drop_next_bps {
+		block_view.next_bps = None;
+	}
+	if let Some(next_bps) = block_view.next_bps.as_mut() {
+		for (index, stake) in mutations.next_stakes {
+			if let Some(ValidatorStakeView::V1(producer)) = next_bps.get_mut(usize::from(index)) {
+				producer.stake = stake;
+			}
+		}
+	}
+	if let Some(len) = mutations.approvals_len {
+		block_view.approvals_after_next.resize(usize::from(len), None);
+	}
+	let approvals = &mut block_view.approvals_after_next;
+	if !approvals.is_empty() {
+		let len = approvals.len();
+		approvals.rotate_left(usize::from(mutations.rotate_approvals) % len);
+		for index in mutations.remove_approvals {
+			approvals[usize::from(index) % len] = None;
+		}
+		if let Some((index, byte)) = mutations.corrupt_approval {
+			if let Some(signature) = approvals[usize::from(index) % len].as_mut() {
+				let mut raw = signature.as_bytes().to_vec();
+				raw[0] = byte;
+				*signature = Signature::try_from(raw.as_slice()).expect("64 bytes long; qed");
+			}
+		}
+	}
+	for (index, stake) in mutations.stakes {
+		for producers in setup.epoch_block_producers.values_mut() {
+			if let Some(ValidatorStakeView::V1(producer)) = producers.get_mut(usize::from(index)) {
+				producer.stake = stake;
+			}
+		}
+	}
+
+	let ours = validate_head::<H>(&setup.head, &setup.block_view, &setup.epoch_block_producers);
+	if let Some(next_bps) = &setup.block_view.next_bps {
+		if validate_next_bps(next_bps).is_err() {
+			assert!(ours.is_err(), "malformed next_bps accepted");
+			return
+		}
+	}
+	let reference = nearcore::accepts_light_block(
+		&nearcore::block_view(&setup.head),
+		&nearcore::block_view(&setup.block_view),
+		&nearcore::epoch_block_producers(&setup.epoch_block_producers),
+	);
+	assert_eq!(ours.is_ok(), reference, "nearcore disagrees, this crate returned {:?}", ours);
+});
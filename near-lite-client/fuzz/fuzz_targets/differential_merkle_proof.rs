@@ -0,0 +1,75 @@
+//! Merkle trees and paths built by this crate and by `near-primitives`, then paths mutated and
+//! verified by both. The roots, the paths and every verdict must be the same.
+#![no_main]
+
+use borsh::BorshSerialize;
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+use near_lite_client::{compute_root_from_path, merklize, MerklePath, Sha256HostFunctions};
+use near_primitives::{
+	hash::{hash, CryptoHash as NearCryptoHash},
+	merkle::{self as near_merkle, Direction},
+};
+
+type H = Sha256HostFunctions;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+	leaves: Vec<[u8; 32]>,
+	index: u16,
+	/// verified instead of the leaf at `index`
+	item: Option<[u8; 32]>,
+	/// index of a path item whose direction is flipped
+	flip_direction: Option<u8>,
+	/// index of a path item and the byte to replace its first byte with
+	corrupt_hash: Option<(u8, u8)>,
+	truncate: Option<u8>,
+	extend: Option<[u8; 32]>,
+}
+
+fuzz_target!(|input: Input| {
+	let leaves = &input.leaves[..input.leaves.len().min(512)];
+	if leaves.is_empty() {
+		return
+	}
+	let (near_root, near_paths) = near_merkle::merklize(leaves);
+	let (root, paths) = merklize::<H, _>(leaves).expect("leaves serialize; qed");
+	assert_eq!(root, near_root.into(), "roots differ");
+	for (path, near_path) in paths.iter().zip(&near_paths) {
+		let converted: MerklePath = near_path.iter().cloned().map(Into::into).collect();
+		assert_eq!(*path, converted, "paths differ");
+	}
+
+	let index = usize::from(input.index) % leaves.len();
+	let mut near_path = near_paths[index].clone();
+	if let Some(position) = input.flip_direction {
+		if let Some(item) = near_path.get_mut(usize::from(position)) {
+			item.direction = match item.direction {
+				Direction::Left => Direction::Right,
+				Direction::Right => Direction::Left,
+			};
+		}
+	}
+	if let Some((position, byte)) = input.corrupt_hash {
+		if let Some(item) = near_path.get_mut(usize::from(position)) {
+			let mut raw = item.hash.0;
+			raw[0] = byte;
+			item.hash = NearCryptoHash(raw);
+		}
+	}
+	if let Some(len) = input.truncate {
+		near_path.truncate(usize::from(len));
+	}
+	if let Some(sibling) = input.extend {
+		near_path.push(near_merkle::MerklePathItem {
+			hash: NearCryptoHash(sibling),
+			direction: Direction::Right,
+		});
+	}
+
+	let item = input.item.unwrap_or(leaves[index]);
+	let item_hash = hash(&item.try_to_vec().expect("arrays serialize; qed"));
+	let path: MerklePath = near_path.iter().cloned().map(Into::into).collect();
+	let ours = compute_root_from_path::<H>(&path, item_hash.into()).expect("hashes serialize; qed");
+	let reference = near_merkle::verify_hash(near_root, &near_path, item_hash);
+	assert_eq!(ours == root, reference, "verdicts differ for {:?}", near_path);
+});
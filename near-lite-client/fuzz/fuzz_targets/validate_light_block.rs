@@ -0,0 +1,71 @@
+//! Headers that start out valid, signed by every block producer of their epoch, and are then
+//! mutated field by field before being validated against the head.
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+use near_lite_client::{
+	benchmarking::HeaderSetup, validate_head, BlockHeight, CryptoHash, EpochId,
+	Sha256HostFunctions, Signature, ValidatorStakeView,
+};
+
+type H = Sha256HostFunctions;
+
+#[derive(Debug, Arbitrary)]
+struct Mutations {
+	block_producers: u8,
+	height: Option<u64>,
+	epoch_id: Option<[u8; 32]>,
+	next_epoch_id: Option<[u8; 32]>,
+	next_bp_hash: Option<[u8; 32]>,
+	drop_next_bps: bool,
+	approvals_len: Option<u8>,
+	/// index of an approval and the byte to replace its first byte with
+	corrupt_approval: Option<(u8, u8)>,
+	/// index of a block producer and its new stake
+	stakes: Vec<(u8, u128)>,
+}
+
+fuzz_target!(|mutations: Mutations| {
+	let block_producers = u32::from(mutations.block_producers % 16);
+	let mut setup = HeaderSetup::new::<H>(block_producers).expect("the setup is valid; qed");
+	let block_view = &mut setup.block_view;
+
+	if let Some(height) = mutations.height {
+		block_view.inner_lite.height = BlockHeight(height);
+	}
+	if let Some(epoch_id) = mutations.epoch_id {
+		block_view.inner_lite.epoch_id = EpochId(CryptoHash(epoch_id));
+	}
+	if let Some(next_epoch_id) = mutations.next_epoch_id {
+		block_view.inner_lite.next_epoch_id = EpochId(CryptoHash(next_epoch_id));
+	}
+	if let Some(next_bp_hash) = mutations.next_bp_hash {
+		block_view.inner_lite.next_bp_hash = CryptoHash(next_bp_hash);
+	}
+	if mutations.drop_next_bps {
+		block_view.next_bps = None;
+	}
+	if let Some(len) = mutations.approvals_len {
+		block_view.approvals_after_next.resize(usize::from(len), None);
+	}
+	if let Some((index, byte)) = mutations.corrupt_approval {
+		let approvals = &mut block_view.approvals_after_next;
+		if !approvals.is_empty() {
+			let index = usize::from(index) % approvals.len();
+			if let Some(signature) = approvals[index].as_mut() {
+				let mut raw = signature.as_bytes().to_vec();
+				raw[0] = byte;
+				*signature = Signature::try_from(raw.as_slice()).expect("64 bytes long; qed");
+			}
+		}
+	}
+	for (index, stake) in mutations.stakes {
+		for producers in setup.epoch_block_producers.values_mut() {
+			if let Some(ValidatorStakeView::V1(producer)) = producers.get_mut(usize::from(index)) {
+				producer.stake = stake;
+			}
+		}
+	}
+
+	let _ = validate_head::<H>(&setup.head, &setup.block_view, &setup.epoch_block_producers);
+});
@@ -0,0 +1,59 @@
+/*
+ * C interface of the NEAR light client, see `src/ffi.rs`.
+ *
+ * Inputs are borsh encoded. Every function but `near_lite_free` returns a status: NEAR_LITE_OK,
+ * the stable code of the client error (1 to 99, or 100 and above for verification failures) or
+ * one of the NEAR_LITE_* codes below.
+ */
+#ifndef NEAR_LITE_H
+#define NEAR_LITE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define NEAR_LITE_OK 0
+/* a pointer that must not be null was */
+#define NEAR_LITE_NULL_POINTER 1000
+/* the client panicked, it may only be freed */
+#define NEAR_LITE_PANIC 1001
+
+typedef struct NearLiteClient NearLiteClient;
+
+typedef struct {
+	uint64_t old_height;
+	uint64_t new_height;
+	uint32_t approvals_checked;
+	uint16_t stake_approved_bps;
+	uint8_t epoch_changed;
+	uint8_t already_validated;
+} NearLiteUpdateSummary;
+
+typedef struct {
+	uint64_t height;
+	uint8_t hash[32];
+} NearLiteHead;
+
+uint16_t near_lite_new(const uint8_t *checkpoint, size_t checkpoint_len, NearLiteClient **client);
+
+void near_lite_free(NearLiteClient *client);
+
+uint16_t near_lite_head(const NearLiteClient *client, NearLiteHead *head);
+
+/* `summary` may be null */
+uint16_t near_lite_update_head(NearLiteClient *client, const uint8_t *block_view,
+                               size_t block_view_len, NearLiteUpdateSummary *summary);
+
+uint16_t near_lite_verify_tx(const NearLiteClient *client, const uint8_t *outcome_proof,
+                             size_t outcome_proof_len, const uint8_t *outcome_root_proof,
+                             size_t outcome_root_proof_len,
+                             const uint8_t expected_block_outcome_root[32]);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NEAR_LITE_H */
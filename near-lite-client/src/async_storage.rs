@@ -0,0 +1,292 @@
+//! # Async storage
+//!
+//! Relayers that keep the state of the client in a remote database can't block on every access,
+//! while the client itself updates its storage synchronously. [`load`] reads the state from an
+//! [`AsyncLightClientStorage`] once, into a [`JournaledStorage`] the client is built on: it keeps
+//! the state in memory and records every change, and [`StorageJournal::commit`] writes them
+//! back in one batch, e.g. after each header.
+//!
+//! ```ignore
+//! let (storage, journal) = load(&database).await?;
+//! let mut client = LightClientBuilder::new().storage(storage).build()?;
+//! client.validate_and_update_head(block_view)?;
+//! journal.commit(&mut database).await?;
+//! ```
+
+use futures::future::BoxFuture;
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, EpochId, LightClientBlockView, ValidatorStakeView,
+};
+use std::{
+	sync::{Arc, Mutex},
+	vec::Vec,
+};
+
+use crate::{
+	kv::KvBackend,
+	storage::{InMemoryStorage, KvStorage, LightClientStorage, StorageStats},
+	LiteClientResult,
+};
+
+/// A change of the state of the client, as made through [`LightClientStorage`].
+#[derive(Debug, Clone)]
+pub enum StorageChange {
+	SetHead(LightClientBlockView),
+	InsertEpochBlockProducers(EpochId, Vec<ValidatorStakeView>),
+	RemoveEpochBlockProducers(EpochId),
+	SetEpochHeight(EpochId, BlockHeight),
+	InsertBlockHash(BlockHeight, CryptoHash),
+	PruneBlockHashes(BlockHeight),
+}
+
+impl StorageChange {
+	pub fn apply(self, storage: &mut dyn LightClientStorage) -> LiteClientResult<()> {
+		match self {
+			Self::SetHead(head) => storage.set_head(&head),
+			Self::InsertEpochBlockProducers(epoch_id, block_producers) =>
+				storage.insert_epoch_block_producers(epoch_id, &block_producers),
+			Self::RemoveEpochBlockProducers(epoch_id) =>
+				storage.remove_epoch_block_producers(&epoch_id),
+			Self::SetEpochHeight(epoch_id, height) => storage.set_epoch_height(epoch_id, height),
+			Self::InsertBlockHash(height, hash) => storage.insert_block_hash(height, hash),
+			Self::PruneBlockHashes(height) => storage.prune_block_hashes(height).map(|_| ()),
+		}
+	}
+}
+
+/// The asynchronous counterpart of [`LightClientStorage`], for the backends that are only
+/// reached over the network. The synchronous trait remains the one used on chain.
+pub trait AsyncLightClientStorage: Send {
+	fn head(&self) -> BoxFuture<'_, LiteClientResult<Option<LightClientBlockView>>>;
+	/// Block producers of every stored epoch, in the order they were inserted.
+	fn epoch_block_producers(
+		&self,
+	) -> BoxFuture<'_, LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>>>;
+	/// See [`LightClientStorage::epoch_heights`], none by default.
+	fn epoch_heights(&self) -> BoxFuture<'_, LiteClientResult<Vec<(EpochId, BlockHeight)>>> {
+		Box::pin(async { Ok(Vec::new()) })
+	}
+	fn block_hash(
+		&self,
+		height: BlockHeight,
+	) -> BoxFuture<'_, LiteClientResult<Option<CryptoHash>>>;
+	/// Applies `changes` in order, atomically if the backend supports it.
+	fn commit(&mut self, changes: Vec<StorageChange>) -> BoxFuture<'_, LiteClientResult<()>>;
+}
+
+/// Applies the changes one by one, as a local [`KvBackend`] doesn't need to wait.
+impl<B: KvBackend + Send + Sync> AsyncLightClientStorage for KvStorage<B> {
+	fn head(&self) -> BoxFuture<'_, LiteClientResult<Option<LightClientBlockView>>> {
+		Box::pin(async move { LightClientStorage::head(self) })
+	}
+
+	fn epoch_block_producers(
+		&self,
+	) -> BoxFuture<'_, LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>>> {
+		Box::pin(async move { LightClientStorage::epoch_block_producers(self) })
+	}
+
+	fn epoch_heights(&self) -> BoxFuture<'_, LiteClientResult<Vec<(EpochId, BlockHeight)>>> {
+		Box::pin(async move { LightClientStorage::epoch_heights(self) })
+	}
+
+	fn block_hash(
+		&self,
+		height: BlockHeight,
+	) -> BoxFuture<'_, LiteClientResult<Option<CryptoHash>>> {
+		Box::pin(async move { LightClientStorage::block_hash(self, height) })
+	}
+
+	fn commit(&mut self, changes: Vec<StorageChange>) -> BoxFuture<'_, LiteClientResult<()>> {
+		Box::pin(async move {
+			for change in changes {
+				change.apply(self)?;
+			}
+			Ok(())
+		})
+	}
+}
+
+/// Reads the head and the block producers of `storage` into a [`JournaledStorage`], and returns
+/// the journal of its changes. The hashes indexed by height are not read, only the ones indexed
+/// from then on are in memory.
+pub async fn load<S: AsyncLightClientStorage + ?Sized>(
+	storage: &S,
+) -> LiteClientResult<(JournaledStorage, StorageJournal)> {
+	let mut state = InMemoryStorage::default();
+	if let Some(head) = storage.head().await? {
+		state.set_head(&head)?;
+	}
+	for (epoch_id, block_producers) in storage.epoch_block_producers().await? {
+		state.insert_epoch_block_producers(epoch_id, &block_producers)?;
+	}
+	for (epoch_id, height) in storage.epoch_heights().await? {
+		state.set_epoch_height(epoch_id, height)?;
+	}
+	let journal = StorageJournal::default();
+	Ok((JournaledStorage { state, journal: journal.clone() }, journal))
+}
+
+/// Changes recorded by a [`JournaledStorage`] and not committed yet. Clones share the same
+/// changes.
+#[derive(Debug, Clone, Default)]
+pub struct StorageJournal {
+	changes: Arc<Mutex<Vec<StorageChange>>>,
+}
+
+impl StorageJournal {
+	pub fn len(&self) -> usize {
+		self.lock().len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.lock().is_empty()
+	}
+
+	/// Takes the recorded changes, leaving the journal empty.
+	pub fn take(&self) -> Vec<StorageChange> {
+		std::mem::take(&mut *self.lock())
+	}
+
+	/// Commits the recorded changes to `storage`. They are recorded again if the commit fails,
+	/// ahead of the changes made since, so that the next commit retries them.
+	pub async fn commit<S: AsyncLightClientStorage + ?Sized>(
+		&self,
+		storage: &mut S,
+	) -> LiteClientResult<()> {
+		let changes = self.take();
+		if changes.is_empty() {
+			return Ok(())
+		}
+		if let Err(err) = storage.commit(changes.clone()).await {
+			let mut recorded = self.lock();
+			let newer = std::mem::replace(&mut *recorded, changes);
+			recorded.extend(newer);
+			return Err(err)
+		}
+		Ok(())
+	}
+
+	fn record(&self, change: StorageChange) {
+		self.lock().push(change);
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, Vec<StorageChange>> {
+		// a panic while the lock is held can't leave the changes half written
+		self.changes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+/// [`LightClientStorage`] in memory, recording every change in its [`StorageJournal`].
+pub struct JournaledStorage {
+	state: InMemoryStorage,
+	journal: StorageJournal,
+}
+
+impl LightClientStorage for JournaledStorage {
+	// `KvStorage` implements both storage traits, so its reads name the synchronous one
+	fn head(&self) -> LiteClientResult<Option<LightClientBlockView>> {
+		LightClientStorage::head(&self.state)
+	}
+
+	fn set_head(&mut self, head: &LightClientBlockView) -> LiteClientResult<()> {
+		self.state.set_head(head)?;
+		self.journal.record(StorageChange::SetHead(head.clone()));
+		Ok(())
+	}
+
+	fn epoch_block_producers(&self) -> LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>> {
+		LightClientStorage::epoch_block_producers(&self.state)
+	}
+
+	fn insert_epoch_block_producers(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<()> {
+		self.state.insert_epoch_block_producers(epoch_id, block_producers)?;
+		self.journal
+			.record(StorageChange::InsertEpochBlockProducers(epoch_id, block_producers.to_vec()));
+		Ok(())
+	}
+
+	fn remove_epoch_block_producers(&mut self, epoch_id: &EpochId) -> LiteClientResult<()> {
+		self.state.remove_epoch_block_producers(epoch_id)?;
+		self.journal.record(StorageChange::RemoveEpochBlockProducers(*epoch_id));
+		Ok(())
+	}
+
+	fn epoch_heights(&self) -> LiteClientResult<Vec<(EpochId, BlockHeight)>> {
+		LightClientStorage::epoch_heights(&self.state)
+	}
+
+	fn set_epoch_height(&mut self, epoch_id: EpochId, height: BlockHeight) -> LiteClientResult<()> {
+		self.state.set_epoch_height(epoch_id, height)?;
+		self.journal.record(StorageChange::SetEpochHeight(epoch_id, height));
+		Ok(())
+	}
+
+	fn epoch_validators(
+		&self,
+		epoch_id: &EpochId,
+	) -> LiteClientResult<Option<Vec<ValidatorStakeView>>> {
+		self.state.epoch_validators(epoch_id)
+	}
+
+	fn block_hash(&self, height: BlockHeight) -> LiteClientResult<Option<CryptoHash>> {
+		LightClientStorage::block_hash(&self.state, height)
+	}
+
+	fn insert_block_hash(&mut self, height: BlockHeight, hash: CryptoHash) -> LiteClientResult<()> {
+		self.state.insert_block_hash(height, hash)?;
+		self.journal.record(StorageChange::InsertBlockHash(height, hash));
+		Ok(())
+	}
+
+	/// Counts the hashes pruned from memory, the backend may have more below `height`.
+	fn prune_block_hashes(&mut self, height: BlockHeight) -> LiteClientResult<usize> {
+		let pruned = self.state.prune_block_hashes(height)?;
+		self.journal.record(StorageChange::PruneBlockHashes(height));
+		Ok(pruned)
+	}
+
+	/// What is in memory, which only has the hashes indexed since it was loaded.
+	fn stats(&self) -> LiteClientResult<StorageStats> {
+		self.state.stats()
+	}
+
+	#[cfg(feature = "deepsize_feature")]
+	fn memory_usage(&self) -> usize {
+		self.state.memory_usage()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{test_utils::MockedHostFunctions, LightClientBuilder};
+	use futures::executor::block_on;
+
+	#[test]
+	fn test_changes_are_committed_in_batches() {
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_lite.height = BlockHeight(10);
+		let mut database = InMemoryStorage::default();
+		database.set_head(&head).unwrap();
+		database.insert_epoch_block_producers(EpochId::default(), &[]).unwrap();
+
+		let (storage, journal) = block_on(load(&database)).unwrap();
+		let client = LightClientBuilder::<MockedHostFunctions>::new()
+			.storage(storage)
+			.build()
+			.unwrap();
+		assert_eq!(client.head_height(), BlockHeight(10));
+		// building the client writes its state back
+		assert!(!journal.is_empty());
+
+		block_on(journal.commit(&mut database)).unwrap();
+		assert!(journal.is_empty());
+		let hash = block_on(AsyncLightClientStorage::block_hash(&database, BlockHeight(10)));
+		assert_eq!(hash.unwrap(), Some(client.head_hash()));
+	}
+}
@@ -0,0 +1,229 @@
+//! # Audit log
+//!
+//! Append-only record of every mutation applied to a light client. Each entry commits to the
+//! hash of the previous one, so removing, reordering or editing an entry breaks the chain and is
+//! caught by [`AuditLog::verify`].
+//!
+//! The log only grows as long as its entries are not taken out by [`AuditLog::drain`], which
+//! long-running clients call to archive them. The entries recorded afterwards keep chaining to the
+//! drained ones, so the archives and the log verify as one chain.
+
+use alloc::string::String;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use core::marker::PhantomData;
+#[cfg(feature = "deepsize_feature")]
+use deepsize::DeepSizeOf;
+use near_primitives_wasm::{BlockHeight, CryptoHash, EpochId, HostFunctions};
+use sp_std::vec::Vec;
+
+use crate::{
+	error::{deserialize, serialize, NearLiteClientError},
+	LiteClientResult,
+};
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum AuditEvent {
+	/// The head moved to the given block.
+	HeadUpdateAccepted { height: BlockHeight, block_hash: CryptoHash },
+	/// A block view was submitted but did not pass validation.
+	HeadUpdateRejected { height: BlockHeight, block_hash: CryptoHash, reason: String },
+	/// The block producers of a new epoch became known to the client.
+	ValidatorSetRotation { epoch_id: EpochId, block_producers: u32 },
+	/// Data older than the given height was dropped from the client.
+	Pruned { below_height: BlockHeight },
+	/// The client was frozen after a misbehaviour at the given height.
+	Frozen { height: BlockHeight },
+}
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct AuditEntry {
+	pub index: u64,
+	pub prev_hash: CryptoHash,
+	pub event: AuditEvent,
+	/// `sha256(borsh(index, prev_hash, event))`
+	pub hash: CryptoHash,
+}
+
+pub struct AuditLog<H: HostFunctions> {
+	entries: Vec<AuditEntry>,
+	/// how many entries were drained before `entries`
+	drained: u64,
+	/// hash of the last drained entry, the default hash if none was
+	drained_hash: CryptoHash,
+	_hf: PhantomData<H>,
+}
+
+impl<H: HostFunctions> AuditLog<H> {
+	pub fn new() -> Self {
+		Self {
+			entries: Vec::new(),
+			drained: 0,
+			drained_hash: CryptoHash::default(),
+			_hf: PhantomData::default(),
+		}
+	}
+
+	/// Appends `event` to the log, chaining it to the last recorded entry.
+	pub fn record(&mut self, event: AuditEvent) -> LiteClientResult<&AuditEntry> {
+		let index = self.drained + self.entries.len() as u64;
+		let prev_hash = self.last_hash();
+		let hash = entry_hash::<H>(index, &prev_hash, &event)?;
+		self.entries.push(AuditEntry { index, prev_hash, event, hash });
+		Ok(&self.entries[self.entries.len() - 1])
+	}
+
+	/// The entries recorded since the log was created or last drained.
+	pub fn entries(&self) -> &[AuditEntry] {
+		&self.entries
+	}
+
+	/// Takes the entries out of the log, e.g. to archive them, leaving it empty.
+	pub fn drain(&mut self) -> Vec<AuditEntry> {
+		self.drained += self.entries.len() as u64;
+		self.drained_hash = self.last_hash();
+		core::mem::take(&mut self.entries)
+	}
+
+	/// Hash of the last entry, drained or not, or the default hash when none was recorded.
+	pub fn last_hash(&self) -> CryptoHash {
+		self.entries.last().map_or(self.drained_hash, |entry| entry.hash)
+	}
+
+	/// Recomputes every entry hash and checks that the chain is unbroken, from the last drained
+	/// entry.
+	pub fn verify(&self) -> LiteClientResult<()> {
+		verify_entries::<H>(&self.entries, self.drained, self.drained_hash)
+	}
+
+	/// Borsh encoding of the entries, suitable for archiving outside of the client.
+	pub fn export(&self) -> LiteClientResult<Vec<u8>> {
+		serialize(&self.entries)
+	}
+
+	/// Rebuilds a log from the output of [`AuditLog::export`], rejecting it if the chain has
+	/// been tampered with.
+	pub fn import(bytes: &[u8]) -> LiteClientResult<Self> {
+		let entries = deserialize::<Vec<AuditEntry>>(bytes)?;
+		verify_entries::<H>(&entries, 0, CryptoHash::default())?;
+		Ok(Self { entries, ..Self::new() })
+	}
+}
+
+impl<H: HostFunctions> Default for AuditLog<H> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(feature = "deepsize_feature")]
+impl<H: HostFunctions> DeepSizeOf for AuditLog<H> {
+	fn deep_size_of_children(&self, context: &mut deepsize::Context) -> usize {
+		self.entries.deep_size_of_children(context)
+	}
+}
+
+/// Checks that `entries` chain from the entry `first_index - 1`, hashed `prev_hash`.
+fn verify_entries<H: HostFunctions>(
+	entries: &[AuditEntry],
+	first_index: u64,
+	mut prev_hash: CryptoHash,
+) -> LiteClientResult<()> {
+	for (index, entry) in (first_index..).zip(entries) {
+		if entry.index != index {
+			return Err(NearLiteClientError::AuditLog(String::from("entry index out of sequence")));
+		}
+		if entry.prev_hash != prev_hash {
+			return Err(NearLiteClientError::AuditLog(String::from(
+				"entry does not chain to the previous one",
+			)));
+		}
+		if entry_hash::<H>(entry.index, &entry.prev_hash, &entry.event)? != entry.hash {
+			return Err(NearLiteClientError::AuditLog(String::from("entry hash mismatch")));
+		}
+		prev_hash = entry.hash;
+	}
+	Ok(())
+}
+
+fn entry_hash<H: HostFunctions>(
+	index: u64,
+	prev_hash: &CryptoHash,
+	event: &AuditEvent,
+) -> LiteClientResult<CryptoHash> {
+	Ok(CryptoHash(H::sha256(&serialize(&(index, prev_hash, event))?)))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+
+	fn sample_log() -> AuditLog<MockedHostFunctions> {
+		let mut log = AuditLog::new();
+		log.record(AuditEvent::HeadUpdateAccepted {
+			height: BlockHeight(10),
+			block_hash: CryptoHash([1; 32]),
+		})
+		.unwrap();
+		log.record(AuditEvent::ValidatorSetRotation {
+			epoch_id: EpochId(CryptoHash([2; 32])),
+			block_producers: 100,
+		})
+		.unwrap();
+		log.record(AuditEvent::HeadUpdateRejected {
+			height: BlockHeight(9),
+			block_hash: CryptoHash([3; 32]),
+			reason: "stale".into(),
+		})
+		.unwrap();
+		log
+	}
+
+	#[test]
+	fn test_audit_log_chains_entries() {
+		let log = sample_log();
+		assert_eq!(log.entries().len(), 3);
+		assert_eq!(log.entries()[0].prev_hash, CryptoHash::default());
+		assert_eq!(log.entries()[1].prev_hash, log.entries()[0].hash);
+		assert_eq!(log.entries()[2].prev_hash, log.entries()[1].hash);
+		assert!(log.verify().is_ok());
+	}
+
+	#[test]
+	fn test_audit_log_export_roundtrip() {
+		let log = sample_log();
+		let imported = AuditLog::<MockedHostFunctions>::import(&log.export().unwrap()).unwrap();
+		assert_eq!(imported.entries(), log.entries());
+		assert_eq!(imported.last_hash(), log.last_hash());
+	}
+
+	#[test]
+	fn test_audit_log_detects_tampering() {
+		let mut entries = sample_log().entries().to_vec();
+		entries[1].event = AuditEvent::Pruned { below_height: BlockHeight(10) };
+		assert!(AuditLog::<MockedHostFunctions>::import(&entries.try_to_vec().unwrap()).is_err());
+
+		let mut entries = sample_log().entries().to_vec();
+		entries.remove(1);
+		assert!(AuditLog::<MockedHostFunctions>::import(&entries.try_to_vec().unwrap()).is_err());
+	}
+
+	#[test]
+	fn test_drained_entries_stay_chained() {
+		let mut log = sample_log();
+		let drained = log.drain();
+		assert_eq!(drained.len(), 3);
+		assert!(log.entries().is_empty());
+		assert_eq!(log.last_hash(), drained[2].hash);
+
+		let entry = log.record(AuditEvent::Frozen { height: BlockHeight(12) }).unwrap().clone();
+		assert_eq!((entry.index, entry.prev_hash), (3, drained[2].hash));
+		assert!(log.verify().is_ok());
+
+		let mut archive = drained;
+		archive.extend(log.drain());
+		assert!(AuditLog::<MockedHostFunctions>::import(&archive.try_to_vec().unwrap()).is_ok());
+	}
+}
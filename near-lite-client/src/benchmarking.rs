@@ -0,0 +1,158 @@
+//! # Benchmarking
+//!
+//! Worst-case inputs of the [`WeightInfo`](crate::weights::WeightInfo) functions, sized by their
+//! component, so that pallets only have to wrap them in their `frame-benchmarking` setup:
+//!
+//! ```ignore
+//! benchmarks! {
+//! 	validate_head {
+//! 		let v in 1 .. MAX_BLOCK_PRODUCERS;
+//! 		let setup = HeaderSetup::new::<Sha256HostFunctions>(v).unwrap();
+//! 	}: { setup.validate::<Sha256HostFunctions>().unwrap() }
+//!
+//! 	verify_outcome {
+//! 		let d in 1 .. MAX_PROOF_DEPTH;
+//! 		let setup = OutcomeSetup::new::<Sha256HostFunctions>(d).unwrap();
+//! 	}: { setup.verify::<Sha256HostFunctions>().unwrap() }
+//! }
+//! ```
+
+use alloc::format;
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, Direction, EpochId, ExecutionOutcomeView, HostFunctions,
+	LightClientBlockView, MerklePath, MerklePathItem, OutcomeProof, PublicKey, Signature,
+	ValidatorStakeView, ValidatorStakeViewV1,
+};
+use sp_core::{ed25519::Pair, Pair as _};
+use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
+
+use crate::{
+	block_validation::BlockViewHashes,
+	error::serialize,
+	merkle_tree::compute_root_from_path,
+	verifier::{calculate_execution_outcome_hash, validate_head, verify_outcome},
+	LiteClientResult, OutcomeRoots,
+};
+
+/// Upper bound of the block producers of an epoch to benchmark with; NEAR's mainnet has 100 seats.
+pub const MAX_BLOCK_PRODUCERS: u32 = 100;
+/// Upper bound of the items of a merkle path to benchmark with.
+pub const MAX_PROOF_DEPTH: u32 = 32;
+
+/// A header at the height after `head`, approved by every block producer of its epoch and
+/// carrying them as its `next_bps`, so that every signature and the `next_bp_hash` get checked.
+pub struct HeaderSetup {
+	pub head: LightClientBlockView,
+	pub block_view: LightClientBlockView,
+	pub epoch_block_producers: BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+}
+
+impl HeaderSetup {
+	pub fn new<H: HostFunctions>(block_producers: u32) -> LiteClientResult<Self> {
+		let pairs = (0..block_producers)
+			.map(|index| {
+				let mut seed = [0u8; 32];
+				seed[..4].copy_from_slice(&index.to_le_bytes());
+				Pair::from_seed(&seed)
+			})
+			.collect::<Vec<_>>();
+		let producers = pairs
+			.iter()
+			.enumerate()
+			.map(|(index, pair)| {
+				ValidatorStakeView::V1(ValidatorStakeViewV1 {
+					account_id: format!("producer{}.near", index),
+					public_key: PublicKey(pair.public().0),
+					stake: 1,
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_lite.next_bp_hash = CryptoHash(H::sha256_borsh(&producers));
+		let mut block_view = head.clone();
+		block_view.inner_lite.height = BlockHeight(head.inner_lite.height.0 + 1);
+		block_view.next_bps = Some(producers.clone());
+		let approval_message = BlockViewHashes::new::<H>(&block_view)?.approval_message;
+		block_view.approvals_after_next = pairs
+			.iter()
+			.map(|pair| Some(Signature::Ed25519(pair.sign(&approval_message))))
+			.collect();
+
+		let mut epoch_block_producers = BTreeMap::new();
+		epoch_block_producers.insert(head.inner_lite.epoch_id, producers);
+		Ok(Self { head, block_view, epoch_block_producers })
+	}
+
+	pub fn validate<H: HostFunctions>(&self) -> LiteClientResult<()> {
+		validate_head::<H>(&self.head, &self.block_view, &self.epoch_block_producers)
+	}
+}
+
+/// An outcome proof with `depth` items in both its proof and its outcome root proof, together
+/// with the outcome root of the block they lead to.
+pub struct OutcomeSetup {
+	pub outcome_proof: OutcomeProof,
+	pub outcome_root_proof: MerklePath,
+	pub block_outcome_root: CryptoHash,
+}
+
+impl OutcomeSetup {
+	pub fn new<H: HostFunctions>(depth: u32) -> LiteClientResult<Self> {
+		let path = |sibling: u8| {
+			(0..depth)
+				.map(|index| MerklePathItem {
+					hash: CryptoHash([sibling; 32]),
+					direction: if index % 2 == 0 { Direction::Left } else { Direction::Right },
+				})
+				.collect::<MerklePath>()
+		};
+		let outcome_proof = OutcomeProof {
+			block_hash: CryptoHash::default(),
+			id: CryptoHash([1; 32]),
+			proof: path(2),
+			outcome: ExecutionOutcomeView {
+				logs: Vec::new(),
+				receipt_ids: vec![CryptoHash([3; 32])],
+				gas_burnt: 1,
+				tokens_burnt: 1,
+				executor_id: "bridge.near".into(),
+				status: Vec::new(),
+			},
+		};
+		let outcome_root_proof = path(4);
+
+		let outcome_hash =
+			calculate_execution_outcome_hash::<H>(&outcome_proof.outcome, outcome_proof.id)?;
+		let shard_outcome_root = compute_root_from_path::<H>(&outcome_proof.proof, outcome_hash)?;
+		let block_outcome_root = compute_root_from_path::<H>(
+			&outcome_root_proof,
+			CryptoHash(H::sha256(&serialize(&shard_outcome_root)?)),
+		)?;
+		Ok(Self { outcome_proof, outcome_root_proof, block_outcome_root })
+	}
+
+	pub fn verify<H: HostFunctions>(&self) -> LiteClientResult<OutcomeRoots> {
+		verify_outcome::<H>(&self.outcome_proof, &self.outcome_root_proof, self.block_outcome_root)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+
+	#[test]
+	fn test_setups_verify() {
+		for size in [1, 4, MAX_BLOCK_PRODUCERS] {
+			let setup = HeaderSetup::new::<MockedHostFunctions>(size).unwrap();
+			assert_eq!(setup.block_view.approvals_after_next.len(), size as usize);
+			setup.validate::<MockedHostFunctions>().unwrap();
+		}
+		for depth in [0, 1, MAX_PROOF_DEPTH] {
+			let setup = OutcomeSetup::new::<MockedHostFunctions>(depth).unwrap();
+			assert_eq!(setup.outcome_root_proof.len(), depth as usize);
+			setup.verify::<MockedHostFunctions>().unwrap();
+		}
+	}
+}
@@ -0,0 +1,224 @@
+//! # Block merkle accumulator
+//!
+//! Every NEAR header commits, in `block_merkle_root`, to the hashes of all the blocks before it,
+//! in an append only merkle tree (nearcore's `PartialMerkleTree`). [`BlockMerkleAccumulator`]
+//! extends its own copy of that tree with the block hashes it is given, so that its root can be
+//! compared against `block_merkle_root` and ancestry proofs can be built for the appended blocks.
+
+use near_primitives_wasm::{
+	CryptoHash, Direction, HostFunctions, MerkleHash, MerklePath, MerklePathItem,
+};
+use sp_std::vec::Vec;
+
+use crate::{error::NearLiteClientError, merkle_tree::combine_hash, LiteClientResult};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+pub struct BlockMerkleAccumulator {
+	/// roots of the complete subtrees covering the tree, from the leftmost (largest) one
+	path: Vec<MerkleHash>,
+	size: u64,
+	/// `path` when the accumulator was created, covering the leaves before `first_leaf`
+	snapshot: Vec<MerkleHash>,
+	first_leaf: u64,
+	/// every leaf from `first_leaf` on
+	leaves: Vec<CryptoHash>,
+}
+
+impl BlockMerkleAccumulator {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Resumes the tree of `size` leaves whose complete subtrees have the roots `path`, in the
+	/// layout of nearcore's `PartialMerkleTree`. Proofs can only be built for the leaves appended
+	/// afterwards.
+	pub fn from_partial_tree(path: Vec<MerkleHash>, size: u64) -> LiteClientResult<Self> {
+		if path.len() != size.count_ones() as usize {
+			return Err(NearLiteClientError::PartialTreeMismatch { size, subtrees: path.len() })
+		}
+		Ok(Self { snapshot: path.clone(), path, size, first_leaf: size, leaves: Vec::new() })
+	}
+
+	pub fn size(&self) -> u64 {
+		self.size
+	}
+
+	/// Roots of the complete subtrees, to resume the tree with [`Self::from_partial_tree`].
+	pub fn path(&self) -> &[MerkleHash] {
+		&self.path
+	}
+
+	pub fn append<H: HostFunctions>(&mut self, block_hash: CryptoHash) -> LiteClientResult<()> {
+		let mut node = block_hash;
+		let mut size = self.size;
+		while size % 2 == 1 {
+			let left = self.path.pop().expect("one subtree per bit set in the size; qed");
+			node = combine_hash::<H>(&left, &node)?;
+			size /= 2;
+		}
+		self.path.push(node);
+		self.size += 1;
+		self.leaves.push(block_hash);
+		Ok(())
+	}
+
+	/// The `block_merkle_root` of the block following the last appended one.
+	pub fn root<H: HostFunctions>(&self) -> LiteClientResult<MerkleHash> {
+		let mut subtrees = self.path.iter().rev();
+		let mut root = match subtrees.next() {
+			Some(root) => *root,
+			None => return Ok(MerkleHash::default()),
+		};
+		for subtree in subtrees {
+			root = combine_hash::<H>(subtree, &root)?;
+		}
+		Ok(root)
+	}
+
+	/// The leaf at `index`, if it was appended to this accumulator.
+	pub fn leaf(&self, index: u64) -> Option<CryptoHash> {
+		let offset = index.checked_sub(self.first_leaf)?;
+		self.leaves.get(offset as usize).copied()
+	}
+
+	/// Path of the leaf at `index` to the current root, checked with `compute_root_from_path`.
+	pub fn proof<H: HostFunctions>(&self, index: u64) -> LiteClientResult<MerklePath> {
+		if index < self.first_leaf || index >= self.size {
+			return Err(NearLiteClientError::LeafNotInAccumulator { index })
+		}
+		let mut path = MerklePath::new();
+		let mut level = 0;
+		while (self.size - 1) >> level > 0 {
+			let position = index >> level;
+			let sibling = position ^ 1;
+			if let Some(hash) = self.node::<H>(level, sibling)? {
+				let direction = if sibling < position { Direction::Left } else { Direction::Right };
+				path.push(MerklePathItem { hash, direction });
+			}
+			level += 1;
+		}
+		Ok(path)
+	}
+
+	/// Root of the subtree at `position` in `level`, `None` if it has no leaves. A subtree with
+	/// leaves only on its left is equal to its left child, as in `merklize`.
+	fn node<H: HostFunctions>(
+		&self,
+		level: u32,
+		position: u64,
+	) -> LiteClientResult<Option<MerkleHash>> {
+		let start = position << level;
+		if start >= self.size {
+			return Ok(None)
+		}
+		if start + (1 << level) <= self.first_leaf {
+			return self.snapshot_node(level, start).map(Some)
+		}
+		if level == 0 {
+			return Ok(Some(self.leaves[(start - self.first_leaf) as usize]))
+		}
+		let left = self.node::<H>(level - 1, position * 2)?;
+		let right = self.node::<H>(level - 1, position * 2 + 1)?;
+		Ok(match (left, right) {
+			(Some(left), Some(right)) => Some(combine_hash::<H>(&left, &right)?),
+			(left, _) => left,
+		})
+	}
+
+	/// The snapshot holds one subtree per bit set in `first_leaf`, from the highest bit.
+	fn snapshot_node(&self, level: u32, start: u64) -> LiteClientResult<MerkleHash> {
+		let mut offset = 0;
+		let bits = (0..u64::BITS).rev().filter(|bit| self.first_leaf & (1 << bit) != 0);
+		for (subtree, bit) in self.snapshot.iter().zip(bits) {
+			if offset == start && bit == level {
+				return Ok(*subtree)
+			}
+			offset += 1 << bit;
+		}
+		Err(NearLiteClientError::LeafNotInAccumulator { index: start })
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{compute_root_from_path, test_utils::MockedHostFunctions};
+	use near_primitives::merkle::PartialMerkleTree;
+
+	fn block_hash(index: u64) -> CryptoHash {
+		CryptoHash::hash_borsh(&index)
+	}
+
+	fn accumulator(size: u64) -> BlockMerkleAccumulator {
+		let mut accumulator = BlockMerkleAccumulator::new();
+		for index in 0..size {
+			accumulator.append::<MockedHostFunctions>(block_hash(index)).unwrap();
+		}
+		accumulator
+	}
+
+	#[test]
+	fn test_root_matches_nearcore() {
+		let mut near_tree = PartialMerkleTree::default();
+		let mut accumulator = BlockMerkleAccumulator::new();
+		assert_eq!(accumulator.root::<MockedHostFunctions>().unwrap(), MerkleHash::default());
+		for index in 0..40 {
+			near_tree.insert(block_hash(index).into());
+			accumulator.append::<MockedHostFunctions>(block_hash(index)).unwrap();
+			assert_eq!(
+				accumulator.root::<MockedHostFunctions>().unwrap(),
+				CryptoHash::from(near_tree.get_root())
+			);
+		}
+	}
+
+	#[test]
+	fn test_proofs_of_appended_blocks() {
+		for size in 1..20 {
+			let accumulator = accumulator(size);
+			let root = accumulator.root::<MockedHostFunctions>().unwrap();
+			for index in 0..size {
+				let proof = accumulator.proof::<MockedHostFunctions>(index).unwrap();
+				assert_eq!(
+					compute_root_from_path::<MockedHostFunctions>(&proof, block_hash(index))
+						.unwrap(),
+					root
+				);
+			}
+			assert!(matches!(
+				accumulator.proof::<MockedHostFunctions>(size),
+				Err(NearLiteClientError::LeafNotInAccumulator { index }) if index == size
+			));
+		}
+	}
+
+	#[test]
+	fn test_resume_from_partial_tree() {
+		let full = accumulator(13);
+		let snapshot = accumulator(5);
+		let mut resumed =
+			BlockMerkleAccumulator::from_partial_tree(snapshot.path().to_vec(), snapshot.size())
+				.unwrap();
+		for index in 5..13 {
+			resumed.append::<MockedHostFunctions>(block_hash(index)).unwrap();
+		}
+
+		assert_eq!(resumed.path(), full.path());
+		assert_eq!(
+			resumed.root::<MockedHostFunctions>().unwrap(),
+			full.root::<MockedHostFunctions>().unwrap()
+		);
+		for index in 5..13 {
+			assert_eq!(
+				resumed.proof::<MockedHostFunctions>(index).unwrap(),
+				full.proof::<MockedHostFunctions>(index).unwrap()
+			);
+		}
+		assert!(resumed.proof::<MockedHostFunctions>(4).is_err());
+		assert!(matches!(
+			BlockMerkleAccumulator::from_partial_tree(snapshot.path().to_vec(), 7),
+			Err(NearLiteClientError::PartialTreeMismatch { size: 7, subtrees: 2 })
+		));
+	}
+}
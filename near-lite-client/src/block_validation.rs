@@ -1,134 +1,952 @@
 use alloc::string::String;
-use near_primitives_wasm::HostFunctions;
-use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use near_primitives_wasm::{Balance, HostFunctions};
+use sp_core::U256;
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
+
+use crate::{
+	error::{NearLiteClientError, NextBpsDefect, VerificationFailure},
+	hashes::reconstruct_light_client_block_view_fields,
+	metrics,
+	signature::{HostFunctionsVerifier, SignatureVerifier},
+	LiteClientResult,
+};
+
+/// Share of the total stake that the approvals of a block must exceed for the block to be
+/// considered final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalThreshold {
+	numerator: u32,
+	denominator: u32,
+}
+
+impl ApprovalThreshold {
+	/// NEAR's finality rule: strictly more than 2/3 of the stake.
+	pub const TWO_THIRDS: Self = Self { numerator: 2, denominator: 3 };
+
+	/// A threshold of `numerator / denominator` of the stake. It can only be stricter than
+	/// [`Self::TWO_THIRDS`], below that the approvals no longer imply finality.
+	pub fn new(numerator: u32, denominator: u32) -> LiteClientResult<Self> {
+		if denominator == 0 || numerator >= denominator {
+			return Err(NearLiteClientError::Config(String::from(
+				"approval threshold must be a fraction below 1",
+			)));
+		}
+		if u64::from(numerator) * 3 < u64::from(denominator) * 2 {
+			return Err(NearLiteClientError::Config(String::from(
+				"approval threshold must be at least 2/3 of the stake",
+			)));
+		}
+		Ok(Self { numerator, denominator })
+	}
+
+	/// Whether `approved_stake` is more than the threshold share of `total_stake`.
+	pub fn is_reached(&self, approved_stake: u128, total_stake: u128) -> bool {
+		// approved_stake / total_stake > numerator / denominator, in 256 bits so that neither
+		// product overflows
+		U256::from(approved_stake) * U256::from(self.denominator) >
+			U256::from(total_stake) * U256::from(self.numerator)
+	}
+}
 
-use crate::{error::NearLiteClientError, LiteClientResult};
+/// What [`verify_approvals`] went through to accept a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApprovalStats {
+	/// number of signatures verified
+	pub approvals_checked: usize,
+	pub approved_stake: Balance,
+	pub total_stake: Balance,
+}
+
+impl ApprovalStats {
+	/// Approved share of the stake, in basis points.
+	pub fn approved_bps(&self) -> u16 {
+		if self.total_stake == 0 {
+			return 0
+		}
+		// rounded down, in 256 bits so that the product doesn't overflow
+		let bps =
+			U256::from(self.approved_stake) * U256::from(10_000u32) / U256::from(self.total_stake);
+		bps.low_u32() as u16
+	}
+}
+
+impl Default for ApprovalThreshold {
+	fn default() -> Self {
+		Self::TWO_THIRDS
+	}
+}
+
+/// Which epochs a block view may be in, relative to the head: check (2) of
+/// [`validate_light_block`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochLinkage {
+	/// The epoch of the head or the next one, as in the specification.
+	Contiguous,
+	/// Also any later epoch whose block producers are known, e.g. from an epoch sync proof,
+	/// skipping the epochs in between. The ids of the epochs don't tell their order: the block
+	/// producers passed along must not include epochs before the one of the head.
+	EpochSync,
+}
+
+impl Default for EpochLinkage {
+	fn default() -> Self {
+		Self::Contiguous
+	}
+}
 
 use near_primitives_wasm::{
-	ApprovalInner, CryptoHash, LightClientBlockView, ValidatorStakeView,
+	CryptoHash, EpochId, LightClientBlockView, PublicKey, Signature, ValidatorStakeView,
 };
 
-use borsh::BorshSerialize;
+/// The hashes and the approval message of a block view. Each of them needs part of the block
+/// view borsh encoded, so they are derived once per validation pass and shared by every check
+/// (and by the caller, e.g. for its audit log) instead of re-encoding the block view each time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockViewHashes {
+	pub current_block_hash: CryptoHash,
+	pub next_block_hash: CryptoHash,
+	/// what the block producers sign in `approvals_after_next`
+	pub approval_message: Vec<u8>,
+	/// `sha256(borsh(next_bps))`, if the block view carries them
+	pub next_bps_hash: Option<CryptoHash>,
+}
+
+impl BlockViewHashes {
+	pub fn new<H: HostFunctions>(block_view: &LightClientBlockView) -> LiteClientResult<Self> {
+		let (current_block_hash, next_block_hash, approval_message) =
+			reconstruct_light_client_block_view_fields::<H>(block_view)?;
+		let next_bps_hash = block_view
+			.next_bps
+			.as_deref()
+			.map(|next_bps| CryptoHash(H::sha256_borsh(next_bps)));
+		Ok(Self { current_block_hash, next_block_hash, approval_message, next_bps_hash })
+	}
+}
 
 pub fn validate_light_block<H: HostFunctions>(
 	head: &LightClientBlockView,
 	block_view: &LightClientBlockView,
-	epoch_block_producers_map: &BTreeMap<CryptoHash, Vec<ValidatorStakeView>>,
-) -> LiteClientResult<()> {
+	epoch_block_producers_map: &BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+	threshold: &ApprovalThreshold,
+) -> LiteClientResult<ApprovalStats> {
+	validate_epoch_derivation(head, &head.current_block_hash::<H>(), block_view)?;
+	let stats = validate_light_block_with_hashes(
+		head,
+		block_view,
+		&BlockViewHashes::new::<H>(block_view)?,
+		epoch_block_producers_map,
+		threshold,
+		&HostFunctionsVerifier::<H>::default(),
+		EpochLinkage::Contiguous,
+	)?;
+	validate_block_producers_commitment::<H>(head, block_view, epoch_block_producers_map)?;
+	Ok(stats)
+}
+
+/// [`validate_light_block`] with the [`BlockViewHashes`] of `block_view` already computed, the
+/// approvals verified by `verifier` and the epochs of `block_view` allowed by `linkage`.
+///
+/// The hash of the head is not known here: the caller checks [`validate_epoch_derivation`] and
+/// [`validate_block_producers_commitment`] too.
+pub fn validate_light_block_with_hashes(
+	head: &LightClientBlockView,
+	block_view: &LightClientBlockView,
+	hashes: &BlockViewHashes,
+	epoch_block_producers_map: &BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+	threshold: &ApprovalThreshold,
+	verifier: &dyn SignatureVerifier,
+	linkage: EpochLinkage,
+) -> LiteClientResult<ApprovalStats> {
 	//The light client updates its head with the information from LightClientBlockView iff:
 
 	// 1. The height of the block is higher than the height of the current head;
 	// 2. The epoch of the block is equal to the epoch_id or next_epoch_id known for the current
-	// head; 3. If the epoch of the block is equal to the next_epoch_id of the head, then next_bps
-	// is not None; 4. approvals_after_next contain valid signatures on approval_message from the
-	// block producers of the corresponding epoch
+	// head, and its next_epoch_id follows from the epoch of the head (see
+	// `validate_next_epoch_id`); 3. If the epoch of the block is equal to the next_epoch_id of
+	// the head, then next_bps is not None; 4. approvals_after_next contain valid signatures on
+	// approval_message from the block producers of the corresponding epoch
 	// 5. The signatures present in approvals_after_next correspond to more than 2/3 of the total
 	// stake (see next section). 6. If next_bps is not none, sha256(borsh(next_bps)) corresponds to
-	// the next_bp_hash in inner_lite.
+	// the next_bp_hash in inner_lite. 7. If next_bps is not none, it is a well formed set of block
+	// producers (see `validate_next_bps`).
 
 	// QUESTION: do we also want to pass the block hash received from the RPC?
 	// it's not on the spec, but it's an extra validation
-	let (_current_block_hash, _next_block_hash, approval_message) =
-		reconstruct_light_client_block_view_fields::<H>(block_view)?;
 
 	// (1)
 	if block_view.inner_lite.height <= head.inner_lite.height {
-		return Err(NearLiteClientError::InvalidLiteBlock(String::from(
-			"block view height is not ahead of the head's height",
-		)));
+		return Err(VerificationFailure::HeightNotAhead {
+			head_height: head.inner_lite.height,
+			block_height: block_view.inner_lite.height,
+		}
+		.into());
 	}
 
 	// (2)
+	let is_synced_epoch = linkage == EpochLinkage::EpochSync &&
+		epoch_block_producers_map.contains_key(&block_view.inner_lite.epoch_id);
 	if ![head.inner_lite.epoch_id, head.inner_lite.next_epoch_id]
-		.contains(&block_view.inner_lite.epoch_id)
+		.contains(&block_view.inner_lite.epoch_id) &&
+		!is_synced_epoch
 	{
-		return Err(NearLiteClientError::InvalidLiteBlock(String::from(
-			"block view epoch id not present in the head",
-		)));
+		return Err(VerificationFailure::EpochMismatch {
+			epoch_id: block_view.inner_lite.epoch_id,
+			head_epoch_id: head.inner_lite.epoch_id,
+			head_next_epoch_id: head.inner_lite.next_epoch_id,
+		}
+		.into());
 	}
+	validate_next_epoch_id(
+		&head.inner_lite.epoch_id,
+		&head.inner_lite.next_epoch_id,
+		&block_view.inner_lite.epoch_id,
+		&block_view.inner_lite.next_epoch_id,
+	)?;
 
-	// (3)
-	if block_view.inner_lite.epoch_id == head.inner_lite.next_epoch_id
+	// (3), for the skipped epochs of an epoch sync too
+	if block_view.inner_lite.epoch_id != head.inner_lite.epoch_id
 		&& block_view.next_bps.is_none()
 	{
-		return Err(NearLiteClientError::InvalidLiteBlock(String::from(
-			"block view epoch id is not the next epoch",
-		)));
+		return Err(VerificationFailure::MissingNextBps {
+			epoch_id: block_view.inner_lite.epoch_id,
+		}
+		.into());
 	}
 
 	//  (4) and (5)
+	let epoch_block_producers =
+		epoch_block_producers_map.get(&block_view.inner_lite.epoch_id).ok_or(
+			VerificationFailure::UnknownBlockProducers { epoch_id: block_view.inner_lite.epoch_id },
+		)?;
+	let stats = metrics::time_signature_verification(|| {
+		verify_approvals_with_verifier(
+			&block_view.approvals_after_next,
+			epoch_block_producers,
+			&hashes.approval_message,
+			threshold,
+			verifier,
+		)
+	})?;
+
+	// # (6)
+	if let Some(next_bp_hash) = hashes.next_bps_hash {
+		if next_bp_hash != block_view.inner_lite.next_bp_hash {
+			return Err(VerificationFailure::NextBpHashMismatch {
+				expected: block_view.inner_lite.next_bp_hash,
+				computed: next_bp_hash,
+			}
+			.into());
+		}
+	}
+
+	// (7)
+	if let Some(next_bps) = block_view.next_bps.as_deref() {
+		validate_next_bps(next_bps)?;
+	}
+	Ok(stats)
+}
+
+/// Checks that the `next_epoch_id` of a block view in `epoch_id` follows from the epochs of the
+/// head. Within an epoch every block names the same next epoch, so a block view in the epoch of
+/// the head names the next epoch of the head. A block view in another epoch names an epoch that
+/// is neither its own nor the one of the head: NEAR derives it from the hash of the last block of
+/// the previous epoch, see [`validate_epoch_derivation`].
+pub fn validate_next_epoch_id(
+	head_epoch_id: &EpochId,
+	head_next_epoch_id: &EpochId,
+	epoch_id: &EpochId,
+	next_epoch_id: &EpochId,
+) -> Result<(), VerificationFailure> {
+	if epoch_id == head_epoch_id {
+		if next_epoch_id != head_next_epoch_id {
+			return Err(VerificationFailure::NextEpochMismatch {
+				next_epoch_id: *next_epoch_id,
+				expected: *head_next_epoch_id,
+			})
+		}
+	} else if [head_epoch_id, epoch_id].contains(&next_epoch_id) {
+		return Err(VerificationFailure::NextEpochNotNew { next_epoch_id: *next_epoch_id })
+	}
+	Ok(())
+}
+
+/// Checks the derivation of the `next_epoch_id` of a block view that directly follows the head,
+/// the one with hash `head_hash`, into the next epoch: the head is then the last block of its
+/// epoch, and the epoch after the one of the block view is identified by the hash of the head.
+/// The other block views can't be checked, the last block of the previous epoch is not known.
+pub fn validate_epoch_derivation(
+	head: &LightClientBlockView,
+	head_hash: &CryptoHash,
+	block_view: &LightClientBlockView,
+) -> Result<(), VerificationFailure> {
+	if block_view.prev_block_hash != *head_hash ||
+		block_view.inner_lite.epoch_id != head.inner_lite.next_epoch_id
+	{
+		return Ok(())
+	}
+	let expected = EpochId(*head_hash);
+	if block_view.inner_lite.next_epoch_id != expected {
+		return Err(VerificationFailure::NextEpochMismatch {
+			next_epoch_id: block_view.inner_lite.next_epoch_id,
+			expected,
+		})
+	}
+	Ok(())
+}
+
+/// Checks that `block_view` is the block right after the head, the one with hash `head_hash`,
+/// for clients that track every block of the chain rather than sparse heads.
+pub fn validate_prev_block_hash(
+	head_hash: &CryptoHash,
+	block_view: &LightClientBlockView,
+) -> Result<(), VerificationFailure> {
+	if block_view.prev_block_hash != *head_hash {
+		return Err(VerificationFailure::NotSequential {
+			prev_block_hash: block_view.prev_block_hash,
+			head_hash: *head_hash,
+		})
+	}
+	Ok(())
+}
+
+/// Checks that the block producers of an epoch are the ones the headers of the previous epoch
+/// committed to, as `next_bp_hash`, so that no header can substitute them. Every header of an
+/// epoch carries the same `next_bp_hash`, the one of the head when `block_view` is in the same
+/// epoch. When `block_view` enters the next epoch, the block producers its approvals were verified
+/// against must hash to the `next_bp_hash` of the head, rather than only the `next_bps` of some
+/// header to their own.
+pub fn validate_block_producers_commitment<H: HostFunctions>(
+	head: &LightClientBlockView,
+	block_view: &LightClientBlockView,
+	epoch_block_producers_map: &BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+) -> Result<(), VerificationFailure> {
+	let expected = head.inner_lite.next_bp_hash;
+	let (epoch_id, computed) = if block_view.inner_lite.epoch_id == head.inner_lite.epoch_id {
+		(block_view.inner_lite.next_epoch_id, block_view.inner_lite.next_bp_hash)
+	} else if block_view.inner_lite.epoch_id == head.inner_lite.next_epoch_id {
+		let epoch_id = block_view.inner_lite.epoch_id;
+		match epoch_block_producers_map.get(&epoch_id) {
+			Some(block_producers) => (epoch_id, CryptoHash(H::sha256_borsh(block_producers))),
+			None => return Ok(()),
+		}
+	} else {
+		// skipped to in an epoch sync, the head committed to other block producers
+		return Ok(())
+	};
+	if computed != expected {
+		return Err(VerificationFailure::BlockProducersNotCommitted { epoch_id, expected, computed })
+	}
+	Ok(())
+}
+
+/// Checks that `next_bps` is a well formed set of block producers: sorted by decreasing stake, as
+/// nearcore assigns the seats, with no zero stake and no account id listed twice. Any list
+/// matching `next_bp_hash` would otherwise become the block producers of the next epoch.
+pub fn validate_next_bps(next_bps: &[ValidatorStakeView]) -> Result<(), VerificationFailure> {
+	let mut account_ids = BTreeSet::new();
+	let mut previous_stake = Balance::MAX;
+	for (index, block_producer) in next_bps.iter().enumerate() {
+		let stake = block_producer.stake();
+		let defect = if stake == 0 {
+			NextBpsDefect::ZeroStake
+		} else if stake > previous_stake {
+			NextBpsDefect::NotSortedByStake
+		} else if !account_ids.insert(block_producer.account_id().as_str()) {
+			NextBpsDefect::DuplicateAccountId
+		} else {
+			previous_stake = stake;
+			continue
+		};
+		return Err(VerificationFailure::MalformedNextBps { index, defect })
+	}
+	Ok(())
+}
+
+/// Checks (4) and (5): every approval is a valid signature of `approval_message` by the block
+/// producer at the same index, and the approvals add up to more than 2/3 of the total stake.
+///
+/// There must be an approval, possibly `None`, for every block producer, otherwise the stake of
+/// the missing ones would not count towards the total. Approvals beyond the last block producer
+/// are ignored, as nearcore and the rainbow bridge do.
+///
+/// Degenerate headers, as produced on small testnets, fail before any signature is verified: with
+/// [`VerificationFailure::ZeroTotalStake`] when the block producers have no stake at all, and
+/// with [`VerificationFailure::NoApprovals`] when none of them approved.
+///
+/// Works on any contiguous list of approvals, either heap allocated (`Vec`) or stack allocated
+/// (`BoundedApprovals`).
+pub fn verify_approvals<H: HostFunctions>(
+	approvals: &(impl AsRef<[Option<Signature>]> + ?Sized),
+	epoch_block_producers: &[ValidatorStakeView],
+	approval_message: &[u8],
+) -> LiteClientResult<ApprovalStats> {
+	verify_approvals_with_threshold::<H>(
+		approvals,
+		epoch_block_producers,
+		approval_message,
+		&ApprovalThreshold::default(),
+	)
+}
+
+/// [`verify_approvals`] with a custom share of the stake to exceed instead of 2/3.
+pub fn verify_approvals_with_threshold<H: HostFunctions>(
+	approvals: &(impl AsRef<[Option<Signature>]> + ?Sized),
+	epoch_block_producers: &[ValidatorStakeView],
+	approval_message: &[u8],
+	threshold: &ApprovalThreshold,
+) -> LiteClientResult<ApprovalStats> {
+	verify_approvals_with_verifier(
+		approvals,
+		epoch_block_producers,
+		approval_message,
+		threshold,
+		&HostFunctionsVerifier::<H>::default(),
+	)
+}
+
+/// [`verify_approvals_with_threshold`] with the signatures verified by `verifier`.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+pub fn verify_approvals_with_verifier(
+	approvals: &(impl AsRef<[Option<Signature>]> + ?Sized),
+	epoch_block_producers: &[ValidatorStakeView],
+	approval_message: &[u8],
+	threshold: &ApprovalThreshold,
+	verifier: &dyn SignatureVerifier,
+) -> LiteClientResult<ApprovalStats> {
+	let approvals = approvals.as_ref();
+	let block_producers = epoch_block_producers.len();
+	if block_producers == 0 {
+		return Err(VerificationFailure::ZeroTotalStake { block_producers }.into())
+	}
+	if approvals.iter().take(block_producers).all(Option::is_none) {
+		return Err(VerificationFailure::NoApprovals { block_producers }.into())
+	}
+	if approvals.len() < block_producers {
+		return Err(VerificationFailure::ApprovalsMissing {
+			approvals: approvals.len(),
+			block_producers: epoch_block_producers.len(),
+		}
+		.into())
+	}
+
 	let mut total_stake = 0;
 	let mut approved_stake = 0;
+	let mut signed = Vec::new();
 
-	let epoch_block_producers = &epoch_block_producers_map[&block_view.inner_lite.epoch_id];
-
-	for (maybe_signature, block_producer) in
-		block_view.approvals_after_next.iter().zip(epoch_block_producers.iter())
+	for (index, (maybe_signature, block_producer)) in
+		approvals.iter().zip(epoch_block_producers.iter()).enumerate()
 	{
-		let bp_stake_view = block_producer.clone().into_validator_stake();
-		let bp_stake = bp_stake_view.stake;
-		total_stake += bp_stake;
+		let bp_stake = block_producer.stake();
+		total_stake =
+			total_stake.checked_add(bp_stake).ok_or(VerificationFailure::StakeOverflow)?;
 
-		if maybe_signature.is_none() {
-			continue;
+		if let Some(signature) = maybe_signature {
+			// can't overflow, the approved stake is part of the total stake
+			approved_stake += bp_stake;
+			signed.push((index, signature, block_producer.public_key().clone()));
 		}
+	}
 
-		approved_stake += bp_stake;
+	if total_stake == 0 {
+		return Err(VerificationFailure::ZeroTotalStake { block_producers }.into())
+	}
+	if let Some(index) = first_invalid_signature(&signed, approval_message, verifier) {
+		return Err(VerificationFailure::InvalidSignature { index }.into());
+	}
+	let approvals_checked = signed.len();
 
-		let validator_public_key = bp_stake_view.public_key.clone();
-		if !maybe_signature
-			.as_ref()
-			.unwrap()
-			.verify(&approval_message, validator_public_key.clone())
-		{
-			return Err(NearLiteClientError::SignatureVerification(String::from(
-				"signature is not valid",
-			)));
+	if !threshold.is_reached(approved_stake, total_stake) {
+		return Err(
+			VerificationFailure::StakeThresholdNotReached { approved_stake, total_stake }.into()
+		);
+	}
+	Ok(ApprovalStats { approvals_checked, approved_stake, total_stake })
+}
+
+/// Index of the first approval, in the order of the block producers, that is not a valid
+/// signature of `message` according to `verifier`.
+#[cfg(not(feature = "parallel"))]
+fn first_invalid_signature(
+	signed: &[(usize, &Signature, PublicKey)],
+	message: &[u8],
+	verifier: &dyn SignatureVerifier,
+) -> Option<usize> {
+	signed
+		.iter()
+		.find(|(_, signature, public_key)| !verifier.verify(signature, message, public_key))
+		.map(|(index, ..)| *index)
+}
+
+/// Verifies the approvals across threads. The failure reported is still the first one in the order
+/// of the block producers, whichever thread finds it first.
+#[cfg(feature = "parallel")]
+fn first_invalid_signature(
+	signed: &[(usize, &Signature, PublicKey)],
+	message: &[u8],
+	verifier: &dyn SignatureVerifier,
+) -> Option<usize> {
+	use rayon::prelude::*;
+
+	signed
+		.par_iter()
+		.find_first(|(_, signature, public_key)| !verifier.verify(signature, message, public_key))
+		.map(|(index, ..)| *index)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+	use alloc::format;
+	use near_primitives_wasm::{BlockHeight, ValidatorStakeViewV1};
+
+	fn epoch(id: u8) -> EpochId {
+		EpochId(CryptoHash([id; 32]))
+	}
+
+	fn block_view(height: u64, epoch_id: u8, next_epoch_id: u8) -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(height);
+		block_view.inner_lite.epoch_id = epoch(epoch_id);
+		block_view.inner_lite.next_epoch_id = epoch(next_epoch_id);
+		block_view
+	}
+
+	fn failure(
+		head: &LightClientBlockView,
+		block_view: &LightClientBlockView,
+		epoch_block_producers: &BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+	) -> VerificationFailure {
+		match validate_light_block::<MockedHostFunctions>(
+			head,
+			block_view,
+			epoch_block_producers,
+			&ApprovalThreshold::default(),
+		) {
+			Err(NearLiteClientError::Verification(failure)) => failure,
+			other => panic!("unexpected result {:?}", other),
 		}
 	}
 
-	let threshold = total_stake * 2 / 3;
-	if approved_stake <= threshold {
-		return Err(NearLiteClientError::InvalidLiteBlock(String::from(
-			"block is not final: stake threshold is not reached",
-		)));
+	#[test]
+	fn test_validate_light_block_failures() {
+		let head = block_view(10, 1, 2);
+		let known = [(epoch(1), Vec::new())].into_iter().collect();
+
+		assert_eq!(
+			failure(&head, &block_view(10, 1, 2), &known),
+			VerificationFailure::HeightNotAhead {
+				head_height: BlockHeight(10),
+				block_height: BlockHeight(10),
+			}
+		);
+		assert_eq!(
+			failure(&head, &block_view(11, 3, 4), &known),
+			VerificationFailure::EpochMismatch {
+				epoch_id: epoch(3),
+				head_epoch_id: epoch(1),
+				head_next_epoch_id: epoch(2),
+			}
+		);
+		assert_eq!(
+			failure(&head, &block_view(11, 2, 3), &known),
+			VerificationFailure::MissingNextBps { epoch_id: epoch(2) }
+		);
+		assert_eq!(
+			failure(&head, &block_view(11, 1, 2), &BTreeMap::new()),
+			VerificationFailure::UnknownBlockProducers { epoch_id: epoch(1) }
+		);
+		assert_eq!(
+			failure(&head, &block_view(11, 1, 2), &known),
+			VerificationFailure::ZeroTotalStake { block_producers: 0 }
+		);
 	}
 
-	// # (6)
-	if block_view.next_bps.is_some() {
-		let block_view_next_bps_serialized =
-			block_view.next_bps.as_deref().unwrap().try_to_vec()?;
-		if H::sha256(&block_view_next_bps_serialized).as_slice()
-			!= block_view.inner_lite.next_bp_hash.as_ref()
-		{
-			return Err(NearLiteClientError::InvalidLiteBlock(String::from(
-				"inccorect next bp hash in block view",
-			)));
+	#[test]
+	fn test_next_epoch_linkage() {
+		let head = block_view(10, 1, 2);
+		let known = [(epoch(1), Vec::new()), (epoch(3), Vec::new())].into_iter().collect();
+
+		assert_eq!(
+			failure(&head, &block_view(11, 1, 3), &known),
+			VerificationFailure::NextEpochMismatch { next_epoch_id: epoch(3), expected: epoch(2) }
+		);
+		for next_epoch in [1, 2] {
+			assert_eq!(
+				failure(&head, &block_view(11, 2, next_epoch), &known),
+				VerificationFailure::NextEpochNotNew { next_epoch_id: epoch(next_epoch) }
+			);
 		}
+
+		// the first block of epoch 2 follows the head, the last block of epoch 1
+		let head_hash = head.current_block_hash::<MockedHostFunctions>();
+		let mut child = block_view(11, 2, 3);
+		child.prev_block_hash = head_hash;
+		assert_eq!(
+			failure(&head, &child, &known),
+			VerificationFailure::NextEpochMismatch {
+				next_epoch_id: epoch(3),
+				expected: EpochId(head_hash),
+			}
+		);
+		child.inner_lite.next_epoch_id = EpochId(head_hash);
+		assert!(validate_epoch_derivation(&head, &head_hash, &child).is_ok());
+
+		// epoch 3 can only be skipped to in an epoch sync
+		let mut synced = block_view(11, 3, 4);
+		synced.next_bps = Some(Vec::new());
+		assert!(matches!(
+			failure(&head, &synced, &known),
+			VerificationFailure::EpochMismatch { .. }
+		));
+		let result = validate_light_block_with_hashes(
+			&head,
+			&synced,
+			&BlockViewHashes::new::<MockedHostFunctions>(&synced).unwrap(),
+			&known,
+			&ApprovalThreshold::default(),
+			&HostFunctionsVerifier::<MockedHostFunctions>::default(),
+			EpochLinkage::EpochSync,
+		);
+		assert!(matches!(
+			result,
+			Err(NearLiteClientError::Verification(VerificationFailure::ZeroTotalStake { .. }))
+		));
 	}
-	Ok(())
-}
 
-pub fn reconstruct_light_client_block_view_fields<H: HostFunctions>(
-	block_view: &LightClientBlockView,
-) -> LiteClientResult<(CryptoHash, CryptoHash, Vec<u8>)> {
-	let current_block_hash = block_view.current_block_hash::<H>();
-	let next_block_hash =
-		next_block_hash::<H>(block_view.next_block_inner_hash, current_block_hash);
-	let approval_message = [
-		ApprovalInner::Endorsement(next_block_hash).try_to_vec()?,
-		(block_view.inner_lite.height + 2).to_le().try_to_vec()?,
-	]
-	.concat();
-	Ok((current_block_hash, next_block_hash, approval_message))
-}
-
-pub(crate) fn next_block_hash<H: HostFunctions>(
-	next_block_inner_hash: CryptoHash,
-	current_block_hash: CryptoHash,
-) -> CryptoHash {
-	H::sha256(&[next_block_inner_hash.as_ref(), current_block_hash.as_ref()].concat())
-		.as_slice()
-		.try_into()
-		.expect("Could not hash the next block")
+	#[test]
+	fn test_block_producers_commitment() {
+		let block_producers = vec![ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "node0".into(),
+			public_key: PublicKey([0; 32]),
+			stake: 10,
+		})];
+		let committed = CryptoHash(MockedHostFunctions::sha256_borsh(&block_producers));
+		let mut head = block_view(10, 1, 2);
+		head.inner_lite.next_bp_hash = committed;
+		let check = |header: &LightClientBlockView,
+		             known: &BTreeMap<EpochId, Vec<ValidatorStakeView>>| {
+			validate_block_producers_commitment::<MockedHostFunctions>(&head, header, known)
+		};
+		let substituted = [(epoch(2), Vec::new())].into_iter().collect();
+
+		// a later header of the epoch announces other block producers
+		let mut same_epoch = block_view(11, 1, 2);
+		same_epoch.inner_lite.next_bp_hash = CryptoHash([7; 32]);
+		assert_eq!(
+			check(&same_epoch, &substituted),
+			Err(VerificationFailure::BlockProducersNotCommitted {
+				epoch_id: epoch(2),
+				expected: committed,
+				computed: CryptoHash([7; 32]),
+			})
+		);
+		same_epoch.inner_lite.next_bp_hash = committed;
+		assert!(check(&same_epoch, &substituted).is_ok());
+
+		let next_epoch = block_view(11, 2, 3);
+		assert_eq!(
+			check(&next_epoch, &substituted),
+			Err(VerificationFailure::BlockProducersNotCommitted {
+				epoch_id: epoch(2),
+				expected: committed,
+				computed: CryptoHash(MockedHostFunctions::sha256_borsh(
+					&Vec::<ValidatorStakeView>::new()
+				)),
+			})
+		);
+		let known = [(epoch(2), block_producers)].into_iter().collect();
+		assert!(check(&next_epoch, &known).is_ok());
+	}
+
+	#[test]
+	fn test_malformed_next_bps() {
+		let block_producer = |account_id: &str, stake| {
+			ValidatorStakeView::V1(ValidatorStakeViewV1 {
+				account_id: account_id.into(),
+				public_key: PublicKey([0; 32]),
+				stake,
+			})
+		};
+		let malformed = |next_bps: &[ValidatorStakeView]| match validate_next_bps(next_bps) {
+			Err(VerificationFailure::MalformedNextBps { index, defect }) => (index, defect),
+			other => panic!("unexpected result {:?}", other),
+		};
+
+		assert!(validate_next_bps(&[]).is_ok());
+		assert!(validate_next_bps(&[
+			block_producer("node0", 30),
+			block_producer("node1", 20),
+			block_producer("node2", 20),
+		])
+		.is_ok());
+		assert_eq!(
+			malformed(&[block_producer("node0", 30), block_producer("node0", 20)]),
+			(1, NextBpsDefect::DuplicateAccountId)
+		);
+		assert_eq!(
+			malformed(&[block_producer("node0", 30), block_producer("node1", 0)]),
+			(1, NextBpsDefect::ZeroStake)
+		);
+		assert_eq!(
+			malformed(&[block_producer("node0", 20), block_producer("node1", 30)]),
+			(1, NextBpsDefect::NotSortedByStake)
+		);
+	}
+
+	#[test]
+	fn test_approval_threshold() {
+		let two_thirds = ApprovalThreshold::default();
+		assert!(!two_thirds.is_reached(200, 300));
+		assert!(two_thirds.is_reached(201, 300));
+		// the threshold is rounded down, as `total_stake * 2 / 3`
+		assert!(two_thirds.is_reached(7, 10));
+		assert!(!two_thirds.is_reached(6, 10));
+		assert!(two_thirds.is_reached(u128::MAX / 3 * 2 + 1, u128::MAX));
+
+		let three_quarters = ApprovalThreshold::new(3, 4).unwrap();
+		assert!(!three_quarters.is_reached(201, 300));
+		assert!(three_quarters.is_reached(226, 300));
+
+		assert!(ApprovalThreshold::new(1, 2).is_err());
+		assert!(ApprovalThreshold::new(4, 4).is_err());
+		assert!(ApprovalThreshold::new(0, 0).is_err());
+		assert_eq!(ApprovalThreshold::new(2, 3).unwrap(), ApprovalThreshold::TWO_THIRDS);
+	}
+
+	#[test]
+	fn test_block_view_hashes() {
+		let mut block_view = block_view(10, 1, 2);
+		let hashes = BlockViewHashes::new::<MockedHostFunctions>(&block_view).unwrap();
+		let (current_block_hash, next_block_hash, approval_message) =
+			reconstruct_light_client_block_view_fields::<MockedHostFunctions>(&block_view).unwrap();
+		assert_eq!(hashes.current_block_hash, current_block_hash);
+		assert_eq!(hashes.next_block_hash, next_block_hash);
+		assert_eq!(hashes.approval_message, approval_message);
+		// borsh of an empty `Vec` is its `u32` length
+		assert_eq!(hashes.next_bps_hash, Some(CryptoHash(MockedHostFunctions::sha256(&[0; 4]))));
+
+		block_view.next_bps = None;
+		let hashes = BlockViewHashes::new::<MockedHostFunctions>(&block_view).unwrap();
+		assert_eq!(hashes.next_bps_hash, None);
+	}
+
+	#[test]
+	fn test_first_invalid_signature_is_reported() {
+		let block_producers: Vec<_> = (0..4)
+			.map(|index| {
+				ValidatorStakeView::V1(ValidatorStakeViewV1 {
+					account_id: format!("node{}", index),
+					public_key: PublicKey([index; 32]),
+					stake: 10,
+				})
+			})
+			.collect();
+		let forged = Some(Signature::from_raw(&[1; 64]));
+		let approvals = [None, forged.clone(), None, forged];
+
+		// with `parallel`, the threads race to find an invalid signature
+		for _ in 0..10 {
+			let result =
+				verify_approvals::<MockedHostFunctions>(&approvals, &block_producers, b"message");
+			match result {
+				Err(NearLiteClientError::Verification(failure)) =>
+					assert_eq!(failure, VerificationFailure::InvalidSignature { index: 1 }),
+				other => panic!("unexpected result {:?}", other),
+			}
+		}
+	}
+
+	#[test]
+	fn test_approvals_are_matched_to_block_producers_by_index() {
+		use sp_core::{ed25519::Pair, Pair as _};
+
+		let pairs: Vec<_> = (0..3u8).map(|seed| Pair::from_seed(&[seed; 32])).collect();
+		let block_producers: Vec<_> = pairs
+			.iter()
+			.enumerate()
+			.map(|(index, pair)| {
+				ValidatorStakeView::V1(ValidatorStakeViewV1 {
+					account_id: format!("node{}", index),
+					public_key: PublicKey(pair.public().0),
+					stake: 10,
+				})
+			})
+			.collect();
+		let mut approvals: Vec<_> = pairs
+			.iter()
+			.map(|pair| Some(Signature::Ed25519(pair.sign(b"message"))))
+			.collect();
+		let verify = |approvals: &[Option<Signature>]| {
+			verify_approvals::<MockedHostFunctions>(approvals, &block_producers, b"message")
+		};
+
+		assert_eq!(verify(&approvals).unwrap().approved_stake, 30);
+		// extra approvals are ignored
+		assert_eq!(verify(&[&approvals[..], &[None]].concat()).unwrap().total_stake, 30);
+
+		struct RejectAll;
+		impl SignatureVerifier for RejectAll {
+			fn verify(&self, _: &Signature, _: &[u8], _: &PublicKey) -> bool {
+				false
+			}
+		}
+		match verify_approvals_with_verifier(
+			&approvals,
+			&block_producers,
+			b"message",
+			&ApprovalThreshold::default(),
+			&RejectAll,
+		) {
+			Err(NearLiteClientError::Verification(failure)) =>
+				assert_eq!(failure, VerificationFailure::InvalidSignature { index: 0 }),
+			other => panic!("unexpected result {:?}", other),
+		}
+
+		approvals.swap(0, 1);
+		match verify(&approvals) {
+			Err(NearLiteClientError::Verification(failure)) =>
+				assert_eq!(failure, VerificationFailure::InvalidSignature { index: 0 }),
+			other => panic!("unexpected result {:?}", other),
+		}
+		match verify(&approvals[..2]) {
+			Err(NearLiteClientError::Verification(failure)) => assert_eq!(
+				failure,
+				VerificationFailure::ApprovalsMissing { approvals: 2, block_producers: 3 }
+			),
+			other => panic!("unexpected result {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_degenerate_approvals() {
+		use sp_core::{ed25519::Pair, Pair as _};
+
+		let pairs: Vec<_> = (0..2u8).map(|seed| Pair::from_seed(&[seed; 32])).collect();
+		let block_producers = |stake| -> Vec<_> {
+			pairs
+				.iter()
+				.enumerate()
+				.map(|(index, pair)| {
+					ValidatorStakeView::V1(ValidatorStakeViewV1 {
+						account_id: format!("node{}", index),
+						public_key: PublicKey(pair.public().0),
+						stake,
+					})
+				})
+				.collect()
+		};
+		let signed: Vec<_> = pairs
+			.iter()
+			.map(|pair| Some(Signature::Ed25519(pair.sign(b"message"))))
+			.collect();
+		fn rejection(
+			approvals: &[Option<Signature>],
+			block_producers: &[ValidatorStakeView],
+		) -> VerificationFailure {
+			match verify_approvals::<MockedHostFunctions>(approvals, block_producers, b"message") {
+				Err(NearLiteClientError::Verification(failure)) => failure,
+				other => panic!("unexpected result {:?}", other),
+			}
+		}
+
+		// a header of an epoch without block producers can't be final
+		assert_eq!(
+			rejection(&signed, &[]),
+			VerificationFailure::ZeroTotalStake { block_producers: 0 }
+		);
+		// nobody approved, whether the approvals are empty or all `None`
+		assert_eq!(
+			rejection(&[], &block_producers(10)),
+			VerificationFailure::NoApprovals { block_producers: 2 }
+		);
+		assert_eq!(
+			rejection(&[None, None], &block_producers(10)),
+			VerificationFailure::NoApprovals { block_producers: 2 }
+		);
+		// an approval past the last block producer doesn't count
+		assert_eq!(
+			rejection(&[None, None, signed[0].clone()], &block_producers(10)),
+			VerificationFailure::NoApprovals { block_producers: 2 }
+		);
+		// everybody approved, but nobody has stake
+		assert_eq!(
+			rejection(&signed, &block_producers(0)),
+			VerificationFailure::ZeroTotalStake { block_producers: 2 }
+		);
+		assert_eq!(
+			verify_approvals::<MockedHostFunctions>(&signed, &block_producers(10), b"message")
+				.unwrap()
+				.approved_stake,
+			20
+		);
+	}
+
+	#[test]
+	fn test_v2_block_producers() {
+		use near_primitives_wasm::ValidatorStakeViewV2;
+		use sp_core::{ed25519::Pair, Pair as _};
+
+		let pair = Pair::from_seed(&[1; 32]);
+		let v1 = ValidatorStakeViewV1 {
+			account_id: "node0".into(),
+			public_key: PublicKey(pair.public().0),
+			stake: 10,
+		};
+		let v2 = ValidatorStakeView::V2(ValidatorStakeViewV2 {
+			account_id: v1.account_id.clone(),
+			public_key: v1.public_key.clone(),
+			stake: v1.stake,
+			is_chunk_only: false,
+		});
+		let approvals = [Some(Signature::Ed25519(pair.sign(b"message")))];
+		let stats =
+			verify_approvals::<MockedHostFunctions>(&approvals, &[v2.clone()], b"message").unwrap();
+		assert_eq!(stats.approved_stake, 10);
+		assert!(validate_next_bps(&[v2.clone()]).is_ok());
+
+		// the version is part of `next_bp_hash`
+		let next_bps_hash = |next_bps| {
+			let mut block_view = block_view(10, 1, 2);
+			block_view.next_bps = Some(next_bps);
+			BlockViewHashes::new::<MockedHostFunctions>(&block_view).unwrap().next_bps_hash
+		};
+		assert_ne!(next_bps_hash(vec![v2]), next_bps_hash(vec![ValidatorStakeView::V1(v1)]));
+	}
+
+	#[test]
+	fn test_stake_overflow_is_an_error() {
+		let block_producer = ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: String::from("node0"),
+			public_key: PublicKey([0; 32]),
+			stake: u128::MAX / 2 + 1,
+		});
+		// the stakes are added up before any signature is verified
+		let result = verify_approvals::<MockedHostFunctions>(
+			&[Some(Signature::from_raw(&[1; 64])), None],
+			&[block_producer.clone(), block_producer],
+			b"message",
+		);
+		match result {
+			Err(NearLiteClientError::Verification(failure)) =>
+				assert_eq!(failure, VerificationFailure::StakeOverflow),
+			other => panic!("unexpected result {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_approved_bps() {
+		let stats = |approved_stake, total_stake| ApprovalStats {
+			approvals_checked: 0,
+			approved_stake,
+			total_stake,
+		};
+		assert_eq!(stats(0, 0).approved_bps(), 0);
+		assert_eq!(stats(2, 3).approved_bps(), 6666);
+		assert_eq!(stats(7, 7).approved_bps(), 10_000);
+		assert_eq!(stats(u128::MAX / 4 * 3, u128::MAX).approved_bps(), 7499);
+	}
 }
@@ -0,0 +1,169 @@
+//! # Bounded types
+//!
+//! Fixed capacity, stack allocated variants of `MerklePath` and of the approvals of a block, for
+//! environments where heap usage has to be provably bounded (zkVM guests, embedded targets).
+//!
+//! Both dereference to the same slices as their heap allocated counterparts, so they go through
+//! the exact same verification code: [`compute_root_from_path`](crate::compute_root_from_path)
+//! and [`verify_approvals`](crate::verify_approvals).
+
+use alloc::string::String;
+use near_primitives_wasm::{CryptoHash, Direction, MerklePath, MerklePathItem, Signature};
+use sp_std::vec::Vec;
+
+use crate::error::NearLiteClientError;
+
+#[derive(Debug, Clone)]
+pub struct BoundedMerklePath<const N: usize> {
+	len: usize,
+	items: [MerklePathItem; N],
+}
+
+impl<const N: usize> BoundedMerklePath<N> {
+	pub fn new() -> Self {
+		let filler = MerklePathItem { hash: CryptoHash::default(), direction: Direction::Left };
+		Self { len: 0, items: [(); N].map(|_| filler.clone()) }
+	}
+
+	pub fn push(&mut self, item: MerklePathItem) -> Result<(), NearLiteClientError> {
+		if self.len == N {
+			return Err(NearLiteClientError::CapacityExceeded(String::from(
+				"merkle path is longer than the bounded capacity",
+			)));
+		}
+		self.items[self.len] = item;
+		self.len += 1;
+		Ok(())
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+}
+
+impl<const N: usize> Default for BoundedMerklePath<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const N: usize> AsRef<[MerklePathItem]> for BoundedMerklePath<N> {
+	fn as_ref(&self) -> &[MerklePathItem] {
+		&self.items[..self.len]
+	}
+}
+
+impl<const N: usize> TryFrom<&[MerklePathItem]> for BoundedMerklePath<N> {
+	type Error = NearLiteClientError;
+	fn try_from(path: &[MerklePathItem]) -> Result<Self, Self::Error> {
+		let mut bounded = Self::new();
+		for item in path {
+			bounded.push(item.clone())?;
+		}
+		Ok(bounded)
+	}
+}
+
+impl<const N: usize> From<BoundedMerklePath<N>> for MerklePath {
+	fn from(path: BoundedMerklePath<N>) -> Self {
+		path.as_ref().to_vec()
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct BoundedApprovals<const N: usize> {
+	len: usize,
+	items: [Option<Signature>; N],
+}
+
+impl<const N: usize> BoundedApprovals<N> {
+	pub fn new() -> Self {
+		Self { len: 0, items: [(); N].map(|_| None) }
+	}
+
+	pub fn push(&mut self, approval: Option<Signature>) -> Result<(), NearLiteClientError> {
+		if self.len == N {
+			return Err(NearLiteClientError::CapacityExceeded(String::from(
+				"approvals are longer than the bounded capacity",
+			)));
+		}
+		self.items[self.len] = approval;
+		self.len += 1;
+		Ok(())
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+}
+
+impl<const N: usize> Default for BoundedApprovals<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const N: usize> AsRef<[Option<Signature>]> for BoundedApprovals<N> {
+	fn as_ref(&self) -> &[Option<Signature>] {
+		&self.items[..self.len]
+	}
+}
+
+impl<const N: usize> TryFrom<&[Option<Signature>]> for BoundedApprovals<N> {
+	type Error = NearLiteClientError;
+	fn try_from(approvals: &[Option<Signature>]) -> Result<Self, Self::Error> {
+		let mut bounded = Self::new();
+		for approval in approvals {
+			bounded.push(approval.clone())?;
+		}
+		Ok(bounded)
+	}
+}
+
+impl<const N: usize> From<BoundedApprovals<N>> for Vec<Option<Signature>> {
+	fn from(approvals: BoundedApprovals<N>) -> Self {
+		approvals.as_ref().to_vec()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{compute_root_from_path, test_utils::MockedHostFunctions};
+
+	fn path() -> MerklePath {
+		(0..3u8)
+			.map(|i| MerklePathItem {
+				hash: CryptoHash([i; 32]),
+				direction: if i % 2 == 0 { Direction::Left } else { Direction::Right },
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_bounded_merkle_path_computes_the_same_root() {
+		let path = path();
+		let bounded = BoundedMerklePath::<4>::try_from(path.as_slice()).unwrap();
+		assert_eq!(bounded.len(), 3);
+		assert_eq!(
+			compute_root_from_path::<MockedHostFunctions>(&bounded, CryptoHash([7; 32])).unwrap(),
+			compute_root_from_path::<MockedHostFunctions>(&path, CryptoHash([7; 32])).unwrap(),
+		);
+		assert_eq!(MerklePath::from(bounded), path);
+	}
+
+	#[test]
+	fn test_bounded_capacity_is_enforced() {
+		assert!(BoundedMerklePath::<2>::try_from(path().as_slice()).is_err());
+		assert!(BoundedApprovals::<1>::try_from([None, None].as_slice()).is_err());
+		assert_eq!(BoundedApprovals::<2>::try_from([None, None].as_slice()).unwrap().len(), 2);
+	}
+}
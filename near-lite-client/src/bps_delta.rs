@@ -0,0 +1,124 @@
+//! # Block producer deltas
+//!
+//! Consecutive epochs share most of their block producers, so [`KvStorage`](crate::KvStorage)
+//! stores the block producers of an epoch as a [`BlockProducersDelta`] against those of the epoch
+//! stored before it, whenever that is smaller: runs of block producers copied from that base, and
+//! the ones it doesn't have.
+
+use alloc::format;
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_primitives_wasm::{EpochId, ValidatorStakeView};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{error::serialize, LiteClientResult, NearLiteClientError};
+
+/// Deltas between a delta and the block producers stored in full it is rebuilt from, past which
+/// the block producers are stored in full, so that reading them stays cheap.
+pub(crate) const MAX_DELTA_DEPTH: u8 = 8;
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+enum DeltaOp {
+	/// `len` block producers of the base, from its `from`-th one
+	Copy {
+		from: u32,
+		len: u32,
+	},
+	Insert(Vec<ValidatorStakeView>),
+}
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub(crate) struct BlockProducersDelta {
+	/// epoch of the block producers the delta applies to
+	pub(crate) base: EpochId,
+	/// number of deltas down to block producers stored in full, this one included
+	pub(crate) depth: u8,
+	ops: Vec<DeltaOp>,
+}
+
+impl BlockProducersDelta {
+	/// Delta from `base`, the block producers of `base_epoch`, to `block_producers`. Validators
+	/// are only copied if their stake and layout are unchanged too.
+	pub(crate) fn new(
+		base_epoch: EpochId,
+		depth: u8,
+		base: &[ValidatorStakeView],
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<Self> {
+		let mut positions = BTreeMap::new();
+		for (index, validator) in base.iter().enumerate() {
+			positions.entry(serialize(validator)?).or_insert(index as u32);
+		}
+		let mut ops = Vec::new();
+		for validator in block_producers {
+			match (positions.get(&serialize(validator)?), ops.last_mut()) {
+				(Some(&index), Some(DeltaOp::Copy { from, len })) if *from + *len == index =>
+					*len += 1,
+				(Some(&index), _) => ops.push(DeltaOp::Copy { from: index, len: 1 }),
+				(None, Some(DeltaOp::Insert(inserted))) => inserted.push(validator.clone()),
+				(None, _) => ops.push(DeltaOp::Insert(Vec::from([validator.clone()]))),
+			}
+		}
+		Ok(Self { base: base_epoch, depth, ops })
+	}
+
+	/// Rebuilds the block producers from `base`, the block producers of the base epoch.
+	pub(crate) fn apply(
+		&self,
+		base: &[ValidatorStakeView],
+	) -> LiteClientResult<Vec<ValidatorStakeView>> {
+		let mut block_producers = Vec::new();
+		for op in &self.ops {
+			match op {
+				DeltaOp::Copy { from, len } => {
+					let (from, len) = (*from as usize, *len as usize);
+					let copied = base.get(from..from + len).ok_or_else(|| {
+						NearLiteClientError::Storage(format!(
+							"delta copies block producers {}..{} of {} in the base",
+							from,
+							from + len,
+							base.len()
+						))
+					})?;
+					block_producers.extend_from_slice(copied);
+				},
+				DeltaOp::Insert(inserted) => block_producers.extend_from_slice(inserted),
+			}
+		}
+		Ok(block_producers)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use near_primitives_wasm::{CryptoHash, PublicKey, ValidatorStakeViewV1};
+
+	fn validator(index: u8, stake: u128) -> ValidatorStakeView {
+		ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: format!("node{}.near", index),
+			public_key: PublicKey([index; 32]),
+			stake,
+		})
+	}
+
+	#[test]
+	fn test_delta_rebuilds_the_block_producers() {
+		let base: Vec<_> = (0..100).map(|index| validator(index, 10)).collect();
+		let mut block_producers = base.clone();
+		block_producers.remove(40);
+		block_producers[70] = validator(70, 11);
+		block_producers.push(validator(100, 10));
+		block_producers.swap(0, 1);
+
+		let delta =
+			BlockProducersDelta::new(EpochId(CryptoHash([1; 32])), 1, &base, &block_producers)
+				.unwrap();
+		// two copies for the swap, the runs around the removed and the changed validators, and
+		// the changed and the new validators
+		assert_eq!(delta.ops.len(), 7);
+		let rebuilt = delta.apply(&base).unwrap();
+		assert_eq!(serialize(&rebuilt).unwrap(), serialize(&block_producers).unwrap());
+		assert!(serialize(&delta).unwrap().len() * 10 < serialize(&block_producers).unwrap().len());
+		assert!(delta.apply(&base[..50]).is_err());
+	}
+}
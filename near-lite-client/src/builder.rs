@@ -0,0 +1,528 @@
+//! # Light client builder
+//!
+//! [`LightClientBuilder`] gathers the configuration of a [`LightClient`] before creating it: the
+//! digest (the `HostFunctions` type parameter), how signatures are verified, where its state is
+//! persisted, the share of stake a block needs to be final, how many epochs of block producers are
+//! kept, the clock headers are checked against, how old the head may get, the protocol rules
+//! of each epoch, whether headers may skip epochs and whether they must follow each other.
+
+use alloc::{boxed::Box, string::String};
+use core::marker::PhantomData;
+use near_primitives_wasm::{HostFunctions, LightClientBlockView, Sha256HostFunctions};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{
+	audit::AuditLog,
+	block_validation::{ApprovalThreshold, EpochLinkage},
+	candidate_pool::{CandidatePool, DEFAULT_CANDIDATE_POOL_CAPACITY},
+	clock::{Clock, TimestampPolicy},
+	error::NearLiteClientError,
+	hash_cache::{HashCache, DEFAULT_VALIDATED_CACHE_CAPACITY},
+	header_mmr::HeaderMmr,
+	header_queue::{HeaderQueue, DEFAULT_HEADER_QUEUE_CAPACITY},
+	protocol::ProtocolSchedule,
+	retention::RetentionPolicy,
+	signature::{HostFunctionsVerifier, SignatureVerifier},
+	storage::LightClientStorage,
+	LightClient, LiteClientResult, TrustedCheckpoint,
+};
+
+/// The head's epoch and the next one, the block producers needed to validate the next headers.
+pub const MIN_RETAINED_EPOCHS: usize = 2;
+
+pub struct LightClientBuilder<H: HostFunctions = Sha256HostFunctions> {
+	checkpoint: Option<TrustedCheckpoint>,
+	storage: Option<Box<dyn LightClientStorage>>,
+	threshold: ApprovalThreshold,
+	signature_verifier: Option<Box<dyn SignatureVerifier>>,
+	retained_epochs: Option<usize>,
+	retention: Option<RetentionPolicy>,
+	clock: Option<Box<dyn Clock>>,
+	timestamp_policy: TimestampPolicy,
+	max_head_age: Option<u64>,
+	audit_log: bool,
+	header_mmr: bool,
+	header_queue_capacity: usize,
+	validated_cache_capacity: usize,
+	protocol: ProtocolSchedule,
+	epoch_linkage: EpochLinkage,
+	sequential: bool,
+	candidate_pool_capacity: usize,
+	_hf: PhantomData<H>,
+}
+
+impl<H: HostFunctions> LightClientBuilder<H> {
+	pub fn new() -> Self {
+		Self {
+			checkpoint: None,
+			storage: None,
+			threshold: ApprovalThreshold::default(),
+			signature_verifier: None,
+			retained_epochs: None,
+			retention: None,
+			clock: None,
+			timestamp_policy: TimestampPolicy::default(),
+			max_head_age: None,
+			audit_log: false,
+			header_mmr: false,
+			header_queue_capacity: DEFAULT_HEADER_QUEUE_CAPACITY,
+			validated_cache_capacity: DEFAULT_VALIDATED_CACHE_CAPACITY,
+			protocol: ProtocolSchedule::default(),
+			epoch_linkage: EpochLinkage::default(),
+			sequential: false,
+			candidate_pool_capacity: DEFAULT_CANDIDATE_POOL_CAPACITY,
+			_hf: PhantomData::default(),
+		}
+	}
+
+	/// The head to start from when the storage doesn't hold one already.
+	pub fn checkpoint(mut self, checkpoint: TrustedCheckpoint) -> Self {
+		self.checkpoint = Some(checkpoint);
+		self
+	}
+
+	/// Persists the state of the client in `storage`. If it already holds a head, the client
+	/// resumes from it and the checkpoint is ignored.
+	pub fn storage(mut self, storage: impl LightClientStorage + 'static) -> Self {
+		self.storage = Some(Box::new(storage));
+		self
+	}
+
+	pub fn approval_threshold(mut self, threshold: ApprovalThreshold) -> Self {
+		self.threshold = threshold;
+		self
+	}
+
+	/// Verifies the approvals of the block producers with `verifier` instead of `H::verify`.
+	pub fn signature_verifier(mut self, verifier: impl SignatureVerifier + 'static) -> Self {
+		self.signature_verifier = Some(Box::new(verifier));
+		self
+	}
+
+	/// Only keeps the block producers of the `epochs` most recent epochs, at least
+	/// [`MIN_RETAINED_EPOCHS`]. All of them are kept by default.
+	pub fn retained_epochs(mut self, epochs: usize) -> Self {
+		self.retained_epochs = Some(epochs);
+		self
+	}
+
+	/// Bounds the history kept by the storage with `policy`, enforced on every head update, so
+	/// that [`LightClient::epoch_validators`] still finds the epochs dropped from memory. By
+	/// default the storage keeps the retained epochs, and the hashes of every head.
+	pub fn retention(mut self, policy: RetentionPolicy) -> Self {
+		self.retention = Some(policy);
+		self
+	}
+
+	/// Rejects the headers whose timestamp is ahead of `clock`, by more than the `max_drift` of
+	/// the [timestamp policy](Self::timestamp_policy).
+	pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+		self.clock = Some(Box::new(clock));
+		self
+	}
+
+	pub fn timestamp_policy(mut self, policy: TimestampPolicy) -> Self {
+		self.timestamp_policy = policy;
+		self
+	}
+
+	/// Refuses to verify proofs once the head is more than `max_age` nanoseconds older than the
+	/// [clock](Self::clock), which is then required.
+	pub fn max_head_age(mut self, max_age: u64) -> Self {
+		self.max_head_age = Some(max_age);
+		self
+	}
+
+	pub fn audit_log(mut self) -> Self {
+		self.audit_log = true;
+		self
+	}
+
+	/// Keeps an MMR of the heads accepted from then on, to prove their inclusion with
+	/// [`LightClient::prove_header_inclusion`].
+	pub fn header_mmr(mut self) -> Self {
+		self.header_mmr = true;
+		self
+	}
+
+	pub fn header_queue_capacity(mut self, capacity: usize) -> Self {
+		self.header_queue_capacity = capacity;
+		self
+	}
+
+	/// How many hashes of accepted block views are remembered so that, once the head moved past
+	/// them, their resubmissions are rejected as stale without an audit entry; none if `0`.
+	pub fn validated_cache_capacity(mut self, capacity: usize) -> Self {
+		self.validated_cache_capacity = capacity;
+		self
+	}
+
+	/// Validates the headers of each epoch with the rules of its protocol version. Every epoch
+	/// follows the rules of the specification by default.
+	pub fn protocol_schedule(mut self, schedule: ProtocolSchedule) -> Self {
+		self.protocol = schedule;
+		self
+	}
+
+	/// Lets headers skip to the epochs given with [`LightClient::insert_synced_epoch`] under
+	/// [`EpochLinkage::EpochSync`]. Every header is in the epoch of the head or the next one by
+	/// default.
+	pub fn epoch_linkage(mut self, linkage: EpochLinkage) -> Self {
+		self.epoch_linkage = linkage;
+		self
+	}
+
+	/// Only accepts the header right after the head, whose `prev_block_hash` is the hash of the
+	/// head, so that the client goes through every block of the chain. By default, any later
+	/// final header can become the head.
+	pub fn sequential_headers(mut self) -> Self {
+		self.sequential = true;
+		self
+	}
+
+	/// How many blocks [`LightClient::submit_candidate`] keeps until they are final.
+	pub fn candidate_pool_capacity(mut self, capacity: usize) -> Self {
+		self.candidate_pool_capacity = capacity;
+		self
+	}
+
+	pub fn build(mut self) -> LiteClientResult<LightClient<H>> {
+		if matches!(self.retained_epochs, Some(epochs) if epochs < MIN_RETAINED_EPOCHS) {
+			return Err(config_error("at least 2 epochs of block producers must be retained"))
+		}
+		if let Some(policy) = self.retention {
+			if self.storage.is_none() {
+				return Err(config_error("a retention policy needs a storage"))
+			}
+			let retained = self.retained_epochs.unwrap_or(usize::MAX);
+			if matches!(policy, RetentionPolicy::Epochs(epochs) if epochs < retained) {
+				return Err(config_error("fewer epochs would be stored than retained"))
+			}
+		}
+		if self.max_head_age.is_some() && self.clock.is_none() {
+			return Err(config_error("a maximum head age needs a clock"))
+		}
+
+		let stored_head = match self.storage.as_mut() {
+			Some(storage) => {
+				storage.recover()?;
+				storage.head()?
+			},
+			None => None,
+		};
+		let (head, epoch_block_producers, epoch_heights) = match (stored_head, self.checkpoint) {
+			(Some(head), _) => {
+				let storage = self.storage.as_ref().expect("the head comes from the storage; qed");
+				(head, storage.epoch_block_producers()?, storage.epoch_heights()?)
+			},
+			(None, Some(checkpoint)) => {
+				let head: LightClientBlockView = checkpoint.into();
+				let next_bps = head.next_bps.clone().unwrap_or_default();
+				let epoch_block_producers = [(head.inner_lite.next_epoch_id, next_bps)].into();
+				(head, epoch_block_producers, Vec::new())
+			},
+			(None, None) => return Err(config_error("neither a checkpoint nor a stored head")),
+		};
+
+		let protocol_version =
+			self.protocol.version(&head.inner_lite.epoch_id, self.protocol.initial_version);
+		let mut client = LightClient {
+			head,
+			epoch_block_producers: BTreeMap::new(),
+			epochs: Vec::new(),
+			retained_epochs: self.retained_epochs,
+			retention: self.retention,
+			stored_epochs: Vec::new(),
+			threshold: self.threshold,
+			signature_verifier: self
+				.signature_verifier
+				.unwrap_or_else(|| Box::new(HostFunctionsVerifier::<H>::default())),
+			storage: self.storage,
+			clock: self.clock,
+			timestamp_policy: self.timestamp_policy,
+			max_head_age: self.max_head_age,
+			audit_log: self.audit_log.then(AuditLog::new),
+			header_mmr: self.header_mmr.then(HeaderMmr::default),
+			header_queue: HeaderQueue::new(self.header_queue_capacity),
+			accepted: HashCache::new(self.validated_cache_capacity),
+			protocol: self.protocol,
+			protocol_version,
+			epoch_linkage: self.epoch_linkage,
+			sequential: self.sequential,
+			candidates: CandidatePool::new(self.candidate_pool_capacity),
+			synced_epochs: Vec::new(),
+		};
+		let head = client.head.clone();
+		let head_hash = head.current_block_hash::<H>();
+		let pruned_below = client.update_storage(false, |client| {
+			client.persist_head(&head, head_hash)?;
+			// the epochs keep the height they were first inserted at, which the retention goes by
+			for (epoch_id, block_producers) in epoch_block_producers {
+				let inserted_at = epoch_heights
+					.iter()
+					.find(|(stored, _)| *stored == epoch_id)
+					.map_or(head.inner_lite.height, |(_, height)| *height);
+				client.insert_epoch_block_producers(epoch_id, block_producers, inserted_at)?;
+			}
+			client.enforce_retention(head.inner_lite.height)
+		})?;
+		if let Some(mmr) = client.header_mmr.as_mut() {
+			mmr.append::<H>(head.inner_lite.height, head_hash)?;
+		}
+		client.record_pruning(pruned_below)?;
+		Ok(client)
+	}
+}
+
+impl<H: HostFunctions> Default for LightClientBuilder<H> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn config_error(reason: &str) -> NearLiteClientError {
+	NearLiteClientError::Config(String::from(reason))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		storage::InMemoryStorage, test_utils::MockedHostFunctions, AuditEvent, OutcomeId,
+		TypedOutcomeProof,
+	};
+	use near_primitives_wasm::{
+		BlockHeight, CryptoHash, EpochId, ExecutionOutcomeView, MerklePath, OutcomeProof,
+	};
+
+	type Builder = LightClientBuilder<MockedHostFunctions>;
+
+	struct FixedClock(u64);
+
+	impl Clock for FixedClock {
+		fn now(&self) -> u64 {
+			self.0
+		}
+	}
+
+	fn block_view(height: u64) -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(height);
+		block_view
+	}
+
+	#[test]
+	fn test_build_requires_a_starting_head() {
+		assert!(Builder::new().build().is_err());
+		assert!(Builder::new().storage(InMemoryStorage::default()).build().is_err());
+		assert!(Builder::new()
+			.checkpoint(TrustedCheckpoint(block_view(1)))
+			.retained_epochs(1)
+			.build()
+			.is_err());
+
+		let client = Builder::new().checkpoint(TrustedCheckpoint(block_view(1))).build().unwrap();
+		assert_eq!(client.head_height(), BlockHeight(1));
+		assert!(client.audit_log().is_none());
+	}
+
+	#[test]
+	fn test_default_host_functions() {
+		let client: LightClient = LightClientBuilder::new()
+			.checkpoint(TrustedCheckpoint(block_view(1)))
+			.build()
+			.unwrap();
+		assert_eq!(
+			client.head_hash(),
+			block_view(1).current_block_hash::<MockedHostFunctions>(),
+			"both hash with SHA-256"
+		);
+	}
+
+	#[test]
+	fn test_resume_from_storage() {
+		let mut storage = InMemoryStorage::default();
+		storage.set_head(&block_view(10)).unwrap();
+		for epoch in 1..=4 {
+			storage
+				.insert_epoch_block_producers(EpochId(CryptoHash([epoch; 32])), &[])
+				.unwrap();
+		}
+
+		let client = Builder::new()
+			.checkpoint(TrustedCheckpoint(block_view(1)))
+			.storage(storage)
+			.retained_epochs(2)
+			.build()
+			.unwrap();
+		assert_eq!(client.head_height(), BlockHeight(10), "the stored head wins");
+		assert_eq!(client.epochs, [EpochId(CryptoHash([3; 32])), EpochId(CryptoHash([4; 32]))]);
+		let stored = client.storage.as_ref().unwrap().epoch_block_producers().unwrap();
+		assert_eq!(stored.iter().map(|(epoch_id, _)| *epoch_id).collect::<Vec<_>>(), client.epochs);
+	}
+
+	#[test]
+	fn test_older_epochs_are_looked_up_in_the_storage() {
+		let epoch_id = |epoch| EpochId(CryptoHash([epoch; 32]));
+		let mut storage = InMemoryStorage::default();
+		storage.set_head(&block_view(10)).unwrap();
+		for epoch in 1..=4 {
+			storage.insert_epoch_block_producers(epoch_id(epoch), &[]).unwrap();
+		}
+		assert!(Builder::new()
+			.storage(storage.clone())
+			.retained_epochs(2)
+			.retention(RetentionPolicy::Epochs(1))
+			.build()
+			.is_err());
+
+		let client = Builder::new()
+			.storage(storage)
+			.retained_epochs(2)
+			.retention(RetentionPolicy::Epochs(3))
+			.build()
+			.unwrap();
+		assert_eq!(client.epochs, [epoch_id(3), epoch_id(4)]);
+		assert!(client.epoch_block_producers(&epoch_id(2)).is_none());
+		assert!(client.epoch_validators(&epoch_id(2)).unwrap().is_some());
+		assert!(client.epoch_validators(&epoch_id(1)).unwrap().is_none(), "beyond the window");
+		assert!(client.epoch_validators(&epoch_id(4)).unwrap().is_some());
+	}
+
+	#[test]
+	fn test_resumed_epochs_keep_their_height() {
+		let epoch_id = |epoch| EpochId(CryptoHash([epoch; 32]));
+		let mut storage = InMemoryStorage::default();
+		storage.set_head(&block_view(100)).unwrap();
+		for (epoch, height) in [(1, 10), (2, 20), (3, 30), (4, 90)] {
+			storage.insert_epoch_block_producers(epoch_id(epoch), &[]).unwrap();
+			storage.set_epoch_height(epoch_id(epoch), BlockHeight(height)).unwrap();
+		}
+
+		// epoch 1 ended before epoch 3 was inserted, below the retained heights
+		let client = Builder::new()
+			.storage(storage)
+			.retained_epochs(2)
+			.retention(RetentionPolicy::Heights(50))
+			.build()
+			.unwrap();
+		assert!(client.epoch_validators(&epoch_id(1)).unwrap().is_none());
+		assert!(client.epoch_validators(&epoch_id(2)).unwrap().is_some());
+		let heights = client.storage.as_ref().unwrap().epoch_heights().unwrap();
+		assert_eq!(
+			heights,
+			[
+				(epoch_id(2), BlockHeight(20)),
+				(epoch_id(3), BlockHeight(30)),
+				(epoch_id(4), BlockHeight(90))
+			]
+		);
+	}
+
+	#[test]
+	fn test_headers_from_the_future_are_rejected() {
+		let checkpoint = block_view(1);
+		let mut client = Builder::new()
+			.checkpoint(TrustedCheckpoint(checkpoint.clone()))
+			.clock(FixedClock(checkpoint.inner_lite.timestamp))
+			.audit_log()
+			.build()
+			.unwrap();
+
+		let mut future = block_view(2);
+		future.inner_lite.timestamp = checkpoint.inner_lite.timestamp + 1;
+		assert!(client.validate_and_update_head(future).is_err());
+		match &client.audit_log().unwrap().entries()[0].event {
+			AuditEvent::HeadUpdateRejected { reason, .. } =>
+				assert!(reason.contains("TimestampAhead")),
+			event => panic!("unexpected event {:?}", event),
+		}
+	}
+
+	#[test]
+	fn test_far_future_headers_are_not_queued() {
+		let checkpoint = block_view(1);
+		let now = checkpoint.inner_lite.timestamp;
+		let mut client = Builder::new()
+			.checkpoint(TrustedCheckpoint(checkpoint))
+			.clock(FixedClock(now))
+			.timestamp_policy(TimestampPolicy { max_drift: 10, monotonic: false })
+			.build()
+			.unwrap();
+
+		// two epochs ahead of the head, it would be queued
+		let mut future = block_view(2);
+		future.inner_lite.epoch_id = EpochId(CryptoHash([3; 32]));
+		future.inner_lite.timestamp = now + 11;
+		match client.submit_header(future.clone()) {
+			Err(NearLiteClientError::Verification(failure)) => assert_eq!(failure.code(), 107),
+			other => panic!("unexpected result {:?}", other),
+		}
+		assert_eq!(client.queued_headers(), 0);
+
+		future.inner_lite.timestamp = now + 10;
+		assert!(client.submit_header(future).unwrap().is_empty());
+		assert_eq!(client.queued_headers(), 1);
+	}
+
+	#[test]
+	fn test_proofs_are_refused_against_an_old_head() {
+		let checkpoint = block_view(1);
+		let timestamp = checkpoint.inner_lite.timestamp;
+		assert!(Builder::new()
+			.checkpoint(TrustedCheckpoint(checkpoint.clone()))
+			.max_head_age(10)
+			.build()
+			.is_err());
+
+		let outcome_proof = OutcomeProof {
+			proof: Vec::new(),
+			block_hash: CryptoHash::default(),
+			id: CryptoHash::default(),
+			outcome: ExecutionOutcomeView {
+				logs: Vec::new(),
+				receipt_ids: Vec::new(),
+				gas_burnt: 0,
+				tokens_burnt: 0,
+				executor_id: "bridge.near".into(),
+				status: Vec::new(),
+			},
+		};
+		let receipt = OutcomeId::Receipt {
+			receipt_id: CryptoHash::default(),
+			receiver_id: "bridge.near".into(),
+		};
+		let outcome = TypedOutcomeProof::new(receipt, outcome_proof.clone()).unwrap();
+		for (now, code) in [(timestamp + 10, 109), (timestamp + 11, 114)] {
+			let client = Builder::new()
+				.checkpoint(TrustedCheckpoint(checkpoint.clone()))
+				.clock(FixedClock(now))
+				.max_head_age(10)
+				.build()
+				.unwrap();
+			let err = client
+				.validate_transaction(&outcome_proof, MerklePath::new(), CryptoHash::default())
+				.unwrap_err();
+			assert_eq!(err.code(), code, "{:?}", err);
+			let err = client
+				.validate_outcome(&outcome, MerklePath::new(), CryptoHash::default())
+				.unwrap_err();
+			assert_eq!(err.code(), code, "{:?}", err);
+		}
+	}
+
+	#[test]
+	fn test_monotonic_timestamps() {
+		let mut client = Builder::new()
+			.checkpoint(TrustedCheckpoint(block_view(1)))
+			.timestamp_policy(TimestampPolicy { max_drift: 0, monotonic: true })
+			.build()
+			.unwrap();
+
+		// same timestamp as the head
+		match client.validate_and_update_head(block_view(2)) {
+			Err(NearLiteClientError::Verification(failure)) => assert_eq!(failure.code(), 110),
+			other => panic!("unexpected result {:?}", other),
+		}
+	}
+}
@@ -0,0 +1,176 @@
+//! # Candidate pool
+//!
+//! Recent blocks are not final yet, and competing blocks may be produced at the same heights.
+//! Consumers that can live with that risk follow the highest of them, the latest head, while the
+//! client only moves its head, the finalized one, once NEAR's doomslug finality is reached: a block
+//! is final when it has a child at the next height, which itself has a child at the next height.
+//! Until then, the approved blocks ahead of the head wait here as candidates.
+
+use alloc::string::String;
+use near_primitives_wasm::{BlockHeight, CryptoHash, LightClientBlockView};
+use sp_std::vec::Vec;
+
+use crate::{error::NearLiteClientError, LiteClientResult};
+
+pub const DEFAULT_CANDIDATE_POOL_CAPACITY: usize = 8;
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone)]
+pub struct CandidatePool {
+	capacity: usize,
+	/// candidates with their hash, in the order they were inserted
+	candidates: Vec<(CryptoHash, LightClientBlockView)>,
+}
+
+impl CandidatePool {
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, candidates: Vec::new() }
+	}
+
+	pub fn len(&self) -> usize {
+		self.candidates.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.candidates.is_empty()
+	}
+
+	pub fn contains(&self, hash: &CryptoHash) -> bool {
+		self.candidates.iter().any(|(candidate, _)| candidate == hash)
+	}
+
+	/// Adds `block_view`, with hash `hash`. When the pool is full the highest candidate is
+	/// evicted, as it is the furthest from being final, unless `block_view` is itself the highest
+	/// one.
+	pub fn insert(
+		&mut self,
+		hash: CryptoHash,
+		block_view: LightClientBlockView,
+	) -> LiteClientResult<()> {
+		if self.contains(&hash) {
+			return Ok(())
+		}
+		if self.candidates.len() >= self.capacity {
+			let height = block_view.inner_lite.height;
+			match self.highest_index() {
+				Some(index) if self.candidates[index].1.inner_lite.height > height => {
+					self.candidates.remove(index);
+				},
+				_ =>
+					return Err(NearLiteClientError::CapacityExceeded(String::from(
+						"candidate pool is full",
+					))),
+			}
+		}
+		self.candidates.push((hash, block_view));
+		Ok(())
+	}
+
+	/// The highest candidate, the first one inserted among competing candidates at that height.
+	pub fn latest(&self) -> Option<&LightClientBlockView> {
+		self.highest_index().map(|index| &self.candidates[index].1)
+	}
+
+	/// Removes and returns the lowest final candidate: the one with a child at the next height
+	/// that has a child at the next height too.
+	pub fn take_final(&mut self) -> Option<LightClientBlockView> {
+		let index = self
+			.candidates
+			.iter()
+			.enumerate()
+			.filter(|(_, (hash, block_view))| {
+				self.child(hash, block_view.inner_lite.height)
+					.and_then(|(child_hash, child)| self.child(child_hash, child.inner_lite.height))
+					.is_some()
+			})
+			.min_by_key(|(_, (_, block_view))| block_view.inner_lite.height)
+			.map(|(index, _)| index)?;
+		Some(self.candidates.remove(index).1)
+	}
+
+	/// Drops the candidates that are not ahead of `height`.
+	pub fn prune(&mut self, height: BlockHeight) {
+		self.candidates.retain(|(_, block_view)| block_view.inner_lite.height > height);
+	}
+
+	/// A candidate built on the block with hash `hash`, at the height after `height`.
+	fn child(
+		&self,
+		hash: &CryptoHash,
+		height: BlockHeight,
+	) -> Option<&(CryptoHash, LightClientBlockView)> {
+		self.candidates.iter().find(|(_, block_view)| {
+			block_view.prev_block_hash == *hash && block_view.inner_lite.height.0 == height.0 + 1
+		})
+	}
+
+	fn highest_index(&self) -> Option<usize> {
+		let mut highest: Option<usize> = None;
+		for (index, (_, block_view)) in self.candidates.iter().enumerate() {
+			if highest.map_or(true, |highest| {
+				block_view.inner_lite.height > self.candidates[highest].1.inner_lite.height
+			}) {
+				highest = Some(index);
+			}
+		}
+		highest
+	}
+}
+
+impl Default for CandidatePool {
+	fn default() -> Self {
+		Self::new(DEFAULT_CANDIDATE_POOL_CAPACITY)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A block at `height`, child of the block whose hash is filled with `parent`, and whose own
+	/// hash is filled with `height`.
+	fn candidate(height: u8, parent: u8) -> (CryptoHash, LightClientBlockView) {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(height.into());
+		block_view.prev_block_hash = CryptoHash([parent; 32]);
+		(CryptoHash([height; 32]), block_view)
+	}
+
+	fn insert(pool: &mut CandidatePool, height: u8, parent: u8) -> LiteClientResult<()> {
+		let (hash, block_view) = candidate(height, parent);
+		pool.insert(hash, block_view)
+	}
+
+	#[test]
+	fn test_doomslug_finality() {
+		let mut pool = CandidatePool::default();
+		insert(&mut pool, 11, 10).unwrap();
+		insert(&mut pool, 12, 11).unwrap();
+		// a skipped height doesn't count towards finality
+		insert(&mut pool, 14, 12).unwrap();
+		assert_eq!(pool.latest().unwrap().inner_lite.height, BlockHeight(14));
+		assert!(pool.take_final().is_none());
+
+		insert(&mut pool, 13, 12).unwrap();
+		assert_eq!(pool.take_final().unwrap().inner_lite.height, BlockHeight(11));
+		assert!(pool.take_final().is_none(), "13 has no child at 14");
+		assert_eq!(pool.len(), 3);
+
+		pool.prune(BlockHeight(12));
+		assert_eq!(pool.len(), 2);
+		insert(&mut pool, 13, 12).unwrap();
+		assert_eq!(pool.len(), 2, "candidates are only added once");
+	}
+
+	#[test]
+	fn test_insert_evicts_the_highest_candidate() {
+		let mut pool = CandidatePool::new(2);
+		insert(&mut pool, 11, 10).unwrap();
+		insert(&mut pool, 13, 12).unwrap();
+		assert!(insert(&mut pool, 14, 13).is_err());
+
+		insert(&mut pool, 12, 11).unwrap();
+		assert_eq!(pool.len(), 2);
+		assert_eq!(pool.latest().unwrap().inner_lite.height, BlockHeight(12));
+	}
+}
@@ -0,0 +1,134 @@
+//! # Chunk headers
+//!
+//! A block commits to the headers of its chunks, one per shard, in `chunk_headers_root`. That root
+//! is part of the block's `inner_rest`, which light client blocks only carry as `inner_rest_hash`,
+//! so a proof of inclusion of a chunk header comes with the encoded `inner_rest` of the block.
+//! Once a chunk header is proven, its per shard roots (`outcome_root`, `tx_root`,
+//! `prev_state_root`, ...) can be trusted as much as the head.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_primitives_wasm::{
+	ChunkHeaderView, CryptoHash, HostFunctions, LightClientBlockView, MerklePath,
+};
+use sp_std::vec::Vec;
+
+use crate::{
+	error::{serialize, NearLiteClientError, VerificationFailure},
+	merkle_tree::compute_root_from_path,
+	LiteClientResult,
+};
+
+/// `chunk_headers_root` comes right after `chunk_receipts_root` in every version of
+/// `BlockHeaderInnerRest`.
+const CHUNK_HEADERS_ROOT_OFFSET: usize = 32;
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct ChunkInclusionProof {
+	pub chunk_header: ChunkHeaderView,
+	/// path of the chunk to the block's `chunk_headers_root`
+	pub path: MerklePath,
+	/// borsh encoded `BlockHeaderInnerRest` of the block
+	pub inner_rest: Vec<u8>,
+}
+
+/// Verifies that `proof.chunk_header` is one of the chunk headers of `head`.
+pub fn verify_chunk_header<H: HostFunctions>(
+	proof: &ChunkInclusionProof,
+	head: &LightClientBlockView,
+) -> LiteClientResult<()> {
+	let inner_rest_hash = CryptoHash(H::sha256(&proof.inner_rest));
+	if inner_rest_hash != head.inner_rest_hash {
+		return Err(VerificationFailure::InnerRestHashMismatch {
+			expected: head.inner_rest_hash,
+			computed: inner_rest_hash,
+		}
+		.into())
+	}
+	let chunk_headers_root = proof
+		.inner_rest
+		.get(CHUNK_HEADERS_ROOT_OFFSET..CHUNK_HEADERS_ROOT_OFFSET + 32)
+		.ok_or_else(|| NearLiteClientError::Serialization {
+			type_name: "BlockHeaderInnerRest",
+			reason: "too short to hold a chunk_headers_root".into(),
+		})?;
+	let chunk_headers_root = CryptoHash::try_from(chunk_headers_root)?;
+
+	// nearcore merklizes `ChunkHashHeight(chunk_hash, height_included)`
+	let leaf = (proof.chunk_header.chunk_hash::<H>(), proof.chunk_header.height_included);
+	let leaf_hash = CryptoHash(H::sha256(&serialize(&leaf)?));
+	let computed = compute_root_from_path::<H>(&proof.path, leaf_hash)?;
+	if computed != chunk_headers_root {
+		return Err(NearLiteClientError::MerkleRootMismatch {
+			expected: chunk_headers_root,
+			computed,
+		})
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{merkle_tree::merklize, test_utils::MockedHostFunctions};
+	use near_primitives_wasm::{BlockHeight, ShardChunkHeaderInner, ShardChunkHeaderInnerV2};
+
+	fn chunk_header(shard_id: u64) -> ChunkHeaderView {
+		ChunkHeaderView {
+			inner: ShardChunkHeaderInner::V2(ShardChunkHeaderInnerV2 {
+				prev_block_hash: CryptoHash([1; 32]),
+				prev_state_root: CryptoHash([2; 32]),
+				outcome_root: CryptoHash([3; 32]),
+				encoded_merkle_root: CryptoHash([4; 32]),
+				encoded_length: 5,
+				height_created: BlockHeight(6),
+				shard_id,
+				gas_used: 8,
+				gas_limit: 9,
+				balance_burnt: 10,
+				outgoing_receipts_root: CryptoHash([11; 32]),
+				tx_root: CryptoHash([12; 32]),
+				validator_proposals: Vec::new(),
+			}),
+			height_included: BlockHeight(6),
+		}
+	}
+
+	fn inclusion_proof(shard_id: u64) -> (LightClientBlockView, ChunkInclusionProof) {
+		let chunks: Vec<_> = (0..4)
+			.map(chunk_header)
+			.map(|chunk| (chunk.chunk_hash::<MockedHostFunctions>(), chunk.height_included))
+			.collect();
+		let (chunk_headers_root, paths) = merklize::<MockedHostFunctions, _>(&chunks).unwrap();
+		// chunk_receipts_root, chunk_headers_root, then the rest of the fields
+		let inner_rest = [&[0u8; 32][..], chunk_headers_root.as_ref(), &[7; 100]].concat();
+
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_rest_hash = CryptoHash(MockedHostFunctions::sha256(&inner_rest));
+		let proof = ChunkInclusionProof {
+			chunk_header: chunk_header(shard_id),
+			path: paths[shard_id as usize].clone(),
+			inner_rest,
+		};
+		(head, proof)
+	}
+
+	#[test]
+	fn test_verify_chunk_header() {
+		let (mut head, proof) = inclusion_proof(2);
+		assert!(verify_chunk_header::<MockedHostFunctions>(&proof, &head).is_ok());
+
+		let mut other_chunk = proof.clone();
+		other_chunk.chunk_header = chunk_header(3);
+		assert!(verify_chunk_header::<MockedHostFunctions>(&other_chunk, &head).is_err());
+
+		let mut other_inner_rest = proof.clone();
+		other_inner_rest.inner_rest[0] = 1;
+		assert!(verify_chunk_header::<MockedHostFunctions>(&other_inner_rest, &head).is_err());
+
+		// the hash matches but the root is missing
+		let mut truncated = proof;
+		truncated.inner_rest.truncate(40);
+		head.inner_rest_hash = CryptoHash(MockedHostFunctions::sha256(&truncated.inner_rest));
+		assert!(verify_chunk_header::<MockedHostFunctions>(&truncated, &head).is_err());
+	}
+}
@@ -0,0 +1,1041 @@
+//! # Light client
+//!
+//! Stateful wrapper around the block validation rules: it keeps the current head together with
+//! the block producers of the epochs it knows about, and only moves the head forward when a block
+//! view passes [`validate_light_block`].
+
+use alloc::{boxed::Box, format, string::String};
+#[cfg(feature = "deepsize_feature")]
+use deepsize::DeepSizeOf;
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, EpochId, HostFunctions, LightClientBlockView, MerklePathItem,
+	OutcomeProof, Sha256HostFunctions, ValidatorStakeView,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{
+	audit::{AuditEntry, AuditEvent, AuditLog},
+	block_validation::{
+		validate_block_producers_commitment, validate_epoch_derivation,
+		validate_light_block_with_hashes, validate_prev_block_hash, ApprovalStats,
+		ApprovalThreshold, BlockViewHashes, EpochLinkage,
+	},
+	candidate_pool::CandidatePool,
+	clock::{check_head_age, Clock, TimestampPolicy},
+	error::VerificationFailure,
+	hash_cache::HashCache,
+	header_mmr::{HeaderInclusionProof, HeaderMmr},
+	header_queue::{is_in_known_epoch, HeaderQueue},
+	metrics,
+	outcome::TypedOutcomeProof,
+	protocol::{ProtocolFeatures, ProtocolSchedule, ProtocolVersion},
+	rainbow::FullOutcomeProof,
+	retention::RetentionPolicy,
+	signature::SignatureVerifier,
+	storage::{LightClientStorage, StorageStats},
+	verifier::{self, OutcomeRoots},
+	LightClientBuilder, LiteClientResult, NearLiteClientError, NearLiteClientTrait,
+	TrustedCheckpoint,
+};
+
+/// What changed when a block view became the head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateSummary {
+	pub old_height: BlockHeight,
+	pub new_height: BlockHeight,
+	/// whether the new head is in a different epoch than the previous one
+	pub epoch_changed: bool,
+	/// number of approval signatures verified
+	pub approvals_checked: usize,
+	/// share of the epoch's stake that approved the block, in basis points
+	pub stake_approved_bps: u16,
+	/// whether the block view was the head already, in which case the client is unchanged
+	pub already_validated: bool,
+	/// height below which the retention policy dropped data from the storage, if it dropped any
+	pub pruned_below: Option<BlockHeight>,
+}
+
+/// What [`LightClient::check_header`] found a block view would do to the client.
+#[derive(Debug)]
+pub enum VerificationOutcome {
+	/// The block view would become the head.
+	Valid {
+		/// whether it is in a different epoch than the head
+		epoch_changed: bool,
+		/// number of approval signatures verified
+		approvals_checked: usize,
+		/// share of the epoch's stake that approved the block, in basis points
+		stake_approved_bps: u16,
+	},
+	/// The block view is the head or was accepted already, submitting it changes nothing.
+	AlreadyValidated,
+	/// The block view would be rejected with this error.
+	Invalid(NearLiteClientError),
+}
+
+impl VerificationOutcome {
+	/// Whether submitting the block view would succeed.
+	pub fn is_valid(&self) -> bool {
+		!matches!(self, Self::Invalid(_))
+	}
+}
+
+/// The epochs in memory before an update, see [`LightClient::update_storage`].
+struct EpochsSnapshot {
+	epochs: Vec<EpochId>,
+	stored_epochs: Vec<(EpochId, BlockHeight)>,
+	epoch_block_producers: Option<BTreeMap<EpochId, Vec<ValidatorStakeView>>>,
+}
+
+/// Hashes through `H`, which defaults to the software SHA-256 of [`Sha256HostFunctions`], and
+/// verifies signatures through `H` too unless it was built with another
+/// [`SignatureVerifier`].
+pub struct LightClient<H: HostFunctions = Sha256HostFunctions> {
+	pub(crate) head: LightClientBlockView,
+	/// block producers of every epoch the client has seen, keyed by epoch id
+	pub(crate) epoch_block_producers: BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+	/// ids of the epochs in `epoch_block_producers`, from the oldest to the newest
+	pub(crate) epochs: Vec<EpochId>,
+	/// how many epochs of block producers are kept, all of them if `None`
+	pub(crate) retained_epochs: Option<usize>,
+	/// history kept by the storage, the retained epochs if `None`
+	pub(crate) retention: Option<RetentionPolicy>,
+	/// ids of the epochs in the storage, from the oldest to the newest, with the height of the
+	/// head when they were stored
+	pub(crate) stored_epochs: Vec<(EpochId, BlockHeight)>,
+	pub(crate) threshold: ApprovalThreshold,
+	/// verifies the approvals of the block producers
+	pub(crate) signature_verifier: Box<dyn SignatureVerifier>,
+	pub(crate) storage: Option<Box<dyn LightClientStorage>>,
+	/// rejects the headers timestamped after its current time
+	pub(crate) clock: Option<Box<dyn Clock>>,
+	pub(crate) timestamp_policy: TimestampPolicy,
+	/// how old the head may be, in nanoseconds, for proofs to be verified against it
+	pub(crate) max_head_age: Option<u64>,
+	pub(crate) audit_log: Option<AuditLog<H>>,
+	/// MMR of the heads accepted since the client was built, if it keeps one
+	pub(crate) header_mmr: Option<HeaderMmr>,
+	/// headers from epochs whose block producers are not known yet
+	pub(crate) header_queue: HeaderQueue,
+	/// hashes of the most recently accepted block views, all at or below the head, so that their
+	/// resubmissions are rejected as stale without an audit entry
+	pub(crate) accepted: HashCache,
+	pub(crate) protocol: ProtocolSchedule,
+	/// protocol version of the epoch of the head
+	pub(crate) protocol_version: ProtocolVersion,
+	pub(crate) epoch_linkage: EpochLinkage,
+	/// epochs from [`LightClient::insert_synced_epoch`] the head may skip to, oldest first
+	pub(crate) synced_epochs: Vec<EpochId>,
+	/// whether only the block right after the head is accepted
+	pub(crate) sequential: bool,
+	/// approved blocks ahead of the head, waiting to be final
+	pub(crate) candidates: CandidatePool,
+}
+
+impl<H: HostFunctions> LightClient<H> {
+	/// A client with the default configuration, see [`LightClientBuilder`] for the others.
+	pub fn with_checkpoint(checkpoint: TrustedCheckpoint) -> LiteClientResult<Self> {
+		LightClientBuilder::new().checkpoint(checkpoint).build()
+	}
+
+	/// Enables the audit log: from now on every mutation of the client is recorded.
+	pub fn with_audit_log(mut self) -> Self {
+		self.audit_log = Some(AuditLog::new());
+		self
+	}
+
+	/// Sets how many future headers [`LightClient::submit_header`] buffers.
+	pub fn with_header_queue_capacity(mut self, capacity: usize) -> Self {
+		self.header_queue = HeaderQueue::new(capacity);
+		self
+	}
+
+	/// The finalized head.
+	pub fn head(&self) -> &LightClientBlockView {
+		&self.head
+	}
+
+	/// The highest candidate of [`LightClient::submit_candidate`], or the head without
+	/// candidates: the tip of the chain as far as the client knows, which may not be final.
+	pub fn latest_head(&self) -> &LightClientBlockView {
+		self.candidates.latest().unwrap_or(&self.head)
+	}
+
+	pub fn head_height(&self) -> BlockHeight {
+		self.head.inner_lite.height
+	}
+
+	pub fn head_hash(&self) -> CryptoHash {
+		self.head.current_block_hash::<H>()
+	}
+
+	pub fn current_epoch_id(&self) -> EpochId {
+		self.head.inner_lite.epoch_id
+	}
+
+	pub fn next_epoch_id(&self) -> EpochId {
+		self.head.inner_lite.next_epoch_id
+	}
+
+	/// Block producers of `epoch_id`, if the client knows them.
+	pub fn epoch_block_producers(&self, epoch_id: &EpochId) -> Option<&[ValidatorStakeView]> {
+		self.epoch_block_producers.get(epoch_id).map(Vec::as_slice)
+	}
+
+	/// Block producers of `epoch_id`, looked up in the storage when the client no longer keeps
+	/// them, e.g. to verify a proof or a misbehaviour from an epoch before the last rollover.
+	pub fn epoch_validators(
+		&self,
+		epoch_id: &EpochId,
+	) -> LiteClientResult<Option<Vec<ValidatorStakeView>>> {
+		if let Some(block_producers) = self.epoch_block_producers.get(epoch_id) {
+			return Ok(Some(block_producers.clone()))
+		}
+		match self.storage.as_ref() {
+			Some(storage) => storage.epoch_validators(epoch_id),
+			None => Ok(None),
+		}
+	}
+
+	/// Hash of the block accepted at `height`: the head, or an earlier head indexed by the storage.
+	pub fn block_hash_at(&self, height: BlockHeight) -> LiteClientResult<Option<CryptoHash>> {
+		if height == self.head.inner_lite.height {
+			return Ok(Some(self.head_hash()))
+		}
+		match self.storage.as_ref() {
+			Some(storage) => storage.block_hash(height),
+			None => Ok(None),
+		}
+	}
+
+	/// What the storage of the client holds, if it has one.
+	pub fn storage_stats(&self) -> LiteClientResult<Option<StorageStats>> {
+		self.storage.as_ref().map(|storage| storage.stats()).transpose()
+	}
+
+	/// Root of the MMR of accepted heads, which [`HeaderInclusionProof`]s are checked against.
+	pub fn header_mmr_root(&self) -> LiteClientResult<Option<CryptoHash>> {
+		self.header_mmr.as_ref().map(|mmr| mmr.root::<H>()).transpose()
+	}
+
+	/// Proves that the head at `height` was accepted, against the current
+	/// [root](Self::header_mmr_root) of the MMR of accepted heads.
+	pub fn prove_header_inclusion(
+		&self,
+		height: BlockHeight,
+	) -> LiteClientResult<HeaderInclusionProof> {
+		match self.header_mmr.as_ref() {
+			Some(mmr) => mmr.prove::<H>(height),
+			None => Err(NearLiteClientError::Config(String::from(
+				"the client was built without a header MMR",
+			))),
+		}
+	}
+
+	pub fn audit_log(&self) -> Option<&AuditLog<H>> {
+		self.audit_log.as_ref()
+	}
+
+	/// Takes the entries out of the audit log, see [`AuditLog::drain`]. Empty without an audit log.
+	pub fn drain_audit_log(&mut self) -> Vec<AuditEntry> {
+		self.audit_log.as_mut().map(AuditLog::drain).unwrap_or_default()
+	}
+
+	/// Trusts `block_producers` as the block producers of `epoch_id`, an epoch after the next one
+	/// of the head, e.g. taken from a verified epoch sync proof. With
+	/// [`EpochLinkage::EpochSync`], the headers of `epoch_id` can then become the head directly.
+	pub fn insert_synced_epoch(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: Vec<ValidatorStakeView>,
+	) -> LiteClientResult<()> {
+		if self.epoch_block_producers.contains_key(&epoch_id) ||
+			epoch_id == self.head.inner_lite.epoch_id
+		{
+			return Err(NearLiteClientError::Config(String::from(
+				"the block producers of the epoch are already known",
+			)))
+		}
+		let rotation = AuditEvent::ValidatorSetRotation {
+			epoch_id,
+			block_producers: block_producers.len() as u32,
+		};
+		self.update_storage(true, |client| {
+			let inserted_at = client.head.inner_lite.height;
+			client.insert_epoch_block_producers(epoch_id, block_producers, inserted_at)
+		})?;
+		self.synced_epochs.push(epoch_id);
+		self.record(rotation)
+	}
+
+	/// Protocol version of the epoch of the head.
+	pub fn protocol_version(&self) -> ProtocolVersion {
+		self.protocol_version
+	}
+
+	/// Protocol version of the epoch of `block_view`, if it follows the head.
+	fn block_protocol_version(&self, block_view: &LightClientBlockView) -> ProtocolVersion {
+		if block_view.inner_lite.epoch_id == self.head.inner_lite.epoch_id {
+			return self.protocol_version
+		}
+		self.protocol.version(&block_view.inner_lite.epoch_id, self.protocol_version)
+	}
+
+	/// Validates `block_view` against the current head and, if it is valid, makes it the new
+	/// head and stores the block producers of the next epoch.
+	///
+	/// Submitting the current head again succeeds without changing anything, so that callers can
+	/// retry a submission whose outcome they missed. Any other block view at or below the height
+	/// of the head fails with [`NearLiteClientError::StaleHeader`].
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(level = "debug", skip_all, fields(height = block_view.inner_lite.height.0))
+	)]
+	pub fn validate_and_update_head(
+		&mut self,
+		block_view: LightClientBlockView,
+	) -> LiteClientResult<UpdateSummary> {
+		let protocol_version = self.block_protocol_version(&block_view);
+		let features = *self.protocol.table.features(protocol_version);
+		let hashes = features.hashes::<H>(&block_view)?;
+		let head_height = self.head.inner_lite.height;
+		if hashes.current_block_hash == self.head_hash() {
+			return Ok(self.unchanged())
+		}
+		if block_view.inner_lite.height <= head_height {
+			// headers accepted earlier keep being relayed, they are not worth an audit entry
+			if !self.accepted.touch(&hashes.current_block_hash) {
+				self.record(AuditEvent::HeadUpdateRejected {
+					height: block_view.inner_lite.height,
+					block_hash: hashes.current_block_hash,
+					reason: "stale header".into(),
+				})?;
+				metrics::head_rejected();
+				#[cfg(feature = "tracing")]
+				tracing::debug!(head_height = head_height.0, "stale header rejected");
+			}
+			return Err(NearLiteClientError::StaleHeader {
+				head_height,
+				block_height: block_view.inner_lite.height,
+			})
+		}
+		self.validate_and_accept(block_view, &hashes, &features, protocol_version)
+	}
+
+	/// Runs the checks of [`LightClient::validate_and_update_head`] on `block_view` without
+	/// changing the head, the storage or the audit log, so that relayers can tell whether a header
+	/// would be accepted before paying for its submission. A failure of the storage while the
+	/// head is updated can't be foreseen.
+	pub fn check_header(&self, block_view: &LightClientBlockView) -> VerificationOutcome {
+		match self.dry_run(block_view) {
+			Ok(Some(approvals)) => VerificationOutcome::Valid {
+				epoch_changed: block_view.inner_lite.epoch_id != self.head.inner_lite.epoch_id,
+				approvals_checked: approvals.approvals_checked,
+				stake_approved_bps: approvals.approved_bps(),
+			},
+			Ok(None) => VerificationOutcome::AlreadyValidated,
+			Err(err) => VerificationOutcome::Invalid(err),
+		}
+	}
+
+	/// [`validate_transaction`](crate::validate_transaction), unless the head is older than the
+	/// maximum head age the client was built with. Like it, it can't tell the outcome of a
+	/// transaction from the one of a receipt: [`LightClient::validate_outcome`] can.
+	#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, err(Debug)))]
+	pub fn validate_transaction(
+		&self,
+		outcome_proof: &OutcomeProof,
+		outcome_root_proof: impl AsRef<[MerklePathItem]>,
+		expected_block_outcome_root: CryptoHash,
+	) -> LiteClientResult<()> {
+		if let (Some(max_age), Some(clock)) = (self.max_head_age, self.clock.as_deref()) {
+			check_head_age(clock, max_age, self.head.inner_lite.timestamp)?;
+		}
+		verifier::validate_transaction::<H>(
+			outcome_proof,
+			outcome_root_proof,
+			expected_block_outcome_root,
+		)
+	}
+
+	/// [`TypedOutcomeProof::verify`], unless the head is older than the maximum head age the
+	/// client was built with.
+	#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, err(Debug)))]
+	pub fn validate_outcome(
+		&self,
+		outcome: &TypedOutcomeProof,
+		outcome_root_proof: impl AsRef<[MerklePathItem]>,
+		expected_block_outcome_root: CryptoHash,
+	) -> LiteClientResult<OutcomeRoots> {
+		if let (Some(max_age), Some(clock)) = (self.max_head_age, self.clock.as_deref()) {
+			check_head_age(clock, max_age, self.head.inner_lite.timestamp)?;
+		}
+		outcome.verify::<H>(outcome_root_proof, expected_block_outcome_root)
+	}
+
+	/// Verifies a Rainbow Bridge proof against the `block_merkle_root` of the head, which commits
+	/// to every block before it. Like [`LightClient::validate_transaction`], the head must not be
+	/// older than the maximum head age.
+	#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, err(Debug)))]
+	pub fn validate_full_outcome_proof(
+		&self,
+		proof: &FullOutcomeProof,
+	) -> LiteClientResult<OutcomeRoots> {
+		if let (Some(max_age), Some(clock)) = (self.max_head_age, self.clock.as_deref()) {
+			check_head_age(clock, max_age, self.head.inner_lite.timestamp)?;
+		}
+		proof.verify::<H>(self.head.inner_lite.block_merkle_root)
+	}
+
+	/// Like [`LightClient::validate_and_update_head`], except that headers from epochs that are
+	/// too far ahead of the head are buffered instead of rejected, and validated as soon as the
+	/// head reaches the previous epoch. Headers timestamped too far ahead of the clock are
+	/// rejected rather than buffered. Returns the heights of the headers that became the head, in
+	/// order.
+	pub fn submit_header(
+		&mut self,
+		block_view: LightClientBlockView,
+	) -> LiteClientResult<Vec<BlockHeight>> {
+		let mut accepted = Vec::new();
+		if block_view.inner_lite.height <= self.head.inner_lite.height ||
+			is_in_known_epoch(&self.head, &block_view) ||
+			self.is_synced_epoch(&block_view.inner_lite.epoch_id)
+		{
+			let height = block_view.inner_lite.height;
+			if !self.validate_and_update_head(block_view)?.already_validated {
+				accepted.push(height);
+			}
+		} else {
+			// the clock can't wait for the header's epoch, far-future headers don't get queued
+			self.timestamp_policy
+				.check_clock(self.clock.as_deref(), block_view.inner_lite.timestamp)?;
+			self.header_queue.push(block_view)?;
+		}
+
+		while let Some(block_view) = self.header_queue.pop_ready(&self.head) {
+			let height = block_view.inner_lite.height;
+			// rejections are recorded in the audit log, the header is just dropped
+			if self.validate_and_update_head(block_view).is_ok() {
+				accepted.push(height);
+			}
+		}
+		Ok(accepted)
+	}
+
+	/// Validates `block_view` against the head and, if it is approved, adds it to the candidates.
+	/// The candidates that became final, those with a child and a grandchild at the next two
+	/// heights, then become the head in order, validated again as the head moved. Returns their
+	/// heights.
+	pub fn submit_candidate(
+		&mut self,
+		block_view: LightClientBlockView,
+	) -> LiteClientResult<Vec<BlockHeight>> {
+		let head_height = self.head.inner_lite.height;
+		if block_view.inner_lite.height <= head_height {
+			return Err(NearLiteClientError::StaleHeader {
+				head_height,
+				block_height: block_view.inner_lite.height,
+			})
+		}
+		let features = *self.protocol.table.features(self.block_protocol_version(&block_view));
+		let hashes = features.hashes::<H>(&block_view)?;
+		if !self.candidates.contains(&hashes.current_block_hash) {
+			self.validate(&block_view, &hashes, &features, true)?;
+			self.candidates.insert(hashes.current_block_hash, block_view)?;
+		}
+
+		let mut finalized = Vec::new();
+		while let Some(candidate) = self.candidates.take_final() {
+			let height = candidate.inner_lite.height;
+			let protocol_version = self.block_protocol_version(&candidate);
+			let features = *self.protocol.table.features(protocol_version);
+			let hashes = features.hashes::<H>(&candidate)?;
+			// rejections are recorded in the audit log, the candidate is just dropped
+			let result = self.validate_and_accept(candidate, &hashes, &features, protocol_version);
+			if result.is_ok() {
+				finalized.push(height);
+			}
+		}
+		Ok(finalized)
+	}
+
+	/// Number of headers waiting for the head to reach their epoch.
+	pub fn queued_headers(&self) -> usize {
+		self.header_queue.len()
+	}
+
+	/// Bytes used by the client: its own size, everything it holds on the heap and what its
+	/// storage backend reports.
+	#[cfg(feature = "deepsize_feature")]
+	pub fn memory_usage(&self) -> usize {
+		self.deep_size_of()
+	}
+
+	/// Summary of a submission that left the client as it was.
+	fn unchanged(&self) -> UpdateSummary {
+		UpdateSummary {
+			old_height: self.head.inner_lite.height,
+			new_height: self.head.inner_lite.height,
+			epoch_changed: false,
+			approvals_checked: 0,
+			stake_approved_bps: 0,
+			already_validated: true,
+			pruned_below: None,
+		}
+	}
+
+	/// The approvals of `block_view` if [`LightClient::validate_and_update_head`] would accept
+	/// it, `None` if it is the head already.
+	fn dry_run(
+		&self,
+		block_view: &LightClientBlockView,
+	) -> LiteClientResult<Option<ApprovalStats>> {
+		let features = *self.protocol.table.features(self.block_protocol_version(block_view));
+		let hashes = features.hashes::<H>(block_view)?;
+		let head_height = self.head.inner_lite.height;
+		if hashes.current_block_hash == self.head_hash() {
+			return Ok(None)
+		}
+		if block_view.inner_lite.height <= head_height {
+			return Err(NearLiteClientError::StaleHeader {
+				head_height,
+				block_height: block_view.inner_lite.height,
+			})
+		}
+		self.validate(block_view, &hashes, &features, false).map(Some)
+	}
+
+	/// Whether headers may skip to `epoch_id` in an epoch sync.
+	fn is_synced_epoch(&self, epoch_id: &EpochId) -> bool {
+		self.epoch_linkage == EpochLinkage::EpochSync && self.synced_epochs.contains(epoch_id)
+	}
+
+	/// Validates `block_view` against the head, then makes it the new head and stores the block
+	/// producers of the next epoch.
+	fn validate_and_accept(
+		&mut self,
+		block_view: LightClientBlockView,
+		hashes: &BlockViewHashes,
+		features: &ProtocolFeatures,
+		protocol_version: ProtocolVersion,
+	) -> LiteClientResult<UpdateSummary> {
+		let approvals = match self.validate(&block_view, hashes, features, false) {
+			Ok(approvals) => approvals,
+			Err(err) => {
+				self.record(AuditEvent::HeadUpdateRejected {
+					height: block_view.inner_lite.height,
+					block_hash: hashes.current_block_hash,
+					reason: format!("{:?}", err),
+				})?;
+				metrics::head_rejected();
+				#[cfg(feature = "tracing")]
+				tracing::warn!(code = err.code(), error = ?err, "header rejected");
+				return Err(err)
+			},
+		};
+
+		// the new head only replaces the old one once the storage committed it
+		let block_hash = hashes.current_block_hash;
+		let height = block_view.inner_lite.height;
+		let next_epoch_id = block_view.inner_lite.next_epoch_id;
+		let rotation = block_view
+			.next_bps
+			.as_ref()
+			.filter(|_| !self.epoch_block_producers.contains_key(&next_epoch_id))
+			.map(|next_bps| AuditEvent::ValidatorSetRotation {
+				epoch_id: next_epoch_id,
+				block_producers: next_bps.len() as u32,
+			});
+		let pruned_below = self.update_storage(block_view.next_bps.is_some(), |client| {
+			if let Some(next_bps) = block_view.next_bps.as_ref() {
+				let inserted_at = client.head.inner_lite.height;
+				client.insert_epoch_block_producers(
+					next_epoch_id,
+					next_bps.clone(),
+					inserted_at,
+				)?;
+			}
+			client.persist_head(&block_view, block_hash)?;
+			client.enforce_retention(height)
+		})?;
+
+		let summary = UpdateSummary {
+			old_height: self.head.inner_lite.height,
+			new_height: height,
+			epoch_changed: block_view.inner_lite.epoch_id != self.head.inner_lite.epoch_id,
+			approvals_checked: approvals.approvals_checked,
+			stake_approved_bps: approvals.approved_bps(),
+			already_validated: false,
+			pruned_below,
+		};
+		self.accepted.insert(block_hash);
+		// the synced epochs up to the one of the new head are behind it now
+		if let Some(index) = self
+			.synced_epochs
+			.iter()
+			.position(|epoch_id| *epoch_id == block_view.inner_lite.epoch_id)
+		{
+			self.synced_epochs.drain(..=index);
+		}
+		self.head = block_view;
+		self.protocol_version = protocol_version;
+		self.candidates.prune(height);
+		if let Some(mmr) = self.header_mmr.as_mut() {
+			mmr.append::<H>(height, block_hash)?;
+		}
+		// the audit log only tells what the storage committed
+		if let Some(rotation) = rotation {
+			self.record(rotation)?;
+		}
+		self.record(AuditEvent::HeadUpdateAccepted { height, block_hash })?;
+		self.record_pruning(pruned_below)?;
+		metrics::head_accepted();
+		#[cfg(feature = "tracing")]
+		tracing::debug!(
+			approvals_checked = summary.approvals_checked,
+			stake_approved_bps = summary.stake_approved_bps,
+			epoch_changed = summary.epoch_changed,
+			"header accepted"
+		);
+		Ok(summary)
+	}
+
+	/// Checks `block_view` on top of the head. A candidate may also follow another candidate in
+	/// sequential mode.
+	fn validate(
+		&self,
+		block_view: &LightClientBlockView,
+		hashes: &BlockViewHashes,
+		features: &ProtocolFeatures,
+		is_candidate: bool,
+	) -> LiteClientResult<ApprovalStats> {
+		features.check(block_view)?;
+		let epoch_id = &block_view.inner_lite.epoch_id;
+		// the block producers of past epochs are known too, they must not be skipped to
+		if self.epoch_linkage == EpochLinkage::EpochSync &&
+			!is_in_known_epoch(&self.head, block_view) &&
+			!self.is_synced_epoch(epoch_id)
+		{
+			return Err(VerificationFailure::EpochMismatch {
+				epoch_id: *epoch_id,
+				head_epoch_id: self.head.inner_lite.epoch_id,
+				head_next_epoch_id: self.head.inner_lite.next_epoch_id,
+			}
+			.into())
+		}
+		let head_hash = self.head_hash();
+		if self.sequential &&
+			!(is_candidate && self.candidates.contains(&block_view.prev_block_hash))
+		{
+			validate_prev_block_hash(&head_hash, block_view)?;
+		}
+		validate_epoch_derivation(&self.head, &head_hash, block_view)?;
+		self.timestamp_policy.check(
+			self.clock.as_deref(),
+			self.head.inner_lite.timestamp,
+			block_view.inner_lite.timestamp,
+		)?;
+		let stats = validate_light_block_with_hashes(
+			&self.head,
+			block_view,
+			hashes,
+			&self.epoch_block_producers,
+			&self.threshold,
+			self.signature_verifier.as_ref(),
+			self.epoch_linkage,
+		)?;
+		validate_block_producers_commitment::<H>(
+			&self.head,
+			block_view,
+			&self.epoch_block_producers,
+		)?;
+		Ok(stats)
+	}
+
+	/// Makes the storage writes of `update` in one batch. If any of them or the commit fails, the
+	/// batch is aborted and the epochs in memory, which `update` changes along with the storage,
+	/// are put back as they were; their block producers too if `inserts_block_producers`.
+	pub(crate) fn update_storage<T>(
+		&mut self,
+		inserts_block_producers: bool,
+		update: impl FnOnce(&mut Self) -> LiteClientResult<T>,
+	) -> LiteClientResult<T> {
+		let snapshot = EpochsSnapshot {
+			epochs: self.epochs.clone(),
+			stored_epochs: self.stored_epochs.clone(),
+			epoch_block_producers: inserts_block_producers
+				.then(|| self.epoch_block_producers.clone()),
+		};
+		let result = match self.begin_update().and_then(|()| update(self)) {
+			Ok(value) => self.commit_update().map(|()| value),
+			Err(err) => Err(err),
+		};
+		if result.is_err() {
+			// the error of the update is the one reported
+			let _ = self.abort_update();
+			self.epochs = snapshot.epochs;
+			self.stored_epochs = snapshot.stored_epochs;
+			if let Some(epoch_block_producers) = snapshot.epoch_block_producers {
+				self.epoch_block_producers = epoch_block_producers;
+			}
+		}
+		result
+	}
+
+	/// Starts the batch of storage writes of an update, see [`LightClientStorage::begin_update`].
+	fn begin_update(&mut self) -> LiteClientResult<()> {
+		match self.storage.as_mut() {
+			Some(storage) => storage.begin_update(),
+			None => Ok(()),
+		}
+	}
+
+	fn commit_update(&mut self) -> LiteClientResult<()> {
+		match self.storage.as_mut() {
+			Some(storage) => storage.commit_update(),
+			None => Ok(()),
+		}
+	}
+
+	fn abort_update(&mut self) -> LiteClientResult<()> {
+		match self.storage.as_mut() {
+			Some(storage) => storage.abort_update(),
+			None => Ok(()),
+		}
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+	pub(crate) fn persist_head(
+		&mut self,
+		head: &LightClientBlockView,
+		hash: CryptoHash,
+	) -> LiteClientResult<()> {
+		if let Some(storage) = self.storage.as_mut() {
+			storage.set_head(head)?;
+			storage.insert_block_hash(head.inner_lite.height, hash)?;
+		}
+		Ok(())
+	}
+
+	/// Stores the block producers of `epoch_id`, inserted with the head at `inserted_at`, then
+	/// drops the oldest epochs beyond the retention window, from the storage too unless it
+	/// follows a [`RetentionPolicy`].
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(level = "trace", skip_all, fields(epoch_id = ?epoch_id))
+	)]
+	pub(crate) fn insert_epoch_block_producers(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: Vec<ValidatorStakeView>,
+		inserted_at: BlockHeight,
+	) -> LiteClientResult<()> {
+		if let Some(storage) = self.storage.as_mut() {
+			storage.insert_epoch_block_producers(epoch_id, &block_producers)?;
+			storage.set_epoch_height(epoch_id, inserted_at)?;
+			self.stored_epochs.retain(|(known, _)| *known != epoch_id);
+			self.stored_epochs.push((epoch_id, inserted_at));
+		}
+		self.epochs.retain(|known| *known != epoch_id);
+		self.epochs.push(epoch_id);
+		self.epoch_block_producers.insert(epoch_id, block_producers);
+
+		let retained = self.retained_epochs.unwrap_or(usize::MAX);
+		while self.epochs.len() > retained {
+			let oldest = self.epochs.remove(0);
+			self.epoch_block_producers.remove(&oldest);
+		}
+		if self.retention.is_none() {
+			while self.stored_epochs.len() > retained {
+				let (oldest, _) = self.stored_epochs.remove(0);
+				if let Some(storage) = self.storage.as_mut() {
+					storage.remove_epoch_block_producers(&oldest)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Drops from the storage the history beyond the [`RetentionPolicy`] for a head at
+	/// `head_height`, returning the height below which it was dropped. The epochs retained in
+	/// memory are always kept.
+	pub(crate) fn enforce_retention(
+		&mut self,
+		head_height: BlockHeight,
+	) -> LiteClientResult<Option<BlockHeight>> {
+		let (policy, storage) = match (self.retention, self.storage.as_mut()) {
+			(Some(policy), Some(storage)) => (policy, storage),
+			_ => return Ok(None),
+		};
+		let epochs = &self.epochs;
+		let stored_epochs = &mut self.stored_epochs;
+		let prunable = |stored_epochs: &[(EpochId, BlockHeight)]| {
+			stored_epochs.first().map_or(false, |(oldest, _)| !epochs.contains(oldest))
+		};
+		let mut pruned = 0;
+		let below_height = match policy {
+			RetentionPolicy::Epochs(n) => {
+				while stored_epochs.len() > n && prunable(stored_epochs) {
+					let (oldest, _) = stored_epochs.remove(0);
+					storage.remove_epoch_block_producers(&oldest)?;
+					pruned += 1;
+				}
+				stored_epochs.first().map_or(BlockHeight(0), |(_, height)| *height)
+			},
+			RetentionPolicy::Heights(m) => {
+				let below_height = BlockHeight(head_height.0.saturating_sub(m));
+				// the block producers of an epoch come with the first block of the epoch before,
+				// so it has ended once those of the epoch two after it are known
+				while stored_epochs.get(2).map_or(false, |(_, height)| *height < below_height) &&
+					prunable(stored_epochs)
+				{
+					let (oldest, _) = stored_epochs.remove(0);
+					storage.remove_epoch_block_producers(&oldest)?;
+					pruned += 1;
+				}
+				below_height
+			},
+		};
+		pruned += storage.prune_block_hashes(below_height)?;
+		Ok((pruned > 0).then(|| below_height))
+	}
+
+	/// Records the pruning [`LightClient::enforce_retention`] did, once committed.
+	pub(crate) fn record_pruning(
+		&mut self,
+		pruned_below: Option<BlockHeight>,
+	) -> LiteClientResult<()> {
+		match pruned_below {
+			Some(below_height) => self.record(AuditEvent::Pruned { below_height }),
+			None => Ok(()),
+		}
+	}
+
+	fn record(&mut self, event: AuditEvent) -> LiteClientResult<()> {
+		if let Some(audit_log) = self.audit_log.as_mut() {
+			audit_log.record(event)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(feature = "deepsize_feature")]
+impl<H: HostFunctions> DeepSizeOf for LightClient<H> {
+	fn deep_size_of_children(&self, context: &mut deepsize::Context) -> usize {
+		self.head.deep_size_of_children(context) +
+			self.epoch_block_producers.deep_size_of_children(context) +
+			self.epochs.deep_size_of_children(context) +
+			self.stored_epochs.deep_size_of_children(context) +
+			self.audit_log.deep_size_of_children(context) +
+			self.header_mmr.deep_size_of_children(context) +
+			self.header_queue.deep_size_of_children(context) +
+			self.candidates.deep_size_of_children(context) +
+			self.accepted.deep_size_of_children(context) +
+			self.storage.as_deref().map_or(0, |storage| storage.memory_usage())
+	}
+}
+
+impl<H: HostFunctions> NearLiteClientTrait for LightClient<H> {
+	fn new_from_checkpoint(
+		checkpoint: TrustedCheckpoint,
+		_heights_to_track: usize,
+	) -> LiteClientResult<Self> {
+		Self::with_checkpoint(checkpoint)
+	}
+
+	fn current_block_height(&self) -> u64 {
+		self.head_height().0
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+
+	#[test]
+	fn test_query_state() {
+		let mut checkpoint = LightClientBlockView::new_for_test();
+		checkpoint.inner_lite.epoch_id = EpochId(CryptoHash([1; 32]));
+		checkpoint.inner_lite.next_epoch_id = EpochId(CryptoHash([2; 32]));
+		checkpoint.next_bps = Some(Vec::new());
+		let client = LightClient::<MockedHostFunctions>::with_checkpoint(TrustedCheckpoint(
+			checkpoint.clone(),
+		))
+		.unwrap();
+
+		assert_eq!(client.head_height(), checkpoint.inner_lite.height);
+		assert_eq!(client.head_hash(), checkpoint.current_block_hash::<MockedHostFunctions>());
+		assert_eq!(client.current_epoch_id(), EpochId(CryptoHash([1; 32])));
+		assert_eq!(client.next_epoch_id(), EpochId(CryptoHash([2; 32])));
+		assert!(client.epoch_block_producers(&EpochId(CryptoHash([2; 32]))).unwrap().is_empty());
+		// the checkpoint only carries the block producers of the next epoch
+		assert!(client.epoch_block_producers(&EpochId(CryptoHash([1; 32]))).is_none());
+	}
+
+	#[test]
+	fn test_resubmitted_head_is_not_validated_again() {
+		let checkpoint = LightClientBlockView::new_for_test();
+		let mut client = LightClient::<MockedHostFunctions>::with_checkpoint(TrustedCheckpoint(
+			checkpoint.clone(),
+		))
+		.unwrap()
+		.with_audit_log();
+		// no approvals, it would be rejected if it were validated
+		assert!(checkpoint.approvals_after_next.is_empty());
+
+		let summary = client.validate_and_update_head(checkpoint.clone()).unwrap();
+		assert!(summary.already_validated);
+		assert_eq!(summary.new_height, summary.old_height);
+		assert!(client.submit_header(checkpoint).unwrap().is_empty());
+		assert_eq!(client.head_height(), BlockHeight(1));
+		assert!(client.audit_log().unwrap().entries().is_empty());
+	}
+
+	#[test]
+	fn test_stale_headers_are_rejected() {
+		let checkpoint = LightClientBlockView::new_for_test();
+		let mut client = LightClient::<MockedHostFunctions>::with_checkpoint(TrustedCheckpoint(
+			checkpoint.clone(),
+		))
+		.unwrap()
+		.with_audit_log();
+
+		let summary = client.validate_and_update_head(checkpoint.clone()).unwrap();
+		assert!(summary.already_validated);
+		assert_eq!(summary.new_height, BlockHeight(1));
+
+		let mut sibling = checkpoint.clone();
+		sibling.inner_lite.timestamp += 1;
+		let mut older = checkpoint;
+		older.inner_lite.height = BlockHeight(0);
+		for block_view in [sibling.clone(), older] {
+			let block_height = block_view.inner_lite.height;
+			let err = client.validate_and_update_head(block_view).unwrap_err();
+			assert!(matches!(
+				err,
+				NearLiteClientError::StaleHeader { head_height: BlockHeight(1), block_height: height }
+					if height == block_height
+			));
+			assert_eq!(err.code(), 16);
+		}
+		assert_eq!(client.audit_log().unwrap().entries().len(), 2);
+
+		// a header accepted earlier is still stale, but its rejection is not recorded
+		client.accepted.insert(sibling.current_block_hash::<MockedHostFunctions>());
+		assert!(client.validate_and_update_head(sibling).is_err());
+		assert_eq!(client.audit_log().unwrap().entries().len(), 2);
+		assert_eq!(client.head_height(), BlockHeight(1));
+
+		assert_eq!(client.drain_audit_log().len(), 2);
+		assert!(client.audit_log().unwrap().entries().is_empty());
+	}
+
+	#[test]
+	fn test_protocol_version_of_the_epoch_applies() {
+		use crate::{
+			error::{NextBpsDefect, VerificationFailure},
+			ProtocolTable,
+		};
+		use near_primitives_wasm::{PublicKey, ValidatorStakeViewV2};
+
+		let mut checkpoint = LightClientBlockView::new_for_test();
+		checkpoint.inner_lite.epoch_id = EpochId(CryptoHash([1; 32]));
+		checkpoint.inner_lite.next_epoch_id = EpochId(CryptoHash([2; 32]));
+		let mut table = ProtocolTable::default();
+		table.insert(2, ProtocolFeatures { validator_stake_v2: false, ..Default::default() });
+		let schedule = ProtocolSchedule::new(table, 1).upgrade_at(EpochId(CryptoHash([2; 32])), 2);
+		let mut client = LightClientBuilder::<MockedHostFunctions>::new()
+			.checkpoint(TrustedCheckpoint(checkpoint.clone()))
+			.protocol_schedule(schedule)
+			.build()
+			.unwrap();
+		assert_eq!(client.protocol_version(), 1);
+
+		let mut block_view = checkpoint;
+		block_view.inner_lite.height = BlockHeight(2);
+		block_view.inner_lite.epoch_id = EpochId(CryptoHash([2; 32]));
+		block_view.next_bps = Some(vec![ValidatorStakeView::V2(ValidatorStakeViewV2 {
+			account_id: "node0.near".into(),
+			public_key: PublicKey([0; 32]),
+			stake: 1,
+			is_chunk_only: false,
+		})]);
+		assert_eq!(client.block_protocol_version(&block_view), 2);
+		match client.validate_and_update_head(block_view) {
+			Err(NearLiteClientError::Verification(VerificationFailure::MalformedNextBps {
+				index: 0,
+				defect: NextBpsDefect::VersionNotEnabled,
+			})) => {},
+			other => panic!("unexpected result {:?}", other),
+		}
+		assert_eq!(client.protocol_version(), 1);
+	}
+
+	#[test]
+	fn test_epoch_sync_only_skips_to_synced_epochs() {
+		let epoch = |id| EpochId(CryptoHash([id; 32]));
+		let mut checkpoint = LightClientBlockView::new_for_test();
+		checkpoint.inner_lite.epoch_id = epoch(1);
+		checkpoint.inner_lite.next_epoch_id = epoch(2);
+		checkpoint.next_bps = Some(Vec::new());
+		let mut client = LightClientBuilder::<MockedHostFunctions>::new()
+			.checkpoint(TrustedCheckpoint(checkpoint.clone()))
+			.epoch_linkage(EpochLinkage::EpochSync)
+			.build()
+			.unwrap();
+
+		let mut block_view = checkpoint;
+		block_view.inner_lite.height = BlockHeight(2);
+		block_view.inner_lite.epoch_id = epoch(4);
+		block_view.inner_lite.next_epoch_id = epoch(5);
+		fn failure(
+			client: &mut LightClient<MockedHostFunctions>,
+			block_view: LightClientBlockView,
+		) -> VerificationFailure {
+			match client.validate_and_update_head(block_view) {
+				Err(NearLiteClientError::Verification(failure)) => failure,
+				other => panic!("unexpected result {:?}", other),
+			}
+		}
+		assert!(matches!(
+			failure(&mut client, block_view.clone()),
+			VerificationFailure::EpochMismatch { .. }
+		));
+
+		assert!(client.insert_synced_epoch(epoch(2), Vec::new()).is_err());
+		client.insert_synced_epoch(epoch(4), Vec::new()).unwrap();
+		// past the epoch checks, epoch 4 has no block producers to approve the block
+		assert!(matches!(
+			failure(&mut client, block_view),
+			VerificationFailure::ZeroTotalStake { block_producers: 0 }
+		));
+	}
+
+	#[cfg(feature = "deepsize_feature")]
+	#[test]
+	fn test_memory_usage() {
+		use near_primitives_wasm::{PublicKey, ValidatorStakeViewV1};
+
+		let mut client = LightClient::<MockedHostFunctions>::with_checkpoint(TrustedCheckpoint(
+			LightClientBlockView::new_for_test(),
+		))
+		.unwrap();
+		let before = client.memory_usage();
+		assert!(before >= core::mem::size_of::<LightClient<MockedHostFunctions>>());
+
+		let block_producer = ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "node0.near".into(),
+			public_key: PublicKey([0; 32]),
+			stake: 1,
+		});
+		client
+			.insert_epoch_block_producers(
+				EpochId(CryptoHash([1; 32])),
+				vec![block_producer; 100],
+				BlockHeight(1),
+			)
+			.unwrap();
+		assert!(client.memory_usage() > before + 100 * core::mem::size_of::<ValidatorStakeView>());
+	}
+}
@@ -0,0 +1,142 @@
+//! # Clock
+//!
+//! Source of the current time for the light client. Runtimes provide their own (e.g. the
+//! timestamp pallet), std users can rely on [`SystemClock`].
+//!
+//! The timestamp of a new head can optionally be checked against the clock, with some tolerated
+//! drift, and against the timestamp of the previous head; see [`TimestampPolicy`]. The drift also
+//! bounds the headers buffered for later epochs, so that fabricated far-future headers are turned
+//! away before they take any room. Headers timestamped in the past are always accepted against
+//! the clock, as catching up or replaying history legitimately produces them.
+//!
+//! The clock also tells how old the head is: a client configured with a maximum head age refuses
+//! to verify proofs against a head that is older, as the bridge may have stopped following NEAR.
+
+use crate::error::VerificationFailure;
+
+/// Current time, in nanoseconds since the unix epoch like the timestamps of NEAR blocks.
+pub trait Clock {
+	fn now(&self) -> u64;
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+	fn now(&self) -> u64 {
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.map(|elapsed| elapsed.as_nanos() as u64)
+			.unwrap_or_default()
+	}
+}
+
+/// Which checks the timestamp of a new head goes through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimestampPolicy {
+	/// How far ahead of the clock, in nanoseconds, a header may be timestamped. Only applies
+	/// when the client has a [`Clock`].
+	pub max_drift: u64,
+	/// Whether a header must be timestamped strictly after the current head.
+	pub monotonic: bool,
+}
+
+impl TimestampPolicy {
+	pub(crate) fn check(
+		&self,
+		clock: Option<&dyn Clock>,
+		head_timestamp: u64,
+		timestamp: u64,
+	) -> Result<(), VerificationFailure> {
+		self.check_clock(clock, timestamp)?;
+		if self.monotonic && timestamp <= head_timestamp {
+			return Err(VerificationFailure::TimestampNotIncreasing { timestamp, head_timestamp })
+		}
+		Ok(())
+	}
+
+	/// The check of `timestamp` against `clock`, if there is one.
+	pub(crate) fn check_clock(
+		&self,
+		clock: Option<&dyn Clock>,
+		timestamp: u64,
+	) -> Result<(), VerificationFailure> {
+		match clock {
+			Some(clock) => check_clock_drift(clock.now(), self.max_drift, timestamp),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Fails if `timestamp` is more than `max_drift` nanoseconds ahead of `now`.
+pub(crate) fn check_clock_drift(
+	now: u64,
+	max_drift: u64,
+	timestamp: u64,
+) -> Result<(), VerificationFailure> {
+	if timestamp > now.saturating_add(max_drift) {
+		return Err(VerificationFailure::TimestampAhead { timestamp, now })
+	}
+	Ok(())
+}
+
+/// Fails once the head is more than `max_age` nanoseconds older than the clock.
+pub(crate) fn check_head_age(
+	clock: &dyn Clock,
+	max_age: u64,
+	head_timestamp: u64,
+) -> Result<(), VerificationFailure> {
+	let now = clock.now();
+	if now.saturating_sub(head_timestamp) > max_age {
+		return Err(VerificationFailure::HeadTooOld { head_timestamp, now, max_age })
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	struct FixedClock(u64);
+
+	impl Clock for FixedClock {
+		fn now(&self) -> u64 {
+			self.0
+		}
+	}
+
+	#[test]
+	fn test_timestamp_policy() {
+		let clock = FixedClock(100);
+		let clock = Some(&clock as &dyn Clock);
+
+		let strict = TimestampPolicy::default();
+		assert!(strict.check(clock, 50, 100).is_ok());
+		assert!(strict.check(clock, 50, 10).is_ok(), "not monotonic by default");
+		assert_eq!(
+			strict.check(clock, 50, 101),
+			Err(VerificationFailure::TimestampAhead { timestamp: 101, now: 100 })
+		);
+		assert!(strict.check(None, 50, 101).is_ok(), "no clock to compare with");
+
+		let tolerant = TimestampPolicy { max_drift: 10, monotonic: true };
+		assert!(tolerant.check(clock, 50, 110).is_ok());
+		assert!(tolerant.check(clock, 50, 111).is_err());
+		assert_eq!(
+			tolerant.check(None, 50, 50),
+			Err(VerificationFailure::TimestampNotIncreasing { timestamp: 50, head_timestamp: 50 })
+		);
+	}
+	#[test]
+	fn test_head_age() {
+		let clock = FixedClock(100);
+		assert!(check_head_age(&clock, 10, 90).is_ok());
+		assert!(check_head_age(&clock, 10, 200).is_ok(), "heads from the future are not old");
+		assert_eq!(
+			check_head_age(&clock, 10, 89),
+			Err(VerificationFailure::HeadTooOld { head_timestamp: 89, now: 100, max_age: 10 })
+		);
+	}
+}
@@ -0,0 +1,228 @@
+//! # Configuration
+//!
+//! Builds a [`TrustedCheckpoint`] and a [`LightClientBuilder`] from a human-editable TOML or JSON
+//! file, so operators don't have to hand-serialize borsh blobs:
+//!
+//! ```toml
+//! network = "testnet"
+//! # all optional, see `LightClientBuilder`
+//! retained_epochs = 4
+//! approval_threshold = { numerator = 3, denominator = 4 }
+//! max_clock_drift_secs = 10
+//! monotonic_timestamps = true
+//! header_queue_capacity = 64
+//!
+//! [checkpoint]
+//! hash = "8hxkU4avDWFDCsZckig7oN2ypnYvLyb1qmZ3SA1t8iZK"
+//! height = 86441383
+//! # the header as returned by `next_light_client_block`, a JSON object in JSON files
+//! header = '''{ "prev_block_hash": "...", "inner_lite": { ... }, ... }'''
+//!
+//! # block producers of the next epoch, only needed if `header` doesn't carry them
+//! [[checkpoint.validators]]
+//! validator_stake_struct_version = "V1"
+//! account_id = "node0"
+//! public_key = "ed25519:..."
+//! stake = "1000000"
+//! ```
+//!
+//! The hash and height of the checkpoint are checked against its header, and the validators
+//! against its `next_bp_hash`, to catch copy-paste mistakes.
+
+use near_primitives::views::{
+	LightClientBlockView as NearLightClientBlockView, ValidatorStakeView as NearValidatorStakeView,
+};
+use near_primitives_wasm::{CryptoHash, HostFunctions, LightClientBlockView, ValidatorStakeView};
+use serde::Deserialize;
+use std::{
+	format,
+	str::FromStr,
+	string::{String, ToString},
+	vec::Vec,
+};
+
+use crate::{
+	block_validation::ApprovalThreshold,
+	clock::TimestampPolicy,
+	error::{serialize, NearLiteClientError},
+	LightClientBuilder, LiteClientResult, TrustedCheckpoint,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientConfig {
+	/// name of the network of the checkpoint (e.g. `mainnet`), reported in errors
+	pub network: String,
+	pub checkpoint: CheckpointConfig,
+	pub retained_epochs: Option<usize>,
+	pub approval_threshold: Option<ThresholdConfig>,
+	#[serde(default)]
+	pub max_clock_drift_secs: u64,
+	#[serde(default)]
+	pub monotonic_timestamps: bool,
+	pub header_queue_capacity: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckpointConfig {
+	/// base58 hash of the checkpoint block
+	pub hash: String,
+	pub height: u64,
+	/// the header in the JSON format of the `next_light_client_block` RPC, either as an object or
+	/// as a string holding it
+	pub header: serde_json::Value,
+	pub validators: Option<Vec<NearValidatorStakeView>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ThresholdConfig {
+	pub numerator: u32,
+	pub denominator: u32,
+}
+
+impl ClientConfig {
+	pub fn from_toml(config: &str) -> LiteClientResult<Self> {
+		toml::from_str(config).map_err(config_error)
+	}
+
+	pub fn from_json(config: &str) -> LiteClientResult<Self> {
+		serde_json::from_str(config).map_err(config_error)
+	}
+
+	pub fn trusted_checkpoint<H: HostFunctions>(&self) -> LiteClientResult<TrustedCheckpoint> {
+		let checkpoint = &self.checkpoint;
+		let header: NearLightClientBlockView = match &checkpoint.header {
+			serde_json::Value::String(header) => serde_json::from_str(header),
+			header => serde_json::from_value(header.clone()),
+		}
+		.map_err(config_error)?;
+		let mut block_view = LightClientBlockView::try_from(header)?;
+
+		let hash = CryptoHash::from_str(&checkpoint.hash)?;
+		if block_view.current_block_hash::<H>() != hash {
+			return Err(self.error("the header does not hash to the checkpoint hash"))
+		}
+		if block_view.inner_lite.height.0 != checkpoint.height {
+			return Err(self.error("the header is not at the checkpoint height"))
+		}
+
+		if let Some(validators) = checkpoint.validators.clone() {
+			let validators = validators
+				.into_iter()
+				.map(ValidatorStakeView::try_from)
+				.collect::<Result<Vec<_>, _>>()?;
+			let next_bp_hash = CryptoHash(H::sha256(&serialize(&validators)?));
+			if next_bp_hash != block_view.inner_lite.next_bp_hash {
+				return Err(self.error("the validators do not match the next_bp_hash of the header"))
+			}
+			block_view.next_bps = Some(validators);
+		}
+		if block_view.next_bps.is_none() {
+			return Err(self.error("the block producers of the next epoch are missing"))
+		}
+
+		Ok(TrustedCheckpoint(block_view))
+	}
+
+	/// A builder starting from the checkpoint, with the settings of the configuration.
+	pub fn builder<H: HostFunctions>(&self) -> LiteClientResult<LightClientBuilder<H>> {
+		let mut builder = LightClientBuilder::new()
+			.checkpoint(self.trusted_checkpoint::<H>()?)
+			.timestamp_policy(TimestampPolicy {
+				max_drift: self.max_clock_drift_secs.saturating_mul(1_000_000_000),
+				monotonic: self.monotonic_timestamps,
+			});
+		if let Some(epochs) = self.retained_epochs {
+			builder = builder.retained_epochs(epochs);
+		}
+		if let Some(ThresholdConfig { numerator, denominator }) = self.approval_threshold {
+			builder = builder.approval_threshold(ApprovalThreshold::new(numerator, denominator)?);
+		}
+		if let Some(capacity) = self.header_queue_capacity {
+			builder = builder.header_queue_capacity(capacity);
+		}
+		Ok(builder)
+	}
+
+	fn error(&self, reason: &str) -> NearLiteClientError {
+		NearLiteClientError::Config(format!("{} checkpoint: {}", self.network, reason))
+	}
+}
+
+fn config_error(err: impl ToString) -> NearLiteClientError {
+	NearLiteClientError::Config(err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+	use serde_json::json;
+
+	fn header() -> serde_json::Value {
+		let block_view = LightClientBlockView::new_for_test();
+		serde_json::to_value(NearLightClientBlockView::try_from(block_view).unwrap()).unwrap()
+	}
+
+	fn hash() -> String {
+		LightClientBlockView::new_for_test()
+			.current_block_hash::<MockedHostFunctions>()
+			.to_string()
+	}
+
+	#[test]
+	fn test_json_config() {
+		let config = ClientConfig::from_json(
+			&json!({
+				"network": "testnet",
+				"checkpoint": { "hash": hash(), "height": 1, "header": header() },
+				"retained_epochs": 4,
+				"monotonic_timestamps": true,
+			})
+			.to_string(),
+		)
+		.unwrap();
+		let client = config.builder::<MockedHostFunctions>().unwrap().build().unwrap();
+		assert_eq!(client.head_hash().to_string(), hash());
+		assert_eq!(client.retained_epochs, Some(4));
+		assert!(client.timestamp_policy.monotonic);
+
+		let mut config = config;
+		config.checkpoint.height = 2;
+		match config.trusted_checkpoint::<MockedHostFunctions>() {
+			Err(NearLiteClientError::Config(reason)) => assert!(reason.starts_with("testnet")),
+			other => panic!("unexpected result {:?}", other.map(|_| ())),
+		}
+	}
+
+	#[test]
+	fn test_toml_config() {
+		let config = ClientConfig::from_toml(&format!(
+			r#"
+			network = "mainnet"
+			approval_threshold = {{ numerator = 3, denominator = 4 }}
+			max_clock_drift_secs = 2
+
+			[checkpoint]
+			hash = "{}"
+			height = 1
+			header = '''{}'''
+			validators = []
+			"#,
+			hash(),
+			header(),
+		))
+		.unwrap();
+		assert_eq!(
+			config.approval_threshold,
+			Some(ThresholdConfig { numerator: 3, denominator: 4 })
+		);
+		// the validators hash to `sha256([0, 0, 0, 0])`, not to the zero `next_bp_hash`
+		assert!(config.trusted_checkpoint::<MockedHostFunctions>().is_err());
+
+		let mut config = config;
+		config.checkpoint.validators = None;
+		let client = config.builder::<MockedHostFunctions>().unwrap().build().unwrap();
+		assert_eq!(client.timestamp_policy.max_drift, 2_000_000_000);
+		assert_eq!(client.threshold, ApprovalThreshold::new(3, 4).unwrap());
+	}
+}
@@ -0,0 +1,184 @@
+//! # Bounded decoding
+//!
+//! Borsh decoding of the data relayers hand to the client. The length prefix of every list whose
+//! items can be (nearly) empty is checked against [`DecodeLimits`] before any item is decoded, so
+//! a crafted prefix can't make the runtime allocate or loop far beyond what a valid NEAR block
+//! needs. The other lists are already bounded by the size of the input.
+
+use alloc::format;
+use borsh::BorshDeserialize;
+use near_primitives_wasm::{LightClientBlockView, MerklePath, OutcomeProof};
+use sp_std::vec::Vec;
+
+use crate::{
+	error::{serialization_error, NearLiteClientError},
+	LiteClientResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+	/// items of `approvals_after_next`, one per block producer of the epoch
+	pub max_approvals: usize,
+	/// items of `next_bps`
+	pub max_block_producers: usize,
+	/// items of a merkle path
+	pub max_merkle_path_len: usize,
+}
+
+impl Default for DecodeLimits {
+	/// Well above mainnet, which has 100 block producer seats and 4 shards.
+	fn default() -> Self {
+		Self { max_approvals: 1024, max_block_producers: 1024, max_merkle_path_len: 64 }
+	}
+}
+
+impl DecodeLimits {
+	pub fn decode_block_view(&self, bytes: &[u8]) -> LiteClientResult<LightClientBlockView> {
+		decode_all(bytes, |buf| {
+			Ok(LightClientBlockView {
+				prev_block_hash: decode(buf)?,
+				next_block_inner_hash: decode(buf)?,
+				inner_lite: decode(buf)?,
+				inner_rest_hash: decode(buf)?,
+				next_bps: match decode::<u8>(buf)? {
+					0 => None,
+					1 => Some(decode_vec(buf, self.max_block_producers, "block producers")?),
+					tag => return Err(invalid_option_tag::<LightClientBlockView>(tag)),
+				},
+				approvals_after_next: decode_vec(buf, self.max_approvals, "approvals")?,
+			})
+		})
+	}
+
+	pub fn decode_merkle_path(&self, bytes: &[u8]) -> LiteClientResult<MerklePath> {
+		decode_all(bytes, |buf| decode_vec(buf, self.max_merkle_path_len, "merkle path items"))
+	}
+
+	pub fn decode_outcome_proof(&self, bytes: &[u8]) -> LiteClientResult<OutcomeProof> {
+		decode_all(bytes, |buf| {
+			Ok(OutcomeProof {
+				proof: decode_vec(buf, self.max_merkle_path_len, "merkle path items")?,
+				block_hash: decode(buf)?,
+				id: decode(buf)?,
+				outcome: decode(buf)?,
+			})
+		})
+	}
+}
+
+/// Decodes a `T` with `decode`, which has to consume the whole of `bytes`.
+pub(crate) fn decode_all<T>(
+	mut bytes: &[u8],
+	decode: impl FnOnce(&mut &[u8]) -> LiteClientResult<T>,
+) -> LiteClientResult<T> {
+	let value = decode(&mut bytes)?;
+	if !bytes.is_empty() {
+		return Err(NearLiteClientError::Serialization {
+			type_name: core::any::type_name::<T>(),
+			reason: format!("{} bytes left after decoding", bytes.len()),
+		})
+	}
+	Ok(value)
+}
+
+pub(crate) fn decode<T: BorshDeserialize>(buf: &mut &[u8]) -> LiteClientResult<T> {
+	T::deserialize(buf).map_err(serialization_error::<T>)
+}
+
+pub(crate) fn decode_vec<T: BorshDeserialize>(
+	buf: &mut &[u8],
+	max_len: usize,
+	items: &str,
+) -> LiteClientResult<Vec<T>> {
+	let len = decode::<u32>(buf)? as usize;
+	if len > max_len {
+		return Err(NearLiteClientError::CapacityExceeded(format!(
+			"{} {} over the limit of {}",
+			len, items, max_len
+		)))
+	}
+	(0..len).map(|_| decode(buf)).collect()
+}
+
+fn invalid_option_tag<T>(tag: u8) -> NearLiteClientError {
+	NearLiteClientError::Serialization {
+		type_name: core::any::type_name::<T>(),
+		reason: format!("invalid Option tag {}", tag),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use borsh::BorshSerialize;
+	use near_primitives_wasm::{
+		CryptoHash, Direction, MerklePathItem, PublicKey, Signature, ValidatorStakeView,
+		ValidatorStakeViewV1,
+	};
+
+	fn block_view(block_producers: usize) -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.next_bps = Some(
+			(0..block_producers)
+				.map(|_| {
+					ValidatorStakeView::V1(ValidatorStakeViewV1 {
+						account_id: "node0.near".into(),
+						public_key: PublicKey([1; 32]),
+						stake: 1,
+					})
+				})
+				.collect(),
+		);
+		block_view.approvals_after_next =
+			(0..block_producers).map(|_| Some(Signature::from_raw(&[2; 64]))).collect();
+		block_view
+	}
+
+	#[test]
+	fn test_decode_block_view_within_limits() {
+		let block_view = block_view(3);
+		let limits =
+			DecodeLimits { max_approvals: 3, max_block_producers: 3, ..Default::default() };
+		let decoded = limits.decode_block_view(&block_view.try_to_vec().unwrap()).unwrap();
+		assert_eq!(decoded.try_to_vec().unwrap(), block_view.try_to_vec().unwrap());
+
+		let mut encoded = block_view.try_to_vec().unwrap();
+		encoded.push(0);
+		assert!(matches!(
+			limits.decode_block_view(&encoded),
+			Err(NearLiteClientError::Serialization { .. })
+		));
+	}
+
+	#[test]
+	fn test_decode_limits_are_enforced() {
+		let encoded = block_view(3).try_to_vec().unwrap();
+		for limits in [
+			DecodeLimits { max_approvals: 2, ..Default::default() },
+			DecodeLimits { max_block_producers: 2, ..Default::default() },
+		] {
+			assert!(matches!(
+				limits.decode_block_view(&encoded),
+				Err(NearLiteClientError::CapacityExceeded(_))
+			));
+		}
+
+		// a huge length prefix is rejected before anything is allocated
+		let mut encoded = LightClientBlockView::new_for_test().try_to_vec().unwrap();
+		let len = encoded.len();
+		encoded[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+		assert!(matches!(
+			DecodeLimits::default().decode_block_view(&encoded),
+			Err(NearLiteClientError::CapacityExceeded(_))
+		));
+
+		let path = (0..3)
+			.map(|_| MerklePathItem { hash: CryptoHash([3; 32]), direction: Direction::Left })
+			.collect::<MerklePath>();
+		let encoded = path.try_to_vec().unwrap();
+		let limits = DecodeLimits { max_merkle_path_len: 3, ..Default::default() };
+		assert_eq!(limits.decode_merkle_path(&encoded).unwrap(), path);
+		let limits = DecodeLimits { max_merkle_path_len: 2, ..Default::default() };
+		assert!(limits.decode_merkle_path(&encoded).is_err());
+	}
+}
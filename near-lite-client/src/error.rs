@@ -1,15 +1,318 @@
-use alloc::string::String;
-use borsh::maybestd::io::Error as BorshError;
-use near_primitives_wasm::ConversionError;
+use alloc::string::{String, ToString};
+use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize};
+use near_primitives_wasm::{AccountId, Balance, BlockHeight, ConversionError, CryptoHash, EpochId};
+use sp_std::vec::Vec;
 
+/// Every variant has a stable numeric [code](NearLiteClientError::code), for consumers that can't
+/// match on the enum itself (e.g. other pallets or contracts). Codes are never reused nor
+/// renumbered; new variants get new codes.
 #[derive(Debug)]
 pub enum NearLiteClientError {
 	Borsh(BorshError),
 	Conversion(ConversionError),
+	#[deprecated(note = "proofs fail with a `Verification` failure or a typed variant; only the \
+	                     string errors of `near-merkle-proofs` still convert into it")]
 	ProofVerificationError(String),
 	InvalidLiteBlock(String),
 	SignatureVerification(String),
+	#[deprecated(note = "outcome proofs fail with a `Verification` failure")]
 	TransactionValidation(String),
+	AuditLog(String),
+	CapacityExceeded(String),
+	TestVector(String),
+	Rpc(String),
+	CheckpointQuorum(String),
+	Replay(String),
+	Config(String),
+	/// A block view or an outcome failed one of the verification rules.
+	Verification(VerificationFailure),
+	/// A value of type `type_name` could not be borsh encoded or decoded.
+	Serialization {
+		type_name: &'static str,
+		reason: String,
+	},
+	/// A merkle proof leads to `computed` instead of the `expected` root.
+	MerkleRootMismatch {
+		expected: CryptoHash,
+		computed: CryptoHash,
+	},
+	/// The block view is not the head of the client and is not ahead of it either.
+	StaleHeader {
+		head_height: BlockHeight,
+		block_height: BlockHeight,
+	},
+	/// The client was frozen after a misbehaviour at `height`.
+	ClientFrozen {
+		height: BlockHeight,
+	},
+	/// The key-value backend of the storage failed.
+	Storage(String),
+	/// A partial merkle tree of `size` leaves is resumed from `subtrees` roots, instead of one
+	/// per bit set in `size`.
+	PartialTreeMismatch {
+		size: u64,
+		subtrees: usize,
+	},
+	/// The leaf at `index`, or the subtree starting at it, is not held by the accumulator, which
+	/// can't prove it.
+	LeafNotInAccumulator {
+		index: u64,
+	},
+	/// No header was appended to the header MMR at `height`.
+	HeaderNotInMmr {
+		height: BlockHeight,
+	},
+}
+
+impl NearLiteClientError {
+	/// Stable code of the error: the failures of [`NearLiteClientError::Verification`] are
+	/// numbered from 100, the other variants below.
+	#[allow(deprecated)]
+	pub fn code(&self) -> u16 {
+		match self {
+			Self::Borsh(_) => 1,
+			Self::Conversion(_) => 2,
+			Self::ProofVerificationError(_) => 3,
+			Self::InvalidLiteBlock(_) => 4,
+			Self::SignatureVerification(_) => 5,
+			Self::TransactionValidation(_) => 6,
+			Self::AuditLog(_) => 7,
+			Self::CapacityExceeded(_) => 8,
+			Self::TestVector(_) => 9,
+			Self::Rpc(_) => 10,
+			Self::CheckpointQuorum(_) => 11,
+			Self::Replay(_) => 12,
+			Self::Config(_) => 13,
+			Self::Serialization { .. } => 14,
+			Self::MerkleRootMismatch { .. } => 15,
+			Self::StaleHeader { .. } => 16,
+			Self::ClientFrozen { .. } => 17,
+			Self::Storage(_) => 18,
+			Self::PartialTreeMismatch { .. } => 19,
+			Self::LeafNotInAccumulator { .. } => 20,
+			Self::HeaderNotInMmr { .. } => 21,
+			Self::Verification(failure) => failure.code(),
+		}
+	}
+
+	/// Whether the error comes from what was submitted rather than from the state of the client,
+	/// so that submitting it again fails the same way. Failures to decode, to verify, and block
+	/// views behind the head are permanent; a client that is behind, frozen or misconfigured, and
+	/// a failing storage, may accept the same submission later.
+	#[allow(deprecated)]
+	pub fn is_permanent(&self) -> bool {
+		match self {
+			Self::Borsh(_) |
+			Self::Conversion(_) |
+			Self::ProofVerificationError(_) |
+			Self::InvalidLiteBlock(_) |
+			Self::SignatureVerification(_) |
+			Self::TransactionValidation(_) |
+			Self::MerkleRootMismatch { .. } |
+			Self::StaleHeader { .. } => true,
+			Self::Verification(failure) => failure.is_permanent(),
+			_ => false,
+		}
+	}
+}
+
+/// The check that rejected a block view or an outcome, with the values it compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationFailure {
+	/// The block view is not ahead of the head.
+	HeightNotAhead { head_height: BlockHeight, block_height: BlockHeight },
+	/// The epoch of the block view is neither the epoch of the head nor the next one.
+	EpochMismatch { epoch_id: EpochId, head_epoch_id: EpochId, head_next_epoch_id: EpochId },
+	/// The first block view of the next epoch does not carry the block producers of the epoch
+	/// after it.
+	MissingNextBps { epoch_id: EpochId },
+	/// The block producers of the epoch of the block view are not known.
+	UnknownBlockProducers { epoch_id: EpochId },
+	/// The approval of the block producer at `index` is not a valid signature.
+	InvalidSignature { index: usize },
+	/// The approvals do not add up to the threshold share of the stake.
+	StakeThresholdNotReached { approved_stake: Balance, total_stake: Balance },
+	/// `sha256(borsh(next_bps))` is not the `next_bp_hash` of the block view.
+	NextBpHashMismatch { expected: CryptoHash, computed: CryptoHash },
+	/// The block view is timestamped further ahead of the client's clock than the tolerated drift.
+	TimestampAhead { timestamp: u64, now: u64 },
+	/// The block view is not timestamped after the head.
+	TimestampNotIncreasing { timestamp: u64, head_timestamp: u64 },
+	/// The outcomes lead to different shard outcome roots.
+	ShardOutcomeRootMismatch,
+	/// The outcome proofs do not lead to the expected block outcome root.
+	BlockOutcomeRootMismatch { expected: CryptoHash, computed: CryptoHash },
+	/// There are fewer approvals than block producers in the epoch of the block view.
+	ApprovalsMissing { approvals: usize, block_producers: usize },
+	/// The stakes of the block producers add up to more than a `Balance` can hold.
+	StakeOverflow,
+	/// The block producer at `index` of `next_bps` does not belong in a valid set.
+	MalformedNextBps { index: usize, defect: NextBpsDefect },
+	/// The head is older than the client tolerates, so it must be synced before verifying proofs.
+	HeadTooOld { head_timestamp: u64, now: u64, max_age: u64 },
+	/// The consensus state was produced more than the trusting period before `now`.
+	ConsensusStateExpired { timestamp: u64, now: u64, trusting_period: u64 },
+	/// The outcome proof is for another block than the header it comes with.
+	BlockHashMismatch { expected: CryptoHash, computed: CryptoHash },
+	/// The block view names another epoch after its own than the one derived from the head.
+	NextEpochMismatch { next_epoch_id: EpochId, expected: EpochId },
+	/// The block view enters a new epoch but names an epoch already known, its own or the one of
+	/// the head, as the epoch after it.
+	NextEpochNotNew { next_epoch_id: EpochId },
+	/// The block producers of `epoch_id` hash to `computed`, not to the `next_bp_hash` the headers
+	/// of the previous epoch committed to.
+	BlockProducersNotCommitted { epoch_id: EpochId, expected: CryptoHash, computed: CryptoHash },
+	/// None of the block producers of the epoch approved the block view, its approvals are empty
+	/// or all `None`.
+	NoApprovals { block_producers: usize },
+	/// The block producers of the epoch of the block view have no stake, so no approvals can make
+	/// a block final. `block_producers` is `0` when the epoch has none.
+	ZeroTotalStake { block_producers: usize },
+	/// In sequential mode, the block view does not extend the head: its previous block is
+	/// `prev_block_hash` instead of the head, with hash `head_hash`.
+	NotSequential { prev_block_hash: CryptoHash, head_hash: CryptoHash },
+	/// The `inner_rest` hashes to `computed`, not to the `inner_rest_hash` of the block view.
+	InnerRestHashMismatch { expected: CryptoHash, computed: CryptoHash },
+	/// The `chunks_included` of the `inner_rest` is not the number of new chunks of its chunk
+	/// mask.
+	ChunksIncludedMismatch { chunks_included: u64, new_chunks: usize },
+	/// The chunk mask of the `inner_rest` has `len` entries instead of one per shard.
+	ChunkMaskLengthMismatch { len: usize, num_shards: usize },
+	/// The gas price of the `inner_rest` is below `min` or above `max`.
+	GasPriceOutOfRange { gas_price: Balance, min: Option<Balance>, max: Option<Balance> },
+	/// The `inner_rest` names a previous block that is not below the block view.
+	PrevHeightNotBelow { prev_height: BlockHeight, height: BlockHeight },
+	/// The outcome proof is for the transaction or receipt `id`, not `expected`.
+	OutcomeIdMismatch { expected: CryptoHash, id: CryptoHash },
+	/// The outcome was executed by `executor_id`, not by the signer of its transaction or the
+	/// receiver of its receipt.
+	ExecutorMismatch { expected: AccountId, executor_id: AccountId },
+	/// The outcome of a transaction lists `receipts` receipts, instead of the one the transaction
+	/// was converted into.
+	TransactionNotConverted { receipts: usize },
+	/// An outcome of the transaction or receipt `id` is part of the block it was proved absent
+	/// from.
+	OutcomeIncluded { id: CryptoHash },
+	/// A batch of `outcome_proofs` outcome proofs comes with `outcome_root_proofs` outcome root
+	/// proofs, instead of one each.
+	OutcomeProofCountMismatch { outcome_proofs: usize, outcome_root_proofs: usize },
+	/// A batch of outcome proofs is empty.
+	NoOutcomeProofs,
+	/// The state holds the value hashing to `found` under the key, not the one hashing to
+	/// `expected`; `None` stands for no value.
+	StateValueMismatch { expected: Option<CryptoHash>, found: Option<CryptoHash> },
+	/// The paths of a merkle multiproof do not lead from its leaves to a root.
+	MalformedMultiproof { defect: MultiproofDefect },
+	/// The block view is at `height`, so close to `u64::MAX` that no block two heights after it,
+	/// the one its approvals endorse, can exist.
+	HeightOverflow { height: BlockHeight },
+	/// The hash `field` of the block view is all zeroes, which no block view commits to.
+	ZeroHash { field: &'static str },
+	/// The block view carries `approvals` approvals, none or more than the `max` allowed.
+	ApprovalCountOutOfRange { approvals: usize, max: usize },
+	/// The block view carries `block_producers` block producers, none or more than the `max`
+	/// allowed.
+	BlockProducerCountOutOfRange { block_producers: usize, max: usize },
+	/// The account id of the block producer at `index` of `next_bps` is `len` bytes long, which
+	/// no NEAR account id is.
+	InvalidAccountId { index: usize, len: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NextBpsDefect {
+	/// its account id is already listed
+	DuplicateAccountId,
+	ZeroStake,
+	/// its stake is higher than the one of the previous block producer
+	NotSortedByStake,
+	/// its version of `ValidatorStakeView` is not enabled by the protocol version of the epoch
+	VersionNotEnabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiproofDefect {
+	/// there are no leaves, or not one path per leaf
+	LengthMismatch,
+	/// a sibling refers to a node the proof does not have
+	NodeOutOfBounds,
+	/// a sibling refers to a path the proof does not have, or to the path it belongs to
+	InvalidLeafReference,
+	/// a sibling refers to a level above the root of the path it refers to
+	InvalidLevelReference,
+	/// the references are circular, so some paths can't be followed up to the root
+	Unresolvable,
+}
+
+impl VerificationFailure {
+	pub fn code(&self) -> u16 {
+		match self {
+			Self::HeightNotAhead { .. } => 100,
+			Self::EpochMismatch { .. } => 101,
+			Self::MissingNextBps { .. } => 102,
+			Self::UnknownBlockProducers { .. } => 103,
+			Self::InvalidSignature { .. } => 104,
+			Self::StakeThresholdNotReached { .. } => 105,
+			Self::NextBpHashMismatch { .. } => 106,
+			Self::TimestampAhead { .. } => 107,
+			Self::ShardOutcomeRootMismatch => 108,
+			Self::BlockOutcomeRootMismatch { .. } => 109,
+			Self::TimestampNotIncreasing { .. } => 110,
+			Self::MalformedNextBps { .. } => 111,
+			Self::StakeOverflow => 112,
+			Self::ApprovalsMissing { .. } => 113,
+			Self::HeadTooOld { .. } => 114,
+			Self::ConsensusStateExpired { .. } => 115,
+			Self::BlockHashMismatch { .. } => 116,
+			Self::NextEpochMismatch { .. } => 117,
+			Self::NextEpochNotNew { .. } => 118,
+			Self::BlockProducersNotCommitted { .. } => 119,
+			Self::NoApprovals { .. } => 120,
+			Self::ZeroTotalStake { .. } => 121,
+			Self::NotSequential { .. } => 122,
+			Self::InnerRestHashMismatch { .. } => 123,
+			Self::ChunksIncludedMismatch { .. } => 124,
+			Self::ChunkMaskLengthMismatch { .. } => 125,
+			Self::GasPriceOutOfRange { .. } => 126,
+			Self::PrevHeightNotBelow { .. } => 127,
+			Self::OutcomeIdMismatch { .. } => 128,
+			Self::ExecutorMismatch { .. } => 129,
+			Self::TransactionNotConverted { .. } => 130,
+			Self::OutcomeIncluded { .. } => 131,
+			Self::OutcomeProofCountMismatch { .. } => 132,
+			Self::NoOutcomeProofs => 133,
+			Self::StateValueMismatch { .. } => 134,
+			Self::MalformedMultiproof { .. } => 135,
+			Self::HeightOverflow { .. } => 136,
+			Self::ZeroHash { .. } => 137,
+			Self::ApprovalCountOutOfRange { .. } => 138,
+			Self::BlockProducerCountOutOfRange { .. } => 139,
+			Self::InvalidAccountId { .. } => 140,
+		}
+	}
+
+	/// See [`NearLiteClientError::is_permanent`]: the failures that depend on the epochs the client
+	/// knows, on its clock or on a head it may move past are not.
+	pub fn is_permanent(&self) -> bool {
+		!matches!(
+			self,
+			Self::EpochMismatch { .. } |
+				Self::MissingNextBps { .. } |
+				Self::NextEpochMismatch { .. } |
+				Self::NextEpochNotNew { .. } |
+				Self::BlockProducersNotCommitted { .. } |
+				Self::UnknownBlockProducers { .. } |
+				Self::TimestampAhead { .. } |
+				Self::HeadTooOld { .. } |
+				Self::ConsensusStateExpired { .. } |
+				Self::NotSequential { .. }
+		)
+	}
+}
+
+impl From<VerificationFailure> for NearLiteClientError {
+	fn from(failure: VerificationFailure) -> Self {
+		Self::Verification(failure)
+	}
 }
 
 // Had to implement this variant manually due to some traits missing on the
@@ -28,8 +331,76 @@ impl From<ConversionError> for NearLiteClientError {
 	}
 }
 
+// `near-merkle-proofs` reports its errors as strings
 impl From<String> for NearLiteClientError {
+	#[allow(deprecated)]
 	fn from(err: String) -> Self {
 		Self::ProofVerificationError(err)
 	}
 }
+
+/// Borsh encodes `value`, naming its type if it fails.
+pub(crate) fn serialize<T: BorshSerialize + ?Sized>(
+	value: &T,
+) -> Result<Vec<u8>, NearLiteClientError> {
+	value.try_to_vec().map_err(serialization_error::<T>)
+}
+
+/// Borsh decodes a `T` from the whole of `bytes`, naming `T` if it fails.
+pub(crate) fn deserialize<T: BorshDeserialize>(bytes: &[u8]) -> Result<T, NearLiteClientError> {
+	T::try_from_slice(bytes).map_err(serialization_error::<T>)
+}
+
+pub(crate) fn serialization_error<T: ?Sized>(err: BorshError) -> NearLiteClientError {
+	NearLiteClientError::Serialization {
+		type_name: core::any::type_name::<T>(),
+		reason: err.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_serialization_errors_name_the_type() {
+		let err = deserialize::<(CryptoHash, u64)>(&[0; 33]).unwrap_err();
+		assert_eq!(err.code(), 14);
+		match err {
+			NearLiteClientError::Serialization { type_name, .. } => {
+				assert!(type_name.contains("CryptoHash"), "{}", type_name)
+			},
+			err => panic!("unexpected error {:?}", err),
+		}
+	}
+
+	#[test]
+	fn test_codes_are_stable() {
+		let hash = CryptoHash::default();
+		let errors = [
+			(NearLiteClientError::Config(String::new()), 13),
+			(NearLiteClientError::MerkleRootMismatch { expected: hash, computed: hash }, 15),
+			(
+				VerificationFailure::HeightNotAhead {
+					head_height: BlockHeight(1),
+					block_height: BlockHeight(1),
+				}
+				.into(),
+				100,
+			),
+			(VerificationFailure::InvalidSignature { index: 3 }.into(), 104),
+			(NearLiteClientError::HeaderNotInMmr { height: BlockHeight(1) }, 21),
+			(
+				VerificationFailure::BlockOutcomeRootMismatch { expected: hash, computed: hash }
+					.into(),
+				109,
+			),
+			(VerificationFailure::OutcomeIncluded { id: hash }.into(), 131),
+			(VerificationFailure::HeightOverflow { height: BlockHeight(1) }.into(), 136),
+			(VerificationFailure::InvalidAccountId { index: 0, len: 1 }.into(), 140),
+		];
+		for (err, code) in errors {
+			assert_eq!(err.code(), code, "{:?}", err);
+		}
+	}
+}
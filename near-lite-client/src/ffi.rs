@@ -0,0 +1,278 @@
+//! # C interface
+//!
+//! [`LightClient`] behind a C ABI, so that services written in other languages (Go, Python, ...)
+//! can embed the verifier rather than reimplement it. The declarations are in
+//! `include/near_lite.h`; build the library with
+//!
+//! ```text
+//! cargo rustc -p near-lite-client --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! Block views, outcome proofs and merkle paths are passed borsh encoded, as pointer and length,
+//! and decoded with the default [`DecodeLimits`]. Every function returns a status:
+//! [`NEAR_LITE_OK`], the stable [code](crate::NearLiteClientError::code) of the error that
+//! occurred, or one of the codes of this module for misuses of the interface. The layouts of the
+//! structs and the values of the codes never change within a major version.
+
+use std::{
+	panic::{catch_unwind, AssertUnwindSafe},
+	slice,
+};
+
+use crate::{
+	CryptoHash, DecodeLimits, LightClient, LiteClientResult, Sha256HostFunctions,
+	TrustedCheckpoint, UpdateSummary,
+};
+
+pub const NEAR_LITE_OK: u16 = 0;
+/// A pointer that must not be null was.
+pub const NEAR_LITE_NULL_POINTER: u16 = 1000;
+/// The client panicked. It must not be used again, except to be freed.
+pub const NEAR_LITE_PANIC: u16 = 1001;
+
+/// Opaque handle of a client, created by [`near_lite_new`] and freed by [`near_lite_free`].
+pub struct NearLiteClient {
+	client: LightClient<Sha256HostFunctions>,
+	limits: DecodeLimits,
+}
+
+/// [`UpdateSummary`], with the booleans as `0` or `1`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NearLiteUpdateSummary {
+	pub old_height: u64,
+	pub new_height: u64,
+	pub approvals_checked: u32,
+	pub stake_approved_bps: u16,
+	pub epoch_changed: u8,
+	pub already_validated: u8,
+}
+
+impl From<UpdateSummary> for NearLiteUpdateSummary {
+	fn from(summary: UpdateSummary) -> Self {
+		Self {
+			old_height: summary.old_height.0,
+			new_height: summary.new_height.0,
+			approvals_checked: summary.approvals_checked as u32,
+			stake_approved_bps: summary.stake_approved_bps,
+			epoch_changed: summary.epoch_changed.into(),
+			already_validated: summary.already_validated.into(),
+		}
+	}
+}
+
+/// Height and hash of the head.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NearLiteHead {
+	pub height: u64,
+	pub hash: [u8; 32],
+}
+
+/// Runs `f`, turning its error or panic into a status.
+fn status(f: impl FnOnce() -> LiteClientResult<()>) -> u16 {
+	match catch_unwind(AssertUnwindSafe(f)) {
+		Ok(Ok(())) => NEAR_LITE_OK,
+		Ok(Err(err)) => err.code(),
+		Err(_) => NEAR_LITE_PANIC,
+	}
+}
+
+/// `len` bytes from `data`, which may only be null if `len` is `0`.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+	match (data.is_null(), len) {
+		(true, 0) => Some(&[]),
+		(true, _) => None,
+		(false, len) => Some(slice::from_raw_parts(data, len)),
+	}
+}
+
+/// Creates a client trusting the borsh encoded light client block `checkpoint`, and stores it in
+/// `*client` on success.
+///
+/// # Safety
+///
+/// `checkpoint` must point to `checkpoint_len` readable bytes, and `client` to a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn near_lite_new(
+	checkpoint: *const u8,
+	checkpoint_len: usize,
+	client: *mut *mut NearLiteClient,
+) -> u16 {
+	let checkpoint = match bytes(checkpoint, checkpoint_len) {
+		Some(checkpoint) if !client.is_null() => checkpoint,
+		_ => return NEAR_LITE_NULL_POINTER,
+	};
+	status(|| {
+		let limits = DecodeLimits::default();
+		let checkpoint = limits.decode_block_view(checkpoint)?;
+		let handle = NearLiteClient {
+			client: LightClient::with_checkpoint(TrustedCheckpoint(checkpoint))?,
+			limits,
+		};
+		*client = Box::into_raw(Box::new(handle));
+		Ok(())
+	})
+}
+
+/// Frees a client created by [`near_lite_new`]. Does nothing if `client` is null.
+///
+/// # Safety
+///
+/// `client` must come from [`near_lite_new`] and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn near_lite_free(client: *mut NearLiteClient) {
+	if !client.is_null() {
+		drop(Box::from_raw(client));
+	}
+}
+
+/// Writes the height and hash of the head of `client` to `*head`.
+///
+/// # Safety
+///
+/// `client` must be a live client and `head` point to a writable [`NearLiteHead`].
+#[no_mangle]
+pub unsafe extern "C" fn near_lite_head(
+	client: *const NearLiteClient,
+	head: *mut NearLiteHead,
+) -> u16 {
+	let (client, head) = match (client.as_ref(), head.as_mut()) {
+		(Some(client), Some(head)) => (client, head),
+		_ => return NEAR_LITE_NULL_POINTER,
+	};
+	status(|| {
+		*head = NearLiteHead {
+			height: client.client.head_height().0,
+			hash: client.client.head_hash().0,
+		};
+		Ok(())
+	})
+}
+
+/// [`LightClient::validate_and_update_head`] with the borsh encoded `block_view`. On success,
+/// writes what changed to `*summary` unless it is null.
+///
+/// # Safety
+///
+/// `client` must be a live client, not used concurrently, `block_view` must point to
+/// `block_view_len` readable bytes and `summary`, if not null, to a writable
+/// [`NearLiteUpdateSummary`].
+#[no_mangle]
+pub unsafe extern "C" fn near_lite_update_head(
+	client: *mut NearLiteClient,
+	block_view: *const u8,
+	block_view_len: usize,
+	summary: *mut NearLiteUpdateSummary,
+) -> u16 {
+	let (client, block_view) = match (client.as_mut(), bytes(block_view, block_view_len)) {
+		(Some(client), Some(block_view)) => (client, block_view),
+		_ => return NEAR_LITE_NULL_POINTER,
+	};
+	status(|| {
+		let block_view = client.limits.decode_block_view(block_view)?;
+		let update = client.client.validate_and_update_head(block_view)?;
+		if let Some(summary) = summary.as_mut() {
+			*summary = update.into();
+		}
+		Ok(())
+	})
+}
+
+/// [`LightClient::validate_transaction`] with the borsh encoded `outcome_proof` and
+/// `outcome_root_proof` (a `MerklePath`), against the 32 bytes of `expected_block_outcome_root`.
+///
+/// # Safety
+///
+/// `client` must be a live client, the pointers must point to as many readable bytes as their
+/// lengths, and `expected_block_outcome_root` to 32 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn near_lite_verify_tx(
+	client: *const NearLiteClient,
+	outcome_proof: *const u8,
+	outcome_proof_len: usize,
+	outcome_root_proof: *const u8,
+	outcome_root_proof_len: usize,
+	expected_block_outcome_root: *const [u8; 32],
+) -> u16 {
+	let (client, outcome_proof, outcome_root_proof, expected_block_outcome_root) = match (
+		client.as_ref(),
+		bytes(outcome_proof, outcome_proof_len),
+		bytes(outcome_root_proof, outcome_root_proof_len),
+		expected_block_outcome_root.as_ref(),
+	) {
+		(Some(client), Some(proof), Some(root_proof), Some(root)) =>
+			(client, proof, root_proof, CryptoHash(*root)),
+		_ => return NEAR_LITE_NULL_POINTER,
+	};
+	status(|| {
+		let outcome_proof = client.limits.decode_outcome_proof(outcome_proof)?;
+		let outcome_root_proof = client.limits.decode_merkle_path(outcome_root_proof)?;
+		client.client.validate_transaction(
+			&outcome_proof,
+			outcome_root_proof,
+			expected_block_outcome_root,
+		)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::NearLiteClientError;
+	use borsh::BorshSerialize;
+	use near_primitives_wasm::{BlockHeight, LightClientBlockView};
+	use std::ptr;
+
+	#[test]
+	fn test_statuses() {
+		let checkpoint = LightClientBlockView::new_for_test();
+		let encoded = checkpoint.try_to_vec().unwrap();
+		let mut client = ptr::null_mut();
+		unsafe {
+			assert_eq!(near_lite_new(encoded.as_ptr(), 3, &mut client), 14);
+			assert!(client.is_null());
+			assert_eq!(
+				near_lite_new(encoded.as_ptr(), encoded.len(), ptr::null_mut()),
+				NEAR_LITE_NULL_POINTER
+			);
+			assert_eq!(near_lite_new(encoded.as_ptr(), encoded.len(), &mut client), NEAR_LITE_OK);
+
+			let mut head = NearLiteHead::default();
+			assert_eq!(near_lite_head(client, &mut head), NEAR_LITE_OK);
+			assert_eq!(head.height, checkpoint.inner_lite.height.0);
+			assert_eq!(
+				CryptoHash(head.hash),
+				checkpoint.current_block_hash::<Sha256HostFunctions>()
+			);
+
+			let mut summary = NearLiteUpdateSummary::default();
+			assert_eq!(
+				near_lite_update_head(client, encoded.as_ptr(), encoded.len(), &mut summary),
+				NEAR_LITE_OK
+			);
+			assert_eq!(summary.already_validated, 1);
+			let mut older = checkpoint;
+			older.inner_lite.height = BlockHeight(0);
+			let older = older.try_to_vec().unwrap();
+			assert_eq!(
+				near_lite_update_head(client, older.as_ptr(), older.len(), ptr::null_mut()),
+				NearLiteClientError::StaleHeader {
+					head_height: BlockHeight(1),
+					block_height: BlockHeight(0)
+				}
+				.code()
+			);
+
+			assert_eq!(
+				near_lite_verify_tx(client, ptr::null(), 0, ptr::null(), 0, ptr::null()),
+				NEAR_LITE_NULL_POINTER
+			);
+			assert_ne!(
+				near_lite_verify_tx(client, ptr::null(), 0, ptr::null(), 0, &[0; 32]),
+				NEAR_LITE_OK
+			);
+			near_lite_free(client);
+		}
+	}
+}
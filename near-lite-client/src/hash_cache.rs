@@ -0,0 +1,86 @@
+//! # Accepted hash cache
+//!
+//! Several relayers usually submit the same headers, and keep relaying them after the head moved
+//! past them. The hashes of the most recently accepted headers are kept here, so that the client
+//! can tell these resubmissions from other stale headers and leave them out of the audit log. They
+//! are rejected as stale all the same.
+
+use near_primitives_wasm::CryptoHash;
+use sp_std::collections::vec_deque::VecDeque;
+
+pub const DEFAULT_VALIDATED_CACHE_CAPACITY: usize = 16;
+
+/// Least recently used set of block hashes, holding at most `capacity` of them.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone)]
+pub struct HashCache {
+	capacity: usize,
+	/// least recently used first
+	hashes: VecDeque<CryptoHash>,
+}
+
+impl HashCache {
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, hashes: VecDeque::with_capacity(capacity) }
+	}
+
+	pub fn len(&self) -> usize {
+		self.hashes.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.hashes.is_empty()
+	}
+
+	/// Whether `hash` is cached, in which case it becomes the most recently used.
+	pub fn touch(&mut self, hash: &CryptoHash) -> bool {
+		match self.hashes.iter().position(|cached| cached == hash) {
+			Some(index) => {
+				let hash = self.hashes.remove(index).expect("the index was just found; qed");
+				self.hashes.push_back(hash);
+				true
+			},
+			None => false,
+		}
+	}
+
+	/// Caches `hash`, evicting the least recently used hash when full.
+	pub fn insert(&mut self, hash: CryptoHash) {
+		if self.capacity == 0 || self.touch(&hash) {
+			return
+		}
+		if self.hashes.len() == self.capacity {
+			self.hashes.pop_front();
+		}
+		self.hashes.push_back(hash);
+	}
+}
+
+impl Default for HashCache {
+	fn default() -> Self {
+		Self::new(DEFAULT_VALIDATED_CACHE_CAPACITY)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_least_recently_used_hash_is_evicted() {
+		let mut cache = HashCache::new(2);
+		cache.insert(CryptoHash([1; 32]));
+		cache.insert(CryptoHash([2; 32]));
+		assert!(cache.touch(&CryptoHash([1; 32])));
+		cache.insert(CryptoHash([3; 32]));
+
+		assert_eq!(cache.len(), 2);
+		assert!(cache.touch(&CryptoHash([1; 32])));
+		assert!(!cache.touch(&CryptoHash([2; 32])));
+		assert!(cache.touch(&CryptoHash([3; 32])));
+
+		let mut disabled = HashCache::new(0);
+		disabled.insert(CryptoHash([1; 32]));
+		assert!(disabled.is_empty());
+	}
+}
@@ -0,0 +1,83 @@
+//! # Block hashes
+//!
+//! How NEAR derives, from a light client block view, the hash of the block, the hash of the block
+//! after it and the message its block producers sign in `approvals_after_next`. Relayers and
+//! runtimes building or checking headers outside of [`LightClient`](crate::LightClient) should
+//! call these rather than copy them: their signatures are part of the stable API of the crate, and
+//! none of them panics on a malformed block view.
+//!
+//! ```ignore
+//! let (current_block_hash, next_block_hash, approval_message) =
+//! 	hashes::reconstruct_light_client_block_view_fields::<H>(&block_view)?;
+//! ```
+
+use near_primitives_wasm::{
+	ApprovalInner, BlockHeight, CryptoHash, HostFunctions, LightClientBlockView,
+};
+use sp_std::vec::Vec;
+
+use crate::{error::serialize, LiteClientResult, VerificationFailure};
+
+/// Hash of the block of `block_view`: `sha256(sha256(inner_lite ++ inner_rest_hash) ++
+/// prev_block_hash)`, where `inner_lite` is hashed without its `timestamp_nanosec`.
+pub fn current_block_hash<H: HostFunctions>(block_view: &LightClientBlockView) -> CryptoHash {
+	block_view.current_block_hash::<H>()
+}
+
+/// Hash of the block after the one hashing to `current_block_hash`, from the hash of its inner
+/// parts that the block view carries.
+pub fn next_block_hash<H: HostFunctions>(
+	next_block_inner_hash: CryptoHash,
+	current_block_hash: CryptoHash,
+) -> CryptoHash {
+	CryptoHash(H::sha256_concat(&[next_block_inner_hash.as_ref(), current_block_hash.as_ref()]))
+}
+
+/// What the block producers sign to endorse the block after `height`, whose hash is
+/// `next_block_hash`: the endorsement, then the height two blocks after `height`. Fails if that
+/// height overflows.
+pub fn approval_message(
+	next_block_hash: CryptoHash,
+	height: BlockHeight,
+) -> LiteClientResult<Vec<u8>> {
+	let target_height =
+		height.0.checked_add(2).ok_or(VerificationFailure::HeightOverflow { height })?;
+	Ok([serialize(&ApprovalInner::Endorsement(next_block_hash))?, serialize(&target_height)?]
+		.concat())
+}
+
+/// The hash of the block of `block_view`, the hash of the next block and the message its block
+/// producers sign in `approvals_after_next`.
+pub fn reconstruct_light_client_block_view_fields<H: HostFunctions>(
+	block_view: &LightClientBlockView,
+) -> LiteClientResult<(CryptoHash, CryptoHash, Vec<u8>)> {
+	let current_block_hash = current_block_hash::<H>(block_view);
+	let next_block_hash =
+		next_block_hash::<H>(block_view.next_block_inner_hash, current_block_hash);
+	let approval_message = approval_message(next_block_hash, block_view.inner_lite.height)?;
+	Ok((current_block_hash, next_block_hash, approval_message))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{test_utils::MockedHostFunctions, NearLiteClientError};
+
+	#[test]
+	fn test_approval_message() {
+		let next_block_hash = CryptoHash([5; 32]);
+		let message = approval_message(next_block_hash, BlockHeight(10)).unwrap();
+		// the tag of `Endorsement`, the hash, then the height in little endian
+		assert_eq!(message[0], 0);
+		assert_eq!(message[1..33], next_block_hash.0);
+		assert_eq!(message[33..], 12u64.to_le_bytes());
+
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(u64::MAX - 1);
+		assert!(matches!(
+			reconstruct_light_client_block_view_fields::<MockedHostFunctions>(&block_view),
+			Err(NearLiteClientError::Verification(VerificationFailure::HeightOverflow { height }))
+				if height == BlockHeight(u64::MAX - 1)
+		));
+	}
+}
@@ -0,0 +1,121 @@
+//! # Header MMR
+//!
+//! A client built with [`LightClientBuilder::header_mmr`](crate::LightClientBuilder::header_mmr)
+//! appends the hash of every head it accepts to a merkle mountain range, the same append only tree
+//! as [`BlockMerkleAccumulator`]. Its root commits to all those heads, so a light client of this
+//! one, e.g. on a bridged chain, only needs the root to check a [`HeaderInclusionProof`] that a
+//! header was accepted.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_primitives_wasm::{BlockHeight, CryptoHash, HostFunctions, MerkleHash, MerklePath};
+use sp_std::vec::Vec;
+
+use crate::{
+	block_merkle::BlockMerkleAccumulator, merkle_tree::compute_root_from_path, LiteClientResult,
+	NearLiteClientError,
+};
+
+/// Proof that the header at `height`, with hash `block_hash`, is the leaf `leaf_index` of the
+/// MMR of accepted headers when it had `size` leaves.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct HeaderInclusionProof {
+	pub height: BlockHeight,
+	pub block_hash: CryptoHash,
+	pub leaf_index: u64,
+	pub size: u64,
+	pub path: MerklePath,
+}
+
+impl HeaderInclusionProof {
+	/// Checks that the proof leads to `root`, the MMR root of the client when it was built.
+	pub fn verify<H: HostFunctions>(&self, root: &MerkleHash) -> LiteClientResult<()> {
+		let computed = compute_root_from_path::<H>(&self.path, self.block_hash)?;
+		if computed != *root {
+			return Err(NearLiteClientError::MerkleRootMismatch { expected: *root, computed })
+		}
+		Ok(())
+	}
+}
+
+/// The MMR of accepted headers, with the height of every leaf.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+pub(crate) struct HeaderMmr {
+	accumulator: BlockMerkleAccumulator,
+	/// height of each leaf, increasing as heads only move forward
+	heights: Vec<BlockHeight>,
+}
+
+impl HeaderMmr {
+	/// Appends the head at `height`, unless it is not above the last one.
+	pub(crate) fn append<H: HostFunctions>(
+		&mut self,
+		height: BlockHeight,
+		block_hash: CryptoHash,
+	) -> LiteClientResult<()> {
+		if self.heights.last().map_or(false, |last| *last >= height) {
+			return Ok(())
+		}
+		self.accumulator.append::<H>(block_hash)?;
+		self.heights.push(height);
+		Ok(())
+	}
+
+	pub(crate) fn root<H: HostFunctions>(&self) -> LiteClientResult<MerkleHash> {
+		self.accumulator.root::<H>()
+	}
+
+	pub(crate) fn prove<H: HostFunctions>(
+		&self,
+		height: BlockHeight,
+	) -> LiteClientResult<HeaderInclusionProof> {
+		let leaf_index = self
+			.heights
+			.binary_search(&height)
+			.map_err(|_| NearLiteClientError::HeaderNotInMmr { height })? as u64;
+		let block_hash = self.accumulator.leaf(leaf_index).expect("one leaf per height; qed");
+		Ok(HeaderInclusionProof {
+			height,
+			block_hash,
+			leaf_index,
+			size: self.accumulator.size(),
+			path: self.accumulator.proof::<H>(leaf_index)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+
+	#[test]
+	fn test_proofs_of_accepted_headers() {
+		let mut mmr = HeaderMmr::default();
+		let headers =
+			(1..12u64).map(|height| (BlockHeight(height * 3), CryptoHash::hash_borsh(&height)));
+		let headers: Vec<_> = headers.collect();
+		for (height, hash) in &headers {
+			mmr.append::<MockedHostFunctions>(*height, *hash).unwrap();
+		}
+		let root = mmr.root::<MockedHostFunctions>().unwrap();
+
+		for (height, hash) in &headers {
+			let proof = mmr.prove::<MockedHostFunctions>(*height).unwrap();
+			assert_eq!(proof.block_hash, *hash);
+			assert_eq!(proof.size, headers.len() as u64);
+			proof.verify::<MockedHostFunctions>(&root).unwrap();
+		}
+		assert!(matches!(
+			mmr.prove::<MockedHostFunctions>(BlockHeight(4)),
+			Err(NearLiteClientError::HeaderNotInMmr { height: BlockHeight(4) })
+		));
+
+		let mut forged = mmr.prove::<MockedHostFunctions>(BlockHeight(3)).unwrap();
+		forged.block_hash = headers[1].1;
+		assert!(matches!(
+			forged.verify::<MockedHostFunctions>(&root),
+			Err(NearLiteClientError::MerkleRootMismatch { .. })
+		));
+	}
+}
@@ -0,0 +1,125 @@
+//! # Header queue
+//!
+//! A header can only be validated once the block producers of its epoch are known, that is once
+//! the head is in the same epoch or in the previous one. Headers from further epochs are buffered
+//! here, ordered by height, and handed back as soon as the head catches up with them.
+
+use alloc::string::String;
+use near_primitives_wasm::{BlockHeight, LightClientBlockView};
+use sp_std::collections::btree_map::BTreeMap;
+
+use crate::{error::NearLiteClientError, LiteClientResult};
+
+pub const DEFAULT_HEADER_QUEUE_CAPACITY: usize = 32;
+
+/// Whether `block_view` can be validated on top of `head`, as far as epochs are concerned.
+pub fn is_in_known_epoch(head: &LightClientBlockView, block_view: &LightClientBlockView) -> bool {
+	[head.inner_lite.epoch_id, head.inner_lite.next_epoch_id]
+		.contains(&block_view.inner_lite.epoch_id)
+}
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone)]
+pub struct HeaderQueue {
+	capacity: usize,
+	pending: BTreeMap<BlockHeight, LightClientBlockView>,
+}
+
+impl HeaderQueue {
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, pending: BTreeMap::new() }
+	}
+
+	pub fn len(&self) -> usize {
+		self.pending.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+
+	/// Buffers `block_view`. When the queue is full the highest header is evicted, as it is the
+	/// furthest from being validated, unless `block_view` is itself the highest one.
+	pub fn push(&mut self, block_view: LightClientBlockView) -> LiteClientResult<()> {
+		let height = block_view.inner_lite.height;
+		if !self.pending.contains_key(&height) && self.pending.len() >= self.capacity {
+			match self.pending.keys().next_back().copied() {
+				Some(highest) if highest > height => {
+					self.pending.remove(&highest);
+				},
+				_ =>
+					return Err(NearLiteClientError::CapacityExceeded(String::from(
+						"header queue is full",
+					))),
+			}
+		}
+		self.pending.insert(height, block_view);
+		Ok(())
+	}
+
+	/// Drops every header that is not ahead of `head`, then removes and returns the lowest header
+	/// that can be validated on top of it.
+	pub fn pop_ready(&mut self, head: &LightClientBlockView) -> Option<LightClientBlockView> {
+		self.pending = self.pending.split_off(&BlockHeight(head.inner_lite.height.0 + 1));
+		let height = self
+			.pending
+			.iter()
+			.find(|(_, block_view)| is_in_known_epoch(head, block_view))
+			.map(|(height, _)| *height)?;
+		self.pending.remove(&height)
+	}
+}
+
+impl Default for HeaderQueue {
+	fn default() -> Self {
+		Self::new(DEFAULT_HEADER_QUEUE_CAPACITY)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use near_primitives_wasm::{CryptoHash, EpochId};
+
+	fn block_view(height: u64, epoch: u8, next_epoch: u8) -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(height);
+		block_view.inner_lite.epoch_id = EpochId(CryptoHash([epoch; 32]));
+		block_view.inner_lite.next_epoch_id = EpochId(CryptoHash([next_epoch; 32]));
+		block_view
+	}
+
+	#[test]
+	fn test_pop_ready_drains_in_height_order() {
+		let mut queue = HeaderQueue::default();
+		queue.push(block_view(30, 3, 4)).unwrap();
+		queue.push(block_view(21, 2, 3)).unwrap();
+		queue.push(block_view(20, 2, 3)).unwrap();
+		queue.push(block_view(5, 1, 2)).unwrap();
+
+		let head = block_view(10, 1, 2);
+		assert_eq!(queue.pop_ready(&head).unwrap().inner_lite.height, BlockHeight(20));
+		assert_eq!(queue.len(), 2, "the stale header is dropped");
+
+		// epoch 3 is not known until the head moves to epoch 2
+		let head = block_view(20, 2, 3);
+		assert_eq!(queue.pop_ready(&head).unwrap().inner_lite.height, BlockHeight(21));
+		assert_eq!(queue.pop_ready(&head).unwrap().inner_lite.height, BlockHeight(30));
+		assert!(queue.is_empty());
+	}
+
+	#[test]
+	fn test_push_evicts_the_highest_header() {
+		let mut queue = HeaderQueue::new(2);
+		queue.push(block_view(20, 2, 3)).unwrap();
+		queue.push(block_view(30, 3, 4)).unwrap();
+		assert!(queue.push(block_view(40, 4, 5)).is_err());
+
+		queue.push(block_view(25, 2, 3)).unwrap();
+		assert_eq!(queue.len(), 2);
+		let head = block_view(10, 2, 3);
+		assert_eq!(queue.pop_ready(&head).unwrap().inner_lite.height, BlockHeight(20));
+		assert_eq!(queue.pop_ready(&head).unwrap().inner_lite.height, BlockHeight(25));
+		assert!(queue.pop_ready(&head).is_none());
+	}
+}
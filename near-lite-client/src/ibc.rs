@@ -0,0 +1,443 @@
+//! # IBC client
+//!
+//! The state and the rules of an ICS-02 light client of NEAR, for IBC hosts to wrap in the client
+//! traits of the IBC implementation they run (e.g. `ClientDef` of ibc-rs):
+//!
+//! - [`ClientState`] follows the headers like [`LightClient`](crate::LightClient) does, through
+//!   [`ClientState::verify_header`] and [`ClientState::update_state`], and turns away the headers
+//!   timestamped more than its maximum clock drift ahead of the host;
+//! - a [`ConsensusState`] is kept per accepted header in [`ConsensusStates`], which drops them once
+//!   they are older than the trusting period;
+//! - two different headers at the same height, both approved by the block producers of their epoch,
+//!   are a [`Misbehaviour`] that freezes the client, which is recorded in the [`AuditLog`] of the
+//!   host if it keeps one.
+
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, EpochId, HostFunctions, LightClientBlockView, MerklePathItem,
+	ValidatorStakeView,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{
+	audit::{AuditEvent, AuditLog},
+	block_validation::{verify_approvals, BlockViewHashes},
+	clock::check_clock_drift,
+	error::{NearLiteClientError, VerificationFailure},
+	verifier::validate_head,
+	LiteClientResult, OutcomeRoots, TrustedCheckpoint, TypedOutcomeProof,
+};
+
+#[derive(Debug, Clone)]
+pub struct ClientState {
+	/// latest accepted header
+	pub head: LightClientBlockView,
+	/// block producers of the epochs of the latest header, the one after it and the one before
+	/// it, keyed by epoch id
+	pub epoch_block_producers: BTreeMap<EpochId, Vec<ValidatorStakeView>>,
+	/// how long, in nanoseconds, a consensus state is trusted after its header was produced
+	pub trusting_period: u64,
+	/// how far ahead of the host clock, in nanoseconds, a header may be timestamped
+	pub max_clock_drift: u64,
+	/// height of the misbehaviour that froze the client
+	pub frozen_height: Option<BlockHeight>,
+}
+
+/// What the counterparty needs from a NEAR block to verify proofs against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsensusState {
+	/// in nanoseconds, like the timestamps of NEAR blocks
+	pub timestamp: u64,
+	pub block_hash: CryptoHash,
+	/// root of the outcomes included in the block, that outcome proofs lead to
+	pub outcome_root: CryptoHash,
+	pub prev_state_root: CryptoHash,
+}
+
+/// Two headers at the same height, each claimed to be approved by the block producers of its
+/// epoch.
+#[derive(Debug, Clone)]
+pub struct Misbehaviour {
+	pub header_1: LightClientBlockView,
+	pub header_2: LightClientBlockView,
+}
+
+impl ClientState {
+	pub fn new(checkpoint: TrustedCheckpoint, trusting_period: u64) -> Self {
+		let head: LightClientBlockView = checkpoint.into();
+		let mut epoch_block_producers = BTreeMap::new();
+		if let Some(next_bps) = head.next_bps.clone() {
+			epoch_block_producers.insert(head.inner_lite.next_epoch_id, next_bps);
+		}
+		Self {
+			head,
+			epoch_block_producers,
+			trusting_period,
+			max_clock_drift: 0,
+			frozen_height: None,
+		}
+	}
+
+	/// Tolerates headers timestamped up to `max_clock_drift` nanoseconds ahead of the host clock,
+	/// none by default.
+	pub fn with_max_clock_drift(mut self, max_clock_drift: u64) -> Self {
+		self.max_clock_drift = max_clock_drift;
+		self
+	}
+
+	pub fn latest_height(&self) -> BlockHeight {
+		self.head.inner_lite.height
+	}
+
+	pub fn is_frozen(&self) -> bool {
+		self.frozen_height.is_some()
+	}
+
+	/// Whether `header` can become the latest header of the client, when the host clock reads
+	/// `now`, in nanoseconds.
+	pub fn verify_header<H: HostFunctions>(
+		&self,
+		header: &LightClientBlockView,
+		now: u64,
+	) -> LiteClientResult<()> {
+		self.ensure_not_frozen()?;
+		check_clock_drift(now, self.max_clock_drift, header.inner_lite.timestamp)?;
+		validate_head::<H>(&self.head, header, &self.epoch_block_producers)
+	}
+
+	/// Makes a header that passed [`ClientState::verify_header`] the latest one, and returns its
+	/// consensus state. Entering a new epoch drops the block producers of the epochs before the
+	/// previous one, which approve neither the headers to come nor recent misbehaviours.
+	pub fn update_state<H: HostFunctions>(
+		&mut self,
+		header: LightClientBlockView,
+	) -> ConsensusState {
+		if let Some(next_bps) = header.next_bps.clone() {
+			self.epoch_block_producers.insert(header.inner_lite.next_epoch_id, next_bps);
+		}
+		if header.inner_lite.epoch_id != self.head.inner_lite.epoch_id {
+			let retained = [
+				self.head.inner_lite.epoch_id,
+				header.inner_lite.epoch_id,
+				header.inner_lite.next_epoch_id,
+			];
+			self.epoch_block_producers.retain(|epoch_id, _| retained.contains(epoch_id));
+		}
+		let consensus_state = ConsensusState::from_header::<H>(&header);
+		self.head = header;
+		consensus_state
+	}
+
+	/// Whether `misbehaviour` proves that the block producers of an epoch approved two different
+	/// headers at the same height. Fails if one of the headers is not approved by them.
+	pub fn check_for_misbehaviour<H: HostFunctions>(
+		&self,
+		misbehaviour: &Misbehaviour,
+	) -> LiteClientResult<bool> {
+		let Misbehaviour { header_1, header_2 } = misbehaviour;
+		if header_1.inner_lite.height != header_2.inner_lite.height {
+			return Ok(false)
+		}
+		let hashes_1 = BlockViewHashes::new::<H>(header_1)?;
+		let hashes_2 = BlockViewHashes::new::<H>(header_2)?;
+		if hashes_1.current_block_hash == hashes_2.current_block_hash {
+			return Ok(false)
+		}
+
+		for (header, hashes) in [(header_1, hashes_1), (header_2, hashes_2)] {
+			let epoch_id = header.inner_lite.epoch_id;
+			let block_producers = self
+				.epoch_block_producers
+				.get(&epoch_id)
+				.ok_or(VerificationFailure::UnknownBlockProducers { epoch_id })?;
+			verify_approvals::<H>(
+				&header.approvals_after_next,
+				block_producers,
+				&hashes.approval_message,
+			)?;
+		}
+		Ok(true)
+	}
+
+	/// Stops the client from accepting headers, after a misbehaviour at `height`, and records it
+	/// in `audit_log`.
+	pub fn freeze<H: HostFunctions>(
+		&mut self,
+		height: BlockHeight,
+		audit_log: Option<&mut AuditLog<H>>,
+	) -> LiteClientResult<()> {
+		if let Some(audit_log) = audit_log {
+			audit_log.record(AuditEvent::Frozen { height })?;
+		}
+		self.frozen_height = Some(height);
+		Ok(())
+	}
+
+	fn ensure_not_frozen(&self) -> LiteClientResult<()> {
+		match self.frozen_height {
+			Some(height) => Err(NearLiteClientError::ClientFrozen { height }),
+			None => Ok(()),
+		}
+	}
+}
+
+impl ConsensusState {
+	pub fn from_header<H: HostFunctions>(header: &LightClientBlockView) -> Self {
+		Self {
+			timestamp: header.inner_lite.timestamp,
+			block_hash: header.current_block_hash::<H>(),
+			outcome_root: header.inner_lite.outcome_root,
+			prev_state_root: header.inner_lite.prev_state_root,
+		}
+	}
+
+	pub fn is_expired(&self, now: u64, trusting_period: u64) -> bool {
+		now.saturating_sub(self.timestamp) > trusting_period
+	}
+
+	/// [`TypedOutcomeProof::verify`] against the outcome root of the consensus state, as long as
+	/// it is still trusted at `now`.
+	pub fn verify_outcome<H: HostFunctions>(
+		&self,
+		now: u64,
+		trusting_period: u64,
+		outcome_proof: &TypedOutcomeProof,
+		outcome_root_proof: impl AsRef<[MerklePathItem]>,
+	) -> LiteClientResult<OutcomeRoots> {
+		if self.is_expired(now, trusting_period) {
+			return Err(VerificationFailure::ConsensusStateExpired {
+				timestamp: self.timestamp,
+				now,
+				trusting_period,
+			}
+			.into())
+		}
+		outcome_proof.verify::<H>(outcome_root_proof, self.outcome_root)
+	}
+}
+
+/// Consensus states of the client by the height of their header.
+#[derive(Debug, Clone, Default)]
+pub struct ConsensusStates(BTreeMap<BlockHeight, ConsensusState>);
+
+impl ConsensusStates {
+	pub fn insert(&mut self, height: BlockHeight, consensus_state: ConsensusState) {
+		self.0.insert(height, consensus_state);
+	}
+
+	pub fn get(&self, height: BlockHeight) -> Option<&ConsensusState> {
+		self.0.get(&height)
+	}
+
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Drops the consensus states expired at `now`, except the latest one, which the client still
+	/// needs to follow the chain. Returns the heights of the dropped ones, in increasing order.
+	pub fn prune(&mut self, now: u64, trusting_period: u64) -> Vec<BlockHeight> {
+		let latest = self.0.keys().next_back().copied();
+		let expired = self
+			.0
+			.iter()
+			.filter(|(height, consensus_state)| {
+				Some(**height) != latest && consensus_state.is_expired(now, trusting_period)
+			})
+			.map(|(height, _)| *height)
+			.collect::<Vec<_>>();
+		for height in &expired {
+			self.0.remove(height);
+		}
+		expired
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+	use near_primitives_wasm::{
+		ExecutionOutcomeView, OutcomeProof, PublicKey, Signature, ValidatorStakeViewV1,
+	};
+	use sp_core::{ed25519::Pair, Pair as _};
+
+	/// A single block producer, with the key of `pair`.
+	fn block_producers(pair: &Pair) -> Vec<ValidatorStakeView> {
+		vec![ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "node0.near".into(),
+			public_key: PublicKey(pair.public().0),
+			stake: 1,
+		})]
+	}
+
+	/// A client whose next epoch is produced by `pair`.
+	fn client_state(pair: &Pair) -> ClientState {
+		let mut checkpoint = LightClientBlockView::new_for_test();
+		checkpoint.inner_lite.next_epoch_id = EpochId(CryptoHash([1; 32]));
+		checkpoint.inner_lite.next_bp_hash =
+			CryptoHash(MockedHostFunctions::sha256_borsh(&block_producers(pair)));
+		checkpoint.next_bps = Some(block_producers(pair));
+		ClientState::new(TrustedCheckpoint(checkpoint), 100)
+	}
+
+	/// A header of the epoch after the checkpoint at `height`, approved with `pair`, which also
+	/// produces the epoch after it.
+	fn header(pair: &Pair, height: u64, timestamp: u64) -> LightClientBlockView {
+		header_of_epoch(pair, 1, height, timestamp)
+	}
+
+	/// A header of the `epoch`-th epoch after the checkpoint, otherwise like [`header`].
+	fn header_of_epoch(
+		pair: &Pair,
+		epoch: u8,
+		height: u64,
+		timestamp: u64,
+	) -> LightClientBlockView {
+		let mut header = LightClientBlockView::new_for_test();
+		header.inner_lite.height = BlockHeight(height);
+		header.inner_lite.timestamp = timestamp;
+		header.inner_lite.epoch_id = EpochId(CryptoHash([epoch; 32]));
+		header.inner_lite.next_epoch_id = EpochId(CryptoHash([epoch + 1; 32]));
+		header.next_bps = Some(block_producers(pair));
+		header.inner_lite.next_bp_hash =
+			CryptoHash(MockedHostFunctions::sha256_borsh(&block_producers(pair)));
+		let approval_message =
+			BlockViewHashes::new::<MockedHostFunctions>(&header).unwrap().approval_message;
+		header.approvals_after_next = vec![Some(Signature::Ed25519(pair.sign(&approval_message)))];
+		header
+	}
+
+	#[test]
+	fn test_conflicting_headers_freeze_the_client() {
+		let pair = Pair::from_seed(&[1; 32]);
+		let mut client_state = client_state(&pair);
+		let misbehaviour =
+			Misbehaviour { header_1: header(&pair, 5, 10), header_2: header(&pair, 5, 11) };
+		assert!(client_state
+			.check_for_misbehaviour::<MockedHostFunctions>(&misbehaviour)
+			.unwrap());
+
+		let same = Misbehaviour { header_1: header(&pair, 5, 10), header_2: header(&pair, 5, 10) };
+		assert!(!client_state.check_for_misbehaviour::<MockedHostFunctions>(&same).unwrap());
+		let other_heights =
+			Misbehaviour { header_1: header(&pair, 5, 10), header_2: header(&pair, 6, 10) };
+		assert!(!client_state
+			.check_for_misbehaviour::<MockedHostFunctions>(&other_heights)
+			.unwrap());
+		let forged = Misbehaviour {
+			header_1: header(&pair, 5, 10),
+			header_2: header(&Pair::from_seed(&[2; 32]), 5, 11),
+		};
+		assert!(client_state.check_for_misbehaviour::<MockedHostFunctions>(&forged).is_err());
+
+		let mut audit_log = AuditLog::<MockedHostFunctions>::new();
+		client_state.freeze(BlockHeight(5), Some(&mut audit_log)).unwrap();
+		assert_eq!(audit_log.entries()[0].event, AuditEvent::Frozen { height: BlockHeight(5) });
+		assert!(matches!(
+			client_state.verify_header::<MockedHostFunctions>(&header(&pair, 6, 10), 10),
+			Err(NearLiteClientError::ClientFrozen { height: BlockHeight(5) })
+		));
+	}
+
+	#[test]
+	fn test_expired_consensus_states_are_pruned() {
+		let pair = Pair::from_seed(&[1; 32]);
+		let mut client_state = client_state(&pair);
+		let mut consensus_states = ConsensusStates::default();
+		for (height, timestamp) in [(2, 10), (3, 50), (4, 120)] {
+			let header = header(&pair, height, timestamp);
+			client_state.verify_header::<MockedHostFunctions>(&header, timestamp).unwrap();
+			let consensus_state = client_state.update_state::<MockedHostFunctions>(header);
+			consensus_states.insert(BlockHeight(height), consensus_state);
+		}
+		assert_eq!(client_state.latest_height(), BlockHeight(4));
+
+		assert_eq!(consensus_states.prune(150, 100), [BlockHeight(2)]);
+		assert_eq!(consensus_states.len(), 2);
+		// the latest one is kept however old it is
+		assert_eq!(consensus_states.prune(1_000, 100), [BlockHeight(3)]);
+		let latest = *consensus_states.get(BlockHeight(4)).unwrap();
+		assert!(latest.is_expired(1_000, 100));
+
+		let outcome_proof = TypedOutcomeProof::ReceiptOutcome {
+			receiver_id: "relayer.near".into(),
+			proof: OutcomeProof {
+				proof: Vec::new(),
+				block_hash: latest.block_hash,
+				id: CryptoHash::default(),
+				outcome: ExecutionOutcomeView {
+					logs: Vec::new(),
+					receipt_ids: Vec::new(),
+					gas_burnt: 0,
+					tokens_burnt: 0,
+					executor_id: "bridge.near".into(),
+					status: Vec::new(),
+				},
+			},
+		};
+		let verify = |now| {
+			latest
+				.verify_outcome::<MockedHostFunctions>(
+					now,
+					100,
+					&outcome_proof,
+					Vec::<MerklePathItem>::new(),
+				)
+				.unwrap_err()
+				.code()
+		};
+		assert_eq!(verify(1_000), 115);
+		// the kind of the outcome is checked, not only its merkle paths
+		assert_eq!(verify(120), 129);
+	}
+
+	#[test]
+	fn test_block_producers_of_past_epochs_are_dropped() {
+		let pair = Pair::from_seed(&[1; 32]);
+		let epoch_ids = |client_state: &ClientState| {
+			client_state
+				.epoch_block_producers
+				.keys()
+				.map(|epoch_id| epoch_id.0 .0[0])
+				.collect::<Vec<_>>()
+		};
+		let mut checkpoint = LightClientBlockView::new_for_test();
+		checkpoint.next_bps = None;
+		assert!(ClientState::new(TrustedCheckpoint(checkpoint), 100)
+			.epoch_block_producers
+			.is_empty());
+
+		let mut client_state = client_state(&pair);
+		let retained: [(u8, &[u8]); 3] = [(1, &[1, 2]), (2, &[1, 2, 3]), (3, &[2, 3, 4])];
+		for (epoch, retained) in retained {
+			let height = epoch as u64 + 1;
+			let header = header_of_epoch(&pair, epoch, height, height * 10);
+			client_state.verify_header::<MockedHostFunctions>(&header, height * 10).unwrap();
+			client_state.update_state::<MockedHostFunctions>(header);
+			assert_eq!(epoch_ids(&client_state), retained);
+		}
+	}
+
+	#[test]
+	fn test_headers_ahead_of_the_host_are_rejected() {
+		let pair = Pair::from_seed(&[1; 32]);
+		let header = header(&pair, 2, 110);
+		let err = client_state(&pair)
+			.verify_header::<MockedHostFunctions>(&header, 100)
+			.unwrap_err();
+		assert!(matches!(
+			err,
+			NearLiteClientError::Verification(VerificationFailure::TimestampAhead {
+				timestamp: 110,
+				now: 100,
+			})
+		));
+
+		let tolerant = client_state(&pair).with_max_clock_drift(10);
+		assert!(tolerant.verify_header::<MockedHostFunctions>(&header, 100).is_ok());
+		assert!(tolerant.verify_header::<MockedHostFunctions>(&header, 99).is_err());
+	}
+}
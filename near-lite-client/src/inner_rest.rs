@@ -0,0 +1,246 @@
+//! # Inner rest
+//!
+//! Light client blocks only carry the hash of the `inner_rest` of their header, the part with the
+//! chunk mask, the gas price, the total supply and the challenges. A bridge that needs those
+//! fields gets the encoded `inner_rest` of a verified header from an RPC node and decodes it here,
+//! once its hash is checked against the `inner_rest_hash` the block hash commits to.
+//! [`validate_inner_rest`] optionally checks its fields for consistency on top of that.
+
+use near_primitives_wasm::{
+	Balance, BlockHeaderInnerRest, CryptoHash, HostFunctions, LightClientBlockView,
+};
+
+use crate::{
+	error::{deserialize, VerificationFailure},
+	LiteClientResult,
+};
+
+/// The layout of `inner_rest`, which follows the version of the block header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InnerRestVersion {
+	V1,
+	V2,
+	/// The layout of the headers produced by current protocol versions.
+	V3,
+}
+
+impl Default for InnerRestVersion {
+	fn default() -> Self {
+		Self::V3
+	}
+}
+
+/// Decodes the `inner_rest` of `block_view`, in the layout of `version`, after checking that it
+/// hashes to the `inner_rest_hash` of `block_view`.
+pub fn decode_inner_rest<H: HostFunctions>(
+	inner_rest: &[u8],
+	version: InnerRestVersion,
+	block_view: &LightClientBlockView,
+) -> LiteClientResult<BlockHeaderInnerRest> {
+	let computed = CryptoHash(H::sha256(inner_rest));
+	if computed != block_view.inner_rest_hash {
+		return Err(VerificationFailure::InnerRestHashMismatch {
+			expected: block_view.inner_rest_hash,
+			computed,
+		}
+		.into())
+	}
+	Ok(match version {
+		InnerRestVersion::V1 => BlockHeaderInnerRest::V1(deserialize(inner_rest)?),
+		InnerRestVersion::V2 => BlockHeaderInnerRest::V2(deserialize(inner_rest)?),
+		InnerRestVersion::V3 => BlockHeaderInnerRest::V3(deserialize(inner_rest)?),
+	})
+}
+
+/// What [`validate_inner_rest`] expects of an `inner_rest`, beyond its own consistency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InnerRestRules {
+	/// number of shards of the chain, the length of the chunk mask
+	pub num_shards: Option<usize>,
+	pub min_gas_price: Option<Balance>,
+	pub max_gas_price: Option<Balance>,
+}
+
+/// Checks that `inner_rest`, decoded from the header of `block_view`, is consistent and follows
+/// `rules`: in v1 `chunks_included` counts the new chunks of the chunk mask, in v3 the previous
+/// block is below `block_view`.
+pub fn validate_inner_rest(
+	inner_rest: &BlockHeaderInnerRest,
+	block_view: &LightClientBlockView,
+	rules: &InnerRestRules,
+) -> Result<(), VerificationFailure> {
+	let chunk_mask = inner_rest.chunk_mask();
+	match inner_rest {
+		BlockHeaderInnerRest::V1(inner) => {
+			let new_chunks = chunk_mask.iter().filter(|is_new| **is_new).count();
+			if inner.chunks_included != new_chunks as u64 {
+				return Err(VerificationFailure::ChunksIncludedMismatch {
+					chunks_included: inner.chunks_included,
+					new_chunks,
+				})
+			}
+		},
+		BlockHeaderInnerRest::V2(_) => {},
+		BlockHeaderInnerRest::V3(inner) =>
+			if inner.prev_height >= block_view.inner_lite.height {
+				return Err(VerificationFailure::PrevHeightNotBelow {
+					prev_height: inner.prev_height,
+					height: block_view.inner_lite.height,
+				})
+			},
+	}
+	if let Some(num_shards) = rules.num_shards {
+		if chunk_mask.len() != num_shards {
+			return Err(VerificationFailure::ChunkMaskLengthMismatch {
+				len: chunk_mask.len(),
+				num_shards,
+			})
+		}
+	}
+	let gas_price = inner_rest.gas_price();
+	if rules.min_gas_price.map_or(false, |min| gas_price < min) ||
+		rules.max_gas_price.map_or(false, |max| gas_price > max)
+	{
+		return Err(VerificationFailure::GasPriceOutOfRange {
+			gas_price,
+			min: rules.min_gas_price,
+			max: rules.max_gas_price,
+		})
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{error::serialize, test_utils::MockedHostFunctions, NearLiteClientError};
+	use near_primitives_wasm::{
+		BlockHeaderInnerRestV1, BlockHeaderInnerRestV3, BlockHeight, SlashedValidator,
+	};
+	use sp_std::vec::Vec;
+
+	fn inner_rest_v3() -> BlockHeaderInnerRestV3 {
+		BlockHeaderInnerRestV3 {
+			chunk_receipts_root: CryptoHash([1; 32]),
+			chunk_headers_root: CryptoHash([2; 32]),
+			chunk_tx_root: CryptoHash([3; 32]),
+			challenges_root: CryptoHash([4; 32]),
+			random_value: CryptoHash([5; 32]),
+			validator_proposals: Vec::new(),
+			chunk_mask: vec![true, false, true, true],
+			gas_price: 100_000_000,
+			block_ordinal: 7,
+			total_supply: 1_000_000,
+			challenges_result: vec![SlashedValidator {
+				account_id: "node0.near".into(),
+				is_double_sign: false,
+			}],
+			last_final_block: CryptoHash([8; 32]),
+			last_ds_final_block: CryptoHash([9; 32]),
+			prev_height: BlockHeight(0),
+			epoch_sync_data_hash: None,
+			approvals: vec![None],
+			latest_protocol_version: 57,
+		}
+	}
+
+	fn block_view(inner_rest: &[u8]) -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_rest_hash = CryptoHash(MockedHostFunctions::sha256(inner_rest));
+		block_view
+	}
+
+	#[test]
+	fn test_decode_inner_rest() {
+		let encoded = serialize(&inner_rest_v3()).unwrap();
+		let block_view = block_view(&encoded);
+		let inner_rest =
+			decode_inner_rest::<MockedHostFunctions>(&encoded, InnerRestVersion::V3, &block_view)
+				.unwrap();
+		assert_eq!(inner_rest.gas_price(), 100_000_000);
+		assert_eq!(inner_rest.total_supply(), 1_000_000);
+		assert_eq!(inner_rest.chunk_mask(), [true, false, true, true]);
+		assert_eq!(inner_rest.challenges_result().len(), 1);
+		assert_eq!(inner_rest.latest_protocol_version(), 57);
+
+		// the hash is checked before decoding
+		let mut other = encoded.clone();
+		other[0] = 0;
+		let err =
+			decode_inner_rest::<MockedHostFunctions>(&other, InnerRestVersion::V3, &block_view)
+				.unwrap_err();
+		assert_eq!(err.code(), 123);
+		// an older layout doesn't decode the same bytes
+		assert!(matches!(
+			decode_inner_rest::<MockedHostFunctions>(&encoded, InnerRestVersion::V1, &block_view),
+			Err(NearLiteClientError::Serialization { .. })
+		));
+	}
+
+	#[test]
+	fn test_validate_inner_rest() {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(1);
+		let inner_rest = BlockHeaderInnerRest::V3(inner_rest_v3());
+		let rules = InnerRestRules {
+			num_shards: Some(4),
+			min_gas_price: Some(100_000_000),
+			max_gas_price: None,
+		};
+		assert!(validate_inner_rest(&inner_rest, &block_view, &rules).is_ok());
+		assert!(validate_inner_rest(&inner_rest, &block_view, &InnerRestRules::default()).is_ok());
+
+		assert_eq!(
+			validate_inner_rest(
+				&inner_rest,
+				&block_view,
+				&InnerRestRules { num_shards: Some(2), ..rules }
+			),
+			Err(VerificationFailure::ChunkMaskLengthMismatch { len: 4, num_shards: 2 })
+		);
+		assert_eq!(
+			validate_inner_rest(
+				&inner_rest,
+				&block_view,
+				&InnerRestRules { max_gas_price: Some(1), ..rules }
+			),
+			Err(VerificationFailure::GasPriceOutOfRange {
+				gas_price: 100_000_000,
+				min: Some(100_000_000),
+				max: Some(1),
+			})
+		);
+
+		block_view.inner_lite.height = BlockHeight(0);
+		assert_eq!(
+			validate_inner_rest(&inner_rest, &block_view, &rules),
+			Err(VerificationFailure::PrevHeightNotBelow {
+				prev_height: BlockHeight(0),
+				height: BlockHeight(0),
+			})
+		);
+
+		let v3 = inner_rest_v3();
+		let v1 = BlockHeaderInnerRest::V1(BlockHeaderInnerRestV1 {
+			chunk_receipts_root: v3.chunk_receipts_root,
+			chunk_headers_root: v3.chunk_headers_root,
+			chunk_tx_root: v3.chunk_tx_root,
+			chunks_included: 4,
+			challenges_root: v3.challenges_root,
+			random_value: v3.random_value,
+			validator_proposals: Vec::new(),
+			chunk_mask: v3.chunk_mask,
+			gas_price: v3.gas_price,
+			total_supply: v3.total_supply,
+			challenges_result: v3.challenges_result,
+			last_final_block: v3.last_final_block,
+			last_ds_final_block: v3.last_ds_final_block,
+			approvals: v3.approvals,
+			latest_protocol_version: v3.latest_protocol_version,
+		});
+		assert_eq!(
+			validate_inner_rest(&v1, &block_view, &rules),
+			Err(VerificationFailure::ChunksIncludedMismatch { chunks_included: 4, new_chunks: 3 })
+		);
+	}
+}
@@ -0,0 +1,181 @@
+//! # Key-value backends
+//!
+//! [`KvStorage`](crate::KvStorage) lays the state of the light client out in any store of byte
+//! keys and values, so a new backend only implements [`KvBackend`]: reading, writing and deleting
+//! a key, and listing the entries under a prefix. Three of them ship with the crate: the memory of
+//! the process with [`InMemoryKv`], the storage of a Substrate runtime with [`SubstrateKv`] and,
+//! with the `sled` feature, a sled tree with [`SledKv`].
+
+#[cfg(feature = "sled")]
+use alloc::string::ToString;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::LiteClientResult;
+#[cfg(feature = "sled")]
+use crate::NearLiteClientError;
+
+pub trait KvBackend {
+	fn get(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>>;
+	fn put(&mut self, key: &[u8], value: &[u8]) -> LiteClientResult<()>;
+	fn delete(&mut self, key: &[u8]) -> LiteClientResult<()>;
+	/// Entries whose key starts with `prefix`, in the order of their keys.
+	fn iter_prefix(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>>;
+	/// Bytes used by the backend, only its own size unless it keeps its data in memory.
+	#[cfg(feature = "deepsize_feature")]
+	fn memory_usage(&self) -> usize {
+		core::mem::size_of_val(self)
+	}
+}
+
+/// Entries kept in memory, as long as the value itself.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryKv {
+	entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl KvBackend for InMemoryKv {
+	fn get(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>> {
+		Ok(self.entries.get(key).cloned())
+	}
+
+	fn put(&mut self, key: &[u8], value: &[u8]) -> LiteClientResult<()> {
+		self.entries.insert(key.to_vec(), value.to_vec());
+		Ok(())
+	}
+
+	fn delete(&mut self, key: &[u8]) -> LiteClientResult<()> {
+		self.entries.remove(key);
+		Ok(())
+	}
+
+	fn iter_prefix(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+		Ok(self
+			.entries
+			.range(prefix.to_vec()..)
+			.take_while(|(key, _)| key.starts_with(prefix))
+			.map(|(key, value)| (key.clone(), value.clone()))
+			.collect())
+	}
+
+	#[cfg(feature = "deepsize_feature")]
+	fn memory_usage(&self) -> usize {
+		deepsize::DeepSizeOf::deep_size_of(self)
+	}
+}
+
+/// The storage of the Substrate runtime it runs in, under the keys starting with `prefix`, e.g.
+/// the prefix of a pallet. Must be used within the externalities of a runtime.
+#[derive(Debug, Clone)]
+pub struct SubstrateKv {
+	prefix: Vec<u8>,
+}
+
+impl SubstrateKv {
+	pub fn new(prefix: impl Into<Vec<u8>>) -> Self {
+		Self { prefix: prefix.into() }
+	}
+
+	fn key(&self, key: &[u8]) -> Vec<u8> {
+		[&self.prefix[..], key].concat()
+	}
+}
+
+impl KvBackend for SubstrateKv {
+	fn get(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>> {
+		Ok(sp_io::storage::get(&self.key(key)))
+	}
+
+	fn put(&mut self, key: &[u8], value: &[u8]) -> LiteClientResult<()> {
+		sp_io::storage::set(&self.key(key), value);
+		Ok(())
+	}
+
+	fn delete(&mut self, key: &[u8]) -> LiteClientResult<()> {
+		sp_io::storage::clear(&self.key(key));
+		Ok(())
+	}
+
+	fn iter_prefix(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+		let prefix = self.key(prefix);
+		let mut entries = Vec::new();
+		let mut key = prefix.clone();
+		if let Some(value) = sp_io::storage::get(&key) {
+			entries.push((key[self.prefix.len()..].to_vec(), value));
+		}
+		while let Some(next) = sp_io::storage::next_key(&key) {
+			if !next.starts_with(&prefix) {
+				break
+			}
+			if let Some(value) = sp_io::storage::get(&next) {
+				entries.push((next[self.prefix.len()..].to_vec(), value));
+			}
+			key = next;
+		}
+		Ok(entries)
+	}
+}
+
+/// A sled tree, which persists every write on disk.
+#[cfg(feature = "sled")]
+#[derive(Debug, Clone)]
+pub struct SledKv {
+	tree: sled::Tree,
+}
+
+#[cfg(feature = "sled")]
+impl SledKv {
+	pub fn new(tree: sled::Tree) -> Self {
+		Self { tree }
+	}
+}
+
+#[cfg(feature = "sled")]
+impl KvBackend for SledKv {
+	fn get(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>> {
+		Ok(self.tree.get(key).map_err(sled_error)?.map(|value| value.to_vec()))
+	}
+
+	fn put(&mut self, key: &[u8], value: &[u8]) -> LiteClientResult<()> {
+		self.tree.insert(key, value).map_err(sled_error)?;
+		Ok(())
+	}
+
+	fn delete(&mut self, key: &[u8]) -> LiteClientResult<()> {
+		self.tree.remove(key).map_err(sled_error)?;
+		Ok(())
+	}
+
+	fn iter_prefix(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+		self.tree
+			.scan_prefix(prefix)
+			.map(|entry| {
+				let (key, value) = entry.map_err(sled_error)?;
+				Ok((key.to_vec(), value.to_vec()))
+			})
+			.collect()
+	}
+}
+
+#[cfg(feature = "sled")]
+fn sled_error(err: sled::Error) -> NearLiteClientError {
+	NearLiteClientError::Storage(err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_iter_prefix() {
+		let mut kv = InMemoryKv::default();
+		for key in [&b"a"[..], b"b/2", b"b/1", b"b", b"c"] {
+			kv.put(key, key).unwrap();
+		}
+		kv.delete(b"b/2").unwrap();
+		let keys: Vec<_> = kv.iter_prefix(b"b").unwrap().into_iter().map(|(key, _)| key).collect();
+		assert_eq!(keys, [&b"b"[..], b"b/1"]);
+		assert!(kv.iter_prefix(b"d").unwrap().is_empty());
+		assert_eq!(kv.get(b"c").unwrap().as_deref(), Some(&b"c"[..]));
+	}
+}
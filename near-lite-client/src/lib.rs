@@ -9,46 +9,169 @@
 //!
 //! ```ignore
 //! use near_lite_client::prelude::*;
-//! // call the Light Client constructuro with a `TrustedCheckpoint`
-//! let mut lite_client = LightClient::with_checkpoint(trusted_checkpoint);
+//! // call the Light Client constructuro with a `TrustedCheckpoint`; `H` defaults to
+//! // `Sha256HostFunctions`, and `DynHostFunctions` hashes with a `DynDigest` installed at runtime
+//! let mut lite_client = LightClient::<H>::with_checkpoint(trusted_checkpoint)?;
+//! // or configure it first
+//! let mut lite_client = LightClientBuilder::<H>::new()
+//! 	.checkpoint(trusted_checkpoint)
+//! 	.retained_epochs(4)
+//! 	.build()?;
 //!
 //! // there are two operations that can be performed:
-//! // `validate_head` & `validate_transaction`
+//! // `validate_and_update_head` & `validate_outcome`
 //!
-//! lite_client.validate_head(block_view);
-//! lite_client.validate_transaction(outcome_proof, outcome_root_proof, expected_block_outcome_root);
+//! lite_client.validate_and_update_head(block_view);
+//! let outcome = TypedOutcomeProof::new(outcome_id, outcome_proof)?;
+//! lite_client.validate_outcome(&outcome, outcome_root_proof, expected_block_outcome_root);
 //! ```
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
+#[cfg(feature = "async-storage")]
+mod async_storage;
+mod audit;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+mod block_merkle;
 mod block_validation;
+mod bps_delta;
+mod bounded;
+mod builder;
+mod candidate_pool;
 mod checkpoint;
+mod chunk;
+mod client;
+mod clock;
+#[cfg(feature = "config")]
+pub mod config;
+mod decode;
 mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod hash_cache;
+pub mod hashes;
+mod header_mmr;
+mod header_queue;
+mod inner_rest;
+pub mod ibc;
+mod kv;
 mod merkle_tree;
+pub mod metrics;
+#[cfg(feature = "scale")]
+mod on_chain;
+mod outcome;
+mod protocol;
+pub mod rainbow;
+#[cfg(feature = "replay")]
+pub mod replay;
+mod retention;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+mod sanity;
+pub mod schema;
+mod signature;
+#[cfg(feature = "solidity")]
+pub mod solidity;
+mod state;
+pub mod stateless;
+mod storage;
+mod submission;
 #[cfg(test)]
 pub mod test_utils;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
 mod verifier;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+pub mod weights;
+#[cfg(feature = "zk")]
+pub mod zk;
 
+#[cfg(feature = "async-storage")]
+pub use async_storage::{
+	load as load_async_storage, AsyncLightClientStorage, JournaledStorage, StorageChange,
+	StorageJournal,
+};
+pub use audit::{AuditEntry, AuditEvent, AuditLog};
+pub use block_merkle::BlockMerkleAccumulator;
+pub use block_validation::{
+	validate_block_producers_commitment, validate_epoch_derivation, validate_next_bps,
+	validate_next_epoch_id, validate_prev_block_hash, verify_approvals,
+	verify_approvals_with_threshold, verify_approvals_with_verifier, ApprovalStats,
+	ApprovalThreshold, BlockViewHashes, EpochLinkage,
+};
+pub use bounded::{BoundedApprovals, BoundedMerklePath};
+pub use builder::{LightClientBuilder, MIN_RETAINED_EPOCHS};
+pub use candidate_pool::{CandidatePool, DEFAULT_CANDIDATE_POOL_CAPACITY};
 pub use checkpoint::TrustedCheckpoint;
+pub use chunk::{verify_chunk_header, ChunkInclusionProof};
+pub use client::{LightClient, UpdateSummary, VerificationOutcome};
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, TimestampPolicy};
+pub use decode::DecodeLimits;
+pub use error::{MultiproofDefect, NearLiteClientError, NextBpsDefect, VerificationFailure};
+pub use hash_cache::DEFAULT_VALIDATED_CACHE_CAPACITY;
+pub use header_mmr::HeaderInclusionProof;
+pub use header_queue::{HeaderQueue, DEFAULT_HEADER_QUEUE_CAPACITY};
+pub use inner_rest::{decode_inner_rest, validate_inner_rest, InnerRestRules, InnerRestVersion};
+#[cfg(feature = "sled")]
+pub use kv::SledKv;
+pub use kv::{InMemoryKv, KvBackend, SubstrateKv};
+pub use merkle_tree::{
+	compute_root, compute_root_from_leaf_hashes, compute_root_from_path, merklize,
+	merklize_leaf_hashes, verify_multiproof, MerkleMultiProof, MultiProofItem, MultiProofSibling,
+};
 pub use near_primitives_wasm::{
-	CryptoHash, LightClientBlockView, MerklePath, OutcomeProof, Signature, ValidatorStakeView,
+	AccessKey, Account, BlockHeaderInnerRest, BlockHeight, ChunkHeaderView, CryptoHash, DynDigest,
+	DynHostFunctions, EpochId, HostFunctions, HostFunctionsDigest, LightClientBlockView,
+	MerklePath, Nonce, OutcomeProof, Sha256HostFunctions, Signature, ValidatorStakeView,
+};
+#[cfg(feature = "scale")]
+pub use on_chain::{
+	BoundedList, OnChainEpoch, OnChainHead, OnChainState, OnChainValidator, MAX_ACCOUNT_ID_LEN,
+	MAX_BLOCK_PRODUCERS,
+};
+pub use outcome::{OutcomeId, TypedOutcomeProof};
+pub use protocol::{
+	ApprovalScheme, BlockHashScheme, ProtocolFeatures, ProtocolSchedule, ProtocolTable,
+	ProtocolVersion,
+};
+pub use retention::RetentionPolicy;
+pub use sanity::SanityCheck;
+#[cfg(feature = "ed25519-dalek")]
+pub use signature::DalekVerifier;
+pub use signature::{HostFunctionsVerifier, SignatureVerifier, StrictVerifier};
+#[cfg(feature = "ed25519-zebra")]
+pub use signature::ZebraVerifier;
+pub use state::{verify_access_key, verify_account, verify_contract_state, StateProof};
+pub use storage::{ColumnStats, InMemoryStorage, KvStorage, LightClientStorage, StorageStats};
+pub use submission::{submission_id, Submission, SubmissionMemo, SubmissionOutcome};
+pub use verifier::{
+	calculate_execution_outcome_hash, validate_head, validate_transaction, validate_transactions,
+	verify_outcome, verify_outcome_non_inclusion, OutcomeLeaf, OutcomeNonInclusionProof,
+	OutcomeRoots,
 };
-pub use verifier::{validate_head, validate_transaction, validate_transactions};
-
-use crate::error::NearLiteClientError;
 
 pub type LiteClientResult<T> = Result<T, NearLiteClientError>;
 
 pub mod prelude {
 	pub use super::{
-		validate_head, validate_transaction, validate_transactions, CryptoHash,
-		LightClientBlockView, MerklePath, NearLiteClientTrait, OutcomeProof, Signature,
-		TrustedCheckpoint, ValidatorStakeView,
+		validate_head, validate_transaction, validate_transactions, CryptoHash, HostFunctions,
+		LightClient, LightClientBlockView, LightClientBuilder, MerklePath, NearLiteClientTrait,
+		OutcomeId, OutcomeProof, SanityCheck, Sha256HostFunctions, Signature, TrustedCheckpoint,
+		TypedOutcomeProof, ValidatorStakeView,
 	};
 }
 
 pub trait NearLiteClientTrait {
-	fn new_from_checkpoint(checkpoint: TrustedCheckpoint, heights_to_track: usize) -> Self;
+	fn new_from_checkpoint(
+		checkpoint: TrustedCheckpoint,
+		heights_to_track: usize,
+	) -> LiteClientResult<Self>
+	where
+		Self: Sized;
 	fn current_block_height(&self) -> u64;
 }
@@ -1,14 +1,19 @@
-use crate::LiteClientResult;
-use borsh::BorshSerialize;
-use near_primitives_wasm::{Direction, HostFunctions, MerkleHash, MerklePathItem};
-use sp_std::vec::Vec;
+use crate::{
+	error::{serialize, MultiproofDefect, NearLiteClientError, VerificationFailure},
+	LiteClientResult,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_primitives_wasm::{Direction, HostFunctions, MerkleHash, MerklePath, MerklePathItem};
+use sp_std::{vec, vec::Vec};
 
+/// Works on any contiguous path, either heap allocated (`MerklePath`) or stack allocated
+/// (`BoundedMerklePath`).
 pub fn compute_root_from_path<H: HostFunctions>(
-	path: &Vec<MerklePathItem>,
+	path: &(impl AsRef<[MerklePathItem]> + ?Sized),
 	item_hash: MerkleHash,
 ) -> LiteClientResult<MerkleHash> {
 	let mut res = item_hash;
-	for item in path {
+	for item in path.as_ref() {
 		match item.direction {
 			Direction::Left => {
 				res = combine_hash::<H>(&item.hash, &res)?;
@@ -25,5 +30,379 @@ pub fn combine_hash<H: HostFunctions>(
 	hash1: &MerkleHash,
 	hash2: &MerkleHash,
 ) -> LiteClientResult<MerkleHash> {
-	Ok(MerkleHash::try_from(H::sha256(&(hash1, hash2).try_to_vec()?).as_slice())?)
+	Ok(MerkleHash::try_from(H::sha256(&serialize(&(hash1, hash2))?).as_slice())?)
+}
+
+/// Builds the merkle tree of `leaves` the same way nearcore's `merklize` does: leaves are
+/// `sha256(borsh(leaf))` and the last node of a level with an odd number of nodes is moved up
+/// as is. Returns the root and the path of every leaf.
+pub fn merklize<H: HostFunctions, T: BorshSerialize>(
+	leaves: &[T],
+) -> LiteClientResult<(MerkleHash, Vec<MerklePath>)> {
+	merklize_leaf_hashes::<H>(&hash_leaves::<H, _>(leaves)?)
+}
+
+/// [`merklize`] for trees whose leaves are already hashes, see
+/// [`compute_root_from_leaf_hashes`].
+pub fn merklize_leaf_hashes<H: HostFunctions>(
+	leaf_hashes: &[MerkleHash],
+) -> LiteClientResult<(MerkleHash, Vec<MerklePath>)> {
+	let mut level = leaf_hashes.to_vec();
+	let mut paths = vec![MerklePath::new(); level.len()];
+	// index of the subtree of every leaf in the current level
+	let mut indices: Vec<usize> = (0..level.len()).collect();
+	while level.len() > 1 {
+		for (path, index) in paths.iter_mut().zip(indices.iter_mut()) {
+			let sibling = *index ^ 1;
+			if let Some(hash) = level.get(sibling) {
+				let direction = if sibling < *index { Direction::Left } else { Direction::Right };
+				path.push(MerklePathItem { hash: *hash, direction });
+			}
+			*index /= 2;
+		}
+		level = next_level::<H>(&level)?;
+	}
+	Ok((level.first().copied().unwrap_or_default(), paths))
+}
+
+/// Root of the merkle tree of `leaves`, without building the paths. See [`merklize`].
+pub fn compute_root<H: HostFunctions, T: BorshSerialize>(
+	leaves: &[T],
+) -> LiteClientResult<MerkleHash> {
+	compute_root_from_leaf_hashes::<H>(&hash_leaves::<H, _>(leaves)?)
+}
+
+/// Root of the tree whose leaves are `leaf_hashes` as they are, for trees whose leaves are already
+/// hashes (e.g. outcome hashes).
+pub fn compute_root_from_leaf_hashes<H: HostFunctions>(
+	leaf_hashes: &[MerkleHash],
+) -> LiteClientResult<MerkleHash> {
+	let mut level = leaf_hashes.to_vec();
+	while level.len() > 1 {
+		level = next_level::<H>(&level)?;
+	}
+	Ok(level.first().copied().unwrap_or_default())
+}
+
+fn hash_leaves<H: HostFunctions, T: BorshSerialize>(
+	leaves: &[T],
+) -> LiteClientResult<Vec<MerkleHash>> {
+	leaves
+		.iter()
+		.map(|leaf| Ok(MerkleHash::try_from(H::sha256(&serialize(leaf)?).as_slice())?))
+		.collect()
+}
+
+fn next_level<H: HostFunctions>(level: &[MerkleHash]) -> LiteClientResult<Vec<MerkleHash>> {
+	level
+		.chunks(2)
+		.filter_map(|pair| match pair {
+			[left, right] => Some(combine_hash::<H>(left, right)),
+			[single] => Some(Ok(*single)),
+			// `chunks(2)` yields neither empty nor longer chunks
+			_ => None,
+		})
+		.collect()
+}
+
+/// Where a multiproof sibling comes from.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum MultiProofSibling {
+	/// index in [`MerkleMultiProof::nodes`]
+	Node(u32),
+	/// hash of the subtree of `leaf` after `level` steps of its path (`level == 0` being the leaf
+	/// itself), which the verifier computes anyway
+	Leaf { leaf: u32, level: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MultiProofItem {
+	pub sibling: MultiProofSibling,
+	pub direction: Direction,
+}
+
+/// Proof of several leaves of the same tree. Unlike a list of independent `MerklePath`s, a hash
+/// that appears in several paths is only included once, and hashes that can be computed from
+/// another proven leaf are not included at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MerkleMultiProof {
+	pub nodes: Vec<MerkleHash>,
+	/// one path per leaf, in the order of the leaves
+	pub paths: Vec<Vec<MultiProofItem>>,
+}
+
+impl MerkleMultiProof {
+	/// Compresses the independent `paths` of `leaves` (`paths[i]` being the path of `leaves[i]`).
+	pub fn from_paths<H: HostFunctions>(
+		leaves: &[MerkleHash],
+		paths: &[impl AsRef<[MerklePathItem]>],
+	) -> LiteClientResult<Self> {
+		if leaves.len() != paths.len() {
+			return Err(multiproof_error(MultiproofDefect::LengthMismatch))
+		}
+		// intermediates[i][l]: hash of the subtree of leaf i after l steps
+		let mut intermediates = Vec::with_capacity(leaves.len());
+		for (leaf, path) in leaves.iter().zip(paths) {
+			let mut hashes = vec![*leaf];
+			for item in path.as_ref() {
+				let last = hashes[hashes.len() - 1];
+				hashes.push(combine_in_direction::<H>(&item.hash, &last, &item.direction)?);
+			}
+			intermediates.push(hashes);
+		}
+
+		// a sibling may only refer to a hash the verifier will have computed without needing the
+		// referring leaf, so references are only handed out to hashes that are already resolved
+		let mut proof = Self { nodes: Vec::new(), paths: vec![Vec::new(); leaves.len()] };
+		let mut levels = vec![0usize; leaves.len()];
+		let resolve = |levels: &[usize], leaf: usize, hash: &MerkleHash| {
+			(0..levels.len()).filter(|other| *other != leaf).find_map(|other| {
+				(0..=levels[other]).find(|level| intermediates[other][*level] == *hash).map(
+					|level| MultiProofSibling::Leaf { leaf: other as u32, level: level as u32 },
+				)
+			})
+		};
+		loop {
+			let mut progress = false;
+			for leaf in 0..leaves.len() {
+				while let Some(item) = paths[leaf].as_ref().get(levels[leaf]) {
+					match resolve(&levels, leaf, &item.hash) {
+						Some(sibling) => {
+							proof.push(leaf, sibling, &item.direction);
+							levels[leaf] += 1;
+							progress = true;
+						},
+						None => break,
+					}
+				}
+			}
+			if progress {
+				continue
+			}
+			// nothing can be computed, so a missing hash has to be given; the lowest one first, as
+			// it is the most likely to let other leaves make progress
+			let leaf = match (0..leaves.len())
+				.filter(|leaf| levels[*leaf] < paths[*leaf].as_ref().len())
+				.min_by_key(|leaf| levels[*leaf])
+			{
+				Some(leaf) => leaf,
+				None => return Ok(proof),
+			};
+			let item = &paths[leaf].as_ref()[levels[leaf]];
+			let node = match proof.nodes.iter().position(|node| *node == item.hash) {
+				Some(node) => node,
+				None => {
+					proof.nodes.push(item.hash);
+					proof.nodes.len() - 1
+				},
+			};
+			proof.push(leaf, MultiProofSibling::Node(node as u32), &item.direction);
+			levels[leaf] += 1;
+		}
+	}
+
+	fn push(&mut self, leaf: usize, sibling: MultiProofSibling, direction: &Direction) {
+		self.paths[leaf].push(MultiProofItem { sibling, direction: direction.clone() });
+	}
+}
+
+/// Computes the root of the tree `proof` was built from, checking that every leaf leads to it.
+pub fn verify_multiproof<H: HostFunctions>(
+	proof: &MerkleMultiProof,
+	leaves: &[MerkleHash],
+) -> LiteClientResult<MerkleHash> {
+	if leaves.is_empty() || leaves.len() != proof.paths.len() {
+		return Err(multiproof_error(MultiproofDefect::LengthMismatch))
+	}
+	let mut intermediates: Vec<Vec<MerkleHash>> = leaves.iter().map(|leaf| vec![*leaf]).collect();
+	// every pass resolves at least one hash, otherwise the references are circular
+	loop {
+		let mut progress = false;
+		for leaf in 0..leaves.len() {
+			while let Some(item) = proof.paths[leaf].get(intermediates[leaf].len() - 1) {
+				let sibling = match item.sibling {
+					MultiProofSibling::Node(node) => *proof
+						.nodes
+						.get(node as usize)
+						.ok_or_else(|| multiproof_error(MultiproofDefect::NodeOutOfBounds))?,
+					MultiProofSibling::Leaf { leaf: other, level } => {
+						let other = proof
+							.paths
+							.get(other as usize)
+							.map(|_| other as usize)
+							.filter(|other| *other != leaf)
+							.ok_or_else(|| {
+								multiproof_error(MultiproofDefect::InvalidLeafReference)
+							})?;
+						if level as usize > proof.paths[other].len() {
+							return Err(multiproof_error(MultiproofDefect::InvalidLevelReference))
+						}
+						match intermediates[other].get(level as usize) {
+							Some(hash) => *hash,
+							None => break,
+						}
+					},
+				};
+				let last = intermediates[leaf][intermediates[leaf].len() - 1];
+				intermediates[leaf].push(combine_in_direction::<H>(
+					&sibling,
+					&last,
+					&item.direction,
+				)?);
+				progress = true;
+			}
+		}
+		if !progress {
+			break
+		}
+	}
+
+	let mut roots = intermediates
+		.iter()
+		.zip(&proof.paths)
+		.map(|(hashes, path)| (hashes.len() == path.len() + 1).then(|| hashes[hashes.len() - 1]));
+	let root = roots
+		.next()
+		.flatten()
+		.ok_or_else(|| multiproof_error(MultiproofDefect::Unresolvable))?;
+	match roots.find(|other| *other != Some(root)) {
+		None => Ok(root),
+		Some(None) => Err(multiproof_error(MultiproofDefect::Unresolvable)),
+		Some(Some(computed)) =>
+			Err(NearLiteClientError::MerkleRootMismatch { expected: root, computed }),
+	}
+}
+
+fn combine_in_direction<H: HostFunctions>(
+	sibling: &MerkleHash,
+	hash: &MerkleHash,
+	direction: &Direction,
+) -> LiteClientResult<MerkleHash> {
+	match direction {
+		Direction::Left => combine_hash::<H>(sibling, hash),
+		Direction::Right => combine_hash::<H>(hash, sibling),
+	}
+}
+
+fn multiproof_error(defect: MultiproofDefect) -> NearLiteClientError {
+	VerificationFailure::MalformedMultiproof { defect }.into()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+	use near_primitives::merkle::merklize as near_merklize;
+	use near_primitives_wasm::CryptoHash;
+
+	fn tree(len: u64) -> (MerkleHash, Vec<MerkleHash>, Vec<MerklePath>) {
+		let items: Vec<u64> = (0..len).collect();
+		let (root, paths) = near_merklize(&items);
+		(
+			root.into(),
+			items.iter().map(CryptoHash::hash_borsh).collect(),
+			paths
+				.into_iter()
+				.map(|path| path.into_iter().map(MerklePathItem::from).collect())
+				.collect(),
+		)
+	}
+
+	fn select<T: Clone>(items: &[T], indices: &[usize]) -> Vec<T> {
+		indices.iter().map(|index| items[*index].clone()).collect()
+	}
+
+	#[test]
+	fn test_multiproof_reconstructs_the_root() {
+		for (len, indices) in
+			[(1, vec![0]), (8, vec![0, 1, 2, 3]), (8, vec![6, 1]), (13, vec![0, 5, 12, 7, 6])]
+		{
+			let (root, leaves, paths) = tree(len);
+			let leaves = select(&leaves, &indices);
+			let paths = select(&paths, &indices);
+			let proof =
+				MerkleMultiProof::from_paths::<MockedHostFunctions>(&leaves, &paths).unwrap();
+
+			assert_eq!(verify_multiproof::<MockedHostFunctions>(&proof, &leaves).unwrap(), root);
+			for (leaf, path) in leaves.iter().zip(&paths) {
+				assert_eq!(
+					compute_root_from_path::<MockedHostFunctions>(path, *leaf).unwrap(),
+					root
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_multiproof_shares_nodes() {
+		let (_, leaves, paths) = tree(8);
+		let proof = MerkleMultiProof::from_paths::<MockedHostFunctions>(&leaves, &paths).unwrap();
+		// every sibling of a complete set of leaves can be computed
+		assert!(proof.nodes.is_empty());
+		assert!(
+			proof.try_to_vec().unwrap().len() < paths.try_to_vec().unwrap().len(),
+			"the multiproof must be smaller than the independent paths"
+		);
+
+		let (_, leaves, paths) = tree(16);
+		let indices = [0, 3];
+		let proof = MerkleMultiProof::from_paths::<MockedHostFunctions>(
+			&select(&leaves, &indices),
+			&select(&paths, &indices),
+		)
+		.unwrap();
+		// the sibling of the common ancestor of both leaves is only given once
+		assert_eq!(proof.nodes.len(), 4);
+	}
+
+	#[test]
+	fn test_multiproof_rejects_tampering() {
+		let (root, leaves, paths) = tree(8);
+		let indices = [1, 2, 5];
+		let mut leaves = select(&leaves, &indices);
+		let proof =
+			MerkleMultiProof::from_paths::<MockedHostFunctions>(&leaves, &select(&paths, &indices))
+				.unwrap();
+
+		leaves[2] = CryptoHash([7; 32]);
+		assert!(verify_multiproof::<MockedHostFunctions>(&proof, &leaves)
+			.map_or(true, |computed| computed != root));
+
+		let mut circular = proof.clone();
+		circular.paths[0][0].sibling = MultiProofSibling::Leaf { leaf: 1, level: 1 };
+		circular.paths[1][0].sibling = MultiProofSibling::Leaf { leaf: 0, level: 1 };
+		assert!(matches!(
+			verify_multiproof::<MockedHostFunctions>(&circular, &leaves),
+			Err(NearLiteClientError::Verification(VerificationFailure::MalformedMultiproof {
+				defect: MultiproofDefect::Unresolvable
+			}))
+		));
+	}
+
+	#[test]
+	fn test_merklize_matches_nearcore() {
+		for len in 1..20 {
+			let items: Vec<u64> = (0..len).collect();
+			let (root, paths) = merklize::<MockedHostFunctions, _>(&items).unwrap();
+			let (near_root, _, near_paths) = tree(len);
+
+			assert_eq!(root, near_root);
+			assert_eq!(paths, near_paths);
+			assert_eq!(compute_root::<MockedHostFunctions, _>(&items).unwrap(), root);
+			for (item, path) in items.iter().zip(&paths) {
+				let leaf = CryptoHash::hash_borsh(item);
+				assert_eq!(
+					compute_root_from_path::<MockedHostFunctions>(path, leaf).unwrap(),
+					root
+				);
+			}
+		}
+
+		let empty: [u64; 0] = [];
+		assert_eq!(
+			merklize::<MockedHostFunctions, _>(&empty).unwrap(),
+			(MerkleHash::default(), vec![])
+		);
+		assert_eq!(compute_root::<MockedHostFunctions, _>(&empty).unwrap(), MerkleHash::default());
+	}
 }
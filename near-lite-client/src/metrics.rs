@@ -0,0 +1,46 @@
+//! # Metrics
+//!
+//! Facade over the [`metrics`](https://docs.rs/metrics) crate. Without the `metrics` feature
+//! every function here is a no-op, so the client can call them unconditionally, `no_std`
+//! included. With it, the values go to whichever recorder is installed, e.g. the Prometheus
+//! exporter of the relayer.
+
+pub const HEADS_ACCEPTED: &str = "near_lite_client_heads_accepted_total";
+pub const HEADS_REJECTED: &str = "near_lite_client_heads_rejected_total";
+pub const SIGNATURE_VERIFICATION_SECONDS: &str = "near_lite_client_signature_verification_seconds";
+pub const HEAD_LAG_BLOCKS: &str = "near_lite_client_head_lag_blocks";
+pub const RPC_ERRORS: &str = "near_lite_client_rpc_errors_total";
+
+pub fn head_accepted() {
+	#[cfg(feature = "metrics")]
+	::metrics::increment_counter!(HEADS_ACCEPTED);
+}
+
+pub fn head_rejected() {
+	#[cfg(feature = "metrics")]
+	::metrics::increment_counter!(HEADS_REJECTED);
+}
+
+/// Runs `verify`, recording how long it took.
+pub fn time_signature_verification<T>(verify: impl FnOnce() -> T) -> T {
+	#[cfg(feature = "metrics")]
+	let start = std::time::Instant::now();
+	let result = verify();
+	#[cfg(feature = "metrics")]
+	::metrics::histogram!(SIGNATURE_VERIFICATION_SECONDS, start.elapsed());
+	result
+}
+
+/// How many blocks the head of the client is behind the tip of NEAR.
+pub fn head_lag(_blocks: u64) {
+	#[cfg(feature = "metrics")]
+	::metrics::gauge!(HEAD_LAG_BLOCKS, _blocks as f64);
+}
+
+pub fn rpc_error(_endpoint: &str) {
+	#[cfg(feature = "metrics")]
+	{
+		use std::string::ToString;
+		::metrics::increment_counter!(RPC_ERRORS, "endpoint" => _endpoint.to_string());
+	}
+}
@@ -0,0 +1,340 @@
+//! # On chain state
+//!
+//! [`OnChainState`] is what a runtime needs to keep validating headers, in one storage item of
+//! bounded size: the head without its approvals, and the block producers of its epoch and of the
+//! next one, keyed by their epoch ids. Its SCALE encoding never exceeds
+//! [`MaxEncodedLen::max_encoded_len`], so it fits in a storage item with a known maximum size.
+//!
+//! ```ignore
+//! let state = OnChainState::<MAX_BLOCK_PRODUCERS>::from_client(&client)?;
+//! // and back
+//! let client = LightClientBuilder::new().storage(state.into_storage()?).build()?;
+//! ```
+
+use alloc::{format, string::String};
+use codec::{Compact, Decode, Encode, EncodeLike, Input, MaxEncodedLen, Output};
+use near_primitives_wasm::{
+	Balance, BlockHeaderInnerLiteView, BlockHeight, CryptoHash, EpochId, HostFunctions,
+	LightClientBlockView, PublicKey, ValidatorStakeView, ValidatorStakeViewV1,
+	ValidatorStakeViewV2,
+};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+use crate::{
+	storage::{InMemoryStorage, LightClientStorage},
+	LightClient, LiteClientResult, NearLiteClientError,
+};
+
+/// Block producer seats of a NEAR epoch.
+pub const MAX_BLOCK_PRODUCERS: usize = 100;
+
+/// Longest account id NEAR accepts.
+pub const MAX_ACCOUNT_ID_LEN: usize = 64;
+
+/// A `Vec` of at most `N` items, SCALE encoded like the `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedList<T, const N: usize>(Vec<T>);
+
+impl<T, const N: usize> BoundedList<T, N> {
+	pub fn as_slice(&self) -> &[T] {
+		&self.0
+	}
+
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for BoundedList<T, N> {
+	type Error = NearLiteClientError;
+	fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+		if items.len() > N {
+			return Err(NearLiteClientError::CapacityExceeded(format!(
+				"{} items, at most {} fit in the on chain state",
+				items.len(),
+				N
+			)))
+		}
+		Ok(Self(items))
+	}
+}
+
+impl<T: Encode, const N: usize> Encode for BoundedList<T, N> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+}
+
+impl<T: Encode, const N: usize> EncodeLike for BoundedList<T, N> {}
+
+impl<T: Decode, const N: usize> Decode for BoundedList<T, N> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let len = <Compact<u32>>::decode(input)?.0 as usize;
+		if len > N {
+			return Err("more items than the bound of the list".into())
+		}
+		let mut items = Vec::with_capacity(len);
+		for _ in 0..len {
+			items.push(T::decode(input)?);
+		}
+		Ok(Self(items))
+	}
+}
+
+impl<T: MaxEncodedLen, const N: usize> MaxEncodedLen for BoundedList<T, N> {
+	fn max_encoded_len() -> usize {
+		Compact(N as u32)
+			.encoded_size()
+			.saturating_add(N.saturating_mul(T::max_encoded_len()))
+	}
+}
+
+/// Described as the `Vec` it is encoded like.
+impl<T: TypeInfo + 'static, const N: usize> TypeInfo for BoundedList<T, N> {
+	type Identity = Vec<T>;
+	fn type_info() -> scale_info::Type {
+		Vec::<T>::type_info()
+	}
+}
+
+/// The head without its approvals and the block producers it announces, which the state keeps
+/// apart. Its hash is the one of the full head.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct OnChainHead {
+	pub prev_block_hash: CryptoHash,
+	pub next_block_inner_hash: CryptoHash,
+	pub height: BlockHeight,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
+	pub prev_state_root: CryptoHash,
+	pub outcome_root: CryptoHash,
+	pub timestamp: u64,
+	pub timestamp_nanosec: u64,
+	pub next_bp_hash: CryptoHash,
+	pub block_merkle_root: CryptoHash,
+	pub inner_rest_hash: CryptoHash,
+}
+
+impl From<&LightClientBlockView> for OnChainHead {
+	fn from(head: &LightClientBlockView) -> Self {
+		let inner_lite = &head.inner_lite;
+		Self {
+			prev_block_hash: head.prev_block_hash,
+			next_block_inner_hash: head.next_block_inner_hash,
+			height: inner_lite.height,
+			epoch_id: inner_lite.epoch_id,
+			next_epoch_id: inner_lite.next_epoch_id,
+			prev_state_root: inner_lite.prev_state_root,
+			outcome_root: inner_lite.outcome_root,
+			timestamp: inner_lite.timestamp,
+			timestamp_nanosec: inner_lite.timestamp_nanosec,
+			next_bp_hash: inner_lite.next_bp_hash,
+			block_merkle_root: inner_lite.block_merkle_root,
+			inner_rest_hash: head.inner_rest_hash,
+		}
+	}
+}
+
+impl From<OnChainHead> for LightClientBlockView {
+	fn from(head: OnChainHead) -> Self {
+		Self {
+			prev_block_hash: head.prev_block_hash,
+			next_block_inner_hash: head.next_block_inner_hash,
+			inner_lite: BlockHeaderInnerLiteView {
+				height: head.height,
+				epoch_id: head.epoch_id,
+				next_epoch_id: head.next_epoch_id,
+				prev_state_root: head.prev_state_root,
+				outcome_root: head.outcome_root,
+				timestamp: head.timestamp,
+				timestamp_nanosec: head.timestamp_nanosec,
+				next_bp_hash: head.next_bp_hash,
+				block_merkle_root: head.block_merkle_root,
+			},
+			inner_rest_hash: head.inner_rest_hash,
+			next_bps: None,
+			approvals_after_next: Vec::new(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct OnChainValidator {
+	/// utf-8 bytes of the account id
+	pub account_id: BoundedList<u8, MAX_ACCOUNT_ID_LEN>,
+	pub public_key: [u8; 32],
+	pub stake: Balance,
+	/// `None` in the V1 layout, which predates chunk-only producers
+	pub is_chunk_only: Option<bool>,
+}
+
+impl TryFrom<&ValidatorStakeView> for OnChainValidator {
+	type Error = NearLiteClientError;
+	fn try_from(validator: &ValidatorStakeView) -> Result<Self, Self::Error> {
+		let (account_id, public_key, stake, is_chunk_only) = match validator {
+			ValidatorStakeView::V1(v1) => (&v1.account_id, &v1.public_key, v1.stake, None),
+			ValidatorStakeView::V2(v2) =>
+				(&v2.account_id, &v2.public_key, v2.stake, Some(v2.is_chunk_only)),
+		};
+		Ok(Self {
+			account_id: account_id.as_bytes().to_vec().try_into()?,
+			public_key: public_key.0,
+			stake,
+			is_chunk_only,
+		})
+	}
+}
+
+impl TryFrom<OnChainValidator> for ValidatorStakeView {
+	type Error = NearLiteClientError;
+	fn try_from(validator: OnChainValidator) -> Result<Self, Self::Error> {
+		let account_id = String::from_utf8(validator.account_id.into_inner()).map_err(|err| {
+			NearLiteClientError::Serialization {
+				type_name: "OnChainValidator",
+				reason: format!("account id is not utf-8: {}", err),
+			}
+		})?;
+		let public_key = PublicKey(validator.public_key);
+		let stake = validator.stake;
+		Ok(match validator.is_chunk_only {
+			None => Self::V1(ValidatorStakeViewV1 { account_id, public_key, stake }),
+			Some(is_chunk_only) =>
+				Self::V2(ValidatorStakeViewV2 { account_id, public_key, stake, is_chunk_only }),
+		})
+	}
+}
+
+/// Block producers of the epoch `epoch_id`, in their original order.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct OnChainEpoch<const N: usize> {
+	pub epoch_id: EpochId,
+	pub block_producers: BoundedList<OnChainValidator, N>,
+}
+
+impl<const N: usize> OnChainEpoch<N> {
+	pub fn new(
+		epoch_id: EpochId,
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<Self> {
+		let block_producers = block_producers
+			.iter()
+			.map(OnChainValidator::try_from)
+			.collect::<LiteClientResult<Vec<_>>>()?;
+		Ok(Self { epoch_id, block_producers: block_producers.try_into()? })
+	}
+
+	pub fn block_producers(self) -> LiteClientResult<Vec<ValidatorStakeView>> {
+		self.block_producers
+			.into_inner()
+			.into_iter()
+			.map(ValidatorStakeView::try_from)
+			.collect()
+	}
+}
+
+/// State of a client with at most `N` block producers per epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct OnChainState<const N: usize = MAX_BLOCK_PRODUCERS> {
+	pub head: OnChainHead,
+	/// block producers of the epoch of the head, unknown to a client started from a checkpoint
+	/// until the next epoch
+	pub current: Option<OnChainEpoch<N>>,
+	/// block producers of the next epoch, announced by the first block of the epoch of the head
+	pub next: Option<OnChainEpoch<N>>,
+}
+
+impl<const N: usize> OnChainState<N> {
+	/// Fails if the block producers of an epoch don't fit in the state.
+	pub fn from_client<H: HostFunctions>(client: &LightClient<H>) -> LiteClientResult<Self> {
+		let epoch = |epoch_id: EpochId| {
+			client
+				.epoch_block_producers(&epoch_id)
+				.map(|block_producers| OnChainEpoch::new(epoch_id, block_producers))
+				.transpose()
+		};
+		let inner_lite = &client.head.inner_lite;
+		Ok(Self {
+			head: OnChainHead::from(&client.head),
+			current: epoch(inner_lite.epoch_id)?,
+			next: epoch(inner_lite.next_epoch_id)?,
+		})
+	}
+
+	/// The head and the block producers in a storage, to build a client from.
+	pub fn into_storage(self) -> LiteClientResult<InMemoryStorage> {
+		let mut storage = InMemoryStorage::default();
+		storage.set_head(&self.head.into())?;
+		for epoch in [self.current, self.next].into_iter().flatten() {
+			let epoch_id = epoch.epoch_id;
+			storage.insert_epoch_block_producers(epoch_id, &epoch.block_producers()?)?;
+		}
+		Ok(storage)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{test_utils::MockedHostFunctions, LightClientBuilder, TrustedCheckpoint};
+	use borsh::BorshSerialize;
+
+	fn validator(index: u8) -> ValidatorStakeView {
+		let account_id = format!("validator-{}.near", index);
+		let public_key = PublicKey([index; 32]);
+		match index % 2 {
+			0 => ValidatorStakeView::V1(ValidatorStakeViewV1 { account_id, public_key, stake: 10 }),
+			_ => ValidatorStakeView::V2(ValidatorStakeViewV2 {
+				account_id,
+				public_key,
+				stake: 10,
+				is_chunk_only: false,
+			}),
+		}
+	}
+
+	fn client(block_producers: u8) -> LightClient<MockedHostFunctions> {
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_lite.next_epoch_id = EpochId(CryptoHash([1; 32]));
+		head.next_bps = Some((0..block_producers).map(validator).collect());
+		head.approvals_after_next = vec![None; 3];
+		LightClientBuilder::new().checkpoint(TrustedCheckpoint(head)).build().unwrap()
+	}
+
+	#[test]
+	fn test_round_trip_through_the_encoding() {
+		let client = client(5);
+		let state = OnChainState::<8>::from_client(&client).unwrap();
+		assert!(state.current.is_none());
+		let encoded = state.encode();
+		assert!(encoded.len() <= OnChainState::<8>::max_encoded_len());
+		let decoded = OnChainState::<8>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, state);
+
+		let resumed = LightClientBuilder::<MockedHostFunctions>::new()
+			.storage(decoded.into_storage().unwrap())
+			.build()
+			.unwrap();
+		assert_eq!(resumed.head_hash(), client.head_hash());
+		let next_epoch_id = client.next_epoch_id();
+		assert_eq!(
+			resumed.epoch_block_producers(&next_epoch_id).unwrap().try_to_vec().unwrap(),
+			client.epoch_block_producers(&next_epoch_id).unwrap().try_to_vec().unwrap()
+		);
+	}
+
+	#[test]
+	fn test_block_producers_beyond_the_bound() {
+		let client = client(5);
+		assert!(matches!(
+			OnChainState::<4>::from_client(&client),
+			Err(NearLiteClientError::CapacityExceeded(_))
+		));
+		let encoded = OnChainState::<8>::from_client(&client).unwrap().encode();
+		assert!(OnChainState::<4>::decode(&mut &encoded[..]).is_err());
+	}
+}
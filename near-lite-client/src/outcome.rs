@@ -0,0 +1,181 @@
+//! # Transaction and receipt outcomes
+//!
+//! An [`OutcomeProof`] proves either the outcome of a transaction, executed in the shard of its
+//! signer where it is converted into a receipt, or the outcome of a receipt, executed in the shard
+//! of its receiver. Both are hashed and proved alike, so nothing in the proof itself tells which
+//! one it is. A [`TypedOutcomeProof`] is bound to the [`OutcomeId`] it was requested for, and its
+//! verification checks what that kind of outcome must look like on top of the merkle paths.
+
+use near_primitives_wasm::{AccountId, CryptoHash, HostFunctions, MerklePathItem, OutcomeProof};
+
+use crate::{
+	error::VerificationFailure,
+	verifier::{verify_outcome, OutcomeRoots},
+	LiteClientResult,
+};
+
+/// The transaction or receipt whose outcome is proved, as `EXPERIMENTAL_light_client_proof` is
+/// asked for it.
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutcomeId {
+	/// the transaction `transaction_hash`, signed by `sender_id`
+	Transaction { transaction_hash: CryptoHash, sender_id: AccountId },
+	/// the receipt `receipt_id`, executed by `receiver_id`
+	Receipt { receipt_id: CryptoHash, receiver_id: AccountId },
+}
+
+impl OutcomeId {
+	/// Hash of the transaction or id of the receipt, the `id` of its outcome.
+	pub fn hash(&self) -> CryptoHash {
+		match self {
+			Self::Transaction { transaction_hash, .. } => *transaction_hash,
+			Self::Receipt { receipt_id, .. } => *receipt_id,
+		}
+	}
+
+	/// The account the outcome is executed by.
+	pub fn executor_id(&self) -> &AccountId {
+		match self {
+			Self::Transaction { sender_id, .. } => sender_id,
+			Self::Receipt { receiver_id, .. } => receiver_id,
+		}
+	}
+}
+
+/// An [`OutcomeProof`] bound to the kind of outcome it proves and to the account that must have
+/// executed it. Built by [`TypedOutcomeProof::new`], which checks that the proof is for the
+/// expected transaction or receipt, so that [`TypedOutcomeProof::verify`] only has the outcome
+/// and the merkle paths left to check.
+#[derive(Debug, Clone)]
+pub enum TypedOutcomeProof {
+	/// The outcome of a transaction: its conversion into a receipt, in the shard of `sender_id`.
+	TransactionOutcome { sender_id: AccountId, proof: OutcomeProof },
+	/// The outcome of a receipt, in the shard of `receiver_id`.
+	ReceiptOutcome { receiver_id: AccountId, proof: OutcomeProof },
+}
+
+impl TypedOutcomeProof {
+	/// Binds `proof` to `id`, failing if it proves the outcome of another transaction or receipt.
+	pub fn new(id: OutcomeId, proof: OutcomeProof) -> LiteClientResult<Self> {
+		if proof.id != id.hash() {
+			return Err(
+				VerificationFailure::OutcomeIdMismatch { expected: id.hash(), id: proof.id }.into()
+			)
+		}
+		Ok(match id {
+			OutcomeId::Transaction { sender_id, .. } =>
+				Self::TransactionOutcome { sender_id, proof },
+			OutcomeId::Receipt { receiver_id, .. } => Self::ReceiptOutcome { receiver_id, proof },
+		})
+	}
+
+	/// The transaction or receipt the proof was bound to by [`TypedOutcomeProof::new`].
+	pub fn id(&self) -> OutcomeId {
+		match self {
+			Self::TransactionOutcome { sender_id, proof } =>
+				OutcomeId::Transaction { transaction_hash: proof.id, sender_id: sender_id.clone() },
+			Self::ReceiptOutcome { receiver_id, proof } =>
+				OutcomeId::Receipt { receipt_id: proof.id, receiver_id: receiver_id.clone() },
+		}
+	}
+
+	/// The proof itself, whatever the kind of its outcome.
+	pub fn outcome_proof(&self) -> &OutcomeProof {
+		match self {
+			Self::TransactionOutcome { proof, .. } | Self::ReceiptOutcome { proof, .. } => proof,
+		}
+	}
+
+	/// Checks that the outcome was executed by the signer of the transaction, which it converted
+	/// into exactly one receipt, or by the receiver of the receipt, then
+	/// [`verify_outcome`](crate::verify_outcome) against `expected_block_outcome_root`.
+	pub fn verify<H: HostFunctions>(
+		&self,
+		outcome_root_proof: impl AsRef<[MerklePathItem]>,
+		expected_block_outcome_root: CryptoHash,
+	) -> LiteClientResult<OutcomeRoots> {
+		let (expected, proof) = match self {
+			Self::TransactionOutcome { sender_id, proof } => {
+				let receipts = proof.outcome.receipt_ids.len();
+				if receipts != 1 {
+					return Err(VerificationFailure::TransactionNotConverted { receipts }.into())
+				}
+				(sender_id, proof)
+			},
+			Self::ReceiptOutcome { receiver_id, proof } => (receiver_id, proof),
+		};
+		if proof.outcome.executor_id != *expected {
+			return Err(VerificationFailure::ExecutorMismatch {
+				expected: expected.clone(),
+				executor_id: proof.outcome.executor_id.clone(),
+			}
+			.into())
+		}
+		verify_outcome::<H>(proof, outcome_root_proof, expected_block_outcome_root)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		error::serialize, test_utils::MockedHostFunctions,
+		verifier::calculate_execution_outcome_hash, NearLiteClientError,
+	};
+	use near_primitives_wasm::ExecutionOutcomeView;
+	use sp_std::{vec, vec::Vec};
+
+	/// Proof of an outcome of `executor_id` alone in its shard and block, with its outcome root.
+	fn proof(id: CryptoHash, executor_id: &str, receipts: usize) -> (OutcomeProof, CryptoHash) {
+		let outcome = ExecutionOutcomeView {
+			logs: vec![],
+			receipt_ids: (0..receipts as u8).map(|index| CryptoHash([index; 32])).collect(),
+			gas_burnt: 1,
+			tokens_burnt: 2,
+			executor_id: executor_id.into(),
+			status: vec![0],
+		};
+		let shard_outcome_root =
+			calculate_execution_outcome_hash::<MockedHostFunctions>(&outcome, id).unwrap();
+		let block_outcome_root =
+			CryptoHash(MockedHostFunctions::sha256(&serialize(&shard_outcome_root).unwrap()));
+		(
+			OutcomeProof { proof: vec![], block_hash: CryptoHash::default(), id, outcome },
+			block_outcome_root,
+		)
+	}
+
+	fn code(result: LiteClientResult<impl core::fmt::Debug>) -> u16 {
+		result.expect_err("the proof was accepted").code()
+	}
+
+	#[test]
+	fn test_outcomes_are_verified_by_kind() {
+		let hash = CryptoHash([9; 32]);
+		let transaction =
+			OutcomeId::Transaction { transaction_hash: hash, sender_id: "alice.near".into() };
+		let receipt = OutcomeId::Receipt { receipt_id: hash, receiver_id: "bob.near".into() };
+		let no_path: Vec<MerklePathItem> = Vec::new();
+
+		let (outcome_proof, root) = proof(hash, "alice.near", 1);
+		let typed = TypedOutcomeProof::new(transaction.clone(), outcome_proof.clone()).unwrap();
+		assert_eq!(typed.id(), transaction);
+		typed.verify::<MockedHostFunctions>(&no_path, root).unwrap();
+		// the same outcome does not pass for the one of a receipt of another account
+		let typed = TypedOutcomeProof::new(receipt.clone(), outcome_proof.clone()).unwrap();
+		assert_eq!(code(typed.verify::<MockedHostFunctions>(&no_path, root)), 129);
+
+		let (outcome_proof, root) = proof(hash, "bob.near", 3);
+		let typed = TypedOutcomeProof::new(receipt, outcome_proof.clone()).unwrap();
+		typed.verify::<MockedHostFunctions>(&no_path, root).unwrap();
+		let typed = TypedOutcomeProof::new(transaction.clone(), outcome_proof).unwrap();
+		assert_eq!(code(typed.verify::<MockedHostFunctions>(&no_path, root)), 130);
+
+		let (outcome_proof, _) = proof(CryptoHash([8; 32]), "alice.near", 1);
+		assert!(matches!(
+			TypedOutcomeProof::new(transaction, outcome_proof),
+			Err(NearLiteClientError::Verification(VerificationFailure::OutcomeIdMismatch { .. }))
+		));
+	}
+}
@@ -0,0 +1,212 @@
+//! # Protocol versions
+//!
+//! The parts of header validation that a NEAR protocol upgrade can change, and which of them
+//! apply to which epoch. A [`ProtocolTable`] lists the [`ProtocolFeatures`] of each protocol
+//! version, from the version they were introduced in; a [`ProtocolSchedule`] tells the version of
+//! the epochs where it changes. Epochs it doesn't list run the version of the epoch before them,
+//! so only upgrades need to be scheduled.
+//!
+//! Every epoch so far runs [`ProtocolFeatures::default`], the rules of the specification. A new
+//! rule is a new variant of the scheme it changes, implemented alongside the existing ones, and
+//! an entry of the table once NEAR announces the version enabling it.
+
+use sp_std::collections::btree_map::BTreeMap;
+
+use near_primitives_wasm::{EpochId, HostFunctions, LightClientBlockView, ValidatorStakeView};
+
+use crate::{
+	block_validation::BlockViewHashes,
+	error::{NextBpsDefect, VerificationFailure},
+	LiteClientResult,
+};
+
+pub type ProtocolVersion = u32;
+
+/// How the hash of a block is computed from its light client view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlockHashScheme {
+	/// `sha256(sha256(sha256(borsh(inner_lite)) || inner_rest_hash) || prev_block_hash)`
+	InnerLiteAndRest,
+}
+
+/// What the block producers sign in `approvals_after_next`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ApprovalScheme {
+	/// `borsh(ApprovalInner::Endorsement(next_block_hash)) || borsh(height + 2)`
+	EndorseNextBlock,
+}
+
+/// The rules of a protocol version that the light client depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolFeatures {
+	pub block_hash: BlockHashScheme,
+	pub approval: ApprovalScheme,
+	/// whether `next_bps` may list [`ValidatorStakeView::V2`]s
+	pub validator_stake_v2: bool,
+}
+
+impl Default for ProtocolFeatures {
+	fn default() -> Self {
+		Self {
+			block_hash: BlockHashScheme::InnerLiteAndRest,
+			approval: ApprovalScheme::EndorseNextBlock,
+			validator_stake_v2: true,
+		}
+	}
+}
+
+impl ProtocolFeatures {
+	/// The hashes and approval message of `block_view` under these rules.
+	pub fn hashes<H: HostFunctions>(
+		&self,
+		block_view: &LightClientBlockView,
+	) -> LiteClientResult<BlockViewHashes> {
+		match (self.block_hash, self.approval) {
+			(BlockHashScheme::InnerLiteAndRest, ApprovalScheme::EndorseNextBlock) =>
+				BlockViewHashes::new::<H>(block_view),
+		}
+	}
+
+	/// Checks what these rules add to the specification.
+	pub fn check(&self, block_view: &LightClientBlockView) -> Result<(), VerificationFailure> {
+		if !self.validator_stake_v2 {
+			let next_bps = block_view.next_bps.as_deref().unwrap_or_default();
+			if let Some(index) = next_bps
+				.iter()
+				.position(|block_producer| matches!(block_producer, ValidatorStakeView::V2(_)))
+			{
+				return Err(VerificationFailure::MalformedNextBps {
+					index,
+					defect: NextBpsDefect::VersionNotEnabled,
+				})
+			}
+		}
+		Ok(())
+	}
+}
+
+/// The features of every protocol version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolTable {
+	/// features, keyed by the version introducing them
+	versions: BTreeMap<ProtocolVersion, ProtocolFeatures>,
+}
+
+impl ProtocolTable {
+	/// A table where every version has the features of `genesis`.
+	pub fn new(genesis: ProtocolFeatures) -> Self {
+		Self { versions: [(0, genesis)].into() }
+	}
+
+	/// Versions from `version` on have `features`, until the next version listed.
+	pub fn insert(&mut self, version: ProtocolVersion, features: ProtocolFeatures) {
+		self.versions.insert(version, features);
+	}
+
+	pub fn features(&self, version: ProtocolVersion) -> &ProtocolFeatures {
+		self.versions
+			.range(..=version)
+			.next_back()
+			.map(|(_, features)| features)
+			.expect("version 0 is always listed; qed")
+	}
+}
+
+impl Default for ProtocolTable {
+	fn default() -> Self {
+		Self::new(ProtocolFeatures::default())
+	}
+}
+
+/// The protocol version of the epochs where it changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtocolSchedule {
+	pub table: ProtocolTable,
+	/// version of the epoch of the checkpoint, unless listed in `epochs`
+	pub initial_version: ProtocolVersion,
+	epochs: BTreeMap<EpochId, ProtocolVersion>,
+}
+
+impl ProtocolSchedule {
+	pub fn new(table: ProtocolTable, initial_version: ProtocolVersion) -> Self {
+		Self { table, initial_version, epochs: BTreeMap::new() }
+	}
+
+	/// `epoch_id` and the epochs after it run `version`, until the next upgrade.
+	pub fn upgrade_at(mut self, epoch_id: EpochId, version: ProtocolVersion) -> Self {
+		self.epochs.insert(epoch_id, version);
+		self
+	}
+
+	/// The version of `epoch_id`, when it is an upgrade.
+	pub fn scheduled_version(&self, epoch_id: &EpochId) -> Option<ProtocolVersion> {
+		self.epochs.get(epoch_id).copied()
+	}
+
+	/// The version of `epoch_id`, following an epoch that ran `previous_version`.
+	pub fn version(
+		&self,
+		epoch_id: &EpochId,
+		previous_version: ProtocolVersion,
+	) -> ProtocolVersion {
+		self.scheduled_version(epoch_id).unwrap_or(previous_version)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+	use near_primitives_wasm::{CryptoHash, PublicKey, ValidatorStakeViewV1, ValidatorStakeViewV2};
+	use sp_std::vec;
+
+	#[test]
+	fn test_versions_inherit_features() {
+		let strict = ProtocolFeatures { validator_stake_v2: false, ..Default::default() };
+		let mut table = ProtocolTable::new(strict);
+		table.insert(60, ProtocolFeatures::default());
+		assert_eq!(*table.features(59), strict);
+		assert_eq!(*table.features(60), ProtocolFeatures::default());
+		assert_eq!(*table.features(u32::MAX), ProtocolFeatures::default());
+
+		let upgrade = EpochId(CryptoHash([1; 32]));
+		let schedule = ProtocolSchedule::new(table, 59).upgrade_at(upgrade, 60);
+		assert_eq!(schedule.version(&upgrade, 59), 60);
+		assert_eq!(schedule.version(&EpochId(CryptoHash([2; 32])), 60), 60);
+		assert_eq!(schedule.scheduled_version(&EpochId(CryptoHash([2; 32]))), None);
+	}
+
+	#[test]
+	fn test_validator_stake_v2_can_be_disabled() {
+		let v1 = ValidatorStakeViewV1 {
+			account_id: "node0".into(),
+			public_key: PublicKey([0; 32]),
+			stake: 10,
+		};
+		let v2 = ValidatorStakeViewV2 {
+			account_id: "node1".into(),
+			public_key: PublicKey([1; 32]),
+			stake: 10,
+			is_chunk_only: false,
+		};
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.next_bps = Some(vec![ValidatorStakeView::V1(v1), ValidatorStakeView::V2(v2)]);
+
+		let features = ProtocolFeatures::default();
+		assert!(features.check(&block_view).is_ok());
+		assert_eq!(
+			features.hashes::<MockedHostFunctions>(&block_view).unwrap(),
+			BlockViewHashes::new::<MockedHostFunctions>(&block_view).unwrap()
+		);
+		let features = ProtocolFeatures { validator_stake_v2: false, ..features };
+		assert_eq!(
+			features.check(&block_view),
+			Err(VerificationFailure::MalformedNextBps {
+				index: 1,
+				defect: NextBpsDefect::VersionNotEnabled
+			})
+		);
+	}
+}
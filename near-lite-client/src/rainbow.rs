@@ -0,0 +1,256 @@
+//! # Rainbow Bridge compatibility
+//!
+//! Relayers of the Rainbow Bridge hand its `NearProver` contract borsh encoded
+//! `FullOutcomeProof`s, the response of `EXPERIMENTAL_light_client_proof` laid out as:
+//!
+//! ```ignore
+//! outcome_proof: (proof: MerklePath, block_hash, id, outcome: ExecutionOutcomeView),
+//! outcome_root_proof: MerklePath,
+//! block_header_lite: (prev_block_hash, inner_rest_hash, inner_lite: BlockHeaderInnerLiteViewFinal),
+//! block_proof: MerklePath,
+//! ```
+//!
+//! where the status of the outcome is the `ExecutionStatusView` enum. [`FullOutcomeProof`]
+//! decodes the same payloads and verifies them the way `NearProver::proveOutcome` does, so
+//! projects migrating from the Rainbow Bridge can keep their relayers.
+
+use alloc::format;
+use near_primitives_wasm::{
+	BlockHeaderInnerLiteView, BlockHeaderInnerLiteViewFinal, CryptoHash, ExecutionOutcomeView,
+	HostFunctions, LightClientBlockLiteView, MerklePath, OutcomeProof,
+};
+use sp_std::vec::Vec;
+
+use crate::{
+	decode::{decode, decode_all, decode_vec},
+	error::{NearLiteClientError, VerificationFailure},
+	merkle_tree::compute_root_from_path,
+	verifier::{verify_outcome, OutcomeRoots},
+	DecodeLimits, LiteClientResult,
+};
+
+#[derive(Debug, Clone)]
+pub struct FullOutcomeProof {
+	pub outcome_proof: OutcomeProof,
+	/// proof of the shard outcome root, up to the outcome root of `block_header_lite`
+	pub outcome_root_proof: MerklePath,
+	/// the block that contains the outcome, `timestamp_nanosec` is set to `timestamp`
+	pub block_header_lite: LightClientBlockLiteView,
+	/// proof of the block in the block merkle tree, up to the root it is verified against
+	pub block_proof: MerklePath,
+}
+
+impl FullOutcomeProof {
+	/// Decodes a Rainbow Bridge payload, every merkle path within `limits`. Outcomes that failed
+	/// are rejected, as the `NearProver` does not support them either.
+	pub fn decode(bytes: &[u8], limits: &DecodeLimits) -> LiteClientResult<Self> {
+		decode_all(bytes, |buf| {
+			let outcome_proof = OutcomeProof {
+				proof: decode_vec(buf, limits.max_merkle_path_len, "merkle path items")?,
+				block_hash: decode(buf)?,
+				id: decode(buf)?,
+				outcome: ExecutionOutcomeView {
+					logs: decode(buf)?,
+					receipt_ids: decode(buf)?,
+					gas_burnt: decode(buf)?,
+					tokens_burnt: decode(buf)?,
+					executor_id: decode(buf)?,
+					status: decode_status(buf)?,
+				},
+			};
+			let outcome_root_proof =
+				decode_vec(buf, limits.max_merkle_path_len, "merkle path items")?;
+			let prev_block_hash = decode(buf)?;
+			let inner_rest_hash = decode(buf)?;
+			let inner_lite = decode::<BlockHeaderInnerLiteViewFinal>(buf)?;
+			Ok(Self {
+				outcome_proof,
+				outcome_root_proof,
+				block_header_lite: LightClientBlockLiteView {
+					prev_block_hash,
+					inner_rest_hash,
+					inner_lite: BlockHeaderInnerLiteView {
+						height: inner_lite.height,
+						epoch_id: inner_lite.epoch_id,
+						next_epoch_id: inner_lite.next_epoch_id,
+						prev_state_root: inner_lite.prev_state_root,
+						outcome_root: inner_lite.outcome_root,
+						timestamp: inner_lite.timestamp,
+						timestamp_nanosec: inner_lite.timestamp,
+						next_bp_hash: inner_lite.next_bp_hash,
+						block_merkle_root: inner_lite.block_merkle_root,
+					},
+				},
+				block_proof: decode_vec(buf, limits.max_merkle_path_len, "merkle path items")?,
+			})
+		})
+	}
+
+	/// Checks that the outcome is in the outcome root of `block_header_lite`, that the outcome
+	/// proof is for that block, and that the block leads to `block_merkle_root`, the
+	/// `block_merkle_root` of a validated header after it.
+	pub fn verify<H: HostFunctions>(
+		&self,
+		block_merkle_root: CryptoHash,
+	) -> LiteClientResult<OutcomeRoots> {
+		let roots = verify_outcome::<H>(
+			&self.outcome_proof,
+			&self.outcome_root_proof,
+			self.block_header_lite.inner_lite.outcome_root,
+		)?;
+
+		let block_hash = self.block_header_lite.current_block_hash::<H>();
+		if block_hash != self.outcome_proof.block_hash {
+			return Err(VerificationFailure::BlockHashMismatch {
+				expected: self.outcome_proof.block_hash,
+				computed: block_hash,
+			}
+			.into())
+		}
+
+		let computed = compute_root_from_path::<H>(&self.block_proof, block_hash)?;
+		if computed != block_merkle_root {
+			return Err(NearLiteClientError::MerkleRootMismatch {
+				expected: block_merkle_root,
+				computed,
+			})
+		}
+		Ok(roots)
+	}
+}
+
+/// Keeps the `ExecutionStatusView` borsh encoded, which is what the outcome hash is computed from.
+fn decode_status(buf: &mut &[u8]) -> LiteClientResult<Vec<u8>> {
+	let start = *buf;
+	match decode::<u8>(buf)? {
+		// Unknown
+		0 => {},
+		// SuccessValue
+		2 => {
+			decode::<Vec<u8>>(buf)?;
+		},
+		// SuccessReceiptId
+		3 => {
+			decode::<CryptoHash>(buf)?;
+		},
+		tag =>
+			return Err(NearLiteClientError::Serialization {
+				type_name: "ExecutionStatusView",
+				reason: format!("unsupported variant {}", tag),
+			}),
+	}
+	Ok(start[..start.len() - buf.len()].to_vec())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		calculate_execution_outcome_hash, merkle_tree::combine_hash,
+		test_utils::MockedHostFunctions,
+	};
+	use borsh::BorshSerialize;
+	use near_primitives_wasm::{Direction, MerklePathItem};
+
+	fn item(hash: u8, direction: Direction) -> MerklePathItem {
+		MerklePathItem { hash: CryptoHash([hash; 32]), direction }
+	}
+
+	/// A payload as the Rainbow Bridge relayer encodes it, and the block merkle root it leads to.
+	fn payload(status: &[u8]) -> (Vec<u8>, CryptoHash) {
+		let outcome = ExecutionOutcomeView {
+			logs: vec!["log".into()],
+			receipt_ids: vec![CryptoHash([1; 32])],
+			gas_burnt: 2,
+			tokens_burnt: 3,
+			executor_id: "bridge.near".into(),
+			status: status.to_vec(),
+		};
+		let id = CryptoHash([4; 32]);
+		let outcome_proof = OutcomeProof {
+			proof: vec![item(5, Direction::Right)],
+			block_hash: CryptoHash::default(),
+			id,
+			outcome: outcome.clone(),
+		};
+		let outcome_root_proof = vec![item(6, Direction::Left)];
+		let shard_outcome_root = compute_root_from_path::<MockedHostFunctions>(
+			&outcome_proof.proof,
+			calculate_execution_outcome_hash::<MockedHostFunctions>(&outcome, id).unwrap(),
+		)
+		.unwrap();
+		let block_outcome_root = compute_root_from_path::<MockedHostFunctions>(
+			&outcome_root_proof,
+			CryptoHash(MockedHostFunctions::sha256(&shard_outcome_root.0)),
+		)
+		.unwrap();
+
+		let mut inner_lite = BlockHeaderInnerLiteView::new_for_test();
+		inner_lite.outcome_root = block_outcome_root;
+		inner_lite.timestamp_nanosec = inner_lite.timestamp;
+		let header_lite = LightClientBlockLiteView {
+			prev_block_hash: CryptoHash([7; 32]),
+			inner_rest_hash: CryptoHash([8; 32]),
+			inner_lite: inner_lite.clone(),
+		};
+		let block_hash = header_lite.current_block_hash::<MockedHostFunctions>();
+		let block_proof = vec![item(9, Direction::Right)];
+		let block_merkle_root =
+			combine_hash::<MockedHostFunctions>(&block_hash, &CryptoHash([9; 32])).unwrap();
+
+		let mut encoded = (&outcome_proof.proof, block_hash, id).try_to_vec().unwrap();
+		encoded.extend(
+			(&outcome.logs, &outcome.receipt_ids, outcome.gas_burnt, outcome.tokens_burnt)
+				.try_to_vec()
+				.unwrap(),
+		);
+		encoded.extend(outcome.executor_id.try_to_vec().unwrap());
+		encoded.extend_from_slice(status);
+		encoded.extend(
+			(
+				&outcome_root_proof,
+				header_lite.prev_block_hash,
+				header_lite.inner_rest_hash,
+				BlockHeaderInnerLiteViewFinal::from(inner_lite),
+				&block_proof,
+			)
+				.try_to_vec()
+				.unwrap(),
+		);
+		(encoded, block_merkle_root)
+	}
+
+	#[test]
+	fn test_rainbow_proofs_verify() {
+		let success_value = [&[2][..], &3u32.to_le_bytes(), b"abc"].concat();
+		let success_receipt_id = [&[3][..], &[10; 32]].concat();
+		for status in [&[0][..], &success_value, &success_receipt_id] {
+			let (encoded, block_merkle_root) = payload(status);
+			let proof = FullOutcomeProof::decode(&encoded, &DecodeLimits::default()).unwrap();
+			assert_eq!(proof.outcome_proof.outcome.status, status);
+			proof.verify::<MockedHostFunctions>(block_merkle_root).unwrap();
+
+			assert!(matches!(
+				proof.verify::<MockedHostFunctions>(CryptoHash::default()),
+				Err(NearLiteClientError::MerkleRootMismatch { .. })
+			));
+			let mut other_block = proof.clone();
+			other_block.block_header_lite.prev_block_hash = CryptoHash::default();
+			assert!(matches!(
+				other_block.verify::<MockedHostFunctions>(block_merkle_root),
+				Err(NearLiteClientError::Verification(
+					VerificationFailure::BlockHashMismatch { .. }
+				))
+			));
+		}
+	}
+
+	#[test]
+	fn test_failed_outcomes_are_not_supported() {
+		let (encoded, _) = payload(&[1]);
+		assert!(matches!(
+			FullOutcomeProof::decode(&encoded, &DecodeLimits::default()),
+			Err(NearLiteClientError::Serialization { .. })
+		));
+	}
+}
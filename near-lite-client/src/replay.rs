@@ -0,0 +1,211 @@
+//! # Replay
+//!
+//! Offline ingestion of headers: reads `LightClientBlockView`s from a file or any other stream and
+//! feeds them to a [`LightClient`], without any network access. This allows verifying a chain of
+//! headers on an air-gapped machine and replaying mainnet history deterministically.
+
+use near_primitives::views::LightClientBlockView as NearLightClientBlockView;
+use near_primitives_wasm::{BlockHeight, HostFunctions, LightClientBlockView};
+use std::{
+	format,
+	io::{BufRead, Read, Write},
+	string::{String, ToString},
+	vec::Vec,
+};
+
+use crate::{
+	error::{serialize, NearLiteClientError},
+	DecodeLimits, LightClient, LiteClientResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderFormat {
+	/// one header per line, in the JSON format of the `next_light_client_block` RPC
+	Json,
+	/// `u32` little endian length followed by the borsh encoding of the header
+	Borsh,
+	/// one hex encoded borsh header per line, as written by the relayer's file sink
+	BorshHex,
+}
+
+/// Iterator over the headers of a stream.
+pub struct HeaderReader<R> {
+	reader: R,
+	format: HeaderFormat,
+	/// applied to the borsh formats
+	limits: DecodeLimits,
+}
+
+impl<R: BufRead> HeaderReader<R> {
+	pub fn new(reader: R, format: HeaderFormat) -> Self {
+		Self { reader, format, limits: DecodeLimits::default() }
+	}
+
+	pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+		self.limits = limits;
+		self
+	}
+
+	fn read_line(&mut self) -> LiteClientResult<Option<String>> {
+		let mut line = String::new();
+		loop {
+			line.clear();
+			if self.reader.read_line(&mut line).map_err(replay_error)? == 0 {
+				return Ok(None)
+			}
+			if !line.trim().is_empty() {
+				return Ok(Some(line))
+			}
+		}
+	}
+
+	fn read_frame(&mut self) -> LiteClientResult<Option<Vec<u8>>> {
+		if self.reader.fill_buf().map_err(replay_error)?.is_empty() {
+			return Ok(None)
+		}
+		let mut len = [0u8; 4];
+		self.reader.read_exact(&mut len).map_err(replay_error)?;
+		let mut frame = std::vec![0u8; u32::from_le_bytes(len) as usize];
+		self.reader.read_exact(&mut frame).map_err(replay_error)?;
+		Ok(Some(frame))
+	}
+
+	fn read_header(&mut self) -> LiteClientResult<Option<LightClientBlockView>> {
+		Ok(match self.format {
+			HeaderFormat::Json => match self.read_line()? {
+				Some(line) => {
+					let block_view: NearLightClientBlockView =
+						serde_json::from_str(&line).map_err(replay_error)?;
+					Some(block_view.try_into()?)
+				},
+				None => None,
+			},
+			HeaderFormat::Borsh => match self.read_frame()? {
+				Some(frame) => Some(self.limits.decode_block_view(&frame)?),
+				None => None,
+			},
+			HeaderFormat::BorshHex => match self.read_line()? {
+				Some(line) => Some(
+					self.limits
+						.decode_block_view(&hex::decode(line.trim()).map_err(replay_error)?)?,
+				),
+				None => None,
+			},
+		})
+	}
+}
+
+impl<R: BufRead> Iterator for HeaderReader<R> {
+	type Item = LiteClientResult<LightClientBlockView>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.read_header().transpose()
+	}
+}
+
+/// Writes `block_view` as a [`HeaderFormat::Borsh`] frame.
+pub fn write_borsh_frame(
+	writer: &mut impl Write,
+	block_view: &LightClientBlockView,
+) -> LiteClientResult<()> {
+	let frame = serialize(block_view)?;
+	writer.write_all(&(frame.len() as u32).to_le_bytes()).map_err(replay_error)?;
+	writer.write_all(&frame).map_err(replay_error)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+	pub accepted: usize,
+	/// height and reason of every header the client did not accept
+	pub rejected: Vec<(BlockHeight, String)>,
+}
+
+/// Feeds every header of `headers`, in order, to `client`. Headers that don't pass validation
+/// are reported and skipped, a stream that cannot be decoded aborts the replay.
+pub fn replay<H: HostFunctions>(
+	client: &mut LightClient<H>,
+	headers: impl IntoIterator<Item = LiteClientResult<LightClientBlockView>>,
+) -> LiteClientResult<ReplayReport> {
+	let mut report = ReplayReport::default();
+	for block_view in headers {
+		let block_view = block_view?;
+		let height = block_view.inner_lite.height;
+		match client.validate_and_update_head(block_view) {
+			Ok(_) => report.accepted += 1,
+			Err(err) => report.rejected.push((height, format!("{:?}", err))),
+		}
+	}
+	Ok(report)
+}
+
+fn replay_error(err: impl ToString) -> NearLiteClientError {
+	NearLiteClientError::Replay(err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{test_utils::MockedHostFunctions, TrustedCheckpoint};
+	use borsh::BorshSerialize;
+	use std::io::Cursor;
+
+	fn block_view(height: u64) -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.inner_lite.height = BlockHeight(height);
+		block_view
+	}
+
+	fn heights(reader: HeaderReader<impl BufRead>) -> Vec<u64> {
+		reader.map(|block_view| block_view.unwrap().inner_lite.height.0).collect()
+	}
+
+	#[test]
+	fn test_read_borsh_frames() {
+		let mut stream = Vec::new();
+		for height in [1, 2, 3] {
+			write_borsh_frame(&mut stream, &block_view(height)).unwrap();
+		}
+		assert_eq!(
+			heights(HeaderReader::new(Cursor::new(&stream), HeaderFormat::Borsh)),
+			[1, 2, 3]
+		);
+
+		let truncated = &stream[..stream.len() - 1];
+		let mut reader = HeaderReader::new(Cursor::new(truncated), HeaderFormat::Borsh);
+		assert!(reader.nth(2).unwrap().is_err());
+	}
+
+	#[test]
+	fn test_read_borsh_hex_and_json_lines() {
+		let hex_lines = [1, 2]
+			.map(|height| hex::encode(block_view(height).try_to_vec().unwrap()))
+			.join("\n\n");
+		let reader = HeaderReader::new(Cursor::new(hex_lines), HeaderFormat::BorshHex);
+		assert_eq!(heights(reader), [1, 2]);
+
+		let json_lines = [1, 2]
+			.map(|height| {
+				serde_json::to_string(
+					&NearLightClientBlockView::try_from(block_view(height)).unwrap(),
+				)
+				.unwrap()
+			})
+			.join("\n");
+		let reader = HeaderReader::new(Cursor::new(json_lines), HeaderFormat::Json);
+		assert_eq!(heights(reader), [1, 2]);
+	}
+
+	#[test]
+	fn test_replay_reports_rejected_headers() {
+		let mut client =
+			LightClient::<MockedHostFunctions>::with_checkpoint(TrustedCheckpoint(block_view(1)))
+				.unwrap();
+		// no block producer approved these blocks
+		let report = replay(&mut client, [Ok(block_view(2)), Ok(block_view(3))]).unwrap();
+		assert_eq!(report.accepted, 0);
+		assert_eq!(report.rejected.iter().map(|(height, _)| height.0).collect::<Vec<_>>(), [2, 3]);
+
+		let err = Err(NearLiteClientError::Replay("corrupted".into()));
+		assert!(replay(&mut client, [err]).is_err());
+	}
+}
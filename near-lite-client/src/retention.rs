@@ -0,0 +1,20 @@
+//! # Retention
+//!
+//! The storage of a client keeps more than validating the next headers needs: the block producers
+//! of older epochs, for [`LightClient::epoch_validators`](crate::LightClient::epoch_validators),
+//! and the hashes of the accepted heads, for
+//! [`LightClient::block_hash_at`](crate::LightClient::block_hash_at). A [`RetentionPolicy`] bounds
+//! that history, and is enforced on every head update.
+
+use near_primitives_wasm::BlockHeight;
+
+/// How much history the storage keeps, never less than the epochs the client retains in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionPolicy {
+	/// The block producers of the `n` most recent epochs, and the hashes of the heads accepted
+	/// since the oldest of them became known.
+	Epochs(usize),
+	/// The hashes of the heads at most `m` heights below the head, and the block producers of the
+	/// epochs that may have blocks in that window.
+	Heights(u64),
+}
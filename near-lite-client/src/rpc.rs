@@ -0,0 +1,699 @@
+//! # RPC
+//!
+//! Async client for the NEAR JSON-RPC methods a light client depends on. Responses are converted
+//! into this crate's types, so they can be fed to the client as they are. Requires a tokio
+//! runtime.
+
+use near_primitives::{
+	hash::CryptoHash as NearCryptoHash,
+	merkle::MerklePath as NearMerklePath,
+	views::{
+		ExecutionOutcomeWithIdView, LightClientBlockLiteView as NearLightClientBlockLiteView,
+		LightClientBlockView as NearLightClientBlockView,
+	},
+};
+use near_primitives_wasm::{
+	AccountId, Balance, BlockHeight, CryptoHash, HostFunctions, LightClientBlockLiteView,
+	LightClientBlockView, MerklePath, MerklePathItem, OutcomeProof, PublicKey, ValidatorStakeView,
+	ValidatorStakeViewV1, ValidatorStakeViewV2,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use std::{
+	string::{String, ToString},
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+	vec::Vec,
+};
+
+use crate::{
+	error::NearLiteClientError, metrics, rainbow::FullOutcomeProof, validate_transaction,
+	LiteClientResult, OutcomeId, TrustedCheckpoint,
+};
+
+pub const MAINNET_RPC_URL: &str = "https://rpc.mainnet.near.org";
+pub const TESTNET_RPC_URL: &str = "https://rpc.testnet.near.org";
+
+/// Everything needed to verify that an execution outcome is part of the chain, as returned by
+/// `EXPERIMENTAL_light_client_proof`.
+#[derive(Debug, Clone)]
+pub struct LightClientProof {
+	pub outcome_proof: OutcomeProof,
+	/// proof of the shard outcome root, see [`validate_transaction`](crate::validate_transaction)
+	pub outcome_root_proof: MerklePath,
+	/// the block that contains the outcome root
+	pub block_header_lite: LightClientBlockLiteView,
+	/// proof of the block in the block merkle tree, up to the light client head
+	pub block_proof: MerklePath,
+}
+
+impl LightClientProof {
+	/// Checks that the outcome is included in the outcome root of `block_header_lite`.
+	pub fn validate<H: HostFunctions>(&self) -> LiteClientResult<()> {
+		validate_transaction::<H>(
+			&self.outcome_proof,
+			&self.outcome_root_proof,
+			self.block_header_lite.inner_lite.outcome_root,
+		)
+	}
+
+	/// Parses the `result` of an `EXPERIMENTAL_light_client_proof` response, e.g. saved to a file.
+	pub fn from_json(result: Value) -> LiteClientResult<Self> {
+		serde_json::from_value::<RpcLightClientProof>(result)
+			.map_err(invalid_response)?
+			.try_into()
+	}
+}
+
+impl From<LightClientProof> for FullOutcomeProof {
+	fn from(proof: LightClientProof) -> Self {
+		Self {
+			outcome_proof: proof.outcome_proof,
+			outcome_root_proof: proof.outcome_root_proof,
+			block_header_lite: proof.block_header_lite,
+			block_proof: proof.block_proof,
+		}
+	}
+}
+
+/// Parameters of `EXPERIMENTAL_light_client_proof`.
+///
+/// ```ignore
+/// let request = LightClientProofRequest::transaction(transaction_hash, "alice.near")
+/// 	.light_client_head(head_hash);
+/// let proof = rpc.light_client_proof(&request).await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightClientProofRequest {
+	target: OutcomeId,
+	light_client_head: Option<CryptoHash>,
+}
+
+impl LightClientProofRequest {
+	/// Proof of the outcome of the transaction `transaction_hash` signed by `sender_id`.
+	pub fn transaction(transaction_hash: CryptoHash, sender_id: impl Into<AccountId>) -> Self {
+		Self {
+			target: OutcomeId::Transaction { transaction_hash, sender_id: sender_id.into() },
+			light_client_head: None,
+		}
+	}
+
+	/// Proof of the outcome of the receipt `receipt_id` executed by `receiver_id`.
+	pub fn receipt(receipt_id: CryptoHash, receiver_id: impl Into<AccountId>) -> Self {
+		Self {
+			target: OutcomeId::Receipt { receipt_id, receiver_id: receiver_id.into() },
+			light_client_head: None,
+		}
+	}
+
+	/// The transaction or receipt whose outcome is requested, to bind the proof to with
+	/// [`TypedOutcomeProof::new`](crate::TypedOutcomeProof::new).
+	pub fn outcome_id(&self) -> &OutcomeId {
+		&self.target
+	}
+
+	/// Hash of the head of the light client that will verify the proof; `block_proof` goes up to
+	/// this block.
+	pub fn light_client_head(mut self, light_client_head: CryptoHash) -> Self {
+		self.light_client_head = Some(light_client_head);
+		self
+	}
+
+	pub fn to_params(&self) -> LiteClientResult<Value> {
+		let light_client_head = self
+			.light_client_head
+			.ok_or_else(|| NearLiteClientError::Rpc("light client head is required".into()))?
+			.to_string();
+		Ok(match &self.target {
+			OutcomeId::Transaction { transaction_hash, sender_id } => json!({
+				"type": "transaction",
+				"transaction_hash": transaction_hash.to_string(),
+				"sender_id": sender_id,
+				"light_client_head": light_client_head,
+			}),
+			OutcomeId::Receipt { receipt_id, receiver_id } => json!({
+				"type": "receipt",
+				"receipt_id": receipt_id.to_string(),
+				"receiver_id": receiver_id,
+				"light_client_head": light_client_head,
+			}),
+		})
+	}
+}
+
+#[derive(Deserialize)]
+struct RpcLightClientProof {
+	outcome_proof: ExecutionOutcomeWithIdView,
+	outcome_root_proof: NearMerklePath,
+	block_header_lite: NearLightClientBlockLiteView,
+	block_proof: NearMerklePath,
+}
+
+impl TryFrom<RpcLightClientProof> for LightClientProof {
+	type Error = NearLiteClientError;
+	fn try_from(proof: RpcLightClientProof) -> Result<Self, Self::Error> {
+		Ok(Self {
+			outcome_proof: proof.outcome_proof.try_into()?,
+			outcome_root_proof: proof.outcome_root_proof.into_iter().map(Into::into).collect(),
+			block_header_lite: proof.block_header_lite.into(),
+			block_proof: proof.block_proof.into_iter().map(MerklePathItem::from).collect(),
+		})
+	}
+}
+
+/// `ValidatorStakeView` as nearcore serializes it. Parsed here rather than by `near-primitives`,
+/// which only knows of the versions of the protocol it was released for.
+#[derive(Deserialize)]
+#[serde(tag = "validator_stake_struct_version")]
+enum RpcValidatorStakeView {
+	V1 { account_id: AccountId, public_key: String, stake: String },
+	V2 { account_id: AccountId, public_key: String, stake: String, is_chunk_only: bool },
+}
+
+impl TryFrom<RpcValidatorStakeView> for ValidatorStakeView {
+	type Error = NearLiteClientError;
+	fn try_from(validator: RpcValidatorStakeView) -> Result<Self, Self::Error> {
+		let parse = |public_key: &str, stake: &str| -> LiteClientResult<(PublicKey, Balance)> {
+			let stake = stake.parse().map_err(|err| {
+				NearLiteClientError::Rpc(std::format!("invalid response: stake {}: {}", stake, err))
+			})?;
+			Ok((public_key.parse()?, stake))
+		};
+		Ok(match validator {
+			RpcValidatorStakeView::V1 { account_id, public_key, stake } => {
+				let (public_key, stake) = parse(&public_key, &stake)?;
+				Self::V1(ValidatorStakeViewV1 { account_id, public_key, stake })
+			},
+			RpcValidatorStakeView::V2 { account_id, public_key, stake, is_chunk_only } => {
+				let (public_key, stake) = parse(&public_key, &stake)?;
+				Self::V2(ValidatorStakeViewV2 { account_id, public_key, stake, is_chunk_only })
+			},
+		})
+	}
+}
+
+/// Parses the `result` of a `next_light_client_block` response, e.g. saved to a file, whatever
+/// the version of its block producers.
+pub fn light_client_block_from_json(mut result: Value) -> LiteClientResult<LightClientBlockView> {
+	let next_bps = match result.get_mut("next_bps").map(Value::take) {
+		None | Some(Value::Null) => None,
+		Some(next_bps) => Some(validators_from_json(next_bps)?),
+	};
+	let block_view: NearLightClientBlockView =
+		serde_json::from_value(result).map_err(invalid_response)?;
+	let mut block_view: LightClientBlockView = block_view.try_into()?;
+	block_view.next_bps = next_bps;
+	Ok(block_view)
+}
+
+fn validators_from_json(validators: Value) -> LiteClientResult<Vec<ValidatorStakeView>> {
+	serde_json::from_value::<Vec<RpcValidatorStakeView>>(validators)
+		.map_err(invalid_response)?
+		.into_iter()
+		.map(ValidatorStakeView::try_from)
+		.collect()
+}
+
+/// How [`NearRpcClient`] retries failed requests. An endpoint that fails is put aside for
+/// `initial_backoff`, doubled on every consecutive failure up to `max_backoff`, and requests fail
+/// over to the other endpoints in the meantime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+	/// attempts per request, across all endpoints
+	pub max_attempts: u32,
+	pub initial_backoff: Duration,
+	pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: 5,
+			initial_backoff: Duration::from_millis(250),
+			max_backoff: Duration::from_secs(30),
+		}
+	}
+}
+
+impl RetryPolicy {
+	fn backoff(&self, consecutive_failures: u32) -> Duration {
+		let exponent = consecutive_failures.saturating_sub(1).min(31);
+		self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff)
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+struct EndpointHealth {
+	consecutive_failures: u32,
+	retry_at: Option<Instant>,
+}
+
+/// The first endpoint that is not backing off or, if they all are, the one that will be available
+/// the soonest.
+fn pick_endpoint(health: &[EndpointHealth], now: Instant) -> (usize, Option<Instant>) {
+	if let Some(index) = health
+		.iter()
+		.position(|endpoint| endpoint.retry_at.map_or(true, |at| at <= now))
+	{
+		return (index, None);
+	}
+	health
+		.iter()
+		.enumerate()
+		.min_by_key(|(_, endpoint)| endpoint.retry_at)
+		.map(|(index, endpoint)| (index, endpoint.retry_at))
+		.unwrap_or((0, None))
+}
+
+/// Whether a request that failed with `err` may succeed on another endpoint.
+enum Failure {
+	Retryable(NearLiteClientError),
+	Fatal(NearLiteClientError),
+}
+
+/// JSON-RPC client backed by a pool of endpoints, with failover and exponential backoff.
+#[derive(Debug, Clone)]
+pub struct NearRpcClient {
+	urls: Vec<String>,
+	health: Arc<Mutex<Vec<EndpointHealth>>>,
+	retry_policy: RetryPolicy,
+	http: reqwest::Client,
+}
+
+impl NearRpcClient {
+	pub fn new(url: impl Into<String>) -> Self {
+		Self::with_endpoints([url])
+	}
+
+	/// Requests go to the first healthy endpoint, in the given order of preference.
+	pub fn with_endpoints(urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		let urls: Vec<String> = urls.into_iter().map(Into::into).collect();
+		Self {
+			health: Arc::new(Mutex::new(std::vec![EndpointHealth::default(); urls.len()])),
+			urls,
+			retry_policy: RetryPolicy::default(),
+			http: reqwest::Client::new(),
+		}
+	}
+
+	pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = retry_policy;
+		self
+	}
+
+	pub fn urls(&self) -> &[String] {
+		&self.urls
+	}
+
+	/// Sends a JSON-RPC request and returns its `result`, failing over to the other endpoints
+	/// when an endpoint is unreachable or answers with an internal error.
+	pub async fn call<T: DeserializeOwned>(
+		&self,
+		method: &str,
+		params: Value,
+	) -> LiteClientResult<T> {
+		if self.urls.is_empty() {
+			return Err(NearLiteClientError::Rpc("no endpoint configured".into()));
+		}
+		let request = json!({
+			"jsonrpc": "2.0",
+			"method": method,
+			"params": params,
+			"id": "dontcare",
+		});
+		let mut last_err = NearLiteClientError::Rpc("no attempt was made".into());
+		for _ in 0..self.retry_policy.max_attempts.max(1) {
+			let (index, retry_at) = pick_endpoint(&self.health.lock().unwrap(), Instant::now());
+			if let Some(retry_at) = retry_at {
+				tokio::time::sleep_until(retry_at.into()).await;
+			}
+			match self.send(&self.urls[index], &request).await {
+				Ok(response) => {
+					self.record(index, true);
+					return parse_response(response);
+				},
+				Err(Failure::Fatal(err)) => {
+					self.record(index, true);
+					return Err(err);
+				},
+				Err(Failure::Retryable(err)) => {
+					log::debug!("request to {} failed: {:?}", self.urls[index], err);
+					metrics::rpc_error(&self.urls[index]);
+					self.record(index, false);
+					last_err = err;
+				},
+			}
+		}
+		Err(last_err)
+	}
+
+	/// Queries `status` on every endpoint, updating their health. Returns whether each endpoint
+	/// answered.
+	pub async fn health_check(&self) -> Vec<bool> {
+		let request = json!({"jsonrpc": "2.0", "method": "status", "params": [], "id": "dontcare"});
+		let mut healthy = Vec::with_capacity(self.urls.len());
+		for (index, url) in self.urls.iter().enumerate() {
+			let is_healthy = !matches!(self.send(url, &request).await, Err(Failure::Retryable(_)));
+			self.record(index, is_healthy);
+			healthy.push(is_healthy);
+		}
+		healthy
+	}
+
+	async fn send(&self, url: &str, request: &Value) -> Result<Value, Failure> {
+		let response = self
+			.http
+			.post(url)
+			.json(request)
+			.send()
+			.await
+			.and_then(|response| response.error_for_status())
+			.map_err(|err| Failure::Retryable(rpc_error(err)))?
+			.json::<Value>()
+			.await
+			.map_err(|err| Failure::Retryable(rpc_error(err)))?;
+		match response.get("error") {
+			// the request itself is wrong or refers to unknown data, which other nodes of the same
+			// network will agree on
+			Some(error)
+				if matches!(
+					error.get("name").and_then(Value::as_str),
+					Some("HANDLER_ERROR") | Some("REQUEST_VALIDATION_ERROR")
+				) =>
+				Err(Failure::Fatal(NearLiteClientError::Rpc(error.to_string()))),
+			Some(error) => Err(Failure::Retryable(NearLiteClientError::Rpc(error.to_string()))),
+			None => Ok(response),
+		}
+	}
+
+	fn record(&self, index: usize, success: bool) {
+		let mut health = self.health.lock().unwrap();
+		let endpoint = &mut health[index];
+		if success {
+			*endpoint = EndpointHealth::default();
+		} else {
+			endpoint.consecutive_failures += 1;
+			endpoint.retry_at =
+				Some(Instant::now() + self.retry_policy.backoff(endpoint.consecutive_failures));
+		}
+	}
+
+	/// The light client block following `last_known_hash`, or `None` if the node has nothing
+	/// newer to offer yet.
+	pub async fn next_light_client_block(
+		&self,
+		last_known_hash: CryptoHash,
+	) -> LiteClientResult<Option<LightClientBlockView>> {
+		let result: Value = self
+			.call("next_light_client_block", json!([last_known_hash.to_string()]))
+			.await?;
+		// an up to date node answers with an empty object
+		if result.as_object().map_or(false, |result| result.is_empty()) {
+			return Ok(None);
+		}
+		light_client_block_from_json(result).map(Some)
+	}
+
+	pub async fn light_client_proof(
+		&self,
+		request: &LightClientProofRequest,
+	) -> LiteClientResult<LightClientProof> {
+		self.call::<RpcLightClientProof>("EXPERIMENTAL_light_client_proof", request.to_params()?)
+			.await?
+			.try_into()
+	}
+
+	/// Height of the latest final block.
+	pub async fn final_block_height(&self) -> LiteClientResult<BlockHeight> {
+		let block: Value = self.call("block", json!({ "finality": "final" })).await?;
+		block["header"]["height"]
+			.as_u64()
+			.map(BlockHeight)
+			.ok_or_else(|| NearLiteClientError::Rpc("invalid response: missing height".into()))
+	}
+
+	/// Block producers of the epoch of `block_hash` (of the latest block if `None`), in the order
+	/// used by `approvals_after_next`.
+	pub async fn validators_ordered(
+		&self,
+		block_hash: Option<CryptoHash>,
+	) -> LiteClientResult<Vec<ValidatorStakeView>> {
+		let params = json!([block_hash.map(NearCryptoHash::from)]);
+		validators_from_json(self.call("EXPERIMENTAL_validators_ordered", params).await?)
+	}
+}
+
+/// Fetches the light client block following `checkpoint_hash` from every endpoint and only trusts
+/// it if at least `quorum` of them agree on its hash.
+///
+/// `next_light_client_block` answers with the last final block of the epoch of `checkpoint_hash`
+/// when that epoch is over, so honest endpoints only agree deterministically if `checkpoint_hash`
+/// belongs to a past epoch.
+pub async fn quorum_checkpoint<H: HostFunctions>(
+	endpoints: &[NearRpcClient],
+	checkpoint_hash: CryptoHash,
+	quorum: usize,
+) -> LiteClientResult<TrustedCheckpoint> {
+	if quorum == 0 || quorum > endpoints.len() {
+		return Err(NearLiteClientError::CheckpointQuorum(std::format!(
+			"quorum of {} is not reachable with {} endpoints",
+			quorum,
+			endpoints.len()
+		)));
+	}
+	let responses = futures::future::join_all(
+		endpoints
+			.iter()
+			.map(|endpoint| endpoint.next_light_client_block(checkpoint_hash)),
+	)
+	.await;
+	select_by_quorum::<H>(responses, quorum).map(TrustedCheckpoint)
+}
+
+fn select_by_quorum<H: HostFunctions>(
+	responses: Vec<LiteClientResult<Option<LightClientBlockView>>>,
+	quorum: usize,
+) -> LiteClientResult<LightClientBlockView> {
+	let mut votes: Vec<(CryptoHash, LightClientBlockView, usize)> = Vec::new();
+	for block_view in responses.into_iter().filter_map(|response| response.ok().flatten()) {
+		let block_hash = block_view.current_block_hash::<H>();
+		match votes.iter_mut().find(|(hash, ..)| *hash == block_hash) {
+			Some((.., count)) => *count += 1,
+			None => votes.push((block_hash, block_view, 1)),
+		}
+	}
+	votes
+		.into_iter()
+		.max_by_key(|(.., count)| *count)
+		.filter(|(.., count)| *count >= quorum)
+		.map(|(_, block_view, _)| block_view)
+		.ok_or_else(|| {
+			NearLiteClientError::CheckpointQuorum(std::format!(
+				"less than {} endpoints agree on the checkpoint",
+				quorum
+			))
+		})
+}
+
+fn rpc_error(err: impl ToString) -> NearLiteClientError {
+	NearLiteClientError::Rpc(err.to_string())
+}
+
+fn parse_response<T: DeserializeOwned>(mut response: Value) -> LiteClientResult<T> {
+	if let Some(error) = response.get("error") {
+		return Err(NearLiteClientError::Rpc(error.to_string()));
+	}
+	let result = response
+		.get_mut("result")
+		.map(Value::take)
+		.ok_or_else(|| NearLiteClientError::Rpc("response without result".into()))?;
+	serde_json::from_value(result).map_err(invalid_response)
+}
+
+fn invalid_response(err: serde_json::Error) -> NearLiteClientError {
+	NearLiteClientError::Rpc(std::format!("invalid response: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_response() {
+		let result: u64 =
+			parse_response(json!({"jsonrpc": "2.0", "result": 42, "id": "dontcare"})).unwrap();
+		assert_eq!(result, 42);
+
+		let err = parse_response::<u64>(json!({
+			"jsonrpc": "2.0",
+			"error": {"code": -32000, "message": "Server error"},
+			"id": "dontcare"
+		}))
+		.unwrap_err();
+		assert!(matches!(err, NearLiteClientError::Rpc(msg) if msg.contains("Server error")));
+	}
+
+	#[test]
+	fn test_validator_stake_view_versions_are_parsed() {
+		let response: Value = serde_json::from_str(include_str!(
+			"../../fixtures/testnet/next_light_client_block/86456070.json"
+		))
+		.unwrap();
+		let result = response["result"].clone();
+		let block_view = light_client_block_from_json(result.clone()).unwrap();
+		let near_block_view: LightClientBlockView =
+			serde_json::from_value::<NearLightClientBlockView>(result.clone())
+				.unwrap()
+				.try_into()
+				.unwrap();
+		assert_eq!(
+			borsh::BorshSerialize::try_to_vec(&block_view).unwrap(),
+			borsh::BorshSerialize::try_to_vec(&near_block_view).unwrap()
+		);
+
+		let mut v2 = result;
+		v2["next_bps"][0]["validator_stake_struct_version"] = json!("V2");
+		v2["next_bps"][0]["is_chunk_only"] = json!(false);
+		let next_bps = light_client_block_from_json(v2.clone()).unwrap().next_bps.unwrap();
+		assert!(
+			matches!(&next_bps[0], ValidatorStakeView::V2(validator) if !validator.is_chunk_only)
+		);
+		assert_eq!(next_bps[0].stake(), near_block_view.next_bps.unwrap()[0].stake());
+		assert!(matches!(next_bps[1], ValidatorStakeView::V1(_)));
+
+		v2["next_bps"][0]["stake"] = json!("many");
+		assert!(light_client_block_from_json(v2.clone()).is_err());
+		v2["next_bps"][0]["validator_stake_struct_version"] = json!("V3");
+		assert!(light_client_block_from_json(v2).is_err());
+	}
+
+	#[test]
+	fn test_light_client_proof_request_params() {
+		let request = LightClientProofRequest::receipt(CryptoHash([1; 32]), "bob.near");
+		assert!(request.to_params().is_err());
+
+		let params = request.light_client_head(CryptoHash([2; 32])).to_params().unwrap();
+		assert_eq!(
+			params,
+			json!({
+				"type": "receipt",
+				"receipt_id": CryptoHash([1; 32]).to_string(),
+				"receiver_id": "bob.near",
+				"light_client_head": CryptoHash([2; 32]).to_string(),
+			})
+		);
+	}
+
+	#[test]
+	fn test_light_client_proof_response_parsing() {
+		let response = json!({
+			"outcome_proof": {
+				"proof": [],
+				"block_hash": "11111111111111111111111111111111",
+				"id": "11111111111111111111111111111111",
+				"outcome": {
+					"logs": [],
+					"receipt_ids": [],
+					"gas_burnt": 0,
+					"tokens_burnt": "0",
+					"executor_id": "alice.near",
+					"status": {"SuccessValue": ""},
+					"metadata": {"version": 1, "gas_profile": null}
+				}
+			},
+			"outcome_root_proof": [
+				{"hash": "11111111111111111111111111111111", "direction": "Right"}
+			],
+			"block_header_lite": {
+				"prev_block_hash": "11111111111111111111111111111111",
+				"inner_rest_hash": "11111111111111111111111111111111",
+				"inner_lite": {
+					"height": 1,
+					"epoch_id": "11111111111111111111111111111111",
+					"next_epoch_id": "11111111111111111111111111111111",
+					"prev_state_root": "11111111111111111111111111111111",
+					"outcome_root": "11111111111111111111111111111111",
+					"timestamp": 0,
+					"timestamp_nanosec": "0",
+					"next_bp_hash": "11111111111111111111111111111111",
+					"block_merkle_root": "11111111111111111111111111111111"
+				}
+			},
+			"block_proof": []
+		});
+		let proof = LightClientProof::try_from(
+			parse_response::<RpcLightClientProof>(json!({ "result": response })).unwrap(),
+		)
+		.unwrap();
+		assert_eq!(proof.outcome_proof.outcome.executor_id, "alice.near");
+		assert_eq!(proof.outcome_root_proof.len(), 1);
+		assert_eq!(proof.block_header_lite.inner_lite.height, BlockHeight(1));
+		assert!(proof.block_proof.is_empty());
+	}
+
+	#[test]
+	fn test_select_by_quorum() {
+		use crate::test_utils::MockedHostFunctions;
+
+		let block_view = LightClientBlockView::new_for_test();
+		let mut forged = LightClientBlockView::new_for_test();
+		forged.inner_lite.height.0 += 1;
+		let responses = || {
+			std::vec![
+				Ok(Some(block_view.clone())),
+				Ok(Some(forged.clone())),
+				Err(NearLiteClientError::Rpc("timeout".into())),
+				Ok(Some(block_view.clone())),
+				Ok(None),
+			]
+		};
+
+		let selected = select_by_quorum::<MockedHostFunctions>(responses(), 2).unwrap();
+		assert_eq!(
+			selected.current_block_hash::<MockedHostFunctions>(),
+			block_view.current_block_hash::<MockedHostFunctions>()
+		);
+		assert!(matches!(
+			select_by_quorum::<MockedHostFunctions>(responses(), 3),
+			Err(NearLiteClientError::CheckpointQuorum(_))
+		));
+	}
+
+	#[test]
+	fn test_retry_policy_backoff() {
+		let policy = RetryPolicy {
+			max_attempts: 5,
+			initial_backoff: Duration::from_millis(100),
+			max_backoff: Duration::from_secs(1),
+		};
+		assert_eq!(policy.backoff(1), Duration::from_millis(100));
+		assert_eq!(policy.backoff(3), Duration::from_millis(400));
+		assert_eq!(policy.backoff(10), Duration::from_secs(1));
+		assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+	}
+
+	#[test]
+	fn test_pick_endpoint_fails_over() {
+		let now = Instant::now();
+		let backing_off = |secs| EndpointHealth {
+			consecutive_failures: 1,
+			retry_at: Some(now + Duration::from_secs(secs)),
+		};
+
+		let health = [backing_off(5), EndpointHealth::default(), EndpointHealth::default()];
+		assert_eq!(pick_endpoint(&health, now), (1, None));
+
+		let health = [backing_off(5), backing_off(2)];
+		assert_eq!(pick_endpoint(&health, now), (1, Some(now + Duration::from_secs(2))));
+		assert_eq!(pick_endpoint(&health, now + Duration::from_secs(6)), (0, None));
+	}
+
+	#[test]
+	fn test_invalid_result_is_rejected() {
+		let err = parse_response::<NearLightClientBlockView>(json!({"result": {"height": 1}}))
+			.unwrap_err();
+		assert!(
+			matches!(err, NearLiteClientError::Rpc(msg) if msg.starts_with("invalid response"))
+		);
+	}
+}
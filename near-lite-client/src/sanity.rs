@@ -0,0 +1,152 @@
+//! # Sanity checks
+//!
+//! Cheap structural checks of a block view, run before any hash or signature is computed, so that
+//! relayers can discard garbage without paying for a full verification. Passing them says nothing
+//! about the validity of the block view, only that it is worth verifying.
+
+use near_primitives_wasm::{CryptoHash, LightClientBlockView};
+
+use crate::{DecodeLimits, LiteClientResult, VerificationFailure};
+
+/// Bounds of the length of NEAR account ids, in bytes.
+const MIN_ACCOUNT_ID_LEN: usize = 2;
+const MAX_ACCOUNT_ID_LEN: usize = 64;
+
+pub trait SanityCheck {
+	/// [`SanityCheck::sanity_check_with`] the default [`DecodeLimits`].
+	fn sanity_check(&self) -> LiteClientResult<()> {
+		self.sanity_check_with(&DecodeLimits::default())
+	}
+
+	/// Checks the structural invariants of every block view: the hashes it commits to are set,
+	/// it has approvals and, when it carries them, block producers, no more of either than
+	/// `limits` allows, and every account id has the length of a NEAR account id.
+	fn sanity_check_with(&self, limits: &DecodeLimits) -> LiteClientResult<()>;
+}
+
+impl SanityCheck for LightClientBlockView {
+	fn sanity_check_with(&self, limits: &DecodeLimits) -> LiteClientResult<()> {
+		let inner_lite = &self.inner_lite;
+		for (field, hash) in [
+			("prev_block_hash", &self.prev_block_hash),
+			("next_block_inner_hash", &self.next_block_inner_hash),
+			("inner_rest_hash", &self.inner_rest_hash),
+			("next_epoch_id", &inner_lite.next_epoch_id.0),
+			("next_bp_hash", &inner_lite.next_bp_hash),
+			("block_merkle_root", &inner_lite.block_merkle_root),
+		] {
+			if *hash == CryptoHash::default() {
+				return Err(VerificationFailure::ZeroHash { field }.into())
+			}
+		}
+
+		let approvals = self.approvals_after_next.len();
+		if approvals == 0 || approvals > limits.max_approvals {
+			return Err(VerificationFailure::ApprovalCountOutOfRange {
+				approvals,
+				max: limits.max_approvals,
+			}
+			.into())
+		}
+		if let Some(next_bps) = self.next_bps.as_ref() {
+			if next_bps.is_empty() || next_bps.len() > limits.max_block_producers {
+				return Err(VerificationFailure::BlockProducerCountOutOfRange {
+					block_producers: next_bps.len(),
+					max: limits.max_block_producers,
+				}
+				.into())
+			}
+			for (index, block_producer) in next_bps.iter().enumerate() {
+				let len = block_producer.account_id().len();
+				if !(MIN_ACCOUNT_ID_LEN..=MAX_ACCOUNT_ID_LEN).contains(&len) {
+					return Err(VerificationFailure::InvalidAccountId { index, len }.into())
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::NearLiteClientError;
+	use near_primitives_wasm::{
+		EpochId, PublicKey, Signature, ValidatorStakeView, ValidatorStakeViewV1,
+	};
+	use sp_std::vec::Vec;
+
+	fn block_view() -> LightClientBlockView {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.prev_block_hash = CryptoHash([1; 32]);
+		block_view.next_block_inner_hash = CryptoHash([2; 32]);
+		block_view.inner_rest_hash = CryptoHash([3; 32]);
+		block_view.inner_lite.next_epoch_id = EpochId(CryptoHash([4; 32]));
+		block_view.inner_lite.next_bp_hash = CryptoHash([5; 32]);
+		block_view.inner_lite.block_merkle_root = CryptoHash([6; 32]);
+		block_view.next_bps = Some(vec![ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "node0.near".into(),
+			public_key: PublicKey([7; 32]),
+			stake: 1,
+		})]);
+		block_view.approvals_after_next = vec![Some(Signature::from_raw(&[8; 64])), None];
+		block_view
+	}
+
+	fn failure(block_view: &LightClientBlockView) -> VerificationFailure {
+		match block_view.sanity_check() {
+			Err(NearLiteClientError::Verification(failure)) => failure,
+			other => panic!("unexpected result {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_sane_block_view() {
+		let mut block_view = block_view();
+		assert!(block_view.sanity_check().is_ok());
+		block_view.next_bps = None;
+		assert!(block_view.sanity_check().is_ok());
+		let limits = DecodeLimits { max_approvals: 1, ..Default::default() };
+		assert!(matches!(
+			block_view.sanity_check_with(&limits),
+			Err(NearLiteClientError::Verification(VerificationFailure::ApprovalCountOutOfRange {
+				approvals: 2,
+				max: 1
+			}))
+		));
+	}
+
+	#[test]
+	fn test_garbage_is_discarded() {
+		let mut zero_hash = block_view();
+		zero_hash.inner_rest_hash = CryptoHash::default();
+		assert_eq!(failure(&zero_hash), VerificationFailure::ZeroHash { field: "inner_rest_hash" });
+
+		let mut no_approvals = block_view();
+		no_approvals.approvals_after_next.clear();
+		assert!(matches!(
+			failure(&no_approvals),
+			VerificationFailure::ApprovalCountOutOfRange { approvals: 0, .. }
+		));
+
+		let mut no_block_producers = block_view();
+		no_block_producers.next_bps = Some(Vec::new());
+		assert!(matches!(
+			failure(&no_block_producers),
+			VerificationFailure::BlockProducerCountOutOfRange { block_producers: 0, .. }
+		));
+
+		for account_id in ["a", &"a".repeat(65)] {
+			let mut block_view = block_view();
+			if let Some(ValidatorStakeView::V1(block_producer)) =
+				block_view.next_bps.as_mut().and_then(|next_bps| next_bps.first_mut())
+			{
+				block_producer.account_id = account_id.into();
+			}
+			assert_eq!(
+				failure(&block_view),
+				VerificationFailure::InvalidAccountId { index: 0, len: account_id.len() }
+			);
+		}
+	}
+}
@@ -0,0 +1,18 @@
+//! # Borsh schema
+//!
+//! Extends the schemas exported by `near-primitives-wasm` with the types owned by this crate.
+
+use borsh::{schema::BorshSchemaContainer, BorshSchema};
+use sp_std::vec::Vec;
+
+use crate::{error::serialize, AuditEntry, LiteClientResult};
+
+pub fn schema_containers() -> Vec<BorshSchemaContainer> {
+	let mut containers = near_primitives_wasm::schema::schema_containers();
+	containers.push(AuditEntry::schema_container());
+	containers
+}
+
+pub fn export_schema_containers() -> LiteClientResult<Vec<u8>> {
+	serialize(&schema_containers())
+}
@@ -0,0 +1,144 @@
+//! # Signature verification backends
+//!
+//! The approvals of the block producers are verified through a [`SignatureVerifier`], chosen when
+//! the client is built (see
+//! [`LightClientBuilder::signature_verifier`](crate::LightClientBuilder::signature_verifier)):
+//!
+//! - [`HostFunctionsVerifier`], the default, defers to `HostFunctions::verify`: the host functions
+//!   of the runtime on Substrate, through `sp_io`;
+//! - [`DalekVerifier`], with the `ed25519-dalek` feature, verifies in software as nearcore does;
+//! - [`ZebraVerifier`], with the `ed25519-zebra` feature, verifies in software with
+//!   `ed25519-zebra`.
+//!
+//! The first two accept the approvals nearcore accepts. `ed25519-zebra` checks the cofactored
+//! equation, which disagrees with the cofactorless one on points with a torsion component, so
+//! [`ZebraVerifier`] first checks the strict encoding rules of [`near_primitives_wasm::ed25519`],
+//! which no honest signer breaks. [`StrictVerifier`] opts any other backend into those rules.
+
+use core::marker::PhantomData;
+use near_primitives_wasm::{HostFunctions, PublicKey, Signature};
+
+pub trait SignatureVerifier: Send + Sync {
+	/// Whether `signature` is a signature of `message` by `public_key`.
+	fn verify(&self, signature: &Signature, message: &[u8], public_key: &PublicKey) -> bool;
+}
+
+/// Verifies with `H::verify`.
+pub struct HostFunctionsVerifier<H>(PhantomData<fn() -> H>);
+
+impl<H> Default for HostFunctionsVerifier<H> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<H: HostFunctions> SignatureVerifier for HostFunctionsVerifier<H> {
+	fn verify(&self, signature: &Signature, message: &[u8], public_key: &PublicKey) -> bool {
+		H::verify(signature.clone(), message, public_key.clone())
+	}
+}
+
+#[cfg(feature = "ed25519-dalek")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DalekVerifier;
+
+#[cfg(feature = "ed25519-dalek")]
+impl SignatureVerifier for DalekVerifier {
+	fn verify(&self, signature: &Signature, message: &[u8], public_key: &PublicKey) -> bool {
+		let Signature::Ed25519(signature) = signature;
+		let public_key = match ed25519_dalek::PublicKey::from_bytes(&public_key.0) {
+			Ok(public_key) => public_key,
+			Err(_) => return false,
+		};
+		match ed25519_dalek::Signature::from_bytes(&signature.0) {
+			Ok(signature) =>
+				ed25519_dalek::Verifier::verify(&public_key, message, &signature).is_ok(),
+			Err(_) => false,
+		}
+	}
+}
+
+#[cfg(feature = "ed25519-zebra")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZebraVerifier;
+
+#[cfg(feature = "ed25519-zebra")]
+impl SignatureVerifier for ZebraVerifier {
+	fn verify(&self, signature: &Signature, message: &[u8], public_key: &PublicKey) -> bool {
+		let Signature::Ed25519(signature) = signature;
+		if !near_primitives_wasm::ed25519::is_strictly_encoded(&signature.0, &public_key.0) {
+			return false
+		}
+		match ed25519_zebra::VerificationKey::try_from(public_key.0) {
+			Ok(public_key) =>
+				public_key.verify(&ed25519_zebra::Signature::from(signature.0), message).is_ok(),
+			Err(_) => false,
+		}
+	}
+}
+
+/// Verifies with `V` the signatures that follow the strict encoding rules of
+/// [`near_primitives_wasm::ed25519`], rejecting some that nearcore accepts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictVerifier<V>(pub V);
+
+impl<V: SignatureVerifier> SignatureVerifier for StrictVerifier<V> {
+	fn verify(&self, signature: &Signature, message: &[u8], public_key: &PublicKey) -> bool {
+		let Signature::Ed25519(raw) = signature;
+		near_primitives_wasm::ed25519::is_strictly_encoded(&raw.0, &public_key.0) &&
+			self.0.verify(signature, message, public_key)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+	use sp_core::{ed25519::Pair, Pair as _};
+
+	fn verifiers() -> Vec<Box<dyn SignatureVerifier>> {
+		let mut verifiers: Vec<Box<dyn SignatureVerifier>> =
+			vec![Box::new(HostFunctionsVerifier::<MockedHostFunctions>::default())];
+		#[cfg(feature = "ed25519-dalek")]
+		verifiers.push(Box::new(DalekVerifier));
+		#[cfg(feature = "ed25519-zebra")]
+		verifiers.push(Box::new(ZebraVerifier));
+		verifiers
+	}
+
+	#[test]
+	fn test_backends_agree() {
+		let pair = Pair::from_seed(&[1; 32]);
+		let public_key = PublicKey(pair.public().0);
+		let signature = Signature::Ed25519(pair.sign(b"message"));
+		let mut non_canonical = pair.sign(b"message");
+		// an S above L is not reduced
+		non_canonical.0[63] |= 0x80;
+
+		for verifier in verifiers() {
+			assert!(verifier.verify(&signature, b"message", &public_key));
+			assert!(!verifier.verify(&signature, b"other message", &public_key));
+			assert!(!verifier.verify(&signature, b"message", &PublicKey([0; 32])));
+			assert!(!verifier.verify(
+				&Signature::Ed25519(non_canonical.clone()),
+				b"message",
+				&public_key
+			));
+		}
+	}
+
+	#[test]
+	fn test_strict_verifier_rejects_small_order_points() {
+		// with the identity as both key and nonce, `S = 0` is valid for any message
+		let mut identity = [0; 32];
+		identity[0] = 1;
+		let mut raw = [0; 64];
+		raw[..32].copy_from_slice(&identity);
+		let signature = Signature::from_raw(&raw);
+		let public_key = PublicKey(identity);
+
+		let verifier = HostFunctionsVerifier::<MockedHostFunctions>::default();
+		assert!(verifier.verify(&signature, b"message", &public_key));
+		assert!(!StrictVerifier(verifier).verify(&signature, b"message", &public_key));
+	}
+}
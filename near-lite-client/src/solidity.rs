@@ -0,0 +1,259 @@
+//! # Solidity compatibility
+//!
+//! Lets an EVM verifier contract interoperate with the client: headers validated here are handed
+//! over as an ABI encoded [`HeaderCommitment`], and merkle proofs are exchanged ABI encoded and
+//! hashed with keccak256, which the EVM provides natively, by running the client with
+//! [`Keccak256HostFunctions`].
+//!
+//! The layouts match `abi.encode` of the following Solidity types:
+//!
+//! ```solidity
+//! struct MerklePathItem { bytes32 hash; uint8 direction; } // 0 for left, 1 for right
+//! struct HeaderCommitment {
+//!     uint64 height;
+//!     bytes32 epochId;
+//!     bytes32 nextEpochId;
+//!     bytes32 blockHash;
+//!     bytes32 prevStateRoot;
+//!     bytes32 outcomeRoot;
+//!     uint64 timestamp;
+//! }
+//! ```
+//!
+//! Two nodes of a keccak merkle tree combine into `keccak256(abi.encodePacked(left, right))`,
+//! which is what [`compute_root_from_path`](crate::compute_root_from_path) gives with
+//! [`Keccak256HostFunctions`].
+
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, Direction, EpochId, HostFunctions, LightClientBlockView, MerklePath,
+	MerklePathItem,
+};
+use sha3::{Digest, Keccak256};
+use sp_std::vec::Vec;
+
+use crate::{error::NearLiteClientError, LiteClientResult};
+
+/// Size of an ABI word.
+const WORD: usize = 32;
+
+/// Hashes with keccak256, in place of SHA-256, and verifies signatures with `sp_io`.
+///
+/// NEAR itself hashes with SHA-256: headers and proofs coming from its RPC must still be verified
+/// with [`Sha256HostFunctions`](crate::Sha256HostFunctions). This is for proofs built for, or by,
+/// an EVM contract.
+pub struct Keccak256HostFunctions;
+
+impl HostFunctions for Keccak256HostFunctions {
+	fn sha256(data: &[u8]) -> [u8; 32] {
+		Keccak256::digest(data).into()
+	}
+
+	fn sha256_concat(chunks: &[&[u8]]) -> [u8; 32] {
+		let mut hasher = Keccak256::new();
+		for chunk in chunks {
+			hasher.update(chunk);
+		}
+		hasher.finalize().into()
+	}
+}
+
+/// What an EVM contract needs to know of a header validated by this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderCommitment {
+	pub height: BlockHeight,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
+	pub block_hash: CryptoHash,
+	pub prev_state_root: CryptoHash,
+	pub outcome_root: CryptoHash,
+	/// In nanoseconds.
+	pub timestamp: u64,
+}
+
+impl HeaderCommitment {
+	/// `H` is the hashing of the chain the header comes from, SHA-256 for NEAR.
+	pub fn from_block_view<H: HostFunctions>(block_view: &LightClientBlockView) -> Self {
+		let inner_lite = &block_view.inner_lite;
+		Self {
+			height: inner_lite.height,
+			epoch_id: inner_lite.epoch_id,
+			next_epoch_id: inner_lite.next_epoch_id,
+			block_hash: block_view.current_block_hash::<H>(),
+			prev_state_root: inner_lite.prev_state_root,
+			outcome_root: inner_lite.outcome_root,
+			timestamp: inner_lite.timestamp,
+		}
+	}
+
+	/// `abi.encode(commitment)`, 7 words.
+	pub fn abi_encode(&self) -> Vec<u8> {
+		let mut encoded = Vec::with_capacity(7 * WORD);
+		encoded.extend_from_slice(&uint_word(self.height.0));
+		encoded.extend_from_slice(&self.epoch_id.0 .0);
+		encoded.extend_from_slice(&self.next_epoch_id.0 .0);
+		encoded.extend_from_slice(&self.block_hash.0);
+		encoded.extend_from_slice(&self.prev_state_root.0);
+		encoded.extend_from_slice(&self.outcome_root.0);
+		encoded.extend_from_slice(&uint_word(self.timestamp));
+		encoded
+	}
+
+	pub fn abi_decode(encoded: &[u8]) -> LiteClientResult<Self> {
+		if encoded.len() != 7 * WORD {
+			return Err(abi_error::<Self>("expected 7 words"))
+		}
+		let words = encoded.chunks_exact(WORD).map(word).collect::<Vec<_>>();
+		Ok(Self {
+			height: BlockHeight(decode_uint::<Self>(&words[0])?),
+			epoch_id: EpochId(CryptoHash(words[1])),
+			next_epoch_id: EpochId(CryptoHash(words[2])),
+			block_hash: CryptoHash(words[3]),
+			prev_state_root: CryptoHash(words[4]),
+			outcome_root: CryptoHash(words[5]),
+			timestamp: decode_uint::<Self>(&words[6])?,
+		})
+	}
+
+	/// `keccak256(abi.encode(commitment))`, for the contract to store instead of the commitment.
+	pub fn keccak256(&self) -> CryptoHash {
+		CryptoHash(Keccak256HostFunctions::sha256(&self.abi_encode()))
+	}
+}
+
+/// `abi.encode(path)`, with `path` a `MerklePathItem[]`.
+pub fn abi_encode_merkle_path(path: &[MerklePathItem]) -> Vec<u8> {
+	let mut encoded = Vec::with_capacity((2 + 2 * path.len()) * WORD);
+	encoded.extend_from_slice(&uint_word(WORD as u64));
+	encoded.extend_from_slice(&uint_word(path.len() as u64));
+	for item in path {
+		encoded.extend_from_slice(&item.hash.0);
+		encoded.extend_from_slice(&uint_word(match item.direction {
+			Direction::Left => 0,
+			Direction::Right => 1,
+		}));
+	}
+	encoded
+}
+
+/// Inverse of [`abi_encode_merkle_path`]; encodings that aren't canonical are rejected.
+pub fn abi_decode_merkle_path(encoded: &[u8]) -> LiteClientResult<MerklePath> {
+	if encoded.len() < 2 * WORD || encoded.len() % (2 * WORD) != 0 {
+		return Err(abi_error::<MerklePath>("truncated"))
+	}
+	let (head, items) = encoded.split_at(2 * WORD);
+	if decode_uint::<MerklePath>(&word(&head[..WORD]))? != WORD as u64 {
+		return Err(abi_error::<MerklePath>("unexpected offset"))
+	}
+	if decode_uint::<MerklePath>(&word(&head[WORD..]))? != (items.len() / (2 * WORD)) as u64 {
+		return Err(abi_error::<MerklePath>("length does not match the items"))
+	}
+	items
+		.chunks_exact(2 * WORD)
+		.map(|item| {
+			let direction = match decode_uint::<MerklePathItem>(&word(&item[WORD..]))? {
+				0 => Direction::Left,
+				1 => Direction::Right,
+				_ => return Err(abi_error::<MerklePathItem>("invalid direction")),
+			};
+			Ok(MerklePathItem { hash: CryptoHash(word(&item[..WORD])), direction })
+		})
+		.collect()
+}
+
+fn uint_word(value: u64) -> [u8; WORD] {
+	let mut word = [0; WORD];
+	word[WORD - 8..].copy_from_slice(&value.to_be_bytes());
+	word
+}
+
+/// Only called on `WORD` long chunks.
+fn word(chunk: &[u8]) -> [u8; WORD] {
+	let mut word = [0; WORD];
+	word.copy_from_slice(chunk);
+	word
+}
+
+fn decode_uint<T: ?Sized>(word: &[u8; WORD]) -> LiteClientResult<u64> {
+	let (padding, value) = word.split_at(WORD - 8);
+	if padding.iter().any(|byte| *byte != 0) {
+		return Err(abi_error::<T>("integer out of range"))
+	}
+	let mut bytes = [0; 8];
+	bytes.copy_from_slice(value);
+	Ok(u64::from_be_bytes(bytes))
+}
+
+fn abi_error<T: ?Sized>(reason: &str) -> NearLiteClientError {
+	NearLiteClientError::Serialization {
+		type_name: core::any::type_name::<T>(),
+		reason: reason.into(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{compute_root_from_path, Sha256HostFunctions};
+
+	fn path() -> MerklePath {
+		vec![
+			MerklePathItem { hash: CryptoHash([1; 32]), direction: Direction::Left },
+			MerklePathItem { hash: CryptoHash([2; 32]), direction: Direction::Right },
+		]
+	}
+
+	#[test]
+	fn test_keccak256() {
+		assert_eq!(
+			format!("{:x}", CryptoHash(Keccak256HostFunctions::sha256(b""))),
+			"c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+		);
+		assert_eq!(
+			Keccak256HostFunctions::sha256_concat(&[b"a", b"bc"]),
+			Keccak256HostFunctions::sha256(b"abc")
+		);
+	}
+
+	#[test]
+	fn test_merkle_path_abi_encoding() {
+		let encoded = abi_encode_merkle_path(&path());
+		assert_eq!(encoded.len(), 6 * WORD);
+		assert_eq!(encoded[WORD - 1], 0x20);
+		assert_eq!(encoded[2 * WORD - 1], 2);
+		assert_eq!(&encoded[2 * WORD..3 * WORD], &[1; 32]);
+		assert_eq!(encoded[4 * WORD - 1], 0);
+		assert_eq!(encoded[6 * WORD - 1], 1);
+		assert_eq!(abi_decode_merkle_path(&encoded).unwrap(), path());
+
+		let mut invalid_direction = encoded.clone();
+		invalid_direction[6 * WORD - 1] = 2;
+		assert!(abi_decode_merkle_path(&invalid_direction).is_err());
+		assert!(abi_decode_merkle_path(&encoded[..5 * WORD]).is_err());
+		assert!(abi_decode_merkle_path(&[]).is_err());
+	}
+
+	#[test]
+	fn test_keccak_root_matches_solidity() {
+		let leaf = CryptoHash([3; 32]);
+		// keccak256(abi.encodePacked(left, right)) at every level
+		let level_1 = Keccak256HostFunctions::sha256(&[[1; 32], leaf.0].concat());
+		let root = Keccak256HostFunctions::sha256(&[level_1, [2; 32]].concat());
+		assert_eq!(
+			compute_root_from_path::<Keccak256HostFunctions>(&path(), leaf).unwrap(),
+			CryptoHash(root)
+		);
+	}
+
+	#[test]
+	fn test_header_commitment_roundtrip() {
+		let block_view = LightClientBlockView::new_for_test();
+		let commitment = HeaderCommitment::from_block_view::<Sha256HostFunctions>(&block_view);
+		assert_eq!(commitment.block_hash, block_view.current_block_hash::<Sha256HostFunctions>());
+		let encoded = commitment.abi_encode();
+		assert_eq!(HeaderCommitment::abi_decode(&encoded).unwrap(), commitment);
+
+		let mut out_of_range = encoded;
+		out_of_range[0] = 1;
+		assert!(HeaderCommitment::abi_decode(&out_of_range).is_err());
+	}
+}
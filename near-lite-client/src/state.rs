@@ -0,0 +1,239 @@
+//! # State proofs
+//!
+//! Verification of values of NEAR's state trie against a validated head. The head commits to the
+//! state of every shard in `prev_state_root`, the merkle root of the shards' state roots, so a
+//! proof is made of two parts: the path of the shard state root to `prev_state_root`, and the trie
+//! nodes from the shard state root down to the key (as returned by the `view_state` RPC with
+//! `include_proof`).
+//!
+//! Note that `prev_state_root` is the state *before* the head's chunks are applied.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_merkle_proofs::trie::{lookup, RawTrieNodeWithSize};
+use near_primitives_wasm::{
+	AccessKey, Account, CryptoHash, HostFunctions, LightClientBlockView, MerklePath, PublicKey,
+};
+use sp_std::vec::Vec;
+
+use crate::{
+	error::{serialize, NearLiteClientError, VerificationFailure},
+	merkle_tree::compute_root_from_path,
+	LiteClientResult,
+};
+
+// columns of the trie keys, see nearcore's `trie_key::col`
+const ACCOUNT: u8 = 0;
+const ACCESS_KEY: u8 = 2;
+const CONTRACT_DATA: u8 = 9;
+const ACCOUNT_DATA_SEPARATOR: u8 = b',';
+
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct StateProof {
+	/// state root of the shard holding the key
+	pub shard_state_root: CryptoHash,
+	/// path of `shard_state_root` to the head's `prev_state_root`
+	pub state_root_proof: MerklePath,
+	/// encoded trie nodes, from the one hashed `shard_state_root` down to the key
+	pub nodes: Vec<Vec<u8>>,
+}
+
+/// Verifies that the storage of the contract `account_id` holds `value` under `key` in the state
+/// committed to by `head`, or that there is no such key if `value` is `None`.
+pub fn verify_contract_state<H: HostFunctions>(
+	account_id: &str,
+	key: &[u8],
+	value: Option<&[u8]>,
+	proof: &StateProof,
+	head: &LightClientBlockView,
+) -> LiteClientResult<()> {
+	let trie_key =
+		[&[CONTRACT_DATA][..], account_id.as_bytes(), &[ACCOUNT_DATA_SEPARATOR], key].concat();
+	verify_state::<H>(&trie_key, value, proof, head)
+}
+
+/// Verifies that `account_id` exists with the given record (balance, locked amount, code hash,
+/// storage usage) in the state committed to by `head`, or that it doesn't exist if `account` is
+/// `None`.
+pub fn verify_account<H: HostFunctions>(
+	account_id: &str,
+	account: Option<&Account>,
+	proof: &StateProof,
+	head: &LightClientBlockView,
+) -> LiteClientResult<()> {
+	let trie_key = [&[ACCOUNT][..], account_id.as_bytes()].concat();
+	let value = account.map(serialize).transpose()?;
+	verify_state::<H>(&trie_key, value.as_deref(), proof, head)
+}
+
+/// Verifies that `public_key` is an access key of `account_id` with the given nonce and permission
+/// in the state committed to by `head`, or that it isn't a key of the account if `access_key` is
+/// `None`.
+pub fn verify_access_key<H: HostFunctions>(
+	account_id: &str,
+	public_key: &PublicKey,
+	access_key: Option<&AccessKey>,
+	proof: &StateProof,
+	head: &LightClientBlockView,
+) -> LiteClientResult<()> {
+	let trie_key =
+		[&[ACCESS_KEY][..], account_id.as_bytes(), &[ACCESS_KEY], &serialize(public_key)?].concat();
+	let value = access_key.map(serialize).transpose()?;
+	verify_state::<H>(&trie_key, value.as_deref(), proof, head)
+}
+
+/// Verifies the value stored under the raw `trie_key`.
+pub(crate) fn verify_state<H: HostFunctions>(
+	trie_key: &[u8],
+	value: Option<&[u8]>,
+	proof: &StateProof,
+	head: &LightClientBlockView,
+) -> LiteClientResult<()> {
+	let shard_state_root_hash = CryptoHash(H::sha256(proof.shard_state_root.as_ref()));
+	let state_root = compute_root_from_path::<H>(&proof.state_root_proof, shard_state_root_hash)?;
+	if state_root != head.inner_lite.prev_state_root {
+		return Err(NearLiteClientError::MerkleRootMismatch {
+			expected: head.inner_lite.prev_state_root,
+			computed: state_root,
+		})
+	}
+
+	let nodes = proof
+		.nodes
+		.iter()
+		.map(|node| RawTrieNodeWithSize::decode(node))
+		.collect::<Result<Vec<_>, _>>()?;
+	let found = lookup::<H>(trie_key, &nodes, proof.shard_state_root)?;
+	let matches = match (&found, value) {
+		(None, None) => true,
+		(Some(found), Some(value)) =>
+			found.length as usize == value.len() && found.hash == CryptoHash(H::sha256(value)),
+		_ => false,
+	};
+	if !matches {
+		return Err(VerificationFailure::StateValueMismatch {
+			expected: value.map(|value| CryptoHash(H::sha256(value))),
+			found: found.map(|found| found.hash),
+		}
+		.into())
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+	use super::*;
+	use crate::{merkle_tree::merklize, test_utils::MockedHostFunctions};
+	use near_merkle_proofs::trie::RawTrieNode;
+	use near_primitives_wasm::{AccessKeyPermission, FunctionCallPermission, Nonce};
+
+	/// A head whose `prev_state_root` commits to a trie made of a single leaf holding `value`
+	/// under `trie_key`, in the second of two shards.
+	pub(crate) fn single_leaf_state(
+		trie_key: &[u8],
+		value: &[u8],
+	) -> (LightClientBlockView, StateProof) {
+		let leaf = RawTrieNodeWithSize {
+			// the key of the leaf is the whole (even length) trie key, nibble encoded
+			node: RawTrieNode::Leaf(
+				[&[0x20][..], trie_key].concat(),
+				value.len() as u32,
+				CryptoHash(MockedHostFunctions::sha256(value)),
+			),
+			memory_usage: 0,
+		};
+		let mut node = Vec::new();
+		leaf.encode_into(&mut node);
+		let shard_state_root = leaf.hash::<MockedHostFunctions>();
+		let (prev_state_root, paths) =
+			merklize::<MockedHostFunctions, _>(&[CryptoHash([1; 32]), shard_state_root]).unwrap();
+
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_lite.prev_state_root = prev_state_root;
+		let proof = StateProof {
+			shard_state_root,
+			state_root_proof: paths[1].clone(),
+			nodes: [node].into(),
+		};
+		(head, proof)
+	}
+
+	#[test]
+	fn test_verify_contract_state() {
+		let trie_key = [&[CONTRACT_DATA][..], b"bridge.near", b",", b"outbox"].concat();
+		let (head, proof) = single_leaf_state(&trie_key, b"message");
+		let verify = |account_id, key: &[u8], value: Option<&[u8]>| {
+			verify_contract_state::<MockedHostFunctions>(account_id, key, value, &proof, &head)
+		};
+
+		assert!(verify("bridge.near", b"outbox", Some(&b"message"[..])).is_ok());
+		assert!(verify("bridge.near", b"outbox", Some(&b"other message"[..])).is_err());
+		assert!(verify("bridge.near", b"outbox", None).is_err());
+		// the leaf proves that no other key exists
+		assert!(verify("bridge.near", b"inbox", None).is_ok());
+		assert!(verify("other.near", b"outbox", Some(&b"message"[..])).is_err());
+
+		let mut other_head = head.clone();
+		other_head.inner_lite.prev_state_root = CryptoHash([2; 32]);
+		assert!(verify_contract_state::<MockedHostFunctions>(
+			"bridge.near",
+			b"outbox",
+			Some(&b"message"[..]),
+			&proof,
+			&other_head
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn test_verify_account() {
+		let account = Account {
+			amount: 10u128.pow(24),
+			locked: 0,
+			code_hash: CryptoHash([3; 32]),
+			storage_usage: 182,
+		};
+		let trie_key = [&[ACCOUNT][..], b"alice.near"].concat();
+		let (head, proof) = single_leaf_state(&trie_key, &account.try_to_vec().unwrap());
+
+		assert!(verify_account::<MockedHostFunctions>("alice.near", Some(&account), &proof, &head)
+			.is_ok());
+		let richer = Account { amount: account.amount + 1, ..account.clone() };
+		assert!(verify_account::<MockedHostFunctions>("alice.near", Some(&richer), &proof, &head)
+			.is_err());
+		assert!(verify_account::<MockedHostFunctions>("alice.near", None, &proof, &head).is_err());
+		assert!(verify_account::<MockedHostFunctions>("bob.near", None, &proof, &head).is_ok());
+	}
+
+	#[test]
+	fn test_verify_access_key() {
+		let public_key = PublicKey([5; 32]);
+		let access_key = AccessKey {
+			nonce: Nonce(42),
+			permission: AccessKeyPermission::FunctionCall(FunctionCallPermission {
+				allowance: None,
+				receiver_id: "bridge.near".into(),
+				method_names: Vec::new(),
+			}),
+		};
+		let trie_key =
+			[&[ACCESS_KEY][..], b"alice.near", &[ACCESS_KEY], &public_key.try_to_vec().unwrap()]
+				.concat();
+		let (head, proof) = single_leaf_state(&trie_key, &access_key.try_to_vec().unwrap());
+		let verify = |public_key: &PublicKey, access_key: Option<&AccessKey>| {
+			verify_access_key::<MockedHostFunctions>(
+				"alice.near",
+				public_key,
+				access_key,
+				&proof,
+				&head,
+			)
+		};
+
+		assert!(verify(&public_key, Some(&access_key)).is_ok());
+		let full_access = AccessKey { permission: AccessKeyPermission::FullAccess, ..access_key };
+		assert!(verify(&public_key, Some(&full_access)).is_err());
+		assert!(verify(&public_key, None).is_err());
+		// the key is not authorized for the account
+		assert!(verify(&PublicKey([6; 32]), None).is_ok());
+	}
+}
@@ -0,0 +1,88 @@
+//! # Stateless verification
+//!
+//! One-shot checks that need neither a [`LightClient`](crate::LightClient) nor a
+//! [`LightClientStorage`](crate::LightClientStorage): everything they trust is passed in, and
+//! nothing is kept once they return. Contracts and zk guests that verify a single header or a
+//! single outcome per call use these, and carry whatever they trust from one call to the next
+//! themselves.
+//!
+//! ```ignore
+//! stateless::verify_header::<H>(&trusted_head, &header, &block_producers)?;
+//! stateless::verify_outcome::<H>(&outcome_proof, header.inner_lite.block_merkle_root)?;
+//! ```
+
+use near_primitives_wasm::{CryptoHash, HostFunctions, LightClientBlockView, ValidatorStakeView};
+use sp_std::collections::btree_map::BTreeMap;
+
+use crate::{
+	block_validation::{validate_light_block, ApprovalStats, ApprovalThreshold},
+	rainbow::FullOutcomeProof,
+	verifier::OutcomeRoots,
+	LiteClientResult,
+};
+
+/// Verifies `header` against `trusted`, a head the caller already trusts or the block view of a
+/// [`TrustedCheckpoint`](crate::TrustedCheckpoint), with the same rules a client applies to a new
+/// head. `block_producers` are those of the epoch of `header`: the `next_bps` of a trusted head
+/// of the previous epoch, or those the caller kept for the epoch of `trusted`.
+///
+/// On success `header` can be trusted in turn, and its `next_bps`, if any, are the block
+/// producers of the epoch after it.
+pub fn verify_header<H: HostFunctions>(
+	trusted: &LightClientBlockView,
+	header: &LightClientBlockView,
+	block_producers: &[ValidatorStakeView],
+) -> LiteClientResult<ApprovalStats> {
+	let epoch_block_producers =
+		BTreeMap::from([(header.inner_lite.epoch_id, block_producers.to_vec())]);
+	validate_light_block::<H>(
+		trusted,
+		header,
+		&epoch_block_producers,
+		&ApprovalThreshold::default(),
+	)
+}
+
+/// Verifies that the outcome of `proof` was executed in a block committed to by `root`, the
+/// `block_merkle_root` of a header verified after that block.
+pub fn verify_outcome<H: HostFunctions>(
+	proof: &FullOutcomeProof,
+	root: CryptoHash,
+) -> LiteClientResult<OutcomeRoots> {
+	proof.verify::<H>(root)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{error::VerificationFailure, test_utils::MockedHostFunctions, NearLiteClientError};
+	use near_primitives::views::LightClientBlockView as NearLightClientBlockView;
+
+	#[derive(serde::Deserialize)]
+	struct ResultFromRpc {
+		result: NearLightClientBlockView,
+	}
+
+	fn block_view(response: &str) -> LightClientBlockView {
+		let response = serde_json::from_str::<ResultFromRpc>(response).unwrap();
+		LightClientBlockView::try_from(response.result).unwrap()
+	}
+
+	#[test]
+	fn test_verify_header() {
+		let checkpoint = block_view(include_str!(
+			"../../fixtures/testnet/next_light_client_block/86441383.json"
+		));
+		let header = block_view(include_str!(
+			"../../fixtures/testnet/next_light_client_block/86455909.json"
+		));
+		let block_producers = checkpoint.next_bps.clone().unwrap();
+
+		verify_header::<MockedHostFunctions>(&checkpoint, &header, &block_producers).unwrap();
+		assert!(matches!(
+			verify_header::<MockedHostFunctions>(&header, &checkpoint, &block_producers),
+			Err(NearLiteClientError::Verification(VerificationFailure::HeightNotAhead { .. }))
+		));
+		assert!(verify_header::<MockedHostFunctions>(&checkpoint, &header, &[]).is_err());
+	}
+}
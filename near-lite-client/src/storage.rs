@@ -0,0 +1,762 @@
+//! # Storage
+//!
+//! The light client keeps its state in memory. A [`LightClientStorage`] backend receives every
+//! change of that state, so that it survives the client, and hands it back when a client is
+//! built on top of it with [`LightClientBuilder`](crate::LightClientBuilder).
+//!
+//! [`KvStorage`] implements it once for every [`KvBackend`](crate::KvBackend), so that a new
+//! database only needs to store bytes. The writes of a head update are first recorded in a
+//! write-ahead log, so that an update interrupted by a crash is either fully applied or not at all
+//! once the storage is [recovered](LightClientStorage::recover). Block producers are stored as
+//! deltas against the epoch stored before them when that is smaller, which it is for most epochs.
+
+use alloc::format;
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, EpochId, LightClientBlockView, ValidatorStakeView,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+use crate::{
+	bps_delta::{BlockProducersDelta, MAX_DELTA_DEPTH},
+	error::{deserialize, serialize},
+	kv::{InMemoryKv, KvBackend},
+	LiteClientResult, NearLiteClientError,
+};
+
+pub trait LightClientStorage {
+	fn head(&self) -> LiteClientResult<Option<LightClientBlockView>>;
+	fn set_head(&mut self, head: &LightClientBlockView) -> LiteClientResult<()>;
+	/// Block producers of every stored epoch, in the order they were inserted.
+	fn epoch_block_producers(&self) -> LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>>;
+	fn insert_epoch_block_producers(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<()>;
+	fn remove_epoch_block_producers(&mut self, epoch_id: &EpochId) -> LiteClientResult<()>;
+	/// Height of the head when the block producers of each stored epoch were inserted, for the
+	/// epochs the backend kept it for. A client resumed on the storage takes the height of its
+	/// head for the others, none by default.
+	fn epoch_heights(&self) -> LiteClientResult<Vec<(EpochId, BlockHeight)>> {
+		Ok(Vec::new())
+	}
+	/// Keeps `height` as the height of the head when the block producers of `epoch_id` were
+	/// inserted, until they are removed, so that the retention policy still knows how old they
+	/// are once the client is resumed. Nothing by default.
+	fn set_epoch_height(
+		&mut self,
+		_epoch_id: EpochId,
+		_height: BlockHeight,
+	) -> LiteClientResult<()> {
+		Ok(())
+	}
+	/// Block producers of `epoch_id`, if they are stored.
+	fn epoch_validators(
+		&self,
+		epoch_id: &EpochId,
+	) -> LiteClientResult<Option<Vec<ValidatorStakeView>>> {
+		Ok(self
+			.epoch_block_producers()?
+			.into_iter()
+			.find(|(stored, _)| stored == epoch_id)
+			.map(|(_, block_producers)| block_producers))
+	}
+	/// Hash of the block accepted at `height`, if the backend indexes them.
+	fn block_hash(&self, _height: BlockHeight) -> LiteClientResult<Option<CryptoHash>> {
+		Ok(None)
+	}
+	/// Indexes `hash` as the hash of the block accepted at `height`, nothing by default.
+	fn insert_block_hash(
+		&mut self,
+		_height: BlockHeight,
+		_hash: CryptoHash,
+	) -> LiteClientResult<()> {
+		Ok(())
+	}
+	/// Drops the hashes indexed below `height`, returning how many were dropped.
+	fn prune_block_hashes(&mut self, _height: BlockHeight) -> LiteClientResult<usize> {
+		Ok(0)
+	}
+	/// Starts a batch of writes, such as those of a head update, that the backend applies all or
+	/// none of. A batch that was not committed is discarded.
+	fn begin_update(&mut self) -> LiteClientResult<()> {
+		Ok(())
+	}
+	/// Applies the writes made since [`LightClientStorage::begin_update`].
+	fn commit_update(&mut self) -> LiteClientResult<()> {
+		Ok(())
+	}
+	/// Discards the writes made since [`LightClientStorage::begin_update`], for an update that
+	/// failed. Called on every error path, the commit included.
+	fn abort_update(&mut self) -> LiteClientResult<()> {
+		Ok(())
+	}
+	/// Completes the batch a crash interrupted, if any, returning whether there was one. Called
+	/// before a client is built on the storage.
+	fn recover(&mut self) -> LiteClientResult<bool> {
+		Ok(false)
+	}
+	/// What the storage holds, for operators to follow its growth. By default from the head and
+	/// the block producers read back and re-encoded, without the hashes indexed by height.
+	fn stats(&self) -> LiteClientResult<StorageStats> {
+		let head = self.head()?;
+		let block_producers = self.epoch_block_producers()?;
+		let mut stats = StorageStats::default();
+		if let Some(head) = head {
+			stats.head.add(serialize(&head)?.len());
+		}
+		for (_, block_producers) in block_producers {
+			stats.block_producers.add(serialize(&block_producers)?.len());
+		}
+		Ok(stats)
+	}
+	/// Bytes used by the backend, only its own size unless it keeps its data in memory.
+	#[cfg(feature = "deepsize_feature")]
+	fn memory_usage(&self) -> usize {
+		core::mem::size_of_val(self)
+	}
+}
+
+/// Number of entries of one kind in a storage, and the bytes of their values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnStats {
+	pub entries: usize,
+	pub bytes: usize,
+}
+
+impl ColumnStats {
+	pub fn add(&mut self, bytes: usize) {
+		self.entries += 1;
+		self.bytes += bytes;
+	}
+}
+
+/// Returned by [`LightClientStorage::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageStats {
+	pub head: ColumnStats,
+	/// block producers of the stored epochs, in full or as deltas
+	pub block_producers: ColumnStats,
+	/// hashes of the accepted heads, indexed by height
+	pub block_hashes: ColumnStats,
+	/// what the storage keeps for itself, such as the order of the epochs
+	pub other: ColumnStats,
+	/// lowest height with an indexed block hash
+	pub oldest_height: Option<BlockHeight>,
+	/// highest height with an indexed block hash
+	pub newest_height: Option<BlockHeight>,
+}
+
+impl StorageStats {
+	pub fn total(&self) -> ColumnStats {
+		let columns = [self.head, self.block_producers, self.block_hashes, self.other];
+		ColumnStats {
+			entries: columns.iter().map(|column| column.entries).sum(),
+			bytes: columns.iter().map(|column| column.bytes).sum(),
+		}
+	}
+}
+
+/// Storage that lives as long as the value itself, mostly useful for tests.
+pub type InMemoryStorage = KvStorage<InMemoryKv>;
+
+const HEAD_KEY: &[u8] = b"head";
+/// Epochs with stored block producers, in the order they were inserted.
+const EPOCHS_KEY: &[u8] = b"epochs";
+/// Height of the head when each stored epoch was inserted.
+const EPOCH_HEIGHTS_KEY: &[u8] = b"epoch-heights";
+const BLOCK_PRODUCERS_PREFIX: &[u8] = b"bps/";
+const BLOCK_PRODUCERS_DELTA_PREFIX: &[u8] = b"bps-delta/";
+const BLOCK_HASH_PREFIX: &[u8] = b"hash/";
+/// Writes of the batch being committed, borsh encoded, `None` for a deletion.
+const WAL_KEY: &[u8] = b"wal";
+
+type Writes = Vec<(Vec<u8>, Option<Vec<u8>>)>;
+
+/// [`LightClientStorage`] over any [`KvBackend`]. Values are borsh encoded: the head under `head`,
+/// the ordered list of epochs under `epochs`, the height each of them was inserted at under
+/// `epoch-heights`, the block producers of each of them under
+/// `bps/<epoch id>`, or their delta against those of another epoch under `bps-delta/<epoch id>`,
+/// and the hash of the block accepted at each height under `hash/<height>`, the height in big
+/// endian.
+///
+/// The writes of a batch are kept in memory until it is committed. They are then written under
+/// `wal` in a single write, which the backend must apply atomically, before being applied one by
+/// one, and `wal` is only deleted once they all were.
+#[derive(Debug, Clone, Default)]
+pub struct KvStorage<B> {
+	backend: B,
+	/// writes of the current batch, by key
+	batch: Option<BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl<B: KvBackend> KvStorage<B> {
+	pub fn new(backend: B) -> Self {
+		Self { backend, batch: None }
+	}
+
+	pub fn backend(&self) -> &B {
+		&self.backend
+	}
+
+	pub fn into_backend(self) -> B {
+		self.backend
+	}
+
+	/// Removes the whole state of the light client from the backend.
+	pub fn clear(&mut self) -> LiteClientResult<()> {
+		self.batch = None;
+		self.backend.delete(HEAD_KEY)?;
+		self.backend.delete(EPOCHS_KEY)?;
+		self.backend.delete(EPOCH_HEIGHTS_KEY)?;
+		for prefix in [BLOCK_PRODUCERS_PREFIX, BLOCK_PRODUCERS_DELTA_PREFIX, BLOCK_HASH_PREFIX] {
+			for (key, _) in self.backend.iter_prefix(prefix)? {
+				self.backend.delete(&key)?;
+			}
+		}
+		self.backend.delete(WAL_KEY)
+	}
+
+	fn epochs(&self) -> LiteClientResult<Vec<EpochId>> {
+		match self.read(EPOCHS_KEY)? {
+			Some(bytes) => deserialize(&bytes),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	fn write_epoch_heights(&mut self, heights: &[(EpochId, BlockHeight)]) -> LiteClientResult<()> {
+		self.write(EPOCH_HEIGHTS_KEY, Some(serialize(heights)?))
+	}
+
+	fn delta(&self, epoch_id: &EpochId) -> LiteClientResult<Option<BlockProducersDelta>> {
+		self.read(&block_producers_delta_key(epoch_id))?
+			.map(|bytes| deserialize(&bytes))
+			.transpose()
+	}
+
+	/// Block producers of `epoch_id`, rebuilt from the deltas they are stored as, if any.
+	fn block_producers(
+		&self,
+		epoch_id: &EpochId,
+	) -> LiteClientResult<Option<Vec<ValidatorStakeView>>> {
+		if let Some(bytes) = self.read(&block_producers_key(epoch_id))? {
+			return deserialize(&bytes).map(Some)
+		}
+		let delta = match self.delta(epoch_id)? {
+			Some(delta) => delta,
+			None => return Ok(None),
+		};
+		let base = self.block_producers(&delta.base)?.ok_or_else(|| missing_base(epoch_id))?;
+		delta.apply(&base).map(Some)
+	}
+
+	/// Stores the block producers of `epoch_id` in full, or as a delta against those of `base`
+	/// when that is smaller.
+	fn write_block_producers(
+		&mut self,
+		epoch_id: &EpochId,
+		block_producers: &[ValidatorStakeView],
+		base: Option<&EpochId>,
+	) -> LiteClientResult<()> {
+		let full = serialize(block_producers)?;
+		let delta = match base {
+			Some(base) => {
+				let depth = self.delta(base)?.map_or(0, |delta| delta.depth) + 1;
+				match self.block_producers(base)? {
+					Some(base_bps) if depth <= MAX_DELTA_DEPTH => Some(serialize(
+						&BlockProducersDelta::new(*base, depth, &base_bps, block_producers)?,
+					)?),
+					_ => None,
+				}
+			},
+			None => None,
+		};
+		match delta.filter(|delta| delta.len() < full.len()) {
+			Some(delta) => {
+				self.write(&block_producers_key(epoch_id), None)?;
+				self.write(&block_producers_delta_key(epoch_id), Some(delta))
+			},
+			None => {
+				self.write(&block_producers_delta_key(epoch_id), None)?;
+				self.write(&block_producers_key(epoch_id), Some(full))
+			},
+		}
+	}
+
+	/// Stores in full the block producers of the epochs stored as deltas against `epoch_id`,
+	/// before those of `epoch_id` change or are removed.
+	fn detach_deltas(&mut self, epoch_id: &EpochId, epochs: &[EpochId]) -> LiteClientResult<()> {
+		for dependent in epochs {
+			if self.delta(dependent)?.map_or(false, |delta| delta.base == *epoch_id) {
+				let block_producers =
+					self.block_producers(dependent)?.ok_or_else(|| missing_base(dependent))?;
+				self.write_block_producers(dependent, &block_producers, None)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn read(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>> {
+		match self.batch.as_ref().and_then(|batch| batch.get(key)) {
+			Some(value) => Ok(value.clone()),
+			None => self.backend.get(key),
+		}
+	}
+
+	fn write(&mut self, key: &[u8], value: Option<Vec<u8>>) -> LiteClientResult<()> {
+		match (self.batch.as_mut(), value) {
+			(Some(batch), value) => {
+				batch.insert(key.to_vec(), value);
+				Ok(())
+			},
+			(None, Some(value)) => self.backend.put(key, &value),
+			(None, None) => self.backend.delete(key),
+		}
+	}
+
+	/// Entries under `prefix`, with the writes of the batch.
+	fn entries(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+		let mut entries: BTreeMap<_, _> = self.backend.iter_prefix(prefix)?.into_iter().collect();
+		let batch = self.batch.iter().flat_map(|batch| batch.range(prefix.to_vec()..));
+		for (key, value) in batch.take_while(|(key, _)| key.starts_with(prefix)) {
+			match value {
+				Some(value) => entries.insert(key.clone(), value.clone()),
+				None => entries.remove(key),
+			};
+		}
+		Ok(entries.into_iter().collect())
+	}
+
+	/// Applies `writes` to the backend, then deletes the log that recorded them.
+	fn apply(&mut self, writes: Writes) -> LiteClientResult<()> {
+		for (key, value) in writes {
+			match value {
+				Some(value) => self.backend.put(&key, &value)?,
+				None => self.backend.delete(&key)?,
+			}
+		}
+		self.backend.delete(WAL_KEY)
+	}
+}
+
+fn block_producers_key(epoch_id: &EpochId) -> Vec<u8> {
+	[BLOCK_PRODUCERS_PREFIX, &epoch_id.0 .0[..]].concat()
+}
+
+fn block_producers_delta_key(epoch_id: &EpochId) -> Vec<u8> {
+	[BLOCK_PRODUCERS_DELTA_PREFIX, &epoch_id.0 .0[..]].concat()
+}
+
+fn missing_base(epoch_id: &EpochId) -> NearLiteClientError {
+	NearLiteClientError::Storage(format!(
+		"the block producers of {:?} are stored against an epoch that is not",
+		epoch_id
+	))
+}
+
+fn block_hash_key(height: BlockHeight) -> Vec<u8> {
+	[BLOCK_HASH_PREFIX, &height.0.to_be_bytes()[..]].concat()
+}
+
+impl<B: KvBackend> LightClientStorage for KvStorage<B> {
+	fn head(&self) -> LiteClientResult<Option<LightClientBlockView>> {
+		self.read(HEAD_KEY)?.map(|bytes| deserialize(&bytes)).transpose()
+	}
+
+	fn set_head(&mut self, head: &LightClientBlockView) -> LiteClientResult<()> {
+		self.write(HEAD_KEY, Some(serialize(head)?))
+	}
+
+	fn epoch_block_producers(&self) -> LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>> {
+		let mut epoch_block_producers: Vec<(EpochId, Vec<ValidatorStakeView>)> = Vec::new();
+		for epoch_id in self.epochs()? {
+			// deltas are mostly against the epoch before, which was just rebuilt
+			let delta = self.delta(&epoch_id)?;
+			let base = delta.as_ref().and_then(|delta| {
+				epoch_block_producers.iter().find(|(stored, _)| *stored == delta.base)
+			});
+			let block_producers = match (delta.as_ref(), base) {
+				(Some(delta), Some((_, base))) => Some(delta.apply(base)?),
+				_ => self.block_producers(&epoch_id)?,
+			};
+			if let Some(block_producers) = block_producers {
+				epoch_block_producers.push((epoch_id, block_producers));
+			}
+		}
+		Ok(epoch_block_producers)
+	}
+
+	fn insert_epoch_block_producers(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<()> {
+		let mut epochs = self.epochs()?;
+		epochs.retain(|stored| *stored != epoch_id);
+		self.detach_deltas(&epoch_id, &epochs)?;
+		self.write_block_producers(&epoch_id, block_producers, epochs.last())?;
+		epochs.push(epoch_id);
+		self.write(EPOCHS_KEY, Some(serialize(&epochs)?))
+	}
+
+	fn remove_epoch_block_producers(&mut self, epoch_id: &EpochId) -> LiteClientResult<()> {
+		let mut epochs = self.epochs()?;
+		epochs.retain(|stored| stored != epoch_id);
+		self.detach_deltas(epoch_id, &epochs)?;
+		self.write(EPOCHS_KEY, Some(serialize(&epochs)?))?;
+		let mut heights = self.epoch_heights()?;
+		if heights.iter().any(|(stored, _)| stored == epoch_id) {
+			heights.retain(|(stored, _)| stored != epoch_id);
+			self.write_epoch_heights(&heights)?;
+		}
+		self.write(&block_producers_delta_key(epoch_id), None)?;
+		self.write(&block_producers_key(epoch_id), None)
+	}
+
+	fn epoch_heights(&self) -> LiteClientResult<Vec<(EpochId, BlockHeight)>> {
+		match self.read(EPOCH_HEIGHTS_KEY)? {
+			Some(bytes) => deserialize(&bytes),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	fn set_epoch_height(&mut self, epoch_id: EpochId, height: BlockHeight) -> LiteClientResult<()> {
+		let mut heights = self.epoch_heights()?;
+		heights.retain(|(stored, _)| *stored != epoch_id);
+		heights.push((epoch_id, height));
+		self.write_epoch_heights(&heights)
+	}
+
+	fn epoch_validators(
+		&self,
+		epoch_id: &EpochId,
+	) -> LiteClientResult<Option<Vec<ValidatorStakeView>>> {
+		self.block_producers(epoch_id)
+	}
+
+	fn block_hash(&self, height: BlockHeight) -> LiteClientResult<Option<CryptoHash>> {
+		self.read(&block_hash_key(height))?.map(|bytes| deserialize(&bytes)).transpose()
+	}
+
+	fn insert_block_hash(&mut self, height: BlockHeight, hash: CryptoHash) -> LiteClientResult<()> {
+		self.write(&block_hash_key(height), Some(serialize(&hash)?))
+	}
+
+	fn prune_block_hashes(&mut self, height: BlockHeight) -> LiteClientResult<usize> {
+		let below = block_hash_key(height);
+		let mut pruned = 0;
+		// keys hold the heights in big endian, so they are in the order of the heights
+		for (key, _) in self.entries(BLOCK_HASH_PREFIX)? {
+			if key >= below {
+				break
+			}
+			self.write(&key, None)?;
+			pruned += 1;
+		}
+		Ok(pruned)
+	}
+
+	/// Counts what the backend holds, with the writes of the current batch.
+	fn stats(&self) -> LiteClientResult<StorageStats> {
+		let mut stats = StorageStats::default();
+		if let Some(head) = self.read(HEAD_KEY)? {
+			stats.head.add(head.len());
+		}
+		for key in [EPOCHS_KEY, EPOCH_HEIGHTS_KEY, WAL_KEY] {
+			if let Some(value) = self.read(key)? {
+				stats.other.add(value.len());
+			}
+		}
+		for prefix in [BLOCK_PRODUCERS_PREFIX, BLOCK_PRODUCERS_DELTA_PREFIX] {
+			for (_, value) in self.entries(prefix)? {
+				stats.block_producers.add(value.len());
+			}
+		}
+		let block_hashes = self.entries(BLOCK_HASH_PREFIX)?;
+		for (_, value) in &block_hashes {
+			stats.block_hashes.add(value.len());
+		}
+		// keys hold the heights in big endian, so they are in the order of the heights
+		let height = |(key, _): &(Vec<u8>, Vec<u8>)| {
+			let bytes = key[BLOCK_HASH_PREFIX.len()..].try_into().ok()?;
+			Some(BlockHeight(u64::from_be_bytes(bytes)))
+		};
+		stats.oldest_height = block_hashes.first().and_then(height);
+		stats.newest_height = block_hashes.last().and_then(height);
+		Ok(stats)
+	}
+
+	fn begin_update(&mut self) -> LiteClientResult<()> {
+		self.batch = Some(BTreeMap::new());
+		Ok(())
+	}
+
+	fn commit_update(&mut self) -> LiteClientResult<()> {
+		let writes: Writes = match self.batch.take() {
+			Some(batch) if !batch.is_empty() => batch.into_iter().collect(),
+			_ => return Ok(()),
+		};
+		// the writes of a batch that failed to apply are only left there, and overwriting them
+		// would lose those not applied yet
+		if self.backend.get(WAL_KEY)?.is_some() {
+			return Err(NearLiteClientError::Storage(
+				"a batch that failed to apply is pending, the storage must be recovered".into(),
+			))
+		}
+		self.backend.put(WAL_KEY, &serialize(&writes)?)?;
+		self.apply(writes)
+	}
+
+	fn abort_update(&mut self) -> LiteClientResult<()> {
+		self.batch = None;
+		Ok(())
+	}
+
+	fn recover(&mut self) -> LiteClientResult<bool> {
+		self.batch = None;
+		match self.backend.get(WAL_KEY)? {
+			Some(wal) => {
+				self.apply(deserialize(&wal)?)?;
+				Ok(true)
+			},
+			None => Ok(false),
+		}
+	}
+
+	#[cfg(feature = "deepsize_feature")]
+	fn memory_usage(&self) -> usize {
+		self.backend.memory_usage()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::NearLiteClientError;
+	use near_primitives_wasm::{PublicKey, ValidatorStakeViewV1};
+
+	fn epoch_id(byte: u8) -> EpochId {
+		EpochId(CryptoHash([byte; 32]))
+	}
+
+	#[test]
+	fn test_kv_storage_keeps_the_order_of_epochs() {
+		let block_producers = [ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "node0.near".into(),
+			public_key: PublicKey([1; 32]),
+			stake: 1,
+		})];
+		let mut storage = InMemoryStorage::default();
+		assert!(storage.head().unwrap().is_none());
+		storage.set_head(&LightClientBlockView::new_for_test()).unwrap();
+		for byte in [3, 1, 2] {
+			storage.insert_epoch_block_producers(epoch_id(byte), &block_producers).unwrap();
+		}
+		storage.insert_epoch_block_producers(epoch_id(3), &block_producers).unwrap();
+		storage.set_epoch_height(epoch_id(1), BlockHeight(4)).unwrap();
+		storage.set_epoch_height(epoch_id(2), BlockHeight(5)).unwrap();
+		storage.remove_epoch_block_producers(&epoch_id(1)).unwrap();
+		assert_eq!(storage.epoch_heights().unwrap(), [(epoch_id(2), BlockHeight(5))]);
+		assert!(storage.epoch_validators(&epoch_id(1)).unwrap().is_none());
+		assert_eq!(storage.epoch_validators(&epoch_id(2)).unwrap().unwrap().len(), 1);
+		let epochs: Vec<_> = storage
+			.epoch_block_producers()
+			.unwrap()
+			.into_iter()
+			.map(|(epoch_id, block_producers)| {
+				assert_eq!(block_producers.len(), 1);
+				epoch_id
+			})
+			.collect();
+		assert_eq!(epochs, [epoch_id(2), epoch_id(3)]);
+		storage.insert_block_hash(BlockHeight(7), CryptoHash([7; 32])).unwrap();
+		assert_eq!(storage.block_hash(BlockHeight(7)).unwrap(), Some(CryptoHash([7; 32])));
+		assert!(storage.block_hash(BlockHeight(8)).unwrap().is_none());
+		storage.insert_block_hash(BlockHeight(9), CryptoHash([9; 32])).unwrap();
+		let stats = storage.stats().unwrap();
+		assert_eq!(stats.head.entries, 1);
+		assert_eq!(stats.block_producers.entries, 2);
+		assert_eq!(stats.block_hashes, ColumnStats { entries: 2, bytes: 64 });
+		assert_eq!(stats.oldest_height, Some(BlockHeight(7)));
+		assert_eq!(stats.newest_height, Some(BlockHeight(9)));
+		assert_eq!(stats.other.entries, 2);
+		assert_eq!(storage.prune_block_hashes(BlockHeight(9)).unwrap(), 1);
+		assert!(storage.block_hash(BlockHeight(7)).unwrap().is_none());
+		assert!(storage.block_hash(BlockHeight(9)).unwrap().is_some());
+
+		storage.clear().unwrap();
+		assert!(storage.head().unwrap().is_none());
+		assert!(storage.epoch_block_producers().unwrap().is_empty());
+		assert!(storage.backend().iter_prefix(b"").unwrap().is_empty());
+		assert_eq!(storage.stats().unwrap(), StorageStats::default());
+	}
+
+	#[test]
+	fn test_block_producers_are_stored_as_deltas() {
+		let validator = |index: u8, stake: u128| {
+			ValidatorStakeView::V1(ValidatorStakeViewV1 {
+				account_id: format!("node{}.near", index),
+				public_key: PublicKey([index; 32]),
+				stake,
+			})
+		};
+		let sets: Vec<Vec<_>> = (0..4)
+			.map(|epoch| {
+				(0..100).map(|index| validator(index, 10 + (index == epoch) as u128)).collect()
+			})
+			.collect();
+		let mut storage = InMemoryStorage::default();
+		for (epoch, block_producers) in sets.iter().enumerate() {
+			storage
+				.insert_epoch_block_producers(epoch_id(epoch as u8), block_producers)
+				.unwrap();
+		}
+		let stored = |storage: &InMemoryStorage, prefix: &[u8]| {
+			storage.backend().iter_prefix(prefix).unwrap().len()
+		};
+		assert_eq!(stored(&storage, BLOCK_PRODUCERS_PREFIX), 1);
+		assert_eq!(stored(&storage, BLOCK_PRODUCERS_DELTA_PREFIX), 3);
+		let encoded = |block_producers: &[ValidatorStakeView]| serialize(block_producers).unwrap();
+		let check = |storage: &InMemoryStorage, epochs: &[usize]| {
+			let stored = storage.epoch_block_producers().unwrap();
+			assert_eq!(stored.len(), epochs.len());
+			for ((stored_id, block_producers), epoch) in stored.iter().zip(epochs) {
+				assert_eq!(*stored_id, epoch_id(*epoch as u8));
+				assert_eq!(encoded(block_producers), encoded(&sets[*epoch]));
+				let validators = storage.epoch_validators(stored_id).unwrap().unwrap();
+				assert_eq!(encoded(&validators), encoded(&sets[*epoch]));
+			}
+		};
+		check(&storage, &[0, 1, 2, 3]);
+
+		// the epoch after a removed or changed base is stored in full
+		storage.remove_epoch_block_producers(&epoch_id(0)).unwrap();
+		check(&storage, &[1, 2, 3]);
+		storage.insert_epoch_block_producers(epoch_id(2), &sets[2]).unwrap();
+		check(&storage, &[1, 3, 2]);
+		assert_eq!(stored(&storage, BLOCK_PRODUCERS_PREFIX), 2);
+		assert_eq!(stored(&storage, BLOCK_PRODUCERS_DELTA_PREFIX), 1);
+
+		// epoch 2 is at depth 1, so the chain of deltas is cut at epoch 17
+		for epoch in 10..20 {
+			storage.insert_epoch_block_producers(epoch_id(epoch), &sets[0]).unwrap();
+		}
+		let depth = |epoch| storage.delta(&epoch_id(epoch)).unwrap().map(|delta| delta.depth);
+		assert_eq!(depth(16), Some(MAX_DELTA_DEPTH));
+		assert_eq!(depth(17), None);
+		assert_eq!(depth(19), Some(2));
+	}
+
+	/// Fails every write once `writes` went through, as a backend whose process died would.
+	struct CrashingKv {
+		kv: InMemoryKv,
+		writes: usize,
+	}
+
+	impl CrashingKv {
+		fn crash(&mut self) -> LiteClientResult<()> {
+			if self.writes == 0 {
+				return Err(NearLiteClientError::Storage("crashed".into()))
+			}
+			self.writes -= 1;
+			Ok(())
+		}
+	}
+
+	impl KvBackend for CrashingKv {
+		fn get(&self, key: &[u8]) -> LiteClientResult<Option<Vec<u8>>> {
+			self.kv.get(key)
+		}
+
+		fn put(&mut self, key: &[u8], value: &[u8]) -> LiteClientResult<()> {
+			self.crash()?;
+			self.kv.put(key, value)
+		}
+
+		fn delete(&mut self, key: &[u8]) -> LiteClientResult<()> {
+			self.crash()?;
+			self.kv.delete(key)
+		}
+
+		fn iter_prefix(&self, prefix: &[u8]) -> LiteClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+			self.kv.iter_prefix(prefix)
+		}
+	}
+
+	fn head(height: u64) -> LightClientBlockView {
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_lite.height = BlockHeight(height);
+		head
+	}
+
+	fn update(storage: &mut KvStorage<CrashingKv>) -> LiteClientResult<()> {
+		storage.begin_update()?;
+		storage.set_head(&head(2))?;
+		storage.insert_epoch_block_producers(epoch_id(2), &[])?;
+		storage.insert_block_hash(BlockHeight(2), CryptoHash([2; 32]))?;
+		storage.prune_block_hashes(BlockHeight(2))?;
+		storage.commit_update()
+	}
+
+	#[test]
+	fn test_interrupted_updates_are_recovered() {
+		let old = (BlockHeight(1), 1, true);
+		let new = (BlockHeight(2), 2, false);
+		for writes in 0.. {
+			let kv = CrashingKv { kv: InMemoryKv::default(), writes: usize::MAX };
+			let mut storage = KvStorage::new(kv);
+			storage.set_head(&head(1)).unwrap();
+			storage.insert_epoch_block_producers(epoch_id(1), &[]).unwrap();
+			storage.insert_block_hash(BlockHeight(1), CryptoHash([1; 32])).unwrap();
+			storage.backend.writes = writes;
+			let result = update(&mut storage);
+
+			// restarting on what reached the backend
+			let kv = storage.into_backend().kv;
+			let mut storage = KvStorage::new(CrashingKv { kv, writes: usize::MAX });
+			let recovered = storage.recover().unwrap();
+			let state = (
+				storage.head().unwrap().unwrap().inner_lite.height,
+				storage.epoch_block_producers().unwrap().len(),
+				storage.block_hash(BlockHeight(1)).unwrap().is_some(),
+			);
+			assert_eq!(state, if recovered || result.is_ok() { new } else { old }, "{}", writes);
+			assert_eq!(recovered, result.is_err() && writes > 0, "{}", writes);
+			assert!(!storage.recover().unwrap());
+			if result.is_ok() {
+				break
+			}
+		}
+	}
+
+	#[test]
+	fn test_failed_updates_are_not_read_back() {
+		let kv = CrashingKv { kv: InMemoryKv::default(), writes: usize::MAX };
+		let mut storage = KvStorage::new(kv);
+		storage.set_head(&head(1)).unwrap();
+		let height =
+			|storage: &KvStorage<CrashingKv>| storage.head().unwrap().unwrap().inner_lite.height;
+
+		storage.begin_update().unwrap();
+		storage.set_head(&head(3)).unwrap();
+		storage.abort_update().unwrap();
+		assert_eq!(height(&storage), BlockHeight(1));
+
+		// the batch reaches `wal` but is not applied
+		storage.backend.writes = 1;
+		assert!(update(&mut storage).is_err());
+		storage.abort_update().unwrap();
+		storage.backend.writes = usize::MAX;
+		storage.begin_update().unwrap();
+		storage.set_head(&head(3)).unwrap();
+		assert!(matches!(storage.commit_update(), Err(NearLiteClientError::Storage(_))));
+		assert_eq!(height(&storage), BlockHeight(1));
+
+		assert!(storage.recover().unwrap());
+		assert_eq!(height(&storage), BlockHeight(2));
+		storage.begin_update().unwrap();
+		storage.set_head(&head(3)).unwrap();
+		storage.commit_update().unwrap();
+		assert_eq!(height(&storage), BlockHeight(3));
+	}
+}
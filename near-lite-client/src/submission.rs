@@ -0,0 +1,176 @@
+//! # Submission memo
+//!
+//! Relayers retry submissions, and several relayers may submit the same payload. Every payload is
+//! identified by a deterministic [`submission_id`] and the outcome of the last processed ids is
+//! kept around, so a pallet can answer a retry with the original result instead of verifying the
+//! payload (and emitting its events) a second time.
+//!
+//! Only outcomes that a retry would get again are kept: acceptances, and rejections for which the
+//! payload itself is to blame (see [`NearLiteClientError::is_permanent`]). A payload rejected
+//! because the client is behind, or because of its storage, is processed again when retried.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use near_primitives_wasm::{CryptoHash, HostFunctions};
+use sp_std::collections::{btree_map::BTreeMap, vec_deque::VecDeque};
+
+use crate::{LiteClientResult, NearLiteClientError};
+
+/// `sha256(payload)`, where `payload` is the encoded extrinsic argument as received.
+pub fn submission_id<H: HostFunctions>(payload: &[u8]) -> CryptoHash {
+	CryptoHash(H::sha256(payload))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum SubmissionOutcome {
+	Accepted,
+	/// Rejected with the [code](NearLiteClientError::code) of a permanent error.
+	Rejected(u16),
+}
+
+impl SubmissionOutcome {
+	/// The outcome to memoize for `result`, `None` if the error is not permanent.
+	pub fn of<T>(result: &LiteClientResult<T>) -> Option<Self> {
+		match result {
+			Ok(_) => Some(Self::Accepted),
+			Err(err) if err.is_permanent() => Some(Self::Rejected(err.code())),
+			Err(_) => None,
+		}
+	}
+}
+
+/// What [`SubmissionMemo::get_or_process`] did with a submission.
+#[derive(Debug)]
+pub enum Submission<T> {
+	/// The submission was processed before, with this outcome.
+	Memoized(SubmissionOutcome),
+	/// The submission was processed, with this result.
+	Processed(LiteClientResult<T>),
+}
+
+/// Bounded memo of the outcomes of the most recently processed submissions. Once `capacity` is
+/// reached the oldest submission is forgotten.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct SubmissionMemo {
+	capacity: u32,
+	/// submission ids in processing order, oldest first
+	order: VecDeque<CryptoHash>,
+	outcomes: BTreeMap<CryptoHash, SubmissionOutcome>,
+}
+
+impl SubmissionMemo {
+	pub fn new(capacity: u32) -> Self {
+		Self { capacity, order: VecDeque::new(), outcomes: BTreeMap::new() }
+	}
+
+	pub fn capacity(&self) -> u32 {
+		self.capacity
+	}
+
+	pub fn get(&self, submission_id: &CryptoHash) -> Option<&SubmissionOutcome> {
+		self.outcomes.get(submission_id)
+	}
+
+	pub fn len(&self) -> usize {
+		self.order.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.order.is_empty()
+	}
+
+	pub fn record(&mut self, submission_id: CryptoHash, outcome: SubmissionOutcome) {
+		if self.capacity == 0 {
+			return;
+		}
+		if self.outcomes.insert(submission_id, outcome).is_some() {
+			return;
+		}
+		self.order.push_back(submission_id);
+		if self.order.len() > self.capacity as usize {
+			if let Some(oldest) = self.order.pop_front() {
+				self.outcomes.remove(&oldest);
+			}
+		}
+	}
+
+	/// Returns the memoized outcome of `submission_id` if it was already processed. Otherwise runs
+	/// `process` and memoizes its outcome, unless it failed with an error that is not permanent.
+	pub fn get_or_process<T>(
+		&mut self,
+		submission_id: CryptoHash,
+		process: impl FnOnce() -> LiteClientResult<T>,
+	) -> Submission<T> {
+		if let Some(outcome) = self.get(&submission_id) {
+			return Submission::Memoized(outcome.clone())
+		}
+		let result = process();
+		if let Some(outcome) = SubmissionOutcome::of(&result) {
+			self.record(submission_id, outcome);
+		}
+		Submission::Processed(result)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{error::VerificationFailure, test_utils::MockedHostFunctions};
+	use near_primitives_wasm::EpochId;
+
+	#[test]
+	fn test_submission_id_is_deterministic() {
+		let id = submission_id::<MockedHostFunctions>(b"payload");
+		assert_eq!(id, submission_id::<MockedHostFunctions>(b"payload"));
+		assert_ne!(id, submission_id::<MockedHostFunctions>(b"payload2"));
+	}
+
+	#[test]
+	fn test_memo_returns_original_outcome_on_retry() {
+		let mut memo = SubmissionMemo::new(8);
+		let id = submission_id::<MockedHostFunctions>(b"payload");
+		let rejection = memo.get_or_process(id, || -> LiteClientResult<()> {
+			Err(VerificationFailure::InvalidSignature { index: 2 }.into())
+		});
+		assert!(matches!(rejection, Submission::Processed(Err(_))));
+
+		let retry = memo.get_or_process(id, || -> LiteClientResult<()> { unreachable!() });
+		assert!(matches!(retry, Submission::Memoized(SubmissionOutcome::Rejected(104))));
+
+		let id = submission_id::<MockedHostFunctions>(b"payload2");
+		assert!(matches!(memo.get_or_process(id, || Ok(7)), Submission::Processed(Ok(7))));
+		assert!(matches!(
+			memo.get_or_process(id, || -> LiteClientResult<()> { unreachable!() }),
+			Submission::Memoized(SubmissionOutcome::Accepted)
+		));
+	}
+
+	#[test]
+	fn test_memo_processes_transient_failures_again() {
+		let mut memo = SubmissionMemo::new(8);
+		let id = submission_id::<MockedHostFunctions>(b"payload");
+		let epoch_id = EpochId(CryptoHash([1; 32]));
+		let failure = memo.get_or_process(id, || -> LiteClientResult<()> {
+			Err(VerificationFailure::UnknownBlockProducers { epoch_id }.into())
+		});
+		assert!(matches!(failure, Submission::Processed(Err(_))));
+		let failure = memo.get_or_process(id, || -> LiteClientResult<()> {
+			Err(NearLiteClientError::Storage("disk full".into()))
+		});
+		assert!(matches!(failure, Submission::Processed(Err(_))));
+		assert!(memo.is_empty());
+
+		assert!(matches!(memo.get_or_process(id, || Ok(())), Submission::Processed(Ok(()))));
+		assert_eq!(memo.get(&id), Some(&SubmissionOutcome::Accepted));
+	}
+
+	#[test]
+	fn test_memo_forgets_oldest_submission() {
+		let mut memo = SubmissionMemo::new(2);
+		for i in 0..3u8 {
+			memo.record(CryptoHash([i; 32]), SubmissionOutcome::Accepted);
+		}
+		assert_eq!(memo.len(), 2);
+		assert!(memo.get(&CryptoHash([0; 32])).is_none());
+		assert!(memo.get(&CryptoHash([2; 32])).is_some());
+	}
+}
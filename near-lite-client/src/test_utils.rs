@@ -9,12 +9,4 @@ impl HostFunctions for MockedHostFunctions {
 		use sha2::Digest;
 		sha2::Sha256::digest(data).try_into().unwrap()
 	}
-
-	fn verify(
-		signature: near_primitives_wasm::Signature,
-		data: impl AsRef<[u8]>,
-		public_key: near_primitives_wasm::PublicKey,
-	) -> bool {
-		todo!()
-	}
 }
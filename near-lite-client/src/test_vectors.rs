@@ -0,0 +1,152 @@
+//! # Test vectors
+//!
+//! Golden vectors that downstream implementations of the light client (Solidity, Go, ...) can
+//! cross-check against: for a given header, the canonical borsh and JSON encodings together with
+//! every hash and message this crate derives from it.
+
+use borsh::BorshSerialize;
+use near_primitives::views::LightClientBlockView as NearLightClientBlockView;
+use near_primitives_wasm::{
+	BlockHeaderInnerLiteViewFinal, CryptoHash, HostFunctions, LightClientBlockView,
+};
+use serde::{Deserialize, Serialize};
+use std::string::{String, ToString};
+
+use crate::{
+	error::NearLiteClientError, hashes::reconstruct_light_client_block_view_fields,
+	LiteClientResult,
+};
+
+/// Hashes are base58 encoded (as NEAR's RPC does), byte strings are hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderTestVector {
+	pub height: u64,
+	/// `borsh(LightClientBlockView)`
+	pub borsh: String,
+	/// the header as returned by `next_light_client_block`
+	pub json: serde_json::Value,
+	/// `borsh(inner_lite)` without `timestamp_nanosec`, which is what gets hashed
+	pub inner_lite_borsh: String,
+	pub block_hash: String,
+	pub next_block_hash: String,
+	/// the message signed by the block producers in `approvals_after_next`
+	pub approval_message: String,
+	/// `borsh(next_bps)`, if present
+	pub next_bps_borsh: Option<String>,
+	/// `sha256(borsh(next_bps))`, if present; equal to `inner_lite.next_bp_hash` for valid headers
+	pub next_bp_hash: Option<String>,
+}
+
+pub fn header_test_vector<H: HostFunctions>(
+	block_view: &LightClientBlockView,
+) -> LiteClientResult<HeaderTestVector> {
+	let (block_hash, next_block_hash, approval_message) =
+		reconstruct_light_client_block_view_fields::<H>(block_view)?;
+	let next_bps_borsh = block_view.next_bps.as_ref().map(|bps| bps.try_to_vec()).transpose()?;
+	let json = serde_json::to_value(NearLightClientBlockView::try_from(block_view.clone())?)
+		.map_err(|err| NearLiteClientError::TestVector(err.to_string()))?;
+
+	Ok(HeaderTestVector {
+		height: block_view.inner_lite.height.0,
+		borsh: hex::encode(block_view.try_to_vec()?),
+		json,
+		inner_lite_borsh: hex::encode(
+			BlockHeaderInnerLiteViewFinal::from(block_view.inner_lite.clone()).try_to_vec()?,
+		),
+		block_hash: block_hash.to_string(),
+		next_block_hash: next_block_hash.to_string(),
+		approval_message: hex::encode(approval_message),
+		next_bp_hash: next_bps_borsh.as_ref().map(|bps| CryptoHash(H::sha256(bps)).to_string()),
+		next_bps_borsh: next_bps_borsh.map(hex::encode),
+	})
+}
+
+/// Pretty printed JSON array with the vectors of every header, in the given order.
+pub fn header_test_vectors_json<H: HostFunctions>(
+	block_views: &[LightClientBlockView],
+) -> LiteClientResult<String> {
+	let vectors = block_views
+		.iter()
+		.map(header_test_vector::<H>)
+		.collect::<LiteClientResult<std::vec::Vec<_>>>()?;
+	serde_json::to_string_pretty(&vectors)
+		.map_err(|err| NearLiteClientError::TestVector(err.to_string()))
+}
+
+/// Vectors for an EVM verifier contract, every byte string is the hex of what the contract is
+/// given or computes.
+#[cfg(feature = "solidity")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolidityTestVector {
+	/// `abi.encode(HeaderCommitment)` of the header
+	pub header_commitment: String,
+	/// `keccak256(abi.encode(HeaderCommitment))`
+	pub header_commitment_hash: String,
+	pub leaf: String,
+	/// `abi.encode(MerklePathItem[])`
+	pub path: String,
+	/// root of the keccak merkle tree `path` proves `leaf` to be in
+	pub root: String,
+}
+
+/// `block_view` is hashed with `H`, the path with keccak256, see [`crate::solidity`].
+#[cfg(feature = "solidity")]
+pub fn solidity_test_vector<H: HostFunctions>(
+	block_view: &LightClientBlockView,
+	leaf: CryptoHash,
+	path: &[near_primitives_wasm::MerklePathItem],
+) -> LiteClientResult<SolidityTestVector> {
+	use crate::solidity::{abi_encode_merkle_path, HeaderCommitment, Keccak256HostFunctions};
+
+	let commitment = HeaderCommitment::from_block_view::<H>(block_view);
+	let root = crate::compute_root_from_path::<Keccak256HostFunctions>(path, leaf)?;
+	Ok(SolidityTestVector {
+		header_commitment: hex::encode(commitment.abi_encode()),
+		header_commitment_hash: hex::encode(commitment.keccak256().0),
+		leaf: hex::encode(leaf.0),
+		path: hex::encode(abi_encode_merkle_path(path)),
+		root: hex::encode(root.0),
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_utils::MockedHostFunctions;
+
+	#[test]
+	fn test_header_test_vector() {
+		let block_view = LightClientBlockView::new_for_test();
+		let vector = header_test_vector::<MockedHostFunctions>(&block_view).unwrap();
+
+		assert_eq!(vector.height, block_view.inner_lite.height.0);
+		assert_eq!(
+			vector.block_hash,
+			block_view.current_block_hash::<MockedHostFunctions>().to_string()
+		);
+		assert_eq!(vector.borsh, hex::encode(block_view.try_to_vec().unwrap()));
+		assert_eq!(vector.json["inner_lite"]["height"], block_view.inner_lite.height.0);
+		assert_eq!(vector.next_bps_borsh.as_deref(), Some("00000000"));
+
+		let json = header_test_vectors_json::<MockedHostFunctions>(&[block_view]).unwrap();
+		let decoded: std::vec::Vec<HeaderTestVector> = serde_json::from_str(&json).unwrap();
+		assert_eq!(decoded, [vector]);
+	}
+
+	#[cfg(feature = "solidity")]
+	#[test]
+	fn test_solidity_test_vector() {
+		use near_primitives_wasm::{Direction, MerklePathItem};
+
+		let block_view = LightClientBlockView::new_for_test();
+		let path = [MerklePathItem { hash: CryptoHash([1; 32]), direction: Direction::Right }];
+		let vector =
+			solidity_test_vector::<MockedHostFunctions>(&block_view, CryptoHash([2; 32]), &path)
+				.unwrap();
+
+		assert_eq!(vector.header_commitment.len(), 2 * 7 * 32);
+		assert_eq!(vector.leaf, "02".repeat(32));
+		assert_eq!(vector.path.len(), 2 * 4 * 32);
+		assert_ne!(vector.root, vector.leaf);
+	}
+}
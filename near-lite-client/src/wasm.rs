@@ -0,0 +1,181 @@
+//! # Browser bindings
+//!
+//! [`LightClient`] for JavaScript, through `wasm-bindgen`, so that dapps and wallets can follow
+//! NEAR and verify transactions client-side with the same validation as the pallet:
+//!
+//! ```js
+//! import { LightClient } from "near-lite-client";
+//!
+//! const client = new LightClient(borsh.serialize(LightClientBlockView, checkpoint));
+//! const summary = client.validateAndUpdateHead(borsh.serialize(LightClientBlockView, blockView));
+//! client.validateTransaction(outcomeProof, outcomeRootProof, blockOutcomeRoot);
+//! ```
+//!
+//! Everything crosses the boundary borsh encoded, as `Uint8Array`s, and is decoded with the
+//! default [`DecodeLimits`]. Heights are `BigInt`s. Errors are thrown as [`WasmError`]s carrying
+//! the stable [code](NearLiteClientError::code) of the error.
+//!
+//! A browser has no `sp_io` host functions: approvals are verified with [`DalekVerifier`].
+
+use alloc::{format, string::String, vec::Vec};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+	CryptoHash, DalekVerifier, DecodeLimits, LightClient, LightClientBuilder, NearLiteClientError,
+	Sha256HostFunctions, TrustedCheckpoint, UpdateSummary,
+};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmError {
+	code: u16,
+	message: String,
+}
+
+#[wasm_bindgen]
+impl WasmError {
+	/// Stable code of the [`NearLiteClientError`].
+	#[wasm_bindgen(getter)]
+	pub fn code(&self) -> u16 {
+		self.code
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn message(&self) -> String {
+		self.message.clone()
+	}
+}
+
+impl From<NearLiteClientError> for WasmError {
+	fn from(err: NearLiteClientError) -> Self {
+		Self { code: err.code(), message: format!("{:?}", err) }
+	}
+}
+
+/// [`UpdateSummary`], with `camelCase` getters.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmUpdateSummary {
+	#[wasm_bindgen(js_name = oldHeight)]
+	pub old_height: u64,
+	#[wasm_bindgen(js_name = newHeight)]
+	pub new_height: u64,
+	#[wasm_bindgen(js_name = epochChanged)]
+	pub epoch_changed: bool,
+	#[wasm_bindgen(js_name = approvalsChecked)]
+	pub approvals_checked: u32,
+	#[wasm_bindgen(js_name = stakeApprovedBps)]
+	pub stake_approved_bps: u16,
+	#[wasm_bindgen(js_name = alreadyValidated)]
+	pub already_validated: bool,
+}
+
+impl From<UpdateSummary> for WasmUpdateSummary {
+	fn from(summary: UpdateSummary) -> Self {
+		Self {
+			old_height: summary.old_height.0,
+			new_height: summary.new_height.0,
+			epoch_changed: summary.epoch_changed,
+			approvals_checked: summary.approvals_checked as u32,
+			stake_approved_bps: summary.stake_approved_bps,
+			already_validated: summary.already_validated,
+		}
+	}
+}
+
+#[wasm_bindgen(js_name = LightClient)]
+pub struct WasmLightClient {
+	client: LightClient<Sha256HostFunctions>,
+	limits: DecodeLimits,
+}
+
+#[wasm_bindgen(js_class = LightClient)]
+impl WasmLightClient {
+	/// A client trusting the borsh encoded light client block `checkpoint`.
+	#[wasm_bindgen(constructor)]
+	pub fn new(checkpoint: &[u8]) -> Result<WasmLightClient, WasmError> {
+		let limits = DecodeLimits::default();
+		let checkpoint = limits.decode_block_view(checkpoint)?;
+		let client = LightClientBuilder::new()
+			.checkpoint(TrustedCheckpoint(checkpoint))
+			.signature_verifier(DalekVerifier)
+			.build()?;
+		Ok(Self { client, limits })
+	}
+
+	#[wasm_bindgen(getter, js_name = headHeight)]
+	pub fn head_height(&self) -> u64 {
+		self.client.head_height().0
+	}
+
+	#[wasm_bindgen(getter, js_name = headHash)]
+	pub fn head_hash(&self) -> Vec<u8> {
+		self.client.head_hash().0.to_vec()
+	}
+
+	/// Borsh encoding of the head.
+	pub fn head(&self) -> Vec<u8> {
+		borsh::BorshSerialize::try_to_vec(self.client.head())
+			.expect("encoding into a Vec can't fail; qed")
+	}
+
+	/// See [`LightClient::validate_and_update_head`].
+	#[wasm_bindgen(js_name = validateAndUpdateHead)]
+	pub fn validate_and_update_head(
+		&mut self,
+		block_view: &[u8],
+	) -> Result<WasmUpdateSummary, WasmError> {
+		let block_view = self.limits.decode_block_view(block_view)?;
+		Ok(self.client.validate_and_update_head(block_view)?.into())
+	}
+
+	/// See [`LightClient::validate_transaction`]. `outcome_root_proof` is a borsh encoded
+	/// `MerklePath` and `expected_block_outcome_root` a 32 bytes hash.
+	#[wasm_bindgen(js_name = validateTransaction)]
+	pub fn validate_transaction(
+		&self,
+		outcome_proof: &[u8],
+		outcome_root_proof: &[u8],
+		expected_block_outcome_root: &[u8],
+	) -> Result<(), WasmError> {
+		let outcome_proof = self.limits.decode_outcome_proof(outcome_proof)?;
+		let outcome_root_proof = self.limits.decode_merkle_path(outcome_root_proof)?;
+		let expected_block_outcome_root =
+			CryptoHash::try_from(expected_block_outcome_root).map_err(NearLiteClientError::from)?;
+		Ok(self.client.validate_transaction(
+			&outcome_proof,
+			outcome_root_proof,
+			expected_block_outcome_root,
+		)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use borsh::BorshSerialize;
+	use near_primitives_wasm::{BlockHeight, LightClientBlockView};
+
+	fn encode(block_view: &LightClientBlockView) -> Vec<u8> {
+		block_view.try_to_vec().unwrap()
+	}
+
+	#[test]
+	fn test_errors_carry_their_code() {
+		let checkpoint = LightClientBlockView::new_for_test();
+		let mut client = WasmLightClient::new(&encode(&checkpoint)).unwrap();
+		assert_eq!(client.head_height(), checkpoint.inner_lite.height.0);
+		assert_eq!(client.head(), encode(&checkpoint));
+		assert_eq!(WasmLightClient::new(&[0; 3]).unwrap_err().code(), 14);
+
+		let summary = client.validate_and_update_head(&encode(&checkpoint)).unwrap();
+		assert!(summary.already_validated);
+		let mut older = checkpoint;
+		older.inner_lite.height = BlockHeight(0);
+		let err = client.validate_and_update_head(&encode(&older)).unwrap_err();
+		assert_eq!(err.code(), 16);
+		assert!(err.message().starts_with("StaleHeader"));
+
+		assert!(client.validate_transaction(&[], &[0; 4], &[0; 32]).is_err());
+	}
+}
@@ -0,0 +1,27 @@
+//! # Weights
+//!
+//! Weight functions for the verification entry points, as a function of the size of their input.
+//! Pallets embedding the client take a `WeightInfo` in their config; the implementation is
+//! generated by running the benchmarks of [`crate::benchmarking`] in the pallet's
+//! `frame-benchmarking` setup. Weights are in picoseconds of reference time, i.e. what
+//! `Weight::from_ref_time` takes.
+
+pub trait WeightInfo {
+	/// [`validate_head`](crate::validate_head) of a header approved by all of the `v` block
+	/// producers of its epoch.
+	fn validate_head(v: u32) -> u64;
+	/// [`verify_outcome`](crate::verify_outcome) with `d` items in both the outcome proof and the
+	/// outcome root proof.
+	fn verify_outcome(d: u32) -> u64;
+}
+
+/// For tests: every call is free.
+impl WeightInfo for () {
+	fn validate_head(_v: u32) -> u64 {
+		0
+	}
+
+	fn verify_outcome(_d: u32) -> u64 {
+		0
+	}
+}
@@ -0,0 +1,369 @@
+//! # zkVM interface
+//!
+//! The header checks of [`validate_light_block`](crate::validate_light_block) as pure functions
+//! over fixed size inputs, for a zkVM guest (RISC Zero, SP1, ...) proving that a header extends a
+//! head. Nothing is allocated, every loop is bounded by `N`, the maximum number of block producers
+//! of an epoch, and hashing and signature verification go through `H`, so a guest can provide the
+//! accelerated versions of its zkVM.
+//!
+//! `next_bps` can't be a fixed size input, its account ids aren't: [`validate_zk_header`] checks
+//! (1) to (5) of the validation, and returns the `next_bp_hash` of the header that the guest
+//! compares with the block producers of the next epoch, (6), before checking them with
+//! [`validate_next_bps`](crate::validate_next_bps), (7).
+//!
+//! The conversions from the regular types allocate, they are meant for the host.
+
+use near_primitives_wasm::{
+	BlockHeaderInnerLiteViewFinal, BlockHeight, CryptoHash, EpochId, HostFunctions,
+	LightClientBlockView, PublicKey, Signature, ValidatorStakeView,
+};
+
+use crate::{
+	block_validation::{validate_next_epoch_id, ApprovalThreshold},
+	error::{NearLiteClientError, VerificationFailure},
+	LiteClientResult,
+};
+
+/// `borsh(BlockHeaderInnerLiteViewFinal)`
+pub const INNER_LITE_LEN: usize = 208;
+/// `borsh(ApprovalInner::Endorsement(next_block_hash)) ++ borsh(height + 2)`
+pub const APPROVAL_MESSAGE_LEN: usize = 41;
+
+/// What [`validate_zk_header`] needs of the head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZkHead {
+	pub height: BlockHeight,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
+	pub next_bp_hash: CryptoHash,
+}
+
+impl From<&LightClientBlockView> for ZkHead {
+	fn from(head: &LightClientBlockView) -> Self {
+		Self {
+			height: head.inner_lite.height,
+			epoch_id: head.inner_lite.epoch_id,
+			next_epoch_id: head.inner_lite.next_epoch_id,
+			next_bp_hash: head.inner_lite.next_bp_hash,
+		}
+	}
+}
+
+/// A `LightClientBlockView` without `next_bps`, with room for `N` approvals.
+#[derive(Debug, Clone)]
+pub struct ZkHeader<const N: usize> {
+	pub prev_block_hash: CryptoHash,
+	pub next_block_inner_hash: CryptoHash,
+	pub inner_lite: BlockHeaderInnerLiteViewFinal,
+	pub inner_rest_hash: CryptoHash,
+	pub has_next_bps: bool,
+	/// number of items of `approvals_after_next`, only the first ones of `approvals` are set
+	pub approvals_len: usize,
+	pub approvals: [Option<[u8; 64]>; N],
+}
+
+impl<const N: usize> TryFrom<&LightClientBlockView> for ZkHeader<N> {
+	type Error = NearLiteClientError;
+
+	/// Approvals after the `N`th are dropped if they are all `None`, as they are ignored anyway.
+	fn try_from(block_view: &LightClientBlockView) -> LiteClientResult<Self> {
+		let mut approvals = [None; N];
+		for (index, approval) in block_view.approvals_after_next.iter().enumerate() {
+			match (approvals.get_mut(index), approval) {
+				(Some(slot), Some(Signature::Ed25519(signature))) => *slot = Some(signature.0),
+				(_, None) => {},
+				(None, Some(_)) =>
+					return Err(NearLiteClientError::CapacityExceeded(alloc::format!(
+						"more than {} approvals",
+						N
+					))),
+			}
+		}
+		Ok(Self {
+			prev_block_hash: block_view.prev_block_hash,
+			next_block_inner_hash: block_view.next_block_inner_hash,
+			inner_lite: block_view.inner_lite.clone().into(),
+			inner_rest_hash: block_view.inner_rest_hash,
+			has_next_bps: block_view.next_bps.is_some(),
+			approvals_len: block_view.approvals_after_next.len().min(N),
+			approvals,
+		})
+	}
+}
+
+/// The public keys and stakes of the block producers of an epoch, in order.
+#[derive(Debug, Clone)]
+pub struct ZkBlockProducers<const N: usize> {
+	pub len: usize,
+	pub public_keys: [[u8; 32]; N],
+	pub stakes: [u128; N],
+}
+
+impl<const N: usize> TryFrom<&[ValidatorStakeView]> for ZkBlockProducers<N> {
+	type Error = NearLiteClientError;
+
+	fn try_from(block_producers: &[ValidatorStakeView]) -> LiteClientResult<Self> {
+		if block_producers.len() > N {
+			return Err(NearLiteClientError::CapacityExceeded(alloc::format!(
+				"more than {} block producers",
+				N
+			)))
+		}
+		let mut zk_block_producers =
+			Self { len: block_producers.len(), public_keys: [[0; 32]; N], stakes: [0; N] };
+		for (index, block_producer) in block_producers.iter().enumerate() {
+			zk_block_producers.public_keys[index] = block_producer.public_key().0;
+			zk_block_producers.stakes[index] = block_producer.stake();
+		}
+		Ok(zk_block_producers)
+	}
+}
+
+/// What a valid header commits to, for the guest to output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZkOutput {
+	pub block_hash: CryptoHash,
+	pub height: BlockHeight,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
+	/// hash of the block producers of the epoch after `next_epoch_id`
+	pub next_bp_hash: CryptoHash,
+	pub approved_stake: u128,
+	pub total_stake: u128,
+}
+
+/// `borsh(inner_lite)`, which the block hash is computed from.
+pub fn encode_inner_lite(inner_lite: &BlockHeaderInnerLiteViewFinal) -> [u8; INNER_LITE_LEN] {
+	let mut encoded = [0; INNER_LITE_LEN];
+	encoded[..8].copy_from_slice(&inner_lite.height.0.to_le_bytes());
+	encoded[8..40].copy_from_slice(&inner_lite.epoch_id.0 .0);
+	encoded[40..72].copy_from_slice(&inner_lite.next_epoch_id.0 .0);
+	encoded[72..104].copy_from_slice(&inner_lite.prev_state_root.0);
+	encoded[104..136].copy_from_slice(&inner_lite.outcome_root.0);
+	encoded[136..144].copy_from_slice(&inner_lite.timestamp.to_le_bytes());
+	encoded[144..176].copy_from_slice(&inner_lite.next_bp_hash.0);
+	encoded[176..].copy_from_slice(&inner_lite.block_merkle_root.0);
+	encoded
+}
+
+pub fn zk_block_hash<H: HostFunctions, const N: usize>(header: &ZkHeader<N>) -> CryptoHash {
+	let inner_lite_hash = H::sha256(&encode_inner_lite(&header.inner_lite));
+	let inner_hash = H::sha256_concat(&[&inner_lite_hash[..], &header.inner_rest_hash.0]);
+	CryptoHash(H::sha256_concat(&[&inner_hash[..], &header.prev_block_hash.0]))
+}
+
+/// What the block producers sign in the approvals of the header with hash `block_hash`.
+pub fn zk_approval_message<H: HostFunctions, const N: usize>(
+	header: &ZkHeader<N>,
+	block_hash: CryptoHash,
+) -> [u8; APPROVAL_MESSAGE_LEN] {
+	let next_block_hash = H::sha256_concat(&[&header.next_block_inner_hash.0[..], &block_hash.0]);
+	let mut message = [0; APPROVAL_MESSAGE_LEN];
+	// `ApprovalInner::Endorsement` is the first variant
+	message[1..33].copy_from_slice(&next_block_hash);
+	message[33..].copy_from_slice(&(header.inner_lite.height.0 + 2).to_le_bytes());
+	message
+}
+
+/// Checks (1) to (5) of [`validate_light_block`](crate::validate_light_block): `header` is ahead
+/// of `head`, in its epoch or the next one (with `next_bps` then) and naming an epoch after it
+/// that follows from the ones of `head`, and approved by more than `threshold` of the stake of
+/// `block_producers`, the block producers of its epoch. `head` has no hash: the guest checks
+/// [`validate_epoch_derivation`](crate::validate_epoch_derivation) if it knows it. In the epoch of
+/// `head`, `header` must carry the same `next_bp_hash`; entering the next epoch, the guest checks
+/// that `block_producers` hash to the `next_bp_hash` of `head`, as
+/// [`validate_block_producers_commitment`](crate::validate_block_producers_commitment) does.
+pub fn validate_zk_header<H: HostFunctions, const N: usize>(
+	head: &ZkHead,
+	header: &ZkHeader<N>,
+	block_producers: &ZkBlockProducers<N>,
+	threshold: &ApprovalThreshold,
+) -> Result<ZkOutput, VerificationFailure> {
+	let inner_lite = &header.inner_lite;
+	if inner_lite.height <= head.height {
+		return Err(VerificationFailure::HeightNotAhead {
+			head_height: head.height,
+			block_height: inner_lite.height,
+		})
+	}
+	if inner_lite.epoch_id != head.epoch_id && inner_lite.epoch_id != head.next_epoch_id {
+		return Err(VerificationFailure::EpochMismatch {
+			epoch_id: inner_lite.epoch_id,
+			head_epoch_id: head.epoch_id,
+			head_next_epoch_id: head.next_epoch_id,
+		})
+	}
+	validate_next_epoch_id(
+		&head.epoch_id,
+		&head.next_epoch_id,
+		&inner_lite.epoch_id,
+		&inner_lite.next_epoch_id,
+	)?;
+	if inner_lite.epoch_id == head.next_epoch_id && !header.has_next_bps {
+		return Err(VerificationFailure::MissingNextBps { epoch_id: inner_lite.epoch_id })
+	}
+	let len = block_producers.len.min(N);
+	if len == 0 {
+		return Err(VerificationFailure::ZeroTotalStake { block_producers: 0 })
+	}
+	if header.approvals[..len].iter().all(Option::is_none) {
+		return Err(VerificationFailure::NoApprovals { block_producers: len })
+	}
+	if header.approvals_len < len {
+		return Err(VerificationFailure::ApprovalsMissing {
+			approvals: header.approvals_len,
+			block_producers: len,
+		})
+	}
+
+	let block_hash = zk_block_hash::<H, N>(header);
+	let message = zk_approval_message::<H, N>(header, block_hash);
+	let mut total_stake: u128 = 0;
+	for stake in &block_producers.stakes[..len] {
+		total_stake = total_stake.checked_add(*stake).ok_or(VerificationFailure::StakeOverflow)?;
+	}
+	if total_stake == 0 {
+		return Err(VerificationFailure::ZeroTotalStake { block_producers: len })
+	}
+	let mut approved_stake: u128 = 0;
+	for index in 0..len {
+		let stake = block_producers.stakes[index];
+		if let Some(signature) = &header.approvals[index] {
+			let public_key = PublicKey::from_raw(&block_producers.public_keys[index]);
+			if !H::verify(Signature::from_raw(signature), message, public_key) {
+				return Err(VerificationFailure::InvalidSignature { index })
+			}
+			// can't overflow, the approved stake is part of the total stake
+			approved_stake += stake;
+		}
+	}
+	if !threshold.is_reached(approved_stake, total_stake) {
+		return Err(VerificationFailure::StakeThresholdNotReached { approved_stake, total_stake })
+	}
+	if inner_lite.epoch_id == head.epoch_id && inner_lite.next_bp_hash != head.next_bp_hash {
+		return Err(VerificationFailure::BlockProducersNotCommitted {
+			epoch_id: inner_lite.next_epoch_id,
+			expected: head.next_bp_hash,
+			computed: inner_lite.next_bp_hash,
+		})
+	}
+
+	Ok(ZkOutput {
+		block_hash,
+		height: inner_lite.height,
+		epoch_id: inner_lite.epoch_id,
+		next_epoch_id: inner_lite.next_epoch_id,
+		next_bp_hash: inner_lite.next_bp_hash,
+		approved_stake,
+		total_stake,
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{validate_light_block, BlockViewHashes, Sha256HostFunctions};
+	use alloc::format;
+	use borsh::BorshSerialize;
+	use near_primitives_wasm::ValidatorStakeViewV1;
+	use sp_core::{ed25519::Pair, Pair as _};
+	use std::collections::BTreeMap;
+
+	type H = Sha256HostFunctions;
+
+	fn block_producers(pairs: &[Pair]) -> Vec<ValidatorStakeView> {
+		pairs
+			.iter()
+			.enumerate()
+			.map(|(index, pair)| {
+				ValidatorStakeView::V1(ValidatorStakeViewV1 {
+					account_id: format!("node{}.near", index),
+					public_key: PublicKey(pair.public().0),
+					stake: 10,
+				})
+			})
+			.collect()
+	}
+
+	fn header(pairs: &[Pair], approving: usize) -> LightClientBlockView {
+		let mut header = LightClientBlockView::new_for_test();
+		header.inner_lite.height = BlockHeight(2);
+		header.inner_lite.timestamp = 7;
+		header.inner_lite.next_bp_hash =
+			CryptoHash(H::sha256_borsh(&Vec::<ValidatorStakeView>::new()));
+		let message = BlockViewHashes::new::<H>(&header).unwrap().approval_message;
+		header.approvals_after_next = pairs
+			.iter()
+			.enumerate()
+			.map(|(index, pair)| {
+				(index < approving).then(|| Signature::Ed25519(pair.sign(&message)))
+			})
+			.collect();
+		header
+	}
+
+	#[test]
+	fn test_encodings_match_borsh() {
+		let header = header(&[], 0);
+		let zk_header = ZkHeader::<4>::try_from(&header).unwrap();
+		assert_eq!(
+			encode_inner_lite(&zk_header.inner_lite).to_vec(),
+			zk_header.inner_lite.try_to_vec().unwrap()
+		);
+		let block_hash = zk_block_hash::<H, 4>(&zk_header);
+		assert_eq!(block_hash, header.current_block_hash::<H>());
+		assert_eq!(
+			zk_approval_message::<H, 4>(&zk_header, block_hash).to_vec(),
+			BlockViewHashes::new::<H>(&header).unwrap().approval_message
+		);
+	}
+
+	#[test]
+	fn test_zk_validation_matches_validate_light_block() {
+		let pairs: Vec<_> = (0..3u8).map(|seed| Pair::from_seed(&[seed; 32])).collect();
+		let mut head = LightClientBlockView::new_for_test();
+		head.inner_lite.next_bp_hash = header(&[], 0).inner_lite.next_bp_hash;
+		let block_producers = block_producers(&pairs);
+		let epoch_block_producers =
+			BTreeMap::from([(head.inner_lite.epoch_id, block_producers.clone())]);
+		let zk_block_producers = ZkBlockProducers::<4>::try_from(&block_producers[..]).unwrap();
+
+		for approving in 0..=3 {
+			let header = header(&pairs, approving);
+			let expected = validate_light_block::<H>(
+				&head,
+				&header,
+				&epoch_block_producers,
+				&ApprovalThreshold::default(),
+			);
+			let result = validate_zk_header::<H, 4>(
+				&ZkHead::from(&head),
+				&ZkHeader::try_from(&header).unwrap(),
+				&zk_block_producers,
+				&ApprovalThreshold::default(),
+			);
+			match (expected, result) {
+				(Ok(stats), Ok(output)) => {
+					assert_eq!(output.approved_stake, stats.approved_stake);
+					assert_eq!(output.block_hash, header.current_block_hash::<H>());
+				},
+				(Err(NearLiteClientError::Verification(expected)), Err(failure)) =>
+					assert_eq!(failure, expected),
+				other => panic!("validations disagree: {:?}", other),
+			}
+		}
+
+		let mut forged = header(&pairs, 3);
+		forged.approvals_after_next.swap(0, 1);
+		assert_eq!(
+			validate_zk_header::<H, 4>(
+				&ZkHead::from(&head),
+				&ZkHeader::try_from(&forged).unwrap(),
+				&zk_block_producers,
+				&ApprovalThreshold::default(),
+			),
+			Err(VerificationFailure::InvalidSignature { index: 0 })
+		);
+		assert!(ZkBlockProducers::<2>::try_from(&block_producers[..]).is_err());
+	}
+}
@@ -0,0 +1,153 @@
+//! Differential tests against nearcore: the same header pairs are validated by this crate and by
+//! a reference built from `near-primitives` and `near-crypto`, which must agree on every accept
+//! and reject decision.
+//!
+//! The reference is [`nearcore`](near_lite_client_test_utils::nearcore), the specification
+//! written with nearcore's own hashing, approval message and signature verification. The corpus is
+//! the testnet headers of the shared `fixtures` and mutations of them.
+
+use std::collections::BTreeMap;
+
+use near_lite_client::{
+	validate_head, BlockHeight, CryptoHash, EpochId, LightClientBlockView, Sha256HostFunctions,
+	Signature, ValidatorStakeView,
+};
+use near_lite_client_test_utils::nearcore;
+use near_primitives::views::LightClientBlockView as NearLightClientBlockView;
+use serde_json::Value;
+
+fn block_view(rpc_response: &str) -> LightClientBlockView {
+	let response: Value = serde_json::from_str(rpc_response).unwrap();
+	serde_json::from_value::<NearLightClientBlockView>(response["result"].clone())
+		.unwrap()
+		.try_into()
+		.unwrap()
+}
+
+fn first_approval(block_view: &mut LightClientBlockView) -> &mut Option<Signature> {
+	block_view
+		.approvals_after_next
+		.iter_mut()
+		.find(|approval| approval.is_some())
+		.expect("recorded headers are approved")
+}
+
+fn first_next_bp(block_view: &mut LightClientBlockView) -> Option<&mut ValidatorStakeView> {
+	block_view.next_bps.as_mut().and_then(|next_bps| next_bps.first_mut())
+}
+
+type Mutation = fn(&LightClientBlockView, &mut LightClientBlockView);
+
+/// Mutations of the block view validated on top of the head, some of which keep it valid.
+const MUTATIONS: &[(&str, Mutation)] = &[
+	("unchanged", |_, _| {}),
+	("height of the head", |head, block_view| {
+		block_view.inner_lite.height = head.inner_lite.height
+	}),
+	("height below the head", |head, block_view| {
+		block_view.inner_lite.height = BlockHeight(head.inner_lite.height.0 - 1)
+	}),
+	("height further ahead", |_, block_view| block_view.inner_lite.height.0 += 1),
+	("unknown epoch", |_, block_view| {
+		block_view.inner_lite.epoch_id = EpochId(CryptoHash([7; 32]))
+	}),
+	("epoch of the head", |head, block_view| {
+		block_view.inner_lite.epoch_id = head.inner_lite.epoch_id
+	}),
+	("next epoch of the head", |head, block_view| {
+		block_view.inner_lite.epoch_id = head.inner_lite.next_epoch_id
+	}),
+	("next_bps removed", |_, block_view| block_view.next_bps = None),
+	("next_bps emptied", |_, block_view| block_view.next_bps = Some(Vec::new())),
+	("stake of a next block producer changed", |_, block_view| {
+		if let Some(ValidatorStakeView::V1(block_producer)) = first_next_bp(block_view) {
+			block_producer.stake += 1;
+		}
+	}),
+	("next_bp_hash changed", |_, block_view| {
+		block_view.inner_lite.next_bp_hash = CryptoHash([7; 32])
+	}),
+	("one approval removed", |_, block_view| *first_approval(block_view) = None),
+	("a third of the approvals removed", |_, block_view| {
+		let len = block_view.approvals_after_next.len();
+		block_view.approvals_after_next[..len / 3].iter_mut().for_each(|a| *a = None);
+	}),
+	("half of the approvals removed", |_, block_view| {
+		let len = block_view.approvals_after_next.len();
+		block_view.approvals_after_next[..len / 2].iter_mut().for_each(|a| *a = None);
+	}),
+	("last approval truncated", |_, block_view| {
+		block_view.approvals_after_next.pop();
+	}),
+	("approvals truncated by half", |_, block_view| {
+		let len = block_view.approvals_after_next.len();
+		block_view.approvals_after_next.truncate(len / 2);
+	}),
+	("extra approval", |_, block_view| block_view.approvals_after_next.push(None)),
+	("signature corrupted", |_, block_view| {
+		if let Some(Signature::Ed25519(signature)) = first_approval(block_view) {
+			signature.0[0] ^= 1;
+		}
+	}),
+	("approvals rotated", |_, block_view| block_view.approvals_after_next.rotate_left(1)),
+	("prev_block_hash changed", |_, block_view| block_view.prev_block_hash = CryptoHash([7; 32])),
+	("inner_rest_hash changed", |_, block_view| block_view.inner_rest_hash = CryptoHash([7; 32])),
+	("next_block_inner_hash changed", |_, block_view| {
+		block_view.next_block_inner_hash = CryptoHash([7; 32])
+	}),
+	("outcome_root changed", |_, block_view| {
+		block_view.inner_lite.outcome_root = CryptoHash([7; 32])
+	}),
+	("block_merkle_root changed", |_, block_view| {
+		block_view.inner_lite.block_merkle_root = CryptoHash([7; 32])
+	}),
+	("timestamp changed", |_, block_view| block_view.inner_lite.timestamp += 1),
+	// not part of the block hash
+	("timestamp_nanosec changed", |_, block_view| block_view.inner_lite.timestamp_nanosec += 1),
+];
+
+#[test]
+fn decisions_match_nearcore() {
+	let checkpoint =
+		block_view(include_str!("../../fixtures/testnet/next_light_client_block/86441383.json"));
+	let block =
+		block_view(include_str!("../../fixtures/testnet/next_light_client_block/86455909.json"));
+	let next_epoch_block =
+		block_view(include_str!("../../fixtures/testnet/next_light_client_block/86456070.json"));
+
+	let epoch_block_producers: BTreeMap<_, _> = [&checkpoint, &block]
+		.into_iter()
+		.map(|head| (head.inner_lite.next_epoch_id, head.next_bps.clone().unwrap()))
+		.collect();
+	let near_epoch_block_producers = nearcore::epoch_block_producers(&epoch_block_producers);
+
+	let (mut accepted, mut rejected, mut mismatches) = (0, 0, Vec::new());
+	for (head, original) in
+		[(&checkpoint, &block), (&block, &next_epoch_block), (&checkpoint, &next_epoch_block)]
+	{
+		for (name, mutate) in MUTATIONS {
+			let mut block_view = original.clone();
+			mutate(head, &mut block_view);
+			let ours =
+				validate_head::<Sha256HostFunctions>(head, &block_view, &epoch_block_producers);
+			let reference = nearcore::accepts_light_block(
+				&nearcore::block_view(head),
+				&nearcore::block_view(&block_view),
+				&near_epoch_block_producers,
+			);
+			if ours.is_ok() != reference {
+				mismatches.push(format!(
+					"{} at height {}: nearcore accepts={}, this crate {:?}",
+					name, original.inner_lite.height, reference, ours
+				));
+			}
+			if reference {
+				accepted += 1;
+			} else {
+				rejected += 1;
+			}
+		}
+	}
+	assert!(mismatches.is_empty(), "{:#?}", mismatches);
+	assert!(accepted > 3 && rejected > 3, "the corpus only exercises one decision");
+}
@@ -0,0 +1,35 @@
+//! Resubmissions through a [`SubmissionMemo`]: a header rejected for what the head is processed
+//! again once the head moved, instead of being answered with the first rejection.
+
+use borsh::BorshSerialize;
+use near_lite_client::{
+	submission_id, LightClient, NearLiteClientError, Sha256HostFunctions, Submission,
+	SubmissionMemo, VerificationFailure,
+};
+use near_lite_client_test_utils::MockNearChain;
+
+#[test]
+fn test_header_missing_next_bps_is_accepted_after_the_epoch_change() {
+	let mut chain = MockNearChain::<Sha256HostFunctions>::new(4);
+	let mut client =
+		LightClient::<Sha256HostFunctions>::with_checkpoint(chain.checkpoint()).unwrap();
+	let epoch_change = chain.epoch_change();
+	let block = chain.block();
+	let id = submission_id::<Sha256HostFunctions>(&block.try_to_vec().unwrap());
+	let mut memo = SubmissionMemo::new(8);
+
+	// in the next epoch of the head, where only the first header counts
+	let rejection = memo.get_or_process(id, || client.validate_and_update_head(block.clone()));
+	assert!(matches!(
+		rejection,
+		Submission::Processed(Err(NearLiteClientError::Verification(
+			VerificationFailure::MissingNextBps { .. }
+		)))
+	));
+	assert!(memo.get(&id).is_none());
+
+	client.validate_and_update_head(epoch_change).unwrap();
+	let retry = memo.get_or_process(id, || client.validate_and_update_head(block.clone()));
+	assert!(matches!(retry, Submission::Processed(Ok(_))));
+	assert_eq!(client.head_height(), block.inner_lite.height);
+}
@@ -0,0 +1,201 @@
+//! Relayer configuration, loaded from a TOML file:
+//!
+//! ```toml
+//! rpc_url = "https://rpc.testnet.near.org"
+//! # requests fail over to these when `rpc_url` is unhealthy
+//! rpc_fallback_urls = ["https://near-testnet.example"]
+//! # the block following this one is trusted as the starting checkpoint
+//! checkpoint_hash = "8hxkU4avDWFDCsZckig7oN2ypnYvLyb1qmZ3SA1t8iZK"
+//! poll_interval_secs = 5
+//! # Prometheus metrics, with the `metrics` feature
+//! metrics_address = "0.0.0.0:9615"
+//! # the checkpoint is only trusted if 2 out of the 3 endpoints agree on it
+//! checkpoint_rpc_urls = ["https://archival-rpc.testnet.near.org", "https://near-testnet.example"]
+//! checkpoint_quorum = 2
+//!
+//! # poll the RPC every `poll_interval_secs` (the default), or wake up on every message of a feed
+//! [ingestion]
+//! type = "websocket"
+//! url = "wss://blocks.example/ws"
+//! subscribe = '{"method": "subscribe_final_blocks"}'
+//!
+//! # coalesce accepted headers into one submission every 60 seconds, at most 10 per submission
+//! [batching]
+//! max_batch_size = 10
+//! flush_interval_secs = 60
+//! max_submissions_per_minute = 2
+//!
+//! [sink]
+//! type = "substrate"
+//! url = "http://127.0.0.1:9933"
+//! pallet_index = 42
+//! call_index = 0
+//! ```
+
+use std::{fs, io, net::SocketAddr, path::Path, time::Duration};
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RelayerConfig {
+	/// NEAR JSON-RPC endpoint
+	pub rpc_url: String,
+	#[serde(default)]
+	pub rpc_fallback_urls: Vec<String>,
+	/// base58 hash of a block from which the relayer bootstraps
+	pub checkpoint_hash: String,
+	#[serde(default = "default_poll_interval_secs")]
+	pub poll_interval_secs: u64,
+	/// where the Prometheus exporter listens, if the `metrics` feature is enabled
+	pub metrics_address: Option<SocketAddr>,
+	/// endpoints queried for the checkpoint on top of `rpc_url`
+	#[serde(default)]
+	pub checkpoint_rpc_urls: Vec<String>,
+	/// how many endpoints have to agree on the checkpoint
+	#[serde(default = "default_checkpoint_quorum")]
+	pub checkpoint_quorum: usize,
+	#[serde(default)]
+	pub ingestion: IngestionConfig,
+	#[serde(default)]
+	pub batching: BatchingConfig,
+	pub sink: SinkConfig,
+}
+
+/// See [`SubmissionScheduler`](crate::scheduler::SubmissionScheduler). By default every header is
+/// submitted on its own, as soon as it is accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BatchingConfig {
+	#[serde(default = "default_max_batch_size")]
+	pub max_batch_size: usize,
+	#[serde(default)]
+	pub flush_interval_secs: u64,
+	pub max_submissions_per_minute: Option<u32>,
+}
+
+impl BatchingConfig {
+	pub fn flush_interval(&self) -> Duration {
+		Duration::from_secs(self.flush_interval_secs)
+	}
+}
+
+impl Default for BatchingConfig {
+	fn default() -> Self {
+		Self {
+			max_batch_size: default_max_batch_size(),
+			flush_interval_secs: 0,
+			max_submissions_per_minute: None,
+		}
+	}
+}
+
+/// How the relayer learns that there may be a new head.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IngestionConfig {
+	/// asks the RPC every `poll_interval_secs`
+	Poll,
+	/// asks the RPC on every message of a WebSocket feed of new final blocks, see
+	/// [`HeadSubscription`](crate::subscription::HeadSubscription)
+	Websocket { url: String, subscribe: Option<String> },
+}
+
+impl Default for IngestionConfig {
+	fn default() -> Self {
+		Self::Poll
+	}
+}
+
+/// Where accepted headers are pushed to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkConfig {
+	/// appends the hex encoded borsh of every header as a new line
+	File { path: String },
+	/// POSTs the borsh of every batch of headers
+	Http { url: String },
+	/// submits an unsigned extrinsic calling `pallet_index::call_index(borsh(headers))`
+	Substrate { url: String, pallet_index: u8, call_index: u8 },
+}
+
+fn default_poll_interval_secs() -> u64 {
+	5
+}
+
+fn default_checkpoint_quorum() -> usize {
+	1
+}
+
+fn default_max_batch_size() -> usize {
+	1
+}
+
+impl RelayerConfig {
+	pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+		Self::from_toml(&fs::read_to_string(path)?)
+	}
+
+	pub fn from_toml(config: &str) -> io::Result<Self> {
+		toml::from_str(config).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_config() {
+		let config = RelayerConfig::from_toml(
+			r#"
+			rpc_url = "https://rpc.testnet.near.org"
+			checkpoint_hash = "8hxkU4avDWFDCsZckig7oN2ypnYvLyb1qmZ3SA1t8iZK"
+
+			[sink]
+			type = "substrate"
+			url = "http://127.0.0.1:9933"
+			pallet_index = 42
+			call_index = 0
+			"#,
+		)
+		.unwrap();
+		assert_eq!(config.poll_interval_secs, 5);
+		assert_eq!(config.checkpoint_quorum, 1);
+		assert!(config.checkpoint_rpc_urls.is_empty());
+		assert!(config.rpc_fallback_urls.is_empty());
+		assert!(config.metrics_address.is_none());
+		assert_eq!(config.ingestion, IngestionConfig::Poll);
+		assert_eq!(config.batching, BatchingConfig::default());
+		assert_eq!(
+			config.sink,
+			SinkConfig::Substrate {
+				url: "http://127.0.0.1:9933".into(),
+				pallet_index: 42,
+				call_index: 0
+			}
+		);
+		assert!(RelayerConfig::from_toml("rpc_url = 1").is_err());
+	}
+
+	#[test]
+	fn test_parse_websocket_ingestion() {
+		let config = RelayerConfig::from_toml(
+			r#"
+			rpc_url = "https://rpc.testnet.near.org"
+			checkpoint_hash = "8hxkU4avDWFDCsZckig7oN2ypnYvLyb1qmZ3SA1t8iZK"
+
+			[ingestion]
+			type = "websocket"
+			url = "ws://127.0.0.1:8080"
+
+			[sink]
+			type = "file"
+			path = "headers.hex"
+			"#,
+		)
+		.unwrap();
+		assert_eq!(
+			config.ingestion,
+			IngestionConfig::Websocket { url: "ws://127.0.0.1:8080".into(), subscribe: None }
+		);
+	}
+}
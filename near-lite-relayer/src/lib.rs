@@ -3,4 +3,9 @@
 pub mod blockchain_connector;
 mod client_block;
 mod client_proof;
+pub mod config;
+pub mod relayer;
+pub mod scheduler;
+pub mod sink;
 pub mod state;
+pub mod subscription;
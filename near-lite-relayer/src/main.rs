@@ -0,0 +1,28 @@
+//! `near-lite-relayer [config.toml]`: keeps feeding new NEAR light client blocks to a light client
+//! and pushes the accepted ones to the configured sink.
+
+use std::{env, io};
+
+use near_lite_relayer::{config::RelayerConfig, relayer::Relayer};
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+	#[cfg(not(feature = "tracing"))]
+	env_logger::init();
+	// `log` records are forwarded to the subscriber, filtered by `RUST_LOG` as well
+	#[cfg(feature = "tracing")]
+	tracing_subscriber::fmt()
+		.with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+		.with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+		.init();
+	let config_path = env::args().nth(1).unwrap_or_else(|| "relayer.toml".to_owned());
+	let config = RelayerConfig::from_file(&config_path)?;
+	#[cfg(feature = "metrics")]
+	if let Some(address) = config.metrics_address {
+		metrics_exporter_prometheus::PrometheusBuilder::new()
+			.with_http_listener(address)
+			.install()
+			.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+	}
+	Relayer::bootstrap(config).await?.run().await
+}
@@ -0,0 +1,164 @@
+//! Polls NEAR for new light client blocks, validates them with [`LightClient`] and pushes the
+//! accepted ones to a [`HeaderSink`].
+
+use std::{
+	io,
+	str::FromStr,
+	time::{Duration, Instant},
+};
+
+use near_lite_client::{
+	rpc::{quorum_checkpoint, NearRpcClient},
+	CryptoHash, LightClient, LightClientBlockView, SanityCheck, Sha256HostFunctions,
+};
+
+use crate::{
+	config::{IngestionConfig, RelayerConfig},
+	scheduler::SubmissionScheduler,
+	sink::HeaderSink,
+	subscription::HeadSubscription,
+};
+
+pub struct Relayer {
+	rpc: NearRpcClient,
+	client: LightClient,
+	sink: HeaderSink,
+	scheduler: SubmissionScheduler<LightClientBlockView>,
+	ingestion: IngestionConfig,
+	poll_interval: Duration,
+}
+
+impl Relayer {
+	/// Trusts the block following `config.checkpoint_hash` as the checkpoint of the client, once
+	/// `config.checkpoint_quorum` of the configured endpoints agree on it.
+	pub async fn bootstrap(config: RelayerConfig) -> io::Result<Self> {
+		let rpc = NearRpcClient::with_endpoints(
+			std::iter::once(config.rpc_url).chain(config.rpc_fallback_urls),
+		);
+		let checkpoint_hash = CryptoHash::from_str(&config.checkpoint_hash).map_err(other)?;
+		let endpoints = std::iter::once(rpc.clone())
+			.chain(config.checkpoint_rpc_urls.into_iter().map(NearRpcClient::new))
+			.collect::<Vec<_>>();
+		let checkpoint = quorum_checkpoint::<Sha256HostFunctions>(
+			&endpoints,
+			checkpoint_hash,
+			config.checkpoint_quorum,
+		)
+		.await
+		.map_err(other)?;
+		log::info!("bootstrapping from checkpoint height={}", checkpoint.0.inner_lite.height);
+
+		Ok(Self {
+			rpc,
+			client: LightClient::with_checkpoint(checkpoint).map_err(other)?,
+			sink: config.sink.into(),
+			scheduler: SubmissionScheduler::new(config.batching),
+			ingestion: config.ingestion,
+			poll_interval: Duration::from_secs(config.poll_interval_secs),
+		})
+	}
+
+	pub fn client(&self) -> &LightClient {
+		&self.client
+	}
+
+	/// Fetches the block following the current head and, if the client accepts it, schedules it
+	/// for submission to the sink. Returns whether the head moved.
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(level = "info", skip_all, fields(head_height = self.client.head_height().0))
+	)]
+	pub async fn step(&mut self) -> io::Result<bool> {
+		let head = self.client.head();
+		let head_hash = head.current_block_hash::<Sha256HostFunctions>();
+		let head_height = head.inner_lite.height;
+		let block_view = match self.rpc.next_light_client_block(head_hash).await.map_err(other)? {
+			Some(block_view) if block_view.inner_lite.height > head_height => block_view,
+			_ => return Ok(false),
+		};
+
+		let height = block_view.inner_lite.height;
+		if let Err(err) = block_view.sanity_check() {
+			log::warn!("discarded malformed block at height={}: {:?}", height, err);
+			return Ok(false);
+		}
+		let summary = match self.client.validate_and_update_head(block_view.clone()) {
+			Ok(summary) => summary,
+			Err(err) => {
+				log::warn!("rejected block at height={}: {:?}", height, err);
+				return Ok(false);
+			},
+		};
+		log::info!(
+			"accepted block at height={} approvals={} stake_approved_bps={} epoch_changed={}",
+			height,
+			summary.approvals_checked,
+			summary.stake_approved_bps,
+			summary.epoch_changed
+		);
+		self.scheduler.push(block_view, Instant::now());
+		self.flush().await?;
+		Ok(true)
+	}
+
+	/// Submits the pending headers to the sink, if a batch is due.
+	#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+	pub async fn flush(&mut self) -> io::Result<()> {
+		while let Some(batch) = self.scheduler.take_batch(Instant::now()) {
+			if let Err(err) = self.sink.push(&batch).await {
+				self.scheduler.requeue(batch, Instant::now());
+				return Err(err)
+			}
+			log::info!("submitted {} headers", batch.len());
+		}
+		Ok(())
+	}
+
+	/// Runs [`Relayer::step`] until the head stops moving.
+	pub async fn catch_up(&mut self) -> io::Result<()> {
+		while self.step().await? {}
+		#[cfg(feature = "metrics")]
+		self.record_head_lag().await;
+		self.flush().await
+	}
+
+	#[cfg(feature = "metrics")]
+	async fn record_head_lag(&self) {
+		match self.rpc.final_block_height().await {
+			Ok(tip) =>
+				near_lite_client::metrics::head_lag(tip.blocks_since(self.client.head_height())),
+			Err(err) => log::debug!("could not fetch the final block height: {:?}", err),
+		}
+	}
+
+	/// Keeps the head up to date forever, as configured by `ingestion`.
+	pub async fn run(&mut self) -> io::Result<()> {
+		loop {
+			let result = match self.ingestion.clone() {
+				IngestionConfig::Poll => self.catch_up().await,
+				IngestionConfig::Websocket { url, subscribe } =>
+					self.run_subscription(&url, subscribe.as_deref()).await,
+			};
+			if let Err(err) = result {
+				log::error!("{}", err);
+			}
+			tokio::time::sleep(self.poll_interval).await;
+		}
+	}
+
+	/// Catches up on every notification of the feed, until it closes.
+	async fn run_subscription(&mut self, url: &str, subscribe: Option<&str>) -> io::Result<()> {
+		let mut subscription = HeadSubscription::connect(url, subscribe).await?;
+		// blocks finalized while disconnected won't be notified
+		self.catch_up().await?;
+		while subscription.next().await? {
+			self.catch_up().await?;
+		}
+		log::warn!("subscription to {} closed, reconnecting", url);
+		Ok(())
+	}
+}
+
+fn other(err: impl std::fmt::Debug) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, format!("{:?}", err))
+}
@@ -0,0 +1,141 @@
+//! Coalesces validated items (headers, proofs) into batches and paces their submission, so that
+//! the destination chain receives at most one submission per `flush_interval` and never more
+//! than `max_submissions_per_minute`.
+
+use std::{
+	collections::VecDeque,
+	time::{Duration, Instant},
+};
+
+use crate::config::BatchingConfig;
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+pub struct SubmissionScheduler<T> {
+	config: BatchingConfig,
+	pending: VecDeque<T>,
+	/// when the oldest pending item was queued
+	pending_since: Option<Instant>,
+	/// submissions within the last `RATE_LIMIT_WINDOW`
+	submitted_at: VecDeque<Instant>,
+}
+
+impl<T> SubmissionScheduler<T> {
+	pub fn new(config: BatchingConfig) -> Self {
+		Self {
+			config,
+			pending: VecDeque::new(),
+			pending_since: None,
+			submitted_at: VecDeque::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.pending.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+
+	pub fn push(&mut self, item: T, now: Instant) {
+		self.pending_since.get_or_insert(now);
+		self.pending.push_back(item);
+	}
+
+	/// Puts back, in front of the queue, a batch whose submission failed.
+	pub fn requeue(&mut self, batch: Vec<T>, now: Instant) {
+		self.pending_since.get_or_insert(now);
+		for item in batch.into_iter().rev() {
+			self.pending.push_front(item);
+		}
+	}
+
+	/// Removes and returns the next batch, if one is due at `now` and the rate limit allows it.
+	pub fn take_batch(&mut self, now: Instant) -> Option<Vec<T>> {
+		let pending_since = self.pending_since?;
+		let is_full = self.pending.len() >= self.config.max_batch_size.max(1);
+		let is_due = now.saturating_duration_since(pending_since) >= self.config.flush_interval();
+		if !(is_full || is_due) || !self.is_within_rate_limit(now) {
+			return None
+		}
+
+		let batch_size = self.pending.len().min(self.config.max_batch_size.max(1));
+		let batch = self.pending.drain(..batch_size).collect();
+		self.pending_since = if self.pending.is_empty() { None } else { Some(now) };
+		self.submitted_at.push_back(now);
+		Some(batch)
+	}
+
+	fn is_within_rate_limit(&mut self, now: Instant) -> bool {
+		while matches!(
+			self.submitted_at.front(),
+			Some(at) if now.saturating_duration_since(*at) >= RATE_LIMIT_WINDOW
+		) {
+			self.submitted_at.pop_front();
+		}
+		self.config
+			.max_submissions_per_minute
+			.map_or(true, |max| self.submitted_at.len() < max as usize)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn scheduler(
+		max_batch_size: usize,
+		flush_interval_secs: u64,
+		max_submissions_per_minute: Option<u32>,
+	) -> SubmissionScheduler<u32> {
+		SubmissionScheduler::new(BatchingConfig {
+			max_batch_size,
+			flush_interval_secs,
+			max_submissions_per_minute,
+		})
+	}
+
+	#[test]
+	fn test_items_are_coalesced_until_the_flush_interval() {
+		let now = Instant::now();
+		let mut scheduler = scheduler(10, 6, None);
+		scheduler.push(1, now);
+		scheduler.push(2, now + Duration::from_secs(2));
+		assert!(scheduler.take_batch(now + Duration::from_secs(5)).is_none());
+		assert_eq!(scheduler.take_batch(now + Duration::from_secs(6)), Some(vec![1, 2]));
+		assert!(scheduler.is_empty());
+	}
+
+	#[test]
+	fn test_full_batches_are_submitted_right_away() {
+		let now = Instant::now();
+		let mut scheduler = scheduler(2, 60, None);
+		(0..3).for_each(|i| scheduler.push(i, now));
+		assert_eq!(scheduler.take_batch(now), Some(vec![0, 1]));
+		assert!(scheduler.take_batch(now).is_none());
+		assert_eq!(scheduler.len(), 1);
+	}
+
+	#[test]
+	fn test_rate_limit() {
+		let now = Instant::now();
+		let mut scheduler = scheduler(1, 0, Some(2));
+		(0..3).for_each(|i| scheduler.push(i, now));
+		assert_eq!(scheduler.take_batch(now), Some(vec![0]));
+		assert_eq!(scheduler.take_batch(now), Some(vec![1]));
+		assert!(scheduler.take_batch(now + Duration::from_secs(59)).is_none());
+		assert_eq!(scheduler.take_batch(now + Duration::from_secs(60)), Some(vec![2]));
+	}
+
+	#[test]
+	fn test_failed_batches_are_requeued_first() {
+		let now = Instant::now();
+		let mut scheduler = scheduler(2, 0, None);
+		(0..3).for_each(|i| scheduler.push(i, now));
+		let batch = scheduler.take_batch(now).unwrap();
+		scheduler.requeue(batch, now);
+		assert_eq!(scheduler.take_batch(now), Some(vec![0, 1]));
+		assert_eq!(scheduler.take_batch(now), Some(vec![2]));
+	}
+}
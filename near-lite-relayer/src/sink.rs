@@ -0,0 +1,102 @@
+//! Destinations of the headers accepted by the relayer.
+
+use std::{
+	io::{self, Write},
+	path::PathBuf,
+};
+
+use borsh::BorshSerialize;
+use near_lite_client::LightClientBlockView;
+use parity_scale_codec::{Compact, Encode};
+
+use crate::config::SinkConfig;
+
+/// Version byte of an unsigned extrinsic (format version 4).
+const UNSIGNED_EXTRINSIC_VERSION: u8 = 4;
+
+pub enum HeaderSink {
+	File(PathBuf),
+	Http { url: String, http: reqwest::Client },
+	Substrate { url: String, pallet_index: u8, call_index: u8, http: reqwest::Client },
+}
+
+impl From<SinkConfig> for HeaderSink {
+	fn from(config: SinkConfig) -> Self {
+		match config {
+			SinkConfig::File { path } => Self::File(path.into()),
+			SinkConfig::Http { url } => Self::Http { url, http: reqwest::Client::new() },
+			SinkConfig::Substrate { url, pallet_index, call_index } =>
+				Self::Substrate { url, pallet_index, call_index, http: reqwest::Client::new() },
+		}
+	}
+}
+
+impl HeaderSink {
+	/// Submits a batch of headers, in one request for the HTTP and Substrate sinks.
+	pub async fn push(&self, headers: &[LightClientBlockView]) -> io::Result<()> {
+		let batch = headers.try_to_vec()?;
+		match self {
+			Self::File(path) => {
+				let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+				for header in headers {
+					writeln!(file, "{}", hex::encode(header.try_to_vec()?))?;
+				}
+				Ok(())
+			},
+			Self::Http { url, http } => {
+				http.post(url)
+					.header("content-type", "application/octet-stream")
+					.body(batch)
+					.send()
+					.await
+					.and_then(|response| response.error_for_status())
+					.map_err(other)?;
+				Ok(())
+			},
+			Self::Substrate { url, pallet_index, call_index, http } => {
+				let extrinsic = unsigned_extrinsic(*pallet_index, *call_index, &batch);
+				let response = http
+					.post(url)
+					.json(&serde_json::json!({
+						"jsonrpc": "2.0",
+						"method": "author_submitExtrinsic",
+						"params": [format!("0x{}", hex::encode(extrinsic))],
+						"id": 1,
+					}))
+					.send()
+					.await
+					.map_err(other)?
+					.json::<serde_json::Value>()
+					.await
+					.map_err(other)?;
+				match response.get("error") {
+					Some(error) => Err(other(error)),
+					None => Ok(()),
+				}
+			},
+		}
+	}
+}
+
+/// SCALE encoding of an unsigned extrinsic whose only argument is `headers: Vec<u8>`.
+fn unsigned_extrinsic(pallet_index: u8, call_index: u8, headers: &[u8]) -> Vec<u8> {
+	let mut body = vec![UNSIGNED_EXTRINSIC_VERSION, pallet_index, call_index];
+	headers.encode_to(&mut body);
+	let mut extrinsic = Compact(body.len() as u32).encode();
+	extrinsic.extend(body);
+	extrinsic
+}
+
+fn other(err: impl ToString) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_unsigned_extrinsic_encoding() {
+		assert_eq!(unsigned_extrinsic(42, 1, &[0xaa, 0xbb]), [24, 4, 42, 1, 8, 0xaa, 0xbb]);
+	}
+}
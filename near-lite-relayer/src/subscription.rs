@@ -0,0 +1,44 @@
+//! WebSocket feed of new final blocks.
+//!
+//! NEAR nodes don't push light client blocks, so the feed (an indexer, NEAR Lake, a node
+//! plugin, ...) is only used as a trigger: every message means "there may be a new head" and the
+//! light client block itself is still fetched from, and verified against, the RPC.
+
+use std::io;
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+pub struct HeadSubscription {
+	stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl HeadSubscription {
+	/// Connects to `url` and sends `subscribe`, if any, as the first text message.
+	pub async fn connect(url: &str, subscribe: Option<&str>) -> io::Result<Self> {
+		let (mut stream, _) = tokio_tungstenite::connect_async(url).await.map_err(other)?;
+		if let Some(subscribe) = subscribe {
+			stream.send(Message::Text(subscribe.to_owned())).await.map_err(other)?;
+		}
+		Ok(Self { stream })
+	}
+
+	/// Waits for the next notification. Returns `false` once the feed is closed.
+	pub async fn next(&mut self) -> io::Result<bool> {
+		while let Some(message) = self.stream.next().await {
+			match message.map_err(other)? {
+				Message::Text(_) | Message::Binary(_) => return Ok(true),
+				Message::Ping(payload) =>
+					self.stream.send(Message::Pong(payload)).await.map_err(other)?,
+				Message::Close(_) => return Ok(false),
+				_ => {},
+			}
+		}
+		Ok(false)
+	}
+}
+
+fn other(err: impl ToString) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, err.to_string())
+}
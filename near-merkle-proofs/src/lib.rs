@@ -9,6 +9,7 @@ extern crate no_std_compat as std;
 
 mod nibble;
 pub mod state_proof;
+pub mod trie;
 
 use borsh::BorshSerialize;
 use core::marker::PhantomData;
@@ -540,7 +541,7 @@ mod tests {
 		let mp2 = &merkle_proofs[1];
 
 		assert_eq!(
-			CryptoHash::from_raw(compute_root_from_path_and_item(mp, &1).0.as_ref()),
+			CryptoHash::from_raw(&compute_root_from_path_and_item(mp, &1).0),
 			root_hash_ext
 		);
 		assert_eq!(compute_root_from_path_and_item(mp2, &2), root_hash);
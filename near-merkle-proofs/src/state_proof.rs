@@ -1,193 +1,12 @@
-//! Most of this code has been ported from NEAR CORE (near-store)
-//! verbatim as much as possible. There has been some tweaks in order
-//! to make it no_std compatible.
+//! Verification of state proofs, the trie nodes themselves are decoded and walked by
+//! [`crate::trie`].
 
 use near_primitives_wasm::{CryptoHash, HostFunctions};
 use no_std_compat as std;
-use std::{string::String, vec::Vec};
+use std::vec::Vec;
 
-use core2::io::{Cursor, Read};
-
-use crate::nibble::NibbleSlice;
-
-#[derive(Debug, Eq, PartialEq)]
-pub struct RawTrieNodeWithSize {
-	node: RawTrieNode,
-	memory_usage: u64,
-}
-
-#[derive(Debug, Eq, PartialEq)]
-#[allow(clippy::large_enum_variant)]
-pub enum RawTrieNode {
-	Leaf(Vec<u8>, u32, CryptoHash),
-	Branch([Option<CryptoHash>; 16], Option<(u32, CryptoHash)>),
-	Extension(Vec<u8>, CryptoHash),
-}
-
-const LEAF_NODE: u8 = 0;
-const BRANCH_NODE_NO_VALUE: u8 = 1;
-const BRANCH_NODE_WITH_VALUE: u8 = 2;
-const EXTENSION_NODE: u8 = 3;
-
-/// ReadBytesExtLittleEndian parses anythign that implements `Read` and converts
-/// the data into primitives such as u8, u16, u32.
-/// ## Assumption
-/// ReadBytesExtLittleEndian assumes data coming in LittleEndian
-trait ReadBytesExtLittleEndian: Read {
-	fn read_u8(&mut self) -> Result<u8, String> {
-		let mut buff = [0u8; 1];
-		self.take(1)
-			.read_exact(&mut buff)
-			.map_err(|e| String::from("Could not read two bytes"))?;
-		Ok(u8::from_le_bytes(buff))
-	}
-	fn read_u16(&mut self) -> Result<u16, String> {
-		let mut buff = [0u8; 2];
-		self.take(2)
-			.read_exact(&mut buff)
-			.map_err(|e| String::from("Could not read two bytes"))?;
-		Ok(u16::from_le_bytes(buff))
-	}
-	fn read_u32(&mut self) -> Result<u32, String> {
-		let mut buff = [0u8; 4];
-		self.take(4)
-			.read_exact(&mut buff)
-			.map_err(|e| String::from("Could not read two bytes"))?;
-		Ok(u32::from_le_bytes(buff))
-	}
-}
-
-impl<T: core::convert::AsRef<[u8]>> ReadBytesExtLittleEndian for Cursor<T> {}
-
-// ported from NEAR CORE: decodes bytes into children nods of the state trie
-fn decode_children(cursor: &mut Cursor<&[u8]>) -> Result<[Option<CryptoHash>; 16], String> {
-	let mut children: [Option<CryptoHash>; 16] = Default::default();
-	let bitmap = cursor.read_u16().map_err(|_| String::from("decoding error"))?;
-	let mut pos = 1;
-	for child in &mut children {
-		if bitmap & pos != 0 {
-			let mut arr = [0; 32];
-			cursor.read_exact(&mut arr).map_err(|_| String::from("decoding error"))?;
-			*child = Some(CryptoHash::try_from(&arr[..]).unwrap());
-		}
-		pos <<= 1;
-	}
-	Ok(children)
-}
-
-impl RawTrieNodeWithSize {
-	fn encode_into(&self, out: &mut Vec<u8>) {
-		self.node.encode_into(out);
-		out.extend(self.memory_usage.to_le_bytes());
-	}
-
-	/// decode is used to convert the proof that is sent by the RPC into `RawTrieNodeWithSize`
-	pub fn decode(bytes: &[u8]) -> Result<Self, String> {
-		if bytes.len() < 8 {
-			return Err("Wrong type".into());
-		}
-		let node = RawTrieNode::decode(&bytes[0..bytes.len() - 8])?;
-		let mut arr: [u8; 8] = Default::default();
-		arr.copy_from_slice(&bytes[bytes.len() - 8..]);
-		let memory_usage = u64::from_le_bytes(arr);
-		Ok(RawTrieNodeWithSize { node, memory_usage })
-	}
-}
-
-impl RawTrieNode {
-	fn encode_into(&self, out: &mut Vec<u8>) {
-		// size in state_parts = size + 8 for RawTrieNodeWithSize + 8 for borsh vector length
-		match &self {
-			// size <= 1 + 4 + 4 + 32 + key_length + value_length
-			RawTrieNode::Leaf(key, value_length, value_hash) => {
-				out.push(LEAF_NODE);
-				out.extend((key.len() as u32).to_le_bytes());
-				out.extend(key);
-				out.extend((*value_length as u32).to_le_bytes());
-				out.extend(value_hash.as_bytes());
-			},
-			// size <= 1 + 4 + 32 + value_length + 2 + 32 * num_children
-			RawTrieNode::Branch(children, value) => {
-				if let Some((value_length, value_hash)) = value {
-					out.push(BRANCH_NODE_WITH_VALUE);
-					out.extend((*value_length as u32).to_le_bytes());
-					out.extend(value_hash.as_bytes());
-				} else {
-					out.push(BRANCH_NODE_NO_VALUE);
-				}
-				let mut bitmap: u16 = 0;
-				let mut pos: u16 = 1;
-				for child in children.iter() {
-					if child.is_some() {
-						bitmap |= pos
-					}
-					pos <<= 1;
-				}
-				out.extend(bitmap.to_le_bytes());
-				for child in children.iter() {
-					if let Some(hash) = child {
-						out.extend(hash.as_bytes());
-					}
-				}
-			},
-			// size <= 1 + 4 + key_length + 32
-			RawTrieNode::Extension(key, child) => {
-				out.push(EXTENSION_NODE);
-				out.extend((key.len() as u32).to_le_bytes());
-				out.extend(key);
-				out.extend(child.as_bytes());
-			},
-		}
-	}
-
-	fn decode(bytes: &[u8]) -> Result<Self, String> {
-		let mut cursor = Cursor::new(bytes);
-		match cursor.read_u8().map_err(|_| String::from("decoding error"))? {
-			LEAF_NODE => {
-				let key_length = cursor.read_u32().map_err(|_| String::from("decoding error"))?;
-				let mut key = (0..key_length as u8).into_iter().collect::<Vec<_>>();
-				cursor
-					.read_exact(&mut key)
-					.map(|err| err.into())
-					.map_err(|_| String::from("decoding error"))?;
-				let value_length = cursor.read_u32().map_err(|_| String::from("decoding error"))?;
-				let mut arr = [0; 32];
-				cursor.read_exact(&mut arr).map_err(|_| String::from("decoding error"))?;
-				let value_hash = CryptoHash(arr);
-				Ok(RawTrieNode::Leaf(key, value_length, value_hash))
-			},
-			BRANCH_NODE_NO_VALUE => {
-				let children = decode_children(&mut cursor)?;
-				Ok(RawTrieNode::Branch(children, None))
-			},
-			BRANCH_NODE_WITH_VALUE => {
-				let value_length = cursor.read_u32().map_err(|_| String::from("decoding error"))?;
-				let mut arr = [0; 32];
-				cursor.read_exact(&mut arr).map_err(|_| String::from("decoding error"))?;
-				let value_hash = CryptoHash(arr);
-				let children = decode_children(&mut cursor)?;
-				Ok(RawTrieNode::Branch(children, Some((value_length, value_hash))))
-			},
-			EXTENSION_NODE => {
-				let key_length = cursor.read_u32().map_err(|_| String::from("decoding error"))?;
-				let mut key = (0..key_length as u8).into_iter().collect::<Vec<_>>();
-				cursor.read_exact(&mut key).map_err(|_| String::from("decoding error"))?;
-				let mut child = [0; 32];
-				cursor.read_exact(&mut child).map_err(|_| String::from("decoding error"))?;
-				Ok(RawTrieNode::Extension(key, CryptoHash(child)))
-			},
-			_ => Err("Wrong type".into()),
-		}
-	}
-
-	fn get_key(&self) -> Option<&[u8]> {
-		match self {
-			RawTrieNode::Leaf(key, _, _) => Some(key),
-			RawTrieNode::Extension(key, _) => Some(key),
-			RawTrieNode::Branch(_, _) => None,
-		}
-	}
-}
+pub use crate::trie::{RawTrieNode, RawTrieNodeWithSize};
+use crate::trie::lookup;
 
 /// Verifies proof of membership and non membership of state proofs.
 pub fn verify_state_proof<H: HostFunctions>(
@@ -195,83 +14,15 @@ pub fn verify_state_proof<H: HostFunctions>(
 	levels: &Vec<RawTrieNodeWithSize>,
 	// when verifying proofs of non-membership, this value should be None
 	maybe_expected_value: Option<&[u8]>,
-	mut expected_hash: CryptoHash,
+	expected_hash: CryptoHash,
 ) -> bool {
-	let mut v = Vec::new();
-	let mut hash_node = |node: &RawTrieNodeWithSize| {
-		v.clear();
-		node.encode_into(&mut v);
-		CryptoHash::from_raw(&H::sha256(&v))
-	};
-	let mut hash = CryptoHash::default();
-	let mut key = NibbleSlice::new(key);
-	if levels.is_empty() && maybe_expected_value.is_some() {
-		return false;
-	}
-	for node in levels.iter() {
-		match node {
-			RawTrieNodeWithSize { node: RawTrieNode::Leaf(_, _, value_hash), .. } => {
-				hash = hash_node(&node);
-				if hash != expected_hash {
-					return false;
-				}
-
-				let node_key = node.node.get_key().expect("we've just wrapped the value; qed");
-				let nib = &NibbleSlice::from_encoded(&node_key).0;
-				if &key != nib {
-					return maybe_expected_value.is_none();
-				}
-
-				return if let Some(value) = maybe_expected_value {
-					CryptoHash::hash_bytes(value) == *value_hash
-				} else {
-					false
-				};
-			},
-			RawTrieNodeWithSize { node: RawTrieNode::Extension(_, child_hash), .. } => {
-				hash = hash_node(&node);
-				if hash != expected_hash {
-					return false;
-				}
-				expected_hash = *child_hash;
-
-				// To avoid unnecessary copy
-				let node_key = node.node.get_key().expect("we've just wrapped the value; qed");
-				let nib = NibbleSlice::from_encoded(&node_key).0;
-				if !key.starts_with(&nib) {
-					return maybe_expected_value.is_none();
-				}
-				key = key.mid(nib.len());
-			},
-			RawTrieNodeWithSize { node: RawTrieNode::Branch(children, value), .. } => {
-				hash = hash_node(&node);
-				if hash != expected_hash {
-					return false;
-				}
-
-				if key.is_empty() {
-					// TODO: validate value size?
-					let maybe_value = value.map(|x| x.1);
-					let maybe_expected_value = maybe_expected_value.map(CryptoHash::hash_bytes);
-					return match (maybe_expected_value, maybe_value) {
-						(Some(expected_value), Some(value)) => expected_value == value,
-						(None, Some(_)) => false,
-						(Some(_), None) => false,
-						(None, None) => true,
-					};
-				}
-				let index = key.at(0);
-				match &children[index as usize] {
-					Some(child_hash) => {
-						key = key.mid(1);
-						expected_hash = *child_hash;
-					},
-					None => return maybe_expected_value.is_none(),
-				}
-			},
-		}
+	match lookup::<H>(key, levels, expected_hash) {
+		Ok(value) => {
+			value.map(|value| value.hash) ==
+				maybe_expected_value.map(|value| CryptoHash(H::sha256(value)))
+		},
+		Err(_) => false,
 	}
-	maybe_expected_value.is_none() && hash == expected_hash
 }
 
 #[cfg(test)]
@@ -0,0 +1,368 @@
+//! # Trie
+//!
+//! Decoding of the nodes of NEAR's state trie (ported from near-store's `RawTrieNode`) and the
+//! walk of a proof path from a state root down to a key. Every proof about the state under
+//! `prev_state_root` (accounts, access keys, contract storage, ...) is such a path.
+
+use near_primitives_wasm::{CryptoHash, HostFunctions};
+use no_std_compat as std;
+use std::{string::String, vec, vec::Vec};
+
+use core2::io::{Cursor, Read};
+
+use crate::nibble::NibbleSlice;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct RawTrieNodeWithSize {
+	pub node: RawTrieNode,
+	pub memory_usage: u64,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum RawTrieNode {
+	/// nibble encoded key suffix, value length and value hash
+	Leaf(Vec<u8>, u32, CryptoHash),
+	/// children by nibble, and the length and hash of the value stored at this node
+	Branch([Option<CryptoHash>; 16], Option<(u32, CryptoHash)>),
+	/// nibble encoded key segment and child
+	Extension(Vec<u8>, CryptoHash),
+}
+
+/// Reference to a value of the trie, which is stored outside of the trie nodes.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ValueRef {
+	pub length: u32,
+	pub hash: CryptoHash,
+}
+
+const LEAF_NODE: u8 = 0;
+const BRANCH_NODE_NO_VALUE: u8 = 1;
+const BRANCH_NODE_WITH_VALUE: u8 = 2;
+const EXTENSION_NODE: u8 = 3;
+
+/// ReadBytesExtLittleEndian parses anythign that implements `Read` and converts
+/// the data into primitives such as u8, u16, u32.
+/// ## Assumption
+/// ReadBytesExtLittleEndian assumes data coming in LittleEndian
+trait ReadBytesExtLittleEndian: Read {
+	fn read_u8(&mut self) -> Result<u8, String> {
+		let mut buff = [0u8; 1];
+		self.read_exact(&mut buff)
+			.map_err(|_| String::from("Could not read one byte"))?;
+		Ok(u8::from_le_bytes(buff))
+	}
+	fn read_u16(&mut self) -> Result<u16, String> {
+		let mut buff = [0u8; 2];
+		self.read_exact(&mut buff)
+			.map_err(|_| String::from("Could not read two bytes"))?;
+		Ok(u16::from_le_bytes(buff))
+	}
+	fn read_u32(&mut self) -> Result<u32, String> {
+		let mut buff = [0u8; 4];
+		self.read_exact(&mut buff)
+			.map_err(|_| String::from("Could not read four bytes"))?;
+		Ok(u32::from_le_bytes(buff))
+	}
+	fn read_hash(&mut self) -> Result<CryptoHash, String> {
+		let mut buff = [0u8; 32];
+		self.read_exact(&mut buff).map_err(|_| String::from("Could not read a hash"))?;
+		Ok(CryptoHash(buff))
+	}
+	/// Reads `len` bytes. `len` comes from the node being decoded, so it is checked against the
+	/// bytes left before anything is allocated.
+	fn read_bytes(&mut self, len: u32) -> Result<Vec<u8>, String> {
+		if len as usize > self.remaining() {
+			return Err(String::from("Key length exceeds the node"))
+		}
+		let mut buff = vec![0u8; len as usize];
+		self.read_exact(&mut buff).map_err(|_| String::from("Could not read a key"))?;
+		Ok(buff)
+	}
+	/// Number of bytes left to read.
+	fn remaining(&self) -> usize;
+}
+
+impl<T: core::convert::AsRef<[u8]>> ReadBytesExtLittleEndian for Cursor<T> {
+	fn remaining(&self) -> usize {
+		let len = self.get_ref().as_ref().len();
+		len.saturating_sub(usize::try_from(self.position()).unwrap_or(usize::MAX))
+	}
+}
+
+/// Reads the key of a leaf or an extension. Keys are nibbles encoded with a leading flags byte
+/// (see [`NibbleSlice::from_encoded`]), so an empty one is malformed.
+fn read_key(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, String> {
+	let key_length = cursor.read_u32()?;
+	if key_length == 0 {
+		return Err("Empty key".into())
+	}
+	cursor.read_bytes(key_length)
+}
+
+// ported from NEAR CORE: decodes bytes into children nods of the state trie
+fn decode_children(cursor: &mut Cursor<&[u8]>) -> Result<[Option<CryptoHash>; 16], String> {
+	let mut children: [Option<CryptoHash>; 16] = Default::default();
+	let bitmap = cursor.read_u16()?;
+	let mut pos = 1;
+	for child in &mut children {
+		if bitmap & pos != 0 {
+			*child = Some(cursor.read_hash()?);
+		}
+		pos <<= 1;
+	}
+	Ok(children)
+}
+
+impl RawTrieNodeWithSize {
+	pub fn encode_into(&self, out: &mut Vec<u8>) {
+		self.node.encode_into(out);
+		out.extend(self.memory_usage.to_le_bytes());
+	}
+
+	/// decode is used to convert the proof that is sent by the RPC into `RawTrieNodeWithSize`
+	pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+		if bytes.len() < 8 {
+			return Err("Wrong type".into())
+		}
+		let node = RawTrieNode::decode(&bytes[0..bytes.len() - 8])?;
+		let mut arr: [u8; 8] = Default::default();
+		arr.copy_from_slice(&bytes[bytes.len() - 8..]);
+		let memory_usage = u64::from_le_bytes(arr);
+		Ok(RawTrieNodeWithSize { node, memory_usage })
+	}
+
+	/// The hash other nodes (or the state root) refer to this node by.
+	pub fn hash<H: HostFunctions>(&self) -> CryptoHash {
+		let mut bytes = Vec::new();
+		self.encode_into(&mut bytes);
+		CryptoHash(H::sha256(&bytes))
+	}
+}
+
+impl RawTrieNode {
+	pub fn encode_into(&self, out: &mut Vec<u8>) {
+		// size in state_parts = size + 8 for RawTrieNodeWithSize + 8 for borsh vector length
+		match &self {
+			// size <= 1 + 4 + 4 + 32 + key_length + value_length
+			RawTrieNode::Leaf(key, value_length, value_hash) => {
+				out.push(LEAF_NODE);
+				out.extend((key.len() as u32).to_le_bytes());
+				out.extend(key);
+				out.extend(value_length.to_le_bytes());
+				out.extend(value_hash.as_bytes());
+			},
+			// size <= 1 + 4 + 32 + value_length + 2 + 32 * num_children
+			RawTrieNode::Branch(children, value) => {
+				if let Some((value_length, value_hash)) = value {
+					out.push(BRANCH_NODE_WITH_VALUE);
+					out.extend(value_length.to_le_bytes());
+					out.extend(value_hash.as_bytes());
+				} else {
+					out.push(BRANCH_NODE_NO_VALUE);
+				}
+				let mut bitmap: u16 = 0;
+				let mut pos: u16 = 1;
+				for child in children.iter() {
+					if child.is_some() {
+						bitmap |= pos
+					}
+					pos <<= 1;
+				}
+				out.extend(bitmap.to_le_bytes());
+				for hash in children.iter().flatten() {
+					out.extend(hash.as_bytes());
+				}
+			},
+			// size <= 1 + 4 + key_length + 32
+			RawTrieNode::Extension(key, child) => {
+				out.push(EXTENSION_NODE);
+				out.extend((key.len() as u32).to_le_bytes());
+				out.extend(key);
+				out.extend(child.as_bytes());
+			},
+		}
+	}
+
+	pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+		let mut cursor = Cursor::new(bytes);
+		let node = match cursor.read_u8()? {
+			LEAF_NODE => {
+				let key = read_key(&mut cursor)?;
+				let value_length = cursor.read_u32()?;
+				RawTrieNode::Leaf(key, value_length, cursor.read_hash()?)
+			},
+			BRANCH_NODE_NO_VALUE => RawTrieNode::Branch(decode_children(&mut cursor)?, None),
+			BRANCH_NODE_WITH_VALUE => {
+				let value_length = cursor.read_u32()?;
+				let value_hash = cursor.read_hash()?;
+				let children = decode_children(&mut cursor)?;
+				RawTrieNode::Branch(children, Some((value_length, value_hash)))
+			},
+			EXTENSION_NODE => {
+				let key = read_key(&mut cursor)?;
+				RawTrieNode::Extension(key, cursor.read_hash()?)
+			},
+			_ => return Err("Wrong type".into()),
+		};
+		if cursor.position() != bytes.len() as u64 {
+			return Err("Trailing bytes after trie node".into())
+		}
+		Ok(node)
+	}
+
+	pub fn get_key(&self) -> Option<&[u8]> {
+		match self {
+			RawTrieNode::Leaf(key, _, _) => Some(key),
+			RawTrieNode::Extension(key, _) => Some(key),
+			RawTrieNode::Branch(_, _) => None,
+		}
+	}
+}
+
+/// Walks `proof` from the node hashed `root` down to `key`.
+///
+/// Returns the value stored under `key`, or `None` if the proof shows there is none. Errors if a
+/// node doesn't hash to what its parent refers to, or if the proof ends before reaching a
+/// conclusion. Nodes after the one deciding the lookup are ignored.
+pub fn lookup<H: HostFunctions>(
+	key: &[u8],
+	proof: &[RawTrieNodeWithSize],
+	root: CryptoHash,
+) -> Result<Option<ValueRef>, String> {
+	// the empty trie has no nodes
+	if proof.is_empty() && root == CryptoHash::default() {
+		return Ok(None)
+	}
+	let mut expected_hash = root;
+	let mut key = NibbleSlice::new(key);
+	for node in proof {
+		if node.hash::<H>() != expected_hash {
+			return Err("Trie node does not match the hash of its parent".into())
+		}
+		match &node.node {
+			RawTrieNode::Leaf(node_key, length, hash) => {
+				let node_key = NibbleSlice::from_encoded(node_key).0;
+				return Ok((key == node_key).then(|| ValueRef { length: *length, hash: *hash }))
+			},
+			RawTrieNode::Extension(node_key, child_hash) => {
+				let node_key = NibbleSlice::from_encoded(node_key).0;
+				if !key.starts_with(&node_key) {
+					return Ok(None)
+				}
+				key = key.mid(node_key.len());
+				expected_hash = *child_hash;
+			},
+			RawTrieNode::Branch(children, value) => {
+				if key.is_empty() {
+					return Ok(value.map(|(length, hash)| ValueRef { length, hash }))
+				}
+				match &children[key.at(0) as usize] {
+					Some(child_hash) => {
+						key = key.mid(1);
+						expected_hash = *child_hash;
+					},
+					None => return Ok(None),
+				}
+			},
+		}
+	}
+	Err("Trie proof ends before reaching the key".into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::str::FromStr;
+	use near_primitives::hash::CryptoHash as NearCryptoHash;
+
+	struct MockedHostFunctions;
+	impl HostFunctions for MockedHostFunctions {
+		fn sha256(data: &[u8]) -> [u8; 32] {
+			use sha2::Digest;
+			sha2::Sha256::digest(data).into()
+		}
+	}
+
+	// the proof of "doge" => "coin" from NEAR CORE, see `state_proof`
+	const DOGE_PROOF: [&str; 7] = [
+		"0301000000165a1e73ea8e3686db1c5938a8d22912c2e2936fe475d933c310c452cbfb4306ff5303000000000000",
+		"011001aa33bd98698cbe05e77f027fc9bd32783d433a9a9f6213e80a523dd82e7fab108d0c9da6ee86e6957cd608f5a9bac9b9069ceaf555431744a4b09311ad62898e1f03000000000000",
+		"0302000000006f7240cc9614e62849c6e37a9b74e3e46707c42e517061622d45a1c62849d744d31002000000000000",
+		"02040000006806f86a17f0ae04d4ad43168fcdd0651c0fe99108b992f5ac398fd6bf235331400044a09c32d7c008aa4cd3f44adbd62bfe01a7811f21591aef4df91634d4530d1dda01000000000000",
+		"01880095581dfffd1f4f734b2c69c960e63193c18fae785d0c20d9a1c52c7c1374b5efe5fe9244cc68ea29ca00d2207f0033bb007be8aed9a00e27e23f1c84456bf02f7201000000000000",
+		"02050000006588ef4db6a357d6d9ca7d0c9feb69bd8e2f236ab88459da5c193b7fa95031874000e2919fa19c4fe63ae7741fc7e42ab169792efc46b715749822df86bb74c977afd300000000000000",
+		"00010000003504000000b3a1984ba0b1d8ad7f9dc881dfd9c9dc78c76c647a7692fbbfd6fcdcb9d9a1216a00000000000000",
+	];
+
+	fn doge_proof() -> Vec<RawTrieNodeWithSize> {
+		DOGE_PROOF
+			.iter()
+			.map(|node| RawTrieNodeWithSize::decode(&hex::decode(node).unwrap()).unwrap())
+			.collect()
+	}
+
+	fn root() -> CryptoHash {
+		CryptoHash(
+			NearCryptoHash::from_str("hvKZryexWm5CPgcvB3VKxKp1uRQWZnSDALLNa9raXJV")
+				.unwrap()
+				.0,
+		)
+	}
+
+	#[test]
+	fn test_decode_encode_roundtrip() {
+		for (node, encoded) in doge_proof().iter().zip(DOGE_PROOF) {
+			let mut bytes = Vec::new();
+			node.encode_into(&mut bytes);
+			assert_eq!(hex::encode(bytes), encoded);
+		}
+		assert!(matches!(doge_proof()[1].node, RawTrieNode::Branch(_, None)));
+		assert!(matches!(doge_proof()[6].node, RawTrieNode::Leaf(_, 4, _)));
+
+		let mut truncated = hex::decode(DOGE_PROOF[6]).unwrap();
+		truncated.remove(10);
+		assert!(RawTrieNodeWithSize::decode(&truncated).is_err());
+	}
+
+	#[test]
+	fn test_oversized_key_length_is_rejected() {
+		// a leaf claiming a key of u32::MAX bytes, followed by a hash and its memory usage
+		let mut bytes = [&[LEAF_NODE][..], &u32::MAX.to_le_bytes()].concat();
+		bytes.extend([0; 32 + 8]);
+		assert_eq!(RawTrieNodeWithSize::decode(&bytes).unwrap_err(), "Key length exceeds the node");
+	}
+
+	#[test]
+	fn test_empty_key_is_rejected() {
+		for node_type in [LEAF_NODE, EXTENSION_NODE] {
+			let mut bytes = [&[node_type][..], &0u32.to_le_bytes()].concat();
+			if node_type == LEAF_NODE {
+				bytes.extend(0u32.to_le_bytes());
+			}
+			bytes.extend([0; 32 + 8]);
+			assert_eq!(RawTrieNodeWithSize::decode(&bytes).unwrap_err(), "Empty key");
+		}
+	}
+
+	#[test]
+	fn test_lookup() {
+		let proof = doge_proof();
+		assert_eq!(
+			lookup::<MockedHostFunctions>(b"doge", &proof, root()).unwrap(),
+			Some(ValueRef { length: 4, hash: CryptoHash(MockedHostFunctions::sha256(b"coin")) })
+		);
+		// the first node already shows there is no such key
+		assert_eq!(
+			lookup::<MockedHostFunctions>(b"white_horse", &proof[..1], root()).unwrap(),
+			None
+		);
+
+		assert!(lookup::<MockedHostFunctions>(b"doge", &proof[..3], root()).is_err());
+		assert!(lookup::<MockedHostFunctions>(b"doge", &proof, CryptoHash([1; 32])).is_err());
+		assert_eq!(
+			lookup::<MockedHostFunctions>(b"doge", &[], CryptoHash::default()).unwrap(),
+			None
+		);
+	}
+}
@@ -0,0 +1,56 @@
+//! CBOR encoding of the header and proof types, for bridges relaying over CBOR native transports
+//! (IPFS, libp2p pubsub, ...).
+//!
+//! Hashes, keys and signatures are encoded as CBOR byte strings. This encoding is only meant for
+//! transport: hashes are always computed over the borsh encoding.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{string::ToString, vec::Vec};
+
+use crate::ConversionError;
+
+pub fn to_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>, ConversionError> {
+	let mut bytes = Vec::new();
+	ciborium::ser::into_writer(value, &mut bytes)
+		.map_err(|err| ConversionError(err.to_string()))?;
+	Ok(bytes)
+}
+
+pub fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ConversionError> {
+	ciborium::de::from_reader(bytes).map_err(|err| ConversionError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		CryptoHash, Direction, LightClientBlockView, MerklePath, MerklePathItem, PublicKey,
+		Signature, ValidatorStakeView, ValidatorStakeViewV1,
+	};
+	use borsh::BorshSerialize;
+
+	#[test]
+	fn test_light_client_block_view_cbor_roundtrip() {
+		let mut block_view = LightClientBlockView::new_for_test();
+		block_view.next_bps = Some(vec![ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "node0.pool.testnet".into(),
+			public_key: PublicKey([3; 32]),
+			stake: 42,
+		})]);
+		block_view.approvals_after_next = vec![Some(Signature::from_raw(&[5; 64])), None];
+
+		let decoded: LightClientBlockView = from_cbor(&to_cbor(&block_view).unwrap()).unwrap();
+		assert_eq!(decoded.try_to_vec().unwrap(), block_view.try_to_vec().unwrap());
+	}
+
+	#[test]
+	fn test_hashes_are_cbor_byte_strings() {
+		let path: MerklePath =
+			vec![MerklePathItem { hash: CryptoHash([1; 32]), direction: Direction::Right }];
+		let bytes = to_cbor(&path).unwrap();
+		// 0x58 0x20: byte string of 32 bytes
+		assert!(bytes.windows(2).any(|window| window == [0x58, 0x20]));
+		assert_eq!(from_cbor::<MerklePath>(&bytes).unwrap(), path);
+		assert!(from_cbor::<CryptoHash>(&to_cbor(&[1u8; 31].as_slice()).unwrap()).is_err());
+	}
+}
@@ -0,0 +1,516 @@
+//! Conversions between the wasm friendly types of this crate and the ones from `near-primitives`
+//! and `near-crypto`, which are only available with `std`.
+//!
+//! Conversions towards `near-primitives` are fallible because account ids have to be validated.
+//! Conversions from `near-primitives` are fallible because only ed25519 keys are supported.
+
+use std::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+use borsh::BorshSerialize;
+use near_crypto::{
+	ED25519PublicKey, KeyType, PublicKey as NearPublicKey, Signature as NearSignature,
+};
+use near_primitives::{
+	account::{
+		AccessKey as NearAccessKey, AccessKeyPermission as NearAccessKeyPermission,
+		Account as NearAccount,
+	},
+	hash::CryptoHash as NearCryptoHash,
+	merkle::{Direction as NearDirection, MerklePathItem as NearMerklePathItem},
+	sharding::{
+		ShardChunkHeader as NearShardChunkHeader,
+		ShardChunkHeaderInner as NearShardChunkHeaderInner,
+	},
+	types::{
+		validator_stake::{
+			ValidatorStake as NearValidatorStake, ValidatorStakeV1 as NearValidatorStakeV1,
+		},
+		AccountId as NearAccountId,
+	},
+	views::{
+		validator_stake_view::ValidatorStakeView as NearValidatorStakeView,
+		AccountView as NearAccountView, BlockHeaderInnerLiteView as NearBlockHeaderInnerLiteView,
+		ExecutionOutcomeView as NearExecutionOutcomeView,
+		ExecutionOutcomeWithIdView as NearExecutionOutcomeWithIdView,
+		LightClientBlockLiteView as NearLightClientBlockLiteView,
+		LightClientBlockView as NearLightClientBlockView,
+		ValidatorStakeViewV1 as NearValidatorStakeViewV1,
+	},
+};
+
+use crate::{
+	AccessKey, AccessKeyPermission, Account, BlockHeaderInnerLiteView, BlockHeight,
+	ChunkHeaderView, ConversionError, CryptoHash, Direction, EpochId, ExecutionOutcomeView,
+	FunctionCallPermission, LightClientBlockLiteView, LightClientBlockView, MerklePathItem, Nonce,
+	OutcomeProof, PublicKey, ShardChunkHeaderInner, ShardChunkHeaderInnerV1,
+	ShardChunkHeaderInnerV2, Signature, ValidatorStakeView, ValidatorStakeViewV1,
+};
+
+fn parse_account_id(account_id: String) -> Result<NearAccountId, ConversionError> {
+	account_id.parse().map_err(|_| ConversionError("invalid account id".into()))
+}
+
+fn collect<T, U: TryFrom<T, Error = ConversionError>>(
+	items: Vec<T>,
+) -> Result<Vec<U>, ConversionError> {
+	items.into_iter().map(U::try_from).collect()
+}
+
+impl From<NearCryptoHash> for CryptoHash {
+	fn from(hash: NearCryptoHash) -> Self {
+		Self(hash.0)
+	}
+}
+
+impl From<CryptoHash> for NearCryptoHash {
+	fn from(hash: CryptoHash) -> Self {
+		Self(hash.0)
+	}
+}
+
+impl TryFrom<NearPublicKey> for PublicKey {
+	type Error = ConversionError;
+	fn try_from(public_key: NearPublicKey) -> Result<Self, Self::Error> {
+		match public_key {
+			NearPublicKey::ED25519(public_key) => Ok(Self(public_key.0)),
+			_ => Err(ConversionError("only ed25519 public keys are supported".into())),
+		}
+	}
+}
+
+impl From<PublicKey> for NearPublicKey {
+	fn from(public_key: PublicKey) -> Self {
+		Self::ED25519(ED25519PublicKey(public_key.0))
+	}
+}
+
+impl TryFrom<NearSignature> for Signature {
+	type Error = ConversionError;
+	fn try_from(signature: NearSignature) -> Result<Self, Self::Error> {
+		match signature {
+			NearSignature::ED25519(signature) => Ok(Self::from_raw(&signature.to_bytes())),
+			_ => Err(ConversionError("only ed25519 signatures are supported".into())),
+		}
+	}
+}
+
+impl From<Signature> for NearSignature {
+	fn from(signature: Signature) -> Self {
+		Self::from_parts(KeyType::ED25519, signature.as_bytes())
+			.expect("an ed25519 signature is always 64 bytes long; qed")
+	}
+}
+
+impl From<NearBlockHeaderInnerLiteView> for BlockHeaderInnerLiteView {
+	fn from(inner_lite: NearBlockHeaderInnerLiteView) -> Self {
+		Self {
+			height: BlockHeight(inner_lite.height),
+			epoch_id: EpochId(inner_lite.epoch_id.into()),
+			next_epoch_id: EpochId(inner_lite.next_epoch_id.into()),
+			prev_state_root: inner_lite.prev_state_root.into(),
+			outcome_root: inner_lite.outcome_root.into(),
+			timestamp: inner_lite.timestamp,
+			timestamp_nanosec: inner_lite.timestamp_nanosec,
+			next_bp_hash: inner_lite.next_bp_hash.into(),
+			block_merkle_root: inner_lite.block_merkle_root.into(),
+		}
+	}
+}
+
+impl From<BlockHeaderInnerLiteView> for NearBlockHeaderInnerLiteView {
+	fn from(inner_lite: BlockHeaderInnerLiteView) -> Self {
+		Self {
+			height: inner_lite.height.0,
+			epoch_id: inner_lite.epoch_id.0.into(),
+			next_epoch_id: inner_lite.next_epoch_id.0.into(),
+			prev_state_root: inner_lite.prev_state_root.into(),
+			outcome_root: inner_lite.outcome_root.into(),
+			timestamp: inner_lite.timestamp,
+			timestamp_nanosec: inner_lite.timestamp_nanosec,
+			next_bp_hash: inner_lite.next_bp_hash.into(),
+			block_merkle_root: inner_lite.block_merkle_root.into(),
+		}
+	}
+}
+
+impl TryFrom<NearValidatorStakeView> for ValidatorStakeView {
+	type Error = ConversionError;
+	#[allow(unreachable_patterns)]
+	fn try_from(validator: NearValidatorStakeView) -> Result<Self, Self::Error> {
+		match validator {
+			NearValidatorStakeView::V1(validator) => Ok(Self::V1(ValidatorStakeViewV1 {
+				account_id: validator.account_id.to_string(),
+				public_key: validator.public_key.try_into()?,
+				stake: validator.stake,
+			})),
+			_ => Err(ConversionError("unsupported validator stake view version".into())),
+		}
+	}
+}
+
+impl TryFrom<ValidatorStakeView> for NearValidatorStakeView {
+	type Error = ConversionError;
+	fn try_from(validator: ValidatorStakeView) -> Result<Self, Self::Error> {
+		match validator {
+			ValidatorStakeView::V1(validator) => Ok(Self::V1(NearValidatorStakeViewV1 {
+				account_id: parse_account_id(validator.account_id)?,
+				public_key: validator.public_key.into(),
+				stake: validator.stake,
+			})),
+			// as V1, it would no longer hash to `next_bp_hash`
+			ValidatorStakeView::V2(_) =>
+				Err(ConversionError("near-primitives has no V2 validator stake view".into())),
+		}
+	}
+}
+
+impl TryFrom<NearLightClientBlockView> for LightClientBlockView {
+	type Error = ConversionError;
+	fn try_from(block_view: NearLightClientBlockView) -> Result<Self, Self::Error> {
+		Ok(Self {
+			prev_block_hash: block_view.prev_block_hash.into(),
+			next_block_inner_hash: block_view.next_block_inner_hash.into(),
+			inner_lite: block_view.inner_lite.into(),
+			inner_rest_hash: block_view.inner_rest_hash.into(),
+			next_bps: block_view.next_bps.map(collect).transpose()?,
+			approvals_after_next: block_view
+				.approvals_after_next
+				.into_iter()
+				.map(|approval| approval.map(Signature::try_from).transpose())
+				.collect::<Result<_, _>>()?,
+		})
+	}
+}
+
+impl TryFrom<LightClientBlockView> for NearLightClientBlockView {
+	type Error = ConversionError;
+	fn try_from(block_view: LightClientBlockView) -> Result<Self, Self::Error> {
+		Ok(Self {
+			prev_block_hash: block_view.prev_block_hash.into(),
+			next_block_inner_hash: block_view.next_block_inner_hash.into(),
+			inner_lite: block_view.inner_lite.into(),
+			inner_rest_hash: block_view.inner_rest_hash.into(),
+			next_bps: block_view.next_bps.map(collect).transpose()?,
+			approvals_after_next: block_view
+				.approvals_after_next
+				.into_iter()
+				.map(|approval| approval.map(NearSignature::from))
+				.collect(),
+		})
+	}
+}
+
+impl From<NearLightClientBlockLiteView> for LightClientBlockLiteView {
+	fn from(block_view: NearLightClientBlockLiteView) -> Self {
+		Self {
+			prev_block_hash: block_view.prev_block_hash.into(),
+			inner_rest_hash: block_view.inner_rest_hash.into(),
+			inner_lite: block_view.inner_lite.into(),
+		}
+	}
+}
+
+impl From<NearMerklePathItem> for MerklePathItem {
+	fn from(item: NearMerklePathItem) -> Self {
+		Self {
+			hash: item.hash.into(),
+			direction: match item.direction {
+				NearDirection::Left => Direction::Left,
+				NearDirection::Right => Direction::Right,
+			},
+		}
+	}
+}
+
+impl From<MerklePathItem> for NearMerklePathItem {
+	fn from(item: MerklePathItem) -> Self {
+		Self {
+			hash: item.hash.into(),
+			direction: match item.direction {
+				Direction::Left => NearDirection::Left,
+				Direction::Right => NearDirection::Right,
+			},
+		}
+	}
+}
+
+impl From<NearAccountView> for Account {
+	fn from(account: NearAccountView) -> Self {
+		Self {
+			amount: account.amount,
+			locked: account.locked,
+			code_hash: account.code_hash.into(),
+			storage_usage: account.storage_usage,
+		}
+	}
+}
+
+impl From<NearAccount> for Account {
+	fn from(account: NearAccount) -> Self {
+		Self {
+			amount: account.amount(),
+			locked: account.locked(),
+			code_hash: account.code_hash().into(),
+			storage_usage: account.storage_usage(),
+		}
+	}
+}
+
+impl From<NearAccessKey> for AccessKey {
+	fn from(access_key: NearAccessKey) -> Self {
+		Self {
+			nonce: Nonce(access_key.nonce),
+			permission: match access_key.permission {
+				NearAccessKeyPermission::FunctionCall(permission) =>
+					AccessKeyPermission::FunctionCall(FunctionCallPermission {
+						allowance: permission.allowance,
+						receiver_id: permission.receiver_id,
+						method_names: permission.method_names,
+					}),
+				NearAccessKeyPermission::FullAccess => AccessKeyPermission::FullAccess,
+			},
+		}
+	}
+}
+
+fn validator_stake_v1(
+	validator: NearValidatorStakeV1,
+) -> Result<ValidatorStakeViewV1, ConversionError> {
+	Ok(ValidatorStakeViewV1 {
+		account_id: validator.account_id.to_string(),
+		public_key: validator.public_key.try_into()?,
+		stake: validator.stake,
+	})
+}
+
+impl TryFrom<NearShardChunkHeaderInner> for ShardChunkHeaderInner {
+	type Error = ConversionError;
+	fn try_from(inner: NearShardChunkHeaderInner) -> Result<Self, Self::Error> {
+		Ok(match inner {
+			NearShardChunkHeaderInner::V1(inner) => Self::V1(ShardChunkHeaderInnerV1 {
+				prev_block_hash: inner.prev_block_hash.into(),
+				prev_state_root: inner.prev_state_root.into(),
+				outcome_root: inner.outcome_root.into(),
+				encoded_merkle_root: inner.encoded_merkle_root.into(),
+				encoded_length: inner.encoded_length,
+				height_created: BlockHeight(inner.height_created),
+				shard_id: inner.shard_id,
+				gas_used: inner.gas_used,
+				gas_limit: inner.gas_limit,
+				balance_burnt: inner.balance_burnt,
+				outgoing_receipts_root: inner.outgoing_receipts_root.into(),
+				tx_root: inner.tx_root.into(),
+				validator_proposals: inner
+					.validator_proposals
+					.into_iter()
+					.map(validator_stake_v1)
+					.collect::<Result<_, _>>()?,
+			}),
+			NearShardChunkHeaderInner::V2(inner) => Self::V2(ShardChunkHeaderInnerV2 {
+				prev_block_hash: inner.prev_block_hash.into(),
+				prev_state_root: inner.prev_state_root.into(),
+				outcome_root: inner.outcome_root.into(),
+				encoded_merkle_root: inner.encoded_merkle_root.into(),
+				encoded_length: inner.encoded_length,
+				height_created: BlockHeight(inner.height_created),
+				shard_id: inner.shard_id,
+				gas_used: inner.gas_used,
+				gas_limit: inner.gas_limit,
+				balance_burnt: inner.balance_burnt,
+				outgoing_receipts_root: inner.outgoing_receipts_root.into(),
+				tx_root: inner.tx_root.into(),
+				validator_proposals: inner
+					.validator_proposals
+					.into_iter()
+					.map(|validator| match validator {
+						NearValidatorStake::V1(validator) =>
+							validator_stake_v1(validator).map(ValidatorStakeView::V1),
+					})
+					.collect::<Result<_, _>>()?,
+			}),
+		})
+	}
+}
+
+impl TryFrom<NearShardChunkHeader> for ChunkHeaderView {
+	type Error = ConversionError;
+	fn try_from(header: NearShardChunkHeader) -> Result<Self, Self::Error> {
+		match header {
+			NearShardChunkHeader::V3(header) => Ok(Self {
+				inner: header.inner.try_into()?,
+				height_included: BlockHeight(header.height_included),
+			}),
+			_ => Err(ConversionError("only chunk headers v3 are supported".into())),
+		}
+	}
+}
+
+impl TryFrom<NearExecutionOutcomeView> for ExecutionOutcomeView {
+	type Error = ConversionError;
+	fn try_from(outcome: NearExecutionOutcomeView) -> Result<Self, Self::Error> {
+		Ok(Self {
+			logs: outcome.logs,
+			receipt_ids: outcome.receipt_ids.into_iter().map(CryptoHash::from).collect(),
+			gas_burnt: outcome.gas_burnt,
+			tokens_burnt: outcome.tokens_burnt,
+			executor_id: outcome.executor_id.to_string(),
+			// the status is kept borsh serialized, see `ExecutionOutcomeView::status`
+			status: outcome
+				.status
+				.try_to_vec()
+				.map_err(|_| ConversionError("could not serialize the execution status".into()))?,
+		})
+	}
+}
+
+impl TryFrom<NearExecutionOutcomeWithIdView> for OutcomeProof {
+	type Error = ConversionError;
+	fn try_from(outcome: NearExecutionOutcomeWithIdView) -> Result<Self, Self::Error> {
+		Ok(Self {
+			proof: outcome.proof.into_iter().map(MerklePathItem::from).collect(),
+			block_hash: outcome.block_hash.into(),
+			id: outcome.id.into(),
+			outcome: outcome.outcome.try_into()?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ValidatorStakeViewV2;
+	use borsh::BorshDeserialize;
+	use std::str::FromStr;
+
+	#[test]
+	fn test_signature_conversion_matches_borsh_roundtrip() {
+		let near_signature = NearSignature::from_str("ed25519:4tC17LadtbHChDDvEJaGrsmc1Jj7F6PT7GQq9Ncd8tykG5tNYxfA9kXz57tvwRbzeZjqjPykAPY2KrN4XMs4M9sB").unwrap();
+		let signature = Signature::try_from(near_signature.clone()).unwrap();
+		assert_eq!(
+			signature.try_to_vec().unwrap(),
+			Signature::try_from_slice(&near_signature.try_to_vec().unwrap())
+				.unwrap()
+				.try_to_vec()
+				.unwrap()
+		);
+		assert_eq!(NearSignature::from(signature), near_signature);
+	}
+
+	#[test]
+	fn test_validator_stake_view_roundtrip() {
+		let near_validator = NearValidatorStakeView::V1(NearValidatorStakeViewV1 {
+			account_id: "node0.pool.testnet".parse().unwrap(),
+			public_key: NearPublicKey::from_str(
+				"ed25519:D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8",
+			)
+			.unwrap(),
+			stake: 42,
+		});
+		let validator = ValidatorStakeView::try_from(near_validator.clone()).unwrap();
+		assert_eq!(
+			validator.try_to_vec().unwrap(),
+			near_validator.try_to_vec().unwrap(),
+			"conversions must preserve the borsh layout used to compute `next_bp_hash`"
+		);
+		assert_eq!(NearValidatorStakeView::try_from(validator).unwrap(), near_validator);
+	}
+
+	#[test]
+	fn test_v2_is_not_downgraded() {
+		let validator = ValidatorStakeView::V2(ValidatorStakeViewV2 {
+			account_id: "node0.pool.testnet".into(),
+			public_key: PublicKey([0; 32]),
+			stake: 42,
+			is_chunk_only: false,
+		});
+		assert!(NearValidatorStakeView::try_from(validator).is_err());
+	}
+
+	#[test]
+	fn test_invalid_account_id_is_rejected() {
+		let validator = ValidatorStakeView::V1(ValidatorStakeViewV1 {
+			account_id: "Not A Valid Account".into(),
+			public_key: PublicKey([0; 32]),
+			stake: 0,
+		});
+		assert!(NearValidatorStakeView::try_from(validator).is_err());
+	}
+
+	#[test]
+	fn test_account_matches_state_layout() {
+		let near_account = NearAccount::new(10u128.pow(24), 5, NearCryptoHash([3; 32]), 182);
+		assert_eq!(
+			Account::from(near_account.clone()).try_to_vec().unwrap(),
+			near_account.try_to_vec().unwrap(),
+			"accounts are proven by the hash of their borsh encoding"
+		);
+	}
+
+	#[test]
+	fn test_access_key_matches_state_layout() {
+		let function_call = NearAccessKey {
+			nonce: 7,
+			permission: NearAccessKeyPermission::FunctionCall(
+				near_primitives::account::FunctionCallPermission {
+					allowance: Some(10u128.pow(24)),
+					receiver_id: "bridge.near".into(),
+					method_names: vec!["submit".into()],
+				},
+			),
+		};
+		for near_access_key in [function_call, NearAccessKey::full_access()] {
+			assert_eq!(
+				AccessKey::from(near_access_key.clone()).try_to_vec().unwrap(),
+				near_access_key.try_to_vec().unwrap(),
+				"access keys are proven by the hash of their borsh encoding"
+			);
+		}
+	}
+
+	use crate::HostFunctions;
+
+	struct MockedHostFunctions;
+	impl HostFunctions for MockedHostFunctions {
+		fn sha256(data: &[u8]) -> [u8; 32] {
+			use sha2::Digest;
+			sha2::Sha256::digest(data).into()
+		}
+	}
+
+	#[test]
+	fn test_chunk_hash_matches_nearcore() {
+		use near_primitives::sharding::{
+			ShardChunkHeaderInnerV2 as NearInnerV2, ShardChunkHeaderV3,
+		};
+
+		let near_inner = NearShardChunkHeaderInner::V2(NearInnerV2 {
+			prev_block_hash: NearCryptoHash([1; 32]),
+			prev_state_root: NearCryptoHash([2; 32]),
+			outcome_root: NearCryptoHash([3; 32]),
+			encoded_merkle_root: NearCryptoHash([4; 32]),
+			encoded_length: 5,
+			height_created: 6,
+			shard_id: 7,
+			gas_used: 8,
+			gas_limit: 9,
+			balance_burnt: 10,
+			outgoing_receipts_root: NearCryptoHash([11; 32]),
+			tx_root: NearCryptoHash([12; 32]),
+			validator_proposals: Vec::new(),
+		});
+		let chunk_hash = ShardChunkHeaderV3::compute_hash(&near_inner);
+		let chunk_header = ChunkHeaderView {
+			inner: near_inner.try_into().unwrap(),
+			height_included: BlockHeight(13),
+		};
+
+		assert_eq!(
+			chunk_header.chunk_hash::<MockedHostFunctions>(),
+			CryptoHash::from(chunk_hash.0)
+		);
+		assert_eq!(chunk_header.inner.tx_root(), CryptoHash([12; 32]));
+	}
+}
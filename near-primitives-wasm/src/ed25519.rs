@@ -0,0 +1,147 @@
+//! # Ed25519 encoding rules
+//!
+//! `sp_io::crypto::ed25519_verify` checks the cofactorless equation of RFC 8032,
+//!
+//! ```text
+//! [S]B = R + [k]A, where k = SHA-512(R || A || M) mod L
+//! ```
+//!
+//! with the `verify` of `ed25519-dalek`, as nearcore does. Like nearcore, [`Signature::verify`]
+//! only adds the check of [`is_canonical_scalar`]: a scalar `S` that is not reduced modulo `L` is
+//! rejected, and `R` is compared as bytes with the one the equation recomputes, so that only its
+//! canonical encoding passes. Both accept and reject the same signatures.
+//!
+//! The equation alone still leaves room for malleable signatures: keys or nonces of small order
+//! make a signature valid for any message. [`Signature::verify_strict`] opts into
+//! [`is_strictly_encoded`], which also rejects:
+//!
+//! - an `R` or an `A` that is not the canonical encoding of a curve point;
+//! - an `R` or an `A` of small order (in the torsion subgroup of order 8).
+//!
+//! A signature produced by a compliant signer with an honestly generated key always passes these
+//! checks, but nearcore accepts signatures that fail them: a client verifying strictly can reject
+//! a header that NEAR finalized.
+//!
+//! [`Signature::verify`]: crate::Signature::verify
+//! [`Signature::verify_strict`]: crate::Signature::verify_strict
+
+use curve25519_dalek::{edwards::CompressedEdwardsY, scalar::Scalar};
+
+/// Whether the scalar `S` of `signature` (`R || S`) is reduced modulo `L`, as nearcore requires.
+pub fn is_canonical_scalar(signature: &[u8; 64]) -> bool {
+	let s: [u8; 32] = signature[32..].try_into().expect("the signature is 64 bytes long; qed");
+	Scalar::from_canonical_bytes(s).is_some()
+}
+
+/// Whether `signature` (`R || S`) and `public_key` (`A`) follow the strict rules of the module.
+pub fn is_strictly_encoded(signature: &[u8; 64], public_key: &[u8; 32]) -> bool {
+	let r: &[u8; 32] = signature[..32].try_into().expect("the signature is 64 bytes long; qed");
+	is_canonical_scalar(signature) && is_strict_point(r) && is_strict_point(public_key)
+}
+
+/// Canonically encoded and not of small order.
+fn is_strict_point(encoded: &[u8; 32]) -> bool {
+	let compressed = CompressedEdwardsY(*encoded);
+	match compressed.decompress() {
+		Some(point) => point.compress() == compressed && !point.is_small_order(),
+		None => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{PublicKey, Signature};
+	use sp_core::{ed25519::Pair, Pair as _};
+
+	fn signed(message: &[u8]) -> ([u8; 64], [u8; 32]) {
+		let pair = Pair::from_seed(&[7; 32]);
+		(pair.sign(message).0, pair.public().0)
+	}
+
+	/// Whether nearcore accepts `signature` of `message` by `public_key`.
+	fn nearcore_verify(signature: &[u8; 64], message: &[u8], public_key: [u8; 32]) -> bool {
+		use near_crypto::{ED25519PublicKey, KeyType, PublicKey, Signature};
+		match Signature::from_parts(KeyType::ED25519, signature) {
+			Ok(signature) =>
+				signature.verify(message, &PublicKey::ED25519(ED25519PublicKey(public_key))),
+			Err(_) => false,
+		}
+	}
+
+	#[test]
+	fn test_honest_signature_is_accepted() {
+		let (signature, public_key) = signed(b"approval");
+		assert!(is_strictly_encoded(&signature, &public_key));
+		assert!(Signature::from_raw(&signature).verify(b"approval", PublicKey(public_key)));
+		assert!(Signature::from_raw(&signature).verify_strict(b"approval", PublicKey(public_key)));
+		assert!(!Signature::from_raw(&signature).verify(b"other approval", PublicKey(public_key)));
+	}
+
+	#[test]
+	fn test_rfc8032_vector_is_accepted() {
+		// TEST 1 of RFC 8032, section 7.1: the empty message
+		const PUBLIC_KEY: [u8; 32] = [
+			0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+			0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+			0xf7, 0x07, 0x51, 0x1a,
+		];
+		const SIGNATURE: [u8; 64] = [
+			0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80, 0x6e,
+			0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73, 0xe0, 0x65,
+			0x22, 0x49, 0x01, 0x55, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b, 0xac, 0xc6, 0x1e,
+			0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0, 0x59, 0x5b, 0xbe, 0x24,
+			0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+		];
+		let (signature, public_key) = (SIGNATURE, PUBLIC_KEY);
+		assert!(nearcore_verify(&signature, b"", public_key));
+		assert!(Signature::from_raw(&signature).verify(b"", PublicKey(public_key)));
+		assert!(Signature::from_raw(&signature).verify_strict(b"", PublicKey(public_key)));
+	}
+
+	#[test]
+	fn test_unreduced_scalar_is_rejected() {
+		// S + L encodes the same scalar as S
+		const L: [u8; 32] = [
+			0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
+			0xde, 0x14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+		];
+		let (mut signature, public_key) = signed(b"approval");
+		let mut carry = 0u16;
+		for (byte, l) in signature[32..].iter_mut().zip(L) {
+			let sum = *byte as u16 + l as u16 + carry;
+			*byte = sum as u8;
+			carry = sum >> 8;
+		}
+		assert!(!is_canonical_scalar(&signature));
+		assert!(!nearcore_verify(&signature, b"approval", public_key));
+		assert!(!Signature::from_raw(&signature).verify(b"approval", PublicKey(public_key)));
+	}
+
+	#[test]
+	fn test_small_order_points_are_only_rejected_strictly() {
+		// with the identity as both key and nonce, `S = 0` satisfies the equation for any message,
+		// which nearcore accepts
+		let mut identity = [0; 32];
+		identity[0] = 1;
+		let mut signature = [0; 64];
+		signature[..32].copy_from_slice(&identity);
+		assert!(nearcore_verify(&signature, b"any message", identity));
+		assert!(Signature::from_raw(&signature).verify(b"any message", PublicKey(identity)));
+		assert!(!is_strictly_encoded(&signature, &identity));
+		assert!(!Signature::from_raw(&signature).verify_strict(b"any message", PublicKey(identity)));
+
+		let (signature, _) = signed(b"approval");
+		assert!(!is_strictly_encoded(&signature, &identity));
+	}
+
+	#[test]
+	fn test_non_canonical_point_is_rejected() {
+		// y = p + 1 reduces to the identity, but is not its canonical encoding
+		let mut non_canonical = [0xff; 32];
+		non_canonical[0] = 0xee;
+		non_canonical[31] = 0x7f;
+		let (signature, _) = signed(b"approval");
+		assert!(!is_strictly_encoded(&signature, &non_canonical));
+	}
+}
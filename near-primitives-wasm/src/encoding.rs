@@ -0,0 +1,240 @@
+//! Human readable encodings of hashes, keys and signatures, matching the ones used by NEAR's RPC
+//! and `near-crypto`: base58 for hashes and `ed25519:<base58>` for keys and signatures.
+
+use core::{fmt, str::FromStr};
+use sp_core::ed25519::Signature as Ed25519Signature;
+use sp_std::vec::Vec;
+
+use crate::{BlockHeight, ConversionError, CryptoHash, EpochId, PublicKey, Signature};
+
+const ED25519_PREFIX: &str = "ed25519:";
+
+fn decode_base58(s: &str) -> Result<Vec<u8>, ConversionError> {
+	bs58::decode(s).into_vec().map_err(|_| ConversionError("invalid base58".into()))
+}
+
+/// Strips the key type prefix. As in `near-crypto`, a missing prefix means ed25519.
+fn strip_key_type(s: &str) -> Result<&str, ConversionError> {
+	match s.split_once(':') {
+		None => Ok(s),
+		Some(("ed25519", data)) => Ok(data),
+		Some(_) => Err(ConversionError("unsupported key type".into())),
+	}
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, ConversionError> {
+	fn nibble(c: u8) -> Result<u8, ConversionError> {
+		match c {
+			b'0'..=b'9' => Ok(c - b'0'),
+			b'a'..=b'f' => Ok(c - b'a' + 10),
+			b'A'..=b'F' => Ok(c - b'A' + 10),
+			_ => Err(ConversionError("invalid hex".into())),
+		}
+	}
+	let s = s.strip_prefix("0x").unwrap_or(s);
+	if s.len() % 2 != 0 {
+		return Err(ConversionError("invalid hex".into()));
+	}
+	s.as_bytes()
+		.chunks(2)
+		.map(|pair| Ok(nibble(pair[0])? << 4 | nibble(pair[1])?))
+		.collect()
+}
+
+impl fmt::Display for CryptoHash {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&bs58::encode(self.0).into_string())
+	}
+}
+
+impl fmt::Display for EpochId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl FromStr for EpochId {
+	type Err = ConversionError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		CryptoHash::from_str(s).map(Self)
+	}
+}
+
+impl fmt::Display for BlockHeight {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl fmt::LowerHex for CryptoHash {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for byte in self.0 {
+			write!(f, "{:02x}", byte)?;
+		}
+		Ok(())
+	}
+}
+
+/// Parses either the base58 encoding used by NEAR or a (optionally `0x` prefixed) hex string.
+impl FromStr for CryptoHash {
+	type Err = ConversionError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match decode_base58(s) {
+			Ok(bytes) if bytes.len() == 32 => Self::try_from(bytes.as_ref()),
+			_ => Self::try_from(decode_hex(s)?.as_ref()),
+		}
+	}
+}
+
+impl fmt::Display for PublicKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}{}", ED25519_PREFIX, bs58::encode(self.0).into_string())
+	}
+}
+
+impl FromStr for PublicKey {
+	type Err = ConversionError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::try_from(decode_base58(strip_key_type(s)?)?.as_ref())
+	}
+}
+
+impl fmt::Display for Signature {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}{}", ED25519_PREFIX, bs58::encode(self.as_bytes()).into_string())
+	}
+}
+
+impl FromStr for Signature {
+	type Err = ConversionError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let bytes = decode_base58(strip_key_type(s)?)?;
+		let raw: [u8; Self::LEN] =
+			bytes.try_into().map_err(|_| ConversionError("wrong size".into()))?;
+		Ok(Self::Ed25519(Ed25519Signature::from_raw(raw)))
+	}
+}
+
+/// Hashes, keys and signatures are (de)serialized as plain byte strings, which binary formats such
+/// as CBOR encode natively. Human readable formats should use the `Display`/`FromStr` encodings.
+#[cfg(feature = "serde")]
+mod serde_impls {
+	use core::fmt;
+	use serde::{
+		de::{Error, SeqAccess, Visitor},
+		Deserialize, Deserializer, Serialize, Serializer,
+	};
+
+	use crate::{CryptoHash, PublicKey, Signature};
+
+	struct BytesVisitor<const N: usize>;
+
+	impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+		type Value = [u8; N];
+
+		fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			write!(f, "{} bytes", N)
+		}
+
+		fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+			v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+		}
+
+		fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+			let mut bytes = [0u8; N];
+			for (i, byte) in bytes.iter_mut().enumerate() {
+				*byte = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+			}
+			if seq.next_element::<u8>()?.is_some() {
+				return Err(A::Error::invalid_length(N + 1, &self));
+			}
+			Ok(bytes)
+		}
+	}
+
+	fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(
+		deserializer: D,
+	) -> Result<[u8; N], D::Error> {
+		deserializer.deserialize_bytes(BytesVisitor::<N>)
+	}
+
+	impl Serialize for CryptoHash {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.serialize_bytes(&self.0)
+		}
+	}
+
+	impl<'de> Deserialize<'de> for CryptoHash {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			deserialize_bytes(deserializer).map(Self)
+		}
+	}
+
+	impl Serialize for PublicKey {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.serialize_bytes(&self.0)
+		}
+	}
+
+	impl<'de> Deserialize<'de> for PublicKey {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			deserialize_bytes::<_, { PublicKey::LEN }>(deserializer).map(Self)
+		}
+	}
+
+	impl Serialize for Signature {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.serialize_bytes(self.as_bytes())
+		}
+	}
+
+	impl<'de> Deserialize<'de> for Signature {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			deserialize_bytes::<_, { Signature::LEN }>(deserializer).map(|raw| Self::from_raw(&raw))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use near_crypto::{PublicKey as NearPublicKey, Signature as NearSignature};
+	use near_primitives::hash::CryptoHash as NearCryptoHash;
+	use std::string::ToString;
+
+	#[test]
+	fn test_crypto_hash_string_roundtrip() {
+		let encoded = "8hxkU4avDWFDCsZckig7oN2ypnYvLyb1qmZ3SA1t8iZK";
+		let hash = CryptoHash::from_str(encoded).unwrap();
+		assert_eq!(hash.0, NearCryptoHash::from_str(encoded).unwrap().0);
+		assert_eq!(hash.to_string(), encoded);
+
+		let hex = format!("{:x}", hash);
+		assert_eq!(CryptoHash::from_str(&hex).unwrap(), hash);
+		assert_eq!(CryptoHash::from_str(&format!("0x{}", hex)).unwrap(), hash);
+	}
+
+	#[test]
+	fn test_public_key_string_matches_near_crypto() {
+		let encoded = "ed25519:D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8";
+		let public_key = PublicKey::from_str(encoded).unwrap();
+		assert_eq!(public_key.to_string(), NearPublicKey::from_str(encoded).unwrap().to_string());
+		assert_eq!(
+			PublicKey::from_str("D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8").unwrap().0,
+			public_key.0
+		);
+		assert!(
+			PublicKey::from_str("secp256k1:D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8").is_err()
+		);
+	}
+
+	#[test]
+	fn test_signature_string_matches_near_crypto() {
+		let encoded = "ed25519:4tC17LadtbHChDDvEJaGrsmc1Jj7F6PT7GQq9Ncd8tykG5tNYxfA9kXz57tvwRbzeZjqjPykAPY2KrN4XMs4M9sB";
+		let signature = Signature::from_str(encoded).unwrap();
+		assert_eq!(signature.to_string(), NearSignature::from_str(encoded).unwrap().to_string());
+		assert!(
+			Signature::from_str("ed25519:D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8").is_err()
+		);
+	}
+}
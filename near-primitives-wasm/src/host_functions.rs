@@ -1,9 +1,209 @@
+use borsh::{maybestd::io, BorshSerialize};
+use core::{
+	marker::PhantomData,
+	sync::atomic::{AtomicPtr, Ordering},
+};
+use sha2::Digest;
+use sp_std::boxed::Box;
+
 use crate::{PublicKey, Signature};
 
 pub trait HostFunctions {
 	fn sha256(data: &[u8]) -> [u8; 32];
 
+	/// `sha256` of the concatenation of `chunks`.
+	fn sha256_concat(chunks: &[&[u8]]) -> [u8; 32] {
+		Self::sha256(&chunks.concat())
+	}
+
+	/// `sha256` of the borsh encoding of `value`. By default the encoding is buffered and hashed
+	/// with [`HostFunctions::sha256`]; implementations with an incremental hasher should stream it
+	/// instead, like [`Sha256HostFunctions`] does.
+	fn sha256_borsh<T: BorshSerialize + ?Sized>(value: &T) -> [u8; 32] {
+		Self::sha256(&value.try_to_vec().expect("encoding into a Vec can't fail; qed"))
+	}
+
 	fn verify(signature: Signature, data: impl AsRef<[u8]>, public_key: PublicKey) -> bool {
 		signature.verify(data, public_key)
 	}
 }
+
+/// Hashes with the SHA-256 of the `sha2` crate and verifies signatures with `sp_io`.
+///
+/// On x86 `sha2` uses the SHA-NI instructions when the CPU detected at runtime has them. The `asm`
+/// feature adds its assembly backends on std targets, including the ARMv8 SHA-2 instructions on
+/// aarch64; WASM always gets portable Rust. Runtimes with a hashing host function should provide
+/// their own [`HostFunctions`] instead.
+pub struct Sha256HostFunctions;
+
+impl HostFunctions for Sha256HostFunctions {
+	fn sha256(data: &[u8]) -> [u8; 32] {
+		sha2::Sha256::digest(data).into()
+	}
+
+	fn sha256_concat(chunks: &[&[u8]]) -> [u8; 32] {
+		let mut hasher = sha2::Sha256::new();
+		for chunk in chunks {
+			hasher.update(chunk);
+		}
+		hasher.finalize().into()
+	}
+
+	fn sha256_borsh<T: BorshSerialize + ?Sized>(value: &T) -> [u8; 32] {
+		let mut writer = HashWriter(sha2::Sha256::new());
+		value.serialize(&mut writer).expect("writing to a hasher can't fail; qed");
+		writer.0.finalize().into()
+	}
+}
+
+/// Object safe counterpart of the hashing of [`HostFunctions`], for a hasher chosen when the
+/// runtime starts rather than when the client is compiled, through [`DynHostFunctions`].
+pub trait DynDigest: Send + Sync {
+	fn sha256(&self, data: &[u8]) -> [u8; 32];
+
+	/// `sha256` of the concatenation of `chunks`.
+	fn sha256_concat(&self, chunks: &[&[u8]]) -> [u8; 32] {
+		self.sha256(&chunks.concat())
+	}
+}
+
+/// Hashes with `H`.
+pub struct HostFunctionsDigest<H>(PhantomData<fn() -> H>);
+
+impl<H> HostFunctionsDigest<H> {
+	pub const fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<H> Default for HostFunctionsDigest<H> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<H: HostFunctions> DynDigest for HostFunctionsDigest<H> {
+	fn sha256(&self, data: &[u8]) -> [u8; 32] {
+		H::sha256(data)
+	}
+
+	fn sha256_concat(&self, chunks: &[&[u8]]) -> [u8; 32] {
+		H::sha256_concat(chunks)
+	}
+}
+
+static SHA256_DIGEST: HostFunctionsDigest<Sha256HostFunctions> = HostFunctionsDigest::new();
+
+/// The installed digest, boxed so that the pointer is thin. Null until one is installed.
+static INSTALLED_DIGEST: AtomicPtr<&'static dyn DynDigest> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Hashes with the [`DynDigest`] given to [`DynHostFunctions::install`], or with
+/// [`Sha256HostFunctions`] until one is, so that the client is compiled once whatever the hasher.
+/// The digest is dispatched dynamically on every hash, and borsh encodings are buffered before
+/// being hashed.
+pub struct DynHostFunctions;
+
+impl DynHostFunctions {
+	/// Hashes with `digest` from then on, in every client over [`DynHostFunctions`]. Meant to be
+	/// called once, when the runtime starts: every call leaks the pointer to the previous digest.
+	pub fn install(digest: &'static dyn DynDigest) {
+		INSTALLED_DIGEST.store(Box::leak(Box::new(digest)), Ordering::Release);
+	}
+
+	fn digest() -> &'static dyn DynDigest {
+		let installed = INSTALLED_DIGEST.load(Ordering::Acquire);
+		if installed.is_null() {
+			return &SHA256_DIGEST
+		}
+		// SAFETY: a non null pointer comes from `Box::leak` in `install` and is never freed
+		unsafe { *installed }
+	}
+}
+
+impl HostFunctions for DynHostFunctions {
+	fn sha256(data: &[u8]) -> [u8; 32] {
+		Self::digest().sha256(data)
+	}
+
+	fn sha256_concat(chunks: &[&[u8]]) -> [u8; 32] {
+		Self::digest().sha256_concat(chunks)
+	}
+}
+
+/// Feeds the bytes written by a borsh encoder straight to the hasher.
+struct HashWriter(sha2::Sha256);
+
+impl io::Write for HashWriter {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.0.update(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::CryptoHash;
+
+	/// Only implements `sha256`, to compare the default methods with the streaming ones.
+	struct BufferingHostFunctions;
+
+	impl HostFunctions for BufferingHostFunctions {
+		fn sha256(data: &[u8]) -> [u8; 32] {
+			Sha256HostFunctions::sha256(data)
+		}
+	}
+
+	#[test]
+	fn test_sha256() {
+		assert_eq!(
+			format!("{:x}", CryptoHash(Sha256HostFunctions::sha256(b"abc"))),
+			"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+		);
+	}
+
+	/// Hashes everything to the same value, to tell it apart from SHA-256.
+	struct ConstantDigest;
+
+	impl DynDigest for ConstantDigest {
+		fn sha256(&self, _data: &[u8]) -> [u8; 32] {
+			[7; 32]
+		}
+	}
+
+	#[test]
+	fn test_installed_digest() {
+		// the only test installing a digest, as it is shared by the whole process
+		assert_eq!(DynHostFunctions::sha256(b"abc"), Sha256HostFunctions::sha256(b"abc"));
+		DynHostFunctions::install(&ConstantDigest);
+		assert_eq!(DynHostFunctions::sha256(b"abc"), [7; 32]);
+		let chunks: [&[u8]; 2] = [b"a", b"bc"];
+		assert_eq!(DynHostFunctions::sha256_concat(&chunks), [7; 32]);
+		assert_eq!(DynHostFunctions::sha256_borsh(&42u64), [7; 32]);
+
+		let digest: &dyn DynDigest = &HostFunctionsDigest::<Sha256HostFunctions>::new();
+		assert_eq!(digest.sha256(b"abc"), Sha256HostFunctions::sha256(b"abc"));
+	}
+
+	#[test]
+	fn test_streaming_matches_buffering() {
+		let value = (42u64, CryptoHash([7; 32]), vec![1u8, 2, 3]);
+		assert_eq!(
+			Sha256HostFunctions::sha256_borsh(&value),
+			BufferingHostFunctions::sha256_borsh(&value)
+		);
+		let chunks: [&[u8]; 3] = [b"a", b"", b"bc"];
+		assert_eq!(
+			Sha256HostFunctions::sha256_concat(&chunks),
+			BufferingHostFunctions::sha256_concat(&chunks)
+		);
+		assert_eq!(
+			Sha256HostFunctions::sha256_concat(&chunks),
+			Sha256HostFunctions::sha256(b"abc")
+		);
+	}
+}
@@ -1,14 +1,23 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "std")]
+mod conversions;
+pub mod ed25519;
+mod encoding;
 pub mod host_functions;
-pub use host_functions::HostFunctions;
+pub mod schema;
+pub use host_functions::{
+	DynDigest, DynHostFunctions, HostFunctions, HostFunctionsDigest, Sha256HostFunctions,
+};
 
 use sp_io::crypto::ed25519_verify;
 use sp_std::prelude::*;
 
 use borsh::maybestd::{io::Write, string::String};
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use sp_core::ed25519::{Public as Ed25519Public, Signature as Ed25519Signature};
 
 #[derive(Debug)]
@@ -21,6 +30,10 @@ pub enum Signature {
 	Ed25519(Ed25519Signature),
 }
 
+#[cfg_attr(
+	feature = "scale",
+	derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo)
+)]
 #[derive(
 	Debug,
 	Default,
@@ -33,14 +46,19 @@ pub enum Signature {
 	Copy,
 	BorshSerialize,
 	BorshDeserialize,
+	BorshSchema,
 )]
 pub struct CryptoHash(pub [u8; 32]);
 
+// hashes, keys and signatures are stored inline
+#[cfg(feature = "deepsize_feature")]
+deepsize::known_deep_size!(0; PublicKey, Signature, CryptoHash);
+
 impl Signature {
 	const LEN: usize = 64;
 
-	pub fn from_raw(raw: &[u8]) -> Self {
-		Self::Ed25519(Ed25519Signature::from_raw(raw.try_into().unwrap()))
+	pub fn from_raw(raw: &[u8; Self::LEN]) -> Self {
+		Self::Ed25519(Ed25519Signature::from_raw(*raw))
 	}
 
 	pub fn as_bytes(&self) -> &[u8] {
@@ -49,11 +67,22 @@ impl Signature {
 		}
 	}
 
+	/// Checks the verification equation, with a canonical `S`, as nearcore does (see [`ed25519`]).
 	pub fn verify(&self, data: impl AsRef<[u8]>, public_key: PublicKey) -> bool {
 		match self {
-			Self::Ed25519(signature) => {
-				ed25519_verify(signature, data.as_ref(), &Ed25519Public::from(&public_key))
-			},
+			Self::Ed25519(signature) =>
+				ed25519::is_canonical_scalar(&signature.0) &&
+					ed25519_verify(signature, data.as_ref(), &Ed25519Public::from(&public_key)),
+		}
+	}
+
+	/// [`Signature::verify`], after the strict encoding rules of [`ed25519`], which reject
+	/// signatures that nearcore accepts.
+	pub fn verify_strict(&self, data: impl AsRef<[u8]>, public_key: PublicKey) -> bool {
+		match self {
+			Self::Ed25519(signature) =>
+				ed25519::is_strictly_encoded(&signature.0, &public_key.0) &&
+					self.verify(data, public_key),
 		}
 	}
 }
@@ -61,19 +90,24 @@ impl Signature {
 impl PublicKey {
 	const LEN: usize = 32;
 
-	pub fn from_raw(raw: &[u8]) -> Self {
-		Self(raw.try_into().unwrap())
+	pub fn from_raw(raw: &[u8; Self::LEN]) -> Self {
+		Self(*raw)
+	}
+}
+
+impl TryFrom<&[u8]> for Signature {
+	type Error = ConversionError;
+	fn try_from(v: &[u8]) -> Result<Self, Self::Error> {
+		v.try_into()
+			.map(Self::from_raw)
+			.map_err(|_| ConversionError("wrong size".into()))
 	}
 }
 
 impl TryFrom<&[u8]> for CryptoHash {
 	type Error = ConversionError;
 	fn try_from(v: &[u8]) -> Result<Self, Self::Error> {
-		if v.len() != 32 {
-			return Err(ConversionError("wrong size".into()));
-		}
-		let inner: [u8; 32] = v.try_into().unwrap();
-		Ok(CryptoHash(inner))
+		v.try_into().map(CryptoHash).map_err(|_| ConversionError("wrong size".into()))
 	}
 }
 use sha2::Digest as Sha2Digest;
@@ -85,12 +119,12 @@ impl CryptoHash {
 	}
 
 	pub fn hash_borsh<T: BorshSerialize>(value: &T) -> CryptoHash {
-		let serialized = value.try_to_vec().unwrap();
+		let serialized = value.try_to_vec().expect("encoding into a Vec can't fail; qed");
 		Self::hash_bytes(&serialized)
 	}
 
-	pub fn from_raw(raw: &[u8]) -> Self {
-		Self::try_from(raw).unwrap()
+	pub fn from_raw(raw: &[u8; 32]) -> Self {
+		Self(*raw)
 	}
 
 	pub fn as_bytes(&self) -> &[u8] {
@@ -113,15 +147,136 @@ impl From<&PublicKey> for Ed25519Public {
 impl TryFrom<&[u8]> for PublicKey {
 	type Error = ConversionError;
 	fn try_from(v: &[u8]) -> Result<Self, Self::Error> {
-		if v.len() != 32 {
-			return Err(ConversionError("wrong size".into()));
-		}
-		let inner: [u8; 32] = v.try_into().unwrap();
-		Ok(PublicKey(inner))
+		v.try_into().map(PublicKey).map_err(|_| ConversionError("wrong size".into()))
+	}
+}
+
+/// Height of a block, kept apart from timestamps and other `u64` counters. Encoded as the inner
+/// `u64` by borsh and SCALE.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[cfg_attr(
+	feature = "scale",
+	derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo)
+)]
+#[derive(
+	Debug,
+	Default,
+	Ord,
+	PartialOrd,
+	PartialEq,
+	Eq,
+	Hash,
+	Clone,
+	Copy,
+	BorshSerialize,
+	BorshDeserialize,
+	BorshSchema,
+)]
+pub struct BlockHeight(pub u64);
+
+/// Id of an epoch, kept apart from block and other hashes. Encoded as the inner hash by borsh and
+/// SCALE.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[cfg_attr(
+	feature = "scale",
+	derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo)
+)]
+#[derive(
+	Debug,
+	Default,
+	Ord,
+	PartialOrd,
+	PartialEq,
+	Eq,
+	Hash,
+	Clone,
+	Copy,
+	BorshSerialize,
+	BorshDeserialize,
+	BorshSchema,
+)]
+pub struct EpochId(pub CryptoHash);
+
+/// Nonce of an access key, kept apart from heights and other `u64` counters. Encoded as the inner
+/// `u64` by borsh and SCALE.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[cfg_attr(
+	feature = "scale",
+	derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo)
+)]
+#[derive(
+	Debug,
+	Default,
+	Ord,
+	PartialOrd,
+	PartialEq,
+	Eq,
+	Hash,
+	Clone,
+	Copy,
+	BorshSerialize,
+	BorshDeserialize,
+	BorshSchema,
+)]
+pub struct Nonce(pub u64);
+
+impl BlockHeight {
+	/// The height `blocks` blocks after this one, `None` on overflow.
+	pub fn checked_add(self, blocks: u64) -> Option<Self> {
+		self.0.checked_add(blocks).map(Self)
+	}
+
+	/// Number of blocks from `earlier` to this height, zero if `earlier` is higher.
+	pub fn blocks_since(self, earlier: Self) -> u64 {
+		self.0.saturating_sub(earlier.0)
+	}
+}
+
+impl From<u64> for BlockHeight {
+	fn from(height: u64) -> Self {
+		Self(height)
+	}
+}
+
+impl From<BlockHeight> for u64 {
+	fn from(height: BlockHeight) -> Self {
+		height.0
+	}
+}
+
+impl From<CryptoHash> for EpochId {
+	fn from(hash: CryptoHash) -> Self {
+		Self(hash)
+	}
+}
+
+impl From<EpochId> for CryptoHash {
+	fn from(epoch_id: EpochId) -> Self {
+		epoch_id.0
+	}
+}
+
+impl AsRef<[u8]> for EpochId {
+	fn as_ref(&self) -> &[u8] {
+		self.0.as_ref()
+	}
+}
+
+impl From<u64> for Nonce {
+	fn from(nonce: u64) -> Self {
+		Self(nonce)
+	}
+}
+
+impl From<Nonce> for u64 {
+	fn from(nonce: Nonce) -> Self {
+		nonce.0
 	}
 }
 
-pub type BlockHeight = u64;
 pub type AccountId = String;
 pub type Balance = u128;
 pub type Gas = u64;
@@ -130,6 +285,8 @@ pub type MerkleHash = CryptoHash;
 
 pub type MerklePath = Vec<MerklePathItem>;
 
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct LightClientBlockLiteView {
 	pub prev_block_hash: CryptoHash,
@@ -137,7 +294,9 @@ pub struct LightClientBlockLiteView {
 	pub inner_lite: BlockHeaderInnerLiteView,
 }
 
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct LightClientBlockView {
 	pub prev_block_hash: CryptoHash,
 	pub next_block_inner_hash: CryptoHash,
@@ -147,11 +306,13 @@ pub struct LightClientBlockView {
 	pub approvals_after_next: Vec<Option<Signature>>,
 }
 
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct BlockHeaderInnerLiteView {
 	pub height: BlockHeight,
-	pub epoch_id: CryptoHash,
-	pub next_epoch_id: CryptoHash,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
 	pub prev_state_root: CryptoHash,
 	pub outcome_root: CryptoHash,
 	pub timestamp: u64,
@@ -162,11 +323,12 @@ pub struct BlockHeaderInnerLiteView {
 
 /// For some reason, when calculating the hash of the current block
 /// `timestamp_nanosec` is ignored
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct BlockHeaderInnerLiteViewFinal {
 	pub height: BlockHeight,
-	pub epoch_id: CryptoHash,
-	pub next_epoch_id: CryptoHash,
+	pub epoch_id: EpochId,
+	pub next_epoch_id: EpochId,
 	pub prev_state_root: CryptoHash,
 	pub outcome_root: CryptoHash,
 	pub timestamp: u64,
@@ -174,24 +336,44 @@ pub struct BlockHeaderInnerLiteViewFinal {
 	pub block_merkle_root: CryptoHash,
 }
 
-#[derive(Debug, BorshDeserialize, BorshSerialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum ApprovalInner {
 	Endorsement(CryptoHash),
 	Skip(BlockHeight),
 }
 
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub enum ValidatorStakeView {
 	V1(ValidatorStakeViewV1),
+	/// nearcore's layout once chunk-only producers are enabled. The borsh tag of the version is
+	/// part of `next_bp_hash`, so block producers must be kept in the version they were sent in.
+	V2(ValidatorStakeViewV2),
 }
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct ValidatorStakeViewV1 {
 	pub account_id: AccountId,
 	pub public_key: PublicKey,
 	pub stake: Balance,
 }
 
-#[derive(Debug, Clone, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct ValidatorStakeViewV2 {
+	pub account_id: AccountId,
+	pub public_key: PublicKey,
+	pub stake: Balance,
+	pub is_chunk_only: bool,
+}
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshDeserialize, BorshSchema)]
 pub struct ExecutionOutcomeView {
 	/// Logs from this transaction or receipt.
 	pub logs: Vec<String>,
@@ -211,7 +393,9 @@ pub struct ExecutionOutcomeView {
 	                      * having to define too many unnecessary structs) */
 }
 
-#[derive(Clone, Debug, BorshDeserialize)]
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, BorshDeserialize, BorshSchema)]
 pub struct OutcomeProof {
 	pub proof: Vec<MerklePathItem>,
 	pub block_hash: CryptoHash,
@@ -220,40 +404,379 @@ pub struct OutcomeProof {
 }
 
 #[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
-#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub enum Direction {
 	Left,
 	Right,
 }
 
 impl ValidatorStakeView {
+	/// The fields every version has, dropping the others.
 	pub fn into_validator_stake(self) -> ValidatorStakeViewV1 {
 		match self {
 			Self::V1(inner) => inner,
+			Self::V2(inner) => ValidatorStakeViewV1 {
+				account_id: inner.account_id,
+				public_key: inner.public_key,
+				stake: inner.stake,
+			},
+		}
+	}
+
+	pub fn account_id(&self) -> &AccountId {
+		match self {
+			Self::V1(inner) => &inner.account_id,
+			Self::V2(inner) => &inner.account_id,
+		}
+	}
+
+	pub fn public_key(&self) -> &PublicKey {
+		match self {
+			Self::V1(inner) => &inner.public_key,
+			Self::V2(inner) => &inner.public_key,
+		}
+	}
+
+	pub fn stake(&self) -> Balance {
+		match self {
+			Self::V1(inner) => inner.stake,
+			Self::V2(inner) => inner.stake,
+		}
+	}
+
+	pub fn stake_mut(&mut self) -> &mut Balance {
+		match self {
+			Self::V1(inner) => &mut inner.stake,
+			Self::V2(inner) => &mut inner.stake,
+		}
+	}
+
+	/// Whether the validator only produces chunks, never before V2.
+	pub fn is_chunk_only(&self) -> bool {
+		match self {
+			Self::V1(_) => false,
+			Self::V2(inner) => inner.is_chunk_only,
 		}
 	}
 }
 #[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
-#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct MerklePathItem {
 	pub hash: MerkleHash,
 	pub direction: Direction,
 }
 
+/// Account record, as stored in the state trie.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Account {
+	/// The total not locked tokens.
+	pub amount: Balance,
+	/// The amount locked due to staking.
+	pub locked: Balance,
+	/// Hash of the code stored in the storage for this account.
+	pub code_hash: CryptoHash,
+	/// Storage used by the given account, includes account id, this struct, access keys and
+	/// other data.
+	pub storage_usage: u64,
+}
+
+/// Access key of an account, as stored in the state trie.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct AccessKey {
+	/// Nonce of the last transaction signed with this key.
+	pub nonce: Nonce,
+	/// What the key is allowed to do.
+	pub permission: AccessKeyPermission,
+}
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum AccessKeyPermission {
+	FunctionCall(FunctionCallPermission),
+	/// Grants full access to the account.
+	FullAccess,
+}
+
+/// Grants access to some methods of a single contract.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct FunctionCallPermission {
+	/// Amount of tokens the key can spend on gas, unlimited if `None`.
+	pub allowance: Option<Balance>,
+	/// The only contract the key can call.
+	pub receiver_id: AccountId,
+	/// The methods the key can call, any method if empty.
+	pub method_names: Vec<String>,
+}
+
+/// Header of a chunk, in the layout used since chunk headers v3.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct ChunkHeaderView {
+	pub inner: ShardChunkHeaderInner,
+	/// Height of the block the chunk was included in.
+	pub height_included: BlockHeight,
+}
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum ShardChunkHeaderInner {
+	V1(ShardChunkHeaderInnerV1),
+	V2(ShardChunkHeaderInnerV2),
+}
+
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct ShardChunkHeaderInnerV1 {
+	pub prev_block_hash: CryptoHash,
+	pub prev_state_root: CryptoHash,
+	/// Root of the outcomes of the previous chunk of the shard.
+	pub outcome_root: CryptoHash,
+	pub encoded_merkle_root: CryptoHash,
+	pub encoded_length: u64,
+	pub height_created: BlockHeight,
+	pub shard_id: u64,
+	pub gas_used: Gas,
+	pub gas_limit: Gas,
+	pub balance_burnt: Balance,
+	pub outgoing_receipts_root: CryptoHash,
+	/// Root of the transactions of the chunk.
+	pub tx_root: CryptoHash,
+	pub validator_proposals: Vec<ValidatorStakeViewV1>,
+}
+
+/// Same as [`ShardChunkHeaderInnerV1`], with versioned validator proposals.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct ShardChunkHeaderInnerV2 {
+	pub prev_block_hash: CryptoHash,
+	pub prev_state_root: CryptoHash,
+	pub outcome_root: CryptoHash,
+	pub encoded_merkle_root: CryptoHash,
+	pub encoded_length: u64,
+	pub height_created: BlockHeight,
+	pub shard_id: u64,
+	pub gas_used: Gas,
+	pub gas_limit: Gas,
+	pub balance_burnt: Balance,
+	pub outgoing_receipts_root: CryptoHash,
+	pub tx_root: CryptoHash,
+	pub validator_proposals: Vec<ValidatorStakeView>,
+}
+
+/// A validator slashed by the challenges of a block.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct SlashedValidator {
+	pub account_id: AccountId,
+	pub is_double_sign: bool,
+}
+
+/// The part of a block header that light client blocks only carry as `inner_rest_hash`, in the
+/// layout of block headers v1.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct BlockHeaderInnerRestV1 {
+	pub chunk_receipts_root: CryptoHash,
+	pub chunk_headers_root: CryptoHash,
+	pub chunk_tx_root: CryptoHash,
+	pub chunks_included: u64,
+	pub challenges_root: CryptoHash,
+	pub random_value: CryptoHash,
+	pub validator_proposals: Vec<ValidatorStakeViewV1>,
+	/// Whether the chunk of each shard is new in the block.
+	pub chunk_mask: Vec<bool>,
+	pub gas_price: Balance,
+	pub total_supply: Balance,
+	pub challenges_result: Vec<SlashedValidator>,
+	pub last_final_block: CryptoHash,
+	pub last_ds_final_block: CryptoHash,
+	/// Approvals of the previous block.
+	pub approvals: Vec<Option<Signature>>,
+	pub latest_protocol_version: u32,
+}
+
+/// Same as [`BlockHeaderInnerRestV1`], without `chunks_included`.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct BlockHeaderInnerRestV2 {
+	pub chunk_receipts_root: CryptoHash,
+	pub chunk_headers_root: CryptoHash,
+	pub chunk_tx_root: CryptoHash,
+	pub challenges_root: CryptoHash,
+	pub random_value: CryptoHash,
+	pub validator_proposals: Vec<ValidatorStakeViewV1>,
+	pub chunk_mask: Vec<bool>,
+	pub gas_price: Balance,
+	pub total_supply: Balance,
+	pub challenges_result: Vec<SlashedValidator>,
+	pub last_final_block: CryptoHash,
+	pub last_ds_final_block: CryptoHash,
+	pub approvals: Vec<Option<Signature>>,
+	pub latest_protocol_version: u32,
+}
+
+/// The layout of block headers v3, with versioned validator proposals, the ordinal of the block,
+/// the height of the previous one and the hash of the epoch sync data.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct BlockHeaderInnerRestV3 {
+	pub chunk_receipts_root: CryptoHash,
+	pub chunk_headers_root: CryptoHash,
+	pub chunk_tx_root: CryptoHash,
+	pub challenges_root: CryptoHash,
+	pub random_value: CryptoHash,
+	pub validator_proposals: Vec<ValidatorStakeView>,
+	pub chunk_mask: Vec<bool>,
+	pub gas_price: Balance,
+	/// Number of blocks on the canonical chain before this one, plus one.
+	pub block_ordinal: u64,
+	pub total_supply: Balance,
+	pub challenges_result: Vec<SlashedValidator>,
+	pub last_final_block: CryptoHash,
+	pub last_ds_final_block: CryptoHash,
+	pub prev_height: BlockHeight,
+	pub epoch_sync_data_hash: Option<CryptoHash>,
+	pub approvals: Vec<Option<Signature>>,
+	pub latest_protocol_version: u32,
+}
+
+/// `BlockHeaderInnerRest` of any version. Its borsh encoding has no tag, so it is decoded as the
+/// version of the header it belongs to.
+#[cfg_attr(feature = "deepsize_feature", derive(deepsize::DeepSizeOf))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub enum BlockHeaderInnerRest {
+	V1(BlockHeaderInnerRestV1),
+	V2(BlockHeaderInnerRestV2),
+	V3(BlockHeaderInnerRestV3),
+}
+
+impl BlockHeaderInnerRest {
+	pub fn chunk_headers_root(&self) -> CryptoHash {
+		match self {
+			Self::V1(inner) => inner.chunk_headers_root,
+			Self::V2(inner) => inner.chunk_headers_root,
+			Self::V3(inner) => inner.chunk_headers_root,
+		}
+	}
+
+	pub fn chunk_mask(&self) -> &[bool] {
+		match self {
+			Self::V1(inner) => &inner.chunk_mask,
+			Self::V2(inner) => &inner.chunk_mask,
+			Self::V3(inner) => &inner.chunk_mask,
+		}
+	}
+
+	pub fn gas_price(&self) -> Balance {
+		match self {
+			Self::V1(inner) => inner.gas_price,
+			Self::V2(inner) => inner.gas_price,
+			Self::V3(inner) => inner.gas_price,
+		}
+	}
+
+	pub fn total_supply(&self) -> Balance {
+		match self {
+			Self::V1(inner) => inner.total_supply,
+			Self::V2(inner) => inner.total_supply,
+			Self::V3(inner) => inner.total_supply,
+		}
+	}
+
+	pub fn challenges_result(&self) -> &[SlashedValidator] {
+		match self {
+			Self::V1(inner) => &inner.challenges_result,
+			Self::V2(inner) => &inner.challenges_result,
+			Self::V3(inner) => &inner.challenges_result,
+		}
+	}
+
+	pub fn latest_protocol_version(&self) -> u32 {
+		match self {
+			Self::V1(inner) => inner.latest_protocol_version,
+			Self::V2(inner) => inner.latest_protocol_version,
+			Self::V3(inner) => inner.latest_protocol_version,
+		}
+	}
+}
+
+impl ShardChunkHeaderInner {
+	pub fn prev_block_hash(&self) -> CryptoHash {
+		match self {
+			Self::V1(inner) => inner.prev_block_hash,
+			Self::V2(inner) => inner.prev_block_hash,
+		}
+	}
+
+	pub fn prev_state_root(&self) -> CryptoHash {
+		match self {
+			Self::V1(inner) => inner.prev_state_root,
+			Self::V2(inner) => inner.prev_state_root,
+		}
+	}
+
+	pub fn outcome_root(&self) -> CryptoHash {
+		match self {
+			Self::V1(inner) => inner.outcome_root,
+			Self::V2(inner) => inner.outcome_root,
+		}
+	}
+
+	pub fn encoded_merkle_root(&self) -> CryptoHash {
+		match self {
+			Self::V1(inner) => inner.encoded_merkle_root,
+			Self::V2(inner) => inner.encoded_merkle_root,
+		}
+	}
+
+	pub fn shard_id(&self) -> u64 {
+		match self {
+			Self::V1(inner) => inner.shard_id,
+			Self::V2(inner) => inner.shard_id,
+		}
+	}
+
+	pub fn tx_root(&self) -> CryptoHash {
+		match self {
+			Self::V1(inner) => inner.tx_root,
+			Self::V2(inner) => inner.tx_root,
+		}
+	}
+}
+
+impl ChunkHeaderView {
+	/// `combine_hash(sha256(borsh(inner)), encoded_merkle_root)`, as for chunk headers v3.
+	pub fn chunk_hash<H: HostFunctions>(&self) -> CryptoHash {
+		let inner_hash = H::sha256_borsh(&self.inner);
+		CryptoHash(H::sha256_concat(&[&inner_hash, self.inner.encoded_merkle_root().as_ref()]))
+	}
+}
+
 impl LightClientBlockView {
 	pub fn current_block_hash<H: HostFunctions>(&self) -> CryptoHash {
 		// NOTE: current block hash does not contain `timestamp_nanosec` from
-		// BlockHeaderInnerLiteView hence the reason of creating a new struct (i.e:
-		// BlockHeaderInnerLiteViewFinal) to conform with the struct that is actually being hashed.
+		// BlockHeaderInnerLiteView hence the reason of encoding it as
+		// BlockHeaderInnerLiteViewFinal, the struct that is actually being hashed.
 		current_block_hash::<H>(
-			H::sha256(
-				&BlockHeaderInnerLiteViewFinal::from(self.inner_lite.clone())
-					.try_to_vec()
-					.unwrap(),
-			)
-			.as_slice()
-			.try_into()
-			.unwrap(),
+			CryptoHash(H::sha256_borsh(&InnerLiteFinal(&self.inner_lite))),
 			self.inner_rest_hash,
 			self.prev_block_hash,
 		)
@@ -270,6 +793,16 @@ impl LightClientBlockView {
 	}
 }
 
+impl LightClientBlockLiteView {
+	pub fn current_block_hash<H: HostFunctions>(&self) -> CryptoHash {
+		current_block_hash::<H>(
+			CryptoHash(H::sha256_borsh(&InnerLiteFinal(&self.inner_lite))),
+			self.inner_rest_hash,
+			self.prev_block_hash,
+		)
+	}
+}
+
 /// The hash of the block is:
 /// ```ignore
 /// sha256(concat(
@@ -286,26 +819,36 @@ fn current_block_hash<H: HostFunctions>(
 	inner_rest_hash: CryptoHash,
 	prev_block_hash: CryptoHash,
 ) -> CryptoHash {
-	CryptoHash(
-		H::sha256(
-			&[
-				H::sha256(&[inner_lite_hash.as_ref(), inner_rest_hash.as_ref()].concat()).as_ref(),
-				prev_block_hash.as_ref(),
-			]
-			.concat(),
-		)
-		.as_slice()
-		.try_into()
-		.unwrap(),
-	)
+	CryptoHash(H::sha256_concat(&[
+		&H::sha256_concat(&[inner_lite_hash.as_ref(), inner_rest_hash.as_ref()]),
+		prev_block_hash.as_ref(),
+	]))
+}
+
+/// Borsh encodes a [`BlockHeaderInnerLiteView`] as the [`BlockHeaderInnerLiteViewFinal`] it
+/// would convert to, without copying it.
+struct InnerLiteFinal<'a>(&'a BlockHeaderInnerLiteView);
+
+impl BorshSerialize for InnerLiteFinal<'_> {
+	fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), borsh::maybestd::io::Error> {
+		let inner_lite = self.0;
+		inner_lite.height.serialize(writer)?;
+		inner_lite.epoch_id.serialize(writer)?;
+		inner_lite.next_epoch_id.serialize(writer)?;
+		inner_lite.prev_state_root.serialize(writer)?;
+		inner_lite.outcome_root.serialize(writer)?;
+		inner_lite.timestamp.serialize(writer)?;
+		inner_lite.next_bp_hash.serialize(writer)?;
+		inner_lite.block_merkle_root.serialize(writer)
+	}
 }
 
 impl BlockHeaderInnerLiteView {
 	pub fn new_for_test() -> Self {
 		Self {
-			height: 1,
-			epoch_id: CryptoHash([0; 32]),
-			next_epoch_id: CryptoHash([0; 32]),
+			height: BlockHeight(1),
+			epoch_id: EpochId(CryptoHash([0; 32])),
+			next_epoch_id: EpochId(CryptoHash([0; 32])),
 			prev_state_root: CryptoHash([0; 32]),
 			outcome_root: CryptoHash([0; 32]),
 			timestamp: 1,
@@ -392,7 +935,7 @@ mod tests {
 		// because it is what it's used to calculate the `next_bp_hash`
 		use near_crypto::Signature as NearSignature;
 		let near_signature = NearSignature::from_str("2evZLyx1HQHy8QuJ5AjZ4LV5ixgQF4RoXjjTQ58ekuQ4NqjrYiY89UXBH9nR4oQfgSzm3beUQiLfjrDzQG5dBdVQ").unwrap();
-		let signature = Signature::from_raw(bs58::decode("2evZLyx1HQHy8QuJ5AjZ4LV5ixgQF4RoXjjTQ58ekuQ4NqjrYiY89UXBH9nR4oQfgSzm3beUQiLfjrDzQG5dBdVQ").into_vec().unwrap().as_ref());
+		let signature = Signature::try_from(bs58::decode("2evZLyx1HQHy8QuJ5AjZ4LV5ixgQF4RoXjjTQ58ekuQ4NqjrYiY89UXBH9nR4oQfgSzm3beUQiLfjrDzQG5dBdVQ").into_vec().unwrap().as_slice()).unwrap();
 		let mut near_signature_buffer = vec![0; 64];
 		let mut signature_buffer = vec![0; 64];
 		near_signature.serialize(&mut near_signature_buffer).unwrap();
@@ -401,6 +944,14 @@ mod tests {
 		assert_eq!(signature_buffer, near_signature_buffer);
 	}
 
+	#[test]
+	fn test_wrong_sizes_are_conversion_errors() {
+		assert!(Signature::try_from(&[1; 63][..]).is_err());
+		assert!(PublicKey::try_from(&[1; 33][..]).is_err());
+		assert!(CryptoHash::try_from(&[][..]).is_err());
+		assert_eq!(CryptoHash::try_from(&[1; 32][..]).unwrap(), CryptoHash([1; 32]));
+	}
+
 	#[test]
 	fn ensure_equality_on_publickey_serialization() {
 		// given that this crate does not use `near-primitive`, we need to ensure that
@@ -411,7 +962,7 @@ mod tests {
 			NearPublicKey::from_str("D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8").unwrap();
 		let pubkey_decoded =
 			bs58::decode("D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8").into_vec().unwrap();
-		let public_key = PublicKey::from_raw(pubkey_decoded.as_ref());
+		let public_key = PublicKey::try_from(pubkey_decoded.as_slice()).unwrap();
 		let mut near_public_key_buffer = vec![0; PublicKey::LEN];
 		let mut public_key_buffer = vec![0; PublicKey::LEN];
 		near_public_key.serialize(&mut near_public_key_buffer).unwrap();
@@ -422,498 +973,8 @@ mod tests {
 
 	#[test]
 	fn test_ensure_deserialization_from_block_view_near_primitives() {
-		const CLIENT_BLOCK_VIEW: &str = r#"
-        {
-            "jsonrpc": "2.0",
-            "result": {
-                "approvals_after_next": [
-                    null,
-                    "ed25519:24pvVMA2ybxuk7fsCNAxDRnby5KQbGBM61T4Am74grDRuhiPbtYWBrubeSNWTejiAwiMZZt1zvLKSR8Djr4nDfHz",
-                    "ed25519:24pvVMA2ybxuk7fsCNAxDRnby5KQbGBM61T4Am74grDRuhiPbtYWBrubeSNWTejiAwiMZZt1zvLKSR8Djr4nDfHz",
-                    "ed25519:24pvVMA2ybxuk7fsCNAxDRnby5KQbGBM61T4Am74grDRuhiPbtYWBrubeSNWTejiAwiMZZt1zvLKSR8Djr4nDfHz",
-                    "ed25519:c78hanGiPzZ5iq9GPQET9pTh6J8pw5YgRGjtbNq35LuCzyTa5b4vdjzcAfHuRznfbTis77nF1aL6zm4CTJTesgU",
-                    "ed25519:65mYbzdjVUkWCh1wL81kZu96XphPP8X5McUVo2ScSKPgNiNBd3AsyR5XbJE7MGW5GnBwaqDPK8ft3yyRa3UMJnua",
-                    "ed25519:4akzNHFaa7w1LvaBTFKir9ExKStoRo44rm7YJ7XvtrigDnWmQ41EV7SyEFqcSqbDSznoxZybLQUV8ccCbia1daNT",
-                    "ed25519:4AGwZcCRk5WhCEuvEk12ANyJKHwwoLAPmGjU9Vqf7Xn7pDQcXw5sY8sPt3LazU7EYVaDUnZwWJUp2cAGHXkuLLyL",
-                    "ed25519:31anmHx3XEyPCnn7Mth5oppwbXoJbDQQjw3WjcDLGs8167RBE4WgCPaHn8kfHyhQ3tWWHudi1CFhy92yjJKPdLNK",
-                    "ed25519:4qLsTS1cF9ahcAnddUGjY7yFx2Sd8gJpp2dU3LjRpzT9vpG3grrGDcqCxVRAgjq7tEyuKXsbL7zyxVgpXjbicVwK",
-                    "ed25519:LtwgqDVPQWvomdx2zoXmfopgRgzLjxpovmjRXgetpZvc3E19iKjHbYtcs8FgGS4b6AT9GqgtoGfuLD1qdR94i2D",
-                    "ed25519:4pqiJapEEMS3czyAwM7QW4qecjT5u4EQkFa79rtCmSEACnKtxuU5PGBwsbZJkq7h8xhS24vN7d5AzszuKGWviNR9",
-                    "ed25519:38FxGgLRJMoD2cC3zX93c47iD51pGMvcVpPzCX9hSYfexJg8st7Ny4vr6U4sBfiyLeToqJTuoobEUzEts2eZXBxa",
-                    "ed25519:2Srg7nZ29C8ySxMKkMFXzFXj5i1RL4NGQ19GQryMmHcEYSVLFGBauhjydbEtaEQ5tzpZpMFPeGLnyV88GQSUHcPA",
-                    null,
-                    "ed25519:2LX76ZV8iB7ZyaVAAtpUjQDwKAshix3zLk9X5kF6gsn7oqMT6Rw6Mns3HZkD9M4mmMGEiUQqETw8P36Kymb1GUjb",
-                    "ed25519:2e39nRRS97kvfkjjGohtggubeTBGX8sqGSuL83nH1PYWDGoSANcUZqYeWZKxy8dzW44HEc9ptHYgGsynf6m4RY5S",
-                    "ed25519:2s6yBzV5D8VFS9hDsyqwHw3QQu4mQhjq4R6VHYMVXgbbogCe11eP4xZYUtw44gZrPawV3yxeqQH2RQFngdw5fABH",
-                    "ed25519:5kZ6EbdnhfwxdzwhEBarnMeNi5ng2UujvYNkafUVEeN39Bbap3WgiNz1j697WrW9Zw1HNNu4ZEGxk4ad96Z3e6rB",
-                    "ed25519:3qXYatuMPnxyRKyytzSBTtRxQ38Sm42Asf9jDo5MPoNnQVhiHBdiAydZWyrKfdqgHnibVc6Xxh6yPzSQxK67xCFu",
-                    null,
-                    "ed25519:5AnKpS5LaHayrW8pFoCkNugAEfSvniMJEzCSq1u4NPYTrkzsrLRiQ2SANVwA3PkXJrz6hd1abQCCWNhJMPsNNHQu",
-                    "ed25519:5D867Gg5xv9XiBWXMxhzx3cfY41moU5g7E62PyQLrEmvLSL5px67ojzasVd4whdqF3CzkN8wuuzGi2vvqqPNLPkr",
-                    "ed25519:5Bi9FH8gmnncJJNjpcNQx3AV12VpmF6Mk3pCGVvitBUrMMAKrYUEHh3knZpGJVWCVjP4TyxfKRwvCGVH2VNbHXMF",
-                    "ed25519:2J13vtY7vzxREcYQU4micZMpNskakdbvbxC5CMUnf4BSRf6my2nQ5g77GWSH4DNC9FTSW6ZQACJHfXyy9opMqLfT",
-                    "ed25519:sLUzhmxwGgVRLePVBvwMrW3Ny7E2ftWVnRAntbqF4sempASFMbwhjHvcBUfmNtJUSL9Qc5gEwbDgMMrriEuK2JK",
-                    "ed25519:2547xqoEPW9hR2Jh5FDgZDvuxmacMdPUdq4mqpJt18StSLoWN3B5ojSztBMdRaRNga5DWneNL9GViB712BJqYksh",
-                    "ed25519:3XKJohJC5Vr79FM5aVfjGkBP2Ck8hNEtEemmMRKfNh8NLQyVAED8rqyHhxSG2G7tnmt37tUgaZQcyNaQe9AC7zB3",
-                    "ed25519:wRkBDZg2MyGHZqhGYq8Pyv3uvu15jghNVWgdhmtsE5CFqzp4ws2YTCRnS4KUe3U7canCByh9hJHGetj9EHaGUop",
-                    null,
-                    "ed25519:5MiiKrUGXpcZB9VDVxUSpvKumFR9yiZgWEsVjHK4erJ4JEfzd7M17KnaaDWdLWn3w23drpqBLZCLxP6d1FqcvWP2",
-                    "ed25519:3WC83k8v1AqtK8QzUsNFWKSQrTNxbfxmtm19xDwnTDG5W22uzYb232eBAwALFqZjSbNifr1DXd25fyE7msM6kfjc",
-                    "ed25519:3UvniTBSgJPp8Mv1b8Z39pj7DSKZ3Epfy5xC7Mo4SAFDhgTZ7rxABtjT7tKj1S73JoREkzvdW2H1zRfeCRpWYyMU",
-                    "ed25519:36XryCFKF9tVv4x6FhaJT8iYfrZPCFYbjnmteNkzXQcJRiRp2MwivcYpvrkUUzFYMDuN4uSdYgozs3uPqgKha6Mb",
-                    null,
-                    null,
-                    "ed25519:5znLSC9mJRDEt5ozPc9cBisW5fn3matgmEcNBQtvvgpNNGgXYxHzW8aJkTmrovXRDiyWDbwkpY3GYbqPy66zGNSW",
-                    "ed25519:35uHJvJ8cmQGxHWsPjkBMg4SCmDEEYmLgUuqvQZBarqx5uck6apdi3SRp3AgSPDzT8tFuGCiXz8EHByHjDmoGbiz",
-                    "ed25519:45Qq5tSNJbzphyGerqEKCsEBq8bmrza5aferuEEijmhdgATdt6f4RDE9PDc86AwdURTLd7UVerkTtHheofa2YJet",
-                    "ed25519:3G1gba8V5YsFdKnQmwpGfcy47J6etLeBz57oZwdrRnqWboQd15TRzJxzfmrMgMn415CpwLFq3iXWBrUUA2B3ZiPe",
-                    null,
-                    null,
-                    "ed25519:5u63DbmzPiyB1R7DeCpKUAN1fJUTUpmh7FieGm6w1JWcJtHcD3EkMQGs5eoQ4XZbStACc3f9CSeQrz13hm7B2ipN",
-                    "ed25519:2vgaVE77b38bFYNJort92hRJQrtxZp13vLCK6WQqs9cbWAQfk5pMnPcUZis2z9rbk411QhmtWo9WPHHspAgMUEaf",
-                    "ed25519:TdUvco7vQAXqor6fBcwPBhyDaVKffYXknRB3T7cyWwDBNJ9etJtNje7wL6oQmUkQwndqwzKscNg8nKN38M5Fzdt",
-                    "ed25519:dYFyNK7uNQECEXzrj4eQZAGdkeKrVxnsR6u3rRDE43uJTBf1tXPffspeonwMuFx9DqsGg4DSRy6hPPmzdNQruNz",
-                    null,
-                    null,
-                    null,
-                    "ed25519:5vsSMabj5pz7um6fvVKwF6WyJvsaEZ8YjyeqgSxSkZGWB2Zm2yaV7QqzTnzurx4KT7Zhdvow4HjA3hBWt8Wt1ti1",
-                    null,
-                    null,
-                    "ed25519:3HfQt71AT6iVygpeNBHUaimx3iNnApbfTSsL5u65uyzkSHPCYwwSoQ7GfUCSuMp7HAm1cvpTf8RxzimKu9WeGa88",
-                    null,
-                    "ed25519:5ds369kTT4eUM1gcebAuuShPtft7LEZTA5oDwsxVv3Bazpb15WmDhSRuhUztGVTpDwMXijs68Gt7kUu3bD45KJpH",
-                    null,
-                    null,
-                    null,
-                    null,
-                    null,
-                    "ed25519:q7c7Mu5mKvZuBfpeRChGMbL1BZuwv72k2YvF9QoQHZE1yMRYnyQxvnAuHMiYLnnqKyD9PKA9ncssJfZcoL6jV2a",
-                    "ed25519:5u6PWvtS88g13Z5aS6y57uBfuDXvXTw7Hr9ZaxcicadfyHZCft9tq71heoUva9ewZLznWsCBy7JCJ7m75JG1CDPA",
-                    "ed25519:xjH8MR2JGH9ofpFPaU8GcoidsAohePQjtSi4M7T6SgXC1qZzhst6WLuguBaKTBqoPZU75N2Kkztfv3SKdyJKUQm",
-                    "ed25519:3nVu8XDkNep3UDWF8QVvf9NvfL37Z5DBFEXJ6VbsosRjpH8NSuU8DquYfd55rfDNHDUxY1yerk1grz1GvorhUon6",
-                    "ed25519:28qtBZAsbnBiZ6wPynzgTXuRm2fB4SPGmeBgyrfY61VSCNkr7LZ5zwLUxhrDUGQnLiaVS13tU9eBECvbocGZBgXE",
-                    null,
-                    "ed25519:M8ybFBsk3xZuXE48RwxSCVwyZB2srJVQ85cWazneyc1SQzuHXciKzouw3NXzwussKvpvvV4jsyPyEosfVmifnMm",
-                    "ed25519:38yS9p1AcoXiS7E4EMn9gpCvAppCrdvygvDQwnP5VTjHahTbyGLV67mre1k4x9TZ2JD36sffYZzh5BBgaXpNSCJF",
-                    "ed25519:G9H34TNeTP5QgifK9a6Y8PQVpnM5x7V2M7zSzsYCjdc1GUVsFFrMWPiJsigKnrV5pKi6yvWFUDwhYgXUPGGKZiY",
-                    "ed25519:4tC17LadtbHChDDvEJaGrsmc1Jj7F6PT7GQq9Ncd8tykG5tNYxfA9kXz57tvwRbzeZjqjPykAPY2KrN4XMs4M9sB"
-                ],
-                "inner_lite": {
-                    "block_merkle_root": "3MBnipBo8GnqJisZN3uFjHLuvMusBSCjMaQmUmj5u4J6",
-                    "epoch_id": "GHmqgUX59irTdh31mtuEs3uEaPNBY5sQTZjEX5w7ASgW",
-                    "height": 86456070,
-                    "next_bp_hash": "9VPzyStHi4X2T7VAbfSTbLXEd8vjFP7wFJjYyjSJxQik",
-                    "next_epoch_id": "8nVTHDfxg2G8AWbKhVfFtnEb5jJeiXV2XBFsdyt2cif1",
-                    "outcome_root": "56KJ7kyW7aADwfDdNE4fz7pmPccBqkmxvnJ3nR1fewop",
-                    "prev_state_root": "2VJekkjBnP36c3sGo9P2YxkEu9dabK9r5VdMTt7jADLv",
-                    "timestamp": 1648810204507638699,
-                    "timestamp_nanosec": "1648810204507638699"
-                },
-                "inner_rest_hash": "GQHrWtXByznAWcawC7GoEMumZ3GUi2T82MXV56c2x8KS",
-                "next_block_inner_hash": "8rHAfAgpXQKXTDWwEPvHwzKaBjG67nv4eNGSfc7A8FZ5",
-                "next_bps": [
-                    {
-                        "account_id": "node1",
-                        "public_key": "ed25519:ydgzeXHJ5Xyt7M1gXLxqLBW1Ejx6scNV5Nx2pxFM8su",
-                        "stake": "22949327592242450816363151898853",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "node0",
-                        "public_key": "ed25519:ydgzeXHJ5Xyt7M1gXLxqLBW1Ejx6scNV5Nx2pxFM8su",
-                        "stake": "16944923507607057621836326590864",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "node2",
-                        "public_key": "ed25519:ydgzeXHJ5Xyt7M1gXLxqLBW1Ejx6scNV5Nx2pxFM8su",
-                        "stake": "16894243398827941870356919783063",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "node3",
-                        "public_key": "ed25519:ydgzeXHJ5Xyt7M1gXLxqLBW1Ejx6scNV5Nx2pxFM8su",
-                        "stake": "8577838094223400746241842212915",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "legends.pool.f863973.m0",
-                        "public_key": "ed25519:AhQ6sUifJYgjqarXSAzdDZU9ZixpUesP9JEH1Vr7NbaF",
-                        "stake": "5793326871499643941084500854531",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "staked.pool.f863973.m0",
-                        "public_key": "ed25519:D2afKYVaKQ1LGiWbMAZRfkKLgqimTR74wvtESvjx5Ft2",
-                        "stake": "4559762052294055739961541809028",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "masternode24.pool.f863973.m0",
-                        "public_key": "ed25519:9E3JvrQN6VGDGg1WJ3TjBsNyfmrU6kncBcDvvJLj6qHr",
-                        "stake": "3416574120678826701003147150326",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "01node.pool.f863973.m0",
-                        "public_key": "ed25519:3iNqnvBgxJPXCxu6hNdvJso1PEAc1miAD35KQMBCA3aL",
-                        "stake": "3061276782639300406837420592214",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "p2p.pool.f863973.m0",
-                        "public_key": "ed25519:4ie5979JdSR4f7MRAG58eghRxndVoKnAYAKa1PLoMYSS",
-                        "stake": "2958427611565856637171061933942",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "nodeasy.pool.f863973.m0",
-                        "public_key": "ed25519:25Dhg8NBvQhsVTuugav3t1To1X1zKiomDmnh8yN9hHMb",
-                        "stake": "1575068818350064235628643461649",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "tribe-pool.pool.f863973.m0",
-                        "public_key": "ed25519:CRS4HTSAeiP8FKD3c3ZrCL5pC92Mu1LQaWj22keThwFY",
-                        "stake": "1429199212043501677779067532132",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "chorusone.pool.f863973.m0",
-                        "public_key": "ed25519:3TkUuDpzrq75KtJhkuLfNNJBPHR5QEWpDxrter3znwto",
-                        "stake": "1278827676875609593894511486301",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "hotones.pool.f863973.m0",
-                        "public_key": "ed25519:2fc5xtbafKiLtxHskoPL2x7BpijxSZcwcAjzXceaxxWt",
-                        "stake": "1273529881837124230828073909315",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "foundryusa.pool.f863973.m0",
-                        "public_key": "ed25519:ABGnMW8c87ZKWxvZLLWgvrNe72HN7UoSf4cTBxCHbEE5",
-                        "stake": "1256081604638924285747937189845",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "lunanova2.pool.f863973.m0",
-                        "public_key": "ed25519:9Jv6e9Kye4wM9EL1XJvXY8CYsLi1HLdRKnTzXBQY44w9",
-                        "stake": "1247431491303762172509349058430",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "chorus-one.pool.f863973.m0",
-                        "public_key": "ed25519:6LFwyEEsqhuDxorWfsKcPPs324zLWTaoqk4o6RDXN7Qc",
-                        "stake": "1110429050842727763339891353120",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "ni.pool.f863973.m0",
-                        "public_key": "ed25519:GfCfFkLk2twbAWdsS3tr7C2eaiHN3znSfbshS5e8NqBS",
-                        "stake": "1076903268858699791106964347506",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "cryptogarik.pool.f863973.m0",
-                        "public_key": "ed25519:FyFYc2MVwgitVf4NDLawxVoiwUZ1gYsxGesGPvaZcv6j",
-                        "stake": "840652974653901124214299092043",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "pathrocknetwork.pool.f863973.m0",
-                        "public_key": "ed25519:CGzLGZEMb84nRSRZ7Au1ETAoQyN7SQXQi55fYafXq736",
-                        "stake": "749739988926667488225409312930",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "stakely_v2.pool.f863973.m0",
-                        "public_key": "ed25519:7BanKZKGvFjK5Yy83gfJ71vPhqRwsDDyVHrV2FMJCUWr",
-                        "stake": "734779467803676488422251769143",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "solidstate.pool.f863973.m0",
-                        "public_key": "ed25519:DTDhqoMXDWhKedWpH7DPvR6dPDcXrk5pTHJw2bkFFvQy",
-                        "stake": "715205657993906057594050568659",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "aurora.pool.f863973.m0",
-                        "public_key": "ed25519:9c7mczZpNzJz98V1sDeGybfD4gMybP4JKHotH8RrrHTm",
-                        "stake": "703162032315675728652111978820",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "namdokmai.pool.f863973.m0",
-                        "public_key": "ed25519:9uGeeM7j1fimpG7vn6EMcBXMei8ttWCohiMf44SoTzaz",
-                        "stake": "699426128043696790256527911933",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "freshtest.pool.f863973.m0",
-                        "public_key": "ed25519:5cbAt8uzmRztXWXKUYivtLsT2kMC414oHYDapfSJcgwv",
-                        "stake": "697072950038835725218153979145",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "optimusvalidatornetwork.pool.f863973.m0",
-                        "public_key": "ed25519:BGoxGmpvN7HdUSREQXfjH6kw5G6ph7NBXVfBVfUSH85V",
-                        "stake": "661182931526239970852421432715",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "baziliknear.pool.f863973.m0",
-                        "public_key": "ed25519:9Rbzfkhkk6RSa1HoPnJXS4q2nn1DwYeB4HMfJBB4WQpU",
-                        "stake": "651150213650042597898598894903",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "blockscope.pool.f863973.m0",
-                        "public_key": "ed25519:6K6xRp88BCQX5pcyrfkXDU371awMAmdXQY4gsxgjKmZz",
-                        "stake": "649506414222131713576984442889",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "tagard.pool.f863973.m0",
-                        "public_key": "ed25519:3KyziFgx3PpzorJnMFifXU4KsK4nwPFaxCGWTHaFBADK",
-                        "stake": "646786097203475534304943885178",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "leadnode.pool.f863973.m0",
-                        "public_key": "ed25519:CdP6CBFETfWYzrEedmpeqkR6rsJNeT22oUFn2mEDGk5i",
-                        "stake": "644367778886663802105399198378",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "stakesstone.pool.f863973.m0",
-                        "public_key": "ed25519:3aAdsKUuzZbjW9hHnmLWFRKwXjmcxsnLNLfNL4gP1wJ8",
-                        "stake": "641198519157648602505664886163",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "basilisk-stake.pool.f863973.m0",
-                        "public_key": "ed25519:CFo8vxoEUZoxbs87mGtG8qWUvSBHB91Vc6qWsaEXQ5cY",
-                        "stake": "639918590440004706626411243128",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "shardlabs.pool.f863973.m0",
-                        "public_key": "ed25519:DxmhGQZ6oqdxw7qGBvzLuBzE6XQjEh67hk5tt66vhLqL",
-                        "stake": "637803882455578964186296090355",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "al3c5.pool.f863973.m0",
-                        "public_key": "ed25519:BoYixTjyBePQ1VYP3s29rZfjtz1FLQ9og4FWZB5UgWCZ",
-                        "stake": "636854880374440657378246667596",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "dehashed.pool.f863973.m0",
-                        "public_key": "ed25519:EmPyD1DV9ajWJxjNN8GGACMyhM9w14brwNwYA5WvVaw",
-                        "stake": "635224150718459403099965806552",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "machfund.pool.f863973.m0",
-                        "public_key": "ed25519:G6fJ79oM6taQGhHeQZrg8N36nkCPMEVPyQMHfFT2wAKc",
-                        "stake": "634686788251976758263963874506",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "blockngine.pool.f863973.m0",
-                        "public_key": "ed25519:CZrTtCP6XkkxWtr3ATnXE8FL6bcG5cHcxfmdRgN7Lm7m",
-                        "stake": "633656065475669726280826427959",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "grassets.pool.f863973.m0",
-                        "public_key": "ed25519:3S4967Dt1VeeKrwBdTTR5tFEUFSwh17hEFLATRmtUNYV",
-                        "stake": "622722987982775798532829252304",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "bflame.pool.f863973.m0",
-                        "public_key": "ed25519:4uYM5RXgR9D6VAGKHgQTVNLEmCgMVX7PzpBstT92Me6R",
-                        "stake": "617234461115345372278772960093",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "shurik.pool.f863973.m0",
-                        "public_key": "ed25519:9zEn7DVpvQDxWdj5jSgrqJzqsLo8T9Wv37t83NXBiWi6",
-                        "stake": "616327809807619407716759066614",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "dsrvlabs.pool.f863973.m0",
-                        "public_key": "ed25519:61ei2efmmLkeDR1CG6JDEC2U3oZCUuC2K1X16Vmxrud9",
-                        "stake": "613792106557214713239288385761",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "zetsi.pool.f863973.m0",
-                        "public_key": "ed25519:6rYx5w1Z2pw46NBHv6Wo4JEUMNtqnDGqPaHT4wm15YRw",
-                        "stake": "611882168159257611258042281605",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "n0ok.pool.f863973.m0",
-                        "public_key": "ed25519:D6Gq2RpUoDUojmE2vLpqQzuZwYmFPW6rMcXPrwRYhqN8",
-                        "stake": "594349395199079126466241101938",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "chelovek_iz_naroda.pool.f863973.m0",
-                        "public_key": "ed25519:89aWsXXytjAZxyefXuGN73efnM9ugKTjPEGV4hDco8AZ",
-                        "stake": "592739793772796190513231168872",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "lavenderfive.pool.f863973.m0",
-                        "public_key": "ed25519:AzwAiLDqprZKpDjhsH7dfyvFdfSasmPTjuJUAHfX1Pg4",
-                        "stake": "586231008421809079867645695624",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "latenthero.pool.f863973.m0",
-                        "public_key": "ed25519:EQqmjRNouRKhwGL7Hnp3vcbDywg2Boj6to2gmnXybhEM",
-                        "stake": "579738101137715103577294987834",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "tayang.pool.f863973.m0",
-                        "public_key": "ed25519:G9XWX55MfWEpT84ckcsJxVTKeZK4WqBGJX3xVpnPB5vv",
-                        "stake": "563498889920635651950224126233",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "smcvalidator.pool.f863973.m0",
-                        "public_key": "ed25519:pG4LYsyoAa8yWYG9nsTQ5yBcwke51i3VqeRcMVbE9Q7",
-                        "stake": "555422197586970576403131175346",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "everstake.pool.f863973.m0",
-                        "public_key": "ed25519:4LDN8tZUTRRc4siGmYCPA67tRyxStACDchdGDZYKdFsw",
-                        "stake": "546400197607367519956748211889",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "rossi-validator.pool.f863973.m0",
-                        "public_key": "ed25519:2eRx2c3KX9wFd3EzuuajFQoSxRTKDqSbxcF13LfkrxCR",
-                        "stake": "545396693549586230215202952473",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "infiniteloop.pool.f863973.m0",
-                        "public_key": "ed25519:2fbiLqksH5viWXYoteyfKP9qQawkRKw4YogRFcvG3Z7f",
-                        "stake": "538321976932135835213436874121",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "lusienda.pool.f863973.m0",
-                        "public_key": "ed25519:HdQb2HEiaMgvUdemTt5rkrFbxTpzZyELvg1Vov4LQAGU",
-                        "stake": "509015164869674763004419847436",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "ino.pool.f863973.m0",
-                        "public_key": "ed25519:B75h2eqpaMgh6WkAvgnz2FsEC9s5TwVx7zwTjqXKfRs6",
-                        "stake": "494974817444468749939621071716",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "pontiff.pool.f863973.m0",
-                        "public_key": "ed25519:4i8j7nwNyy18hfARtrVpckT8MiicdCXuWBX1TubdMb5Y",
-                        "stake": "478587210879643963063840990682",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "kiln.pool.f863973.m0",
-                        "public_key": "ed25519:Bq8fe1eUgDRexX2CYDMhMMQBiN13j8vTAVFyTNhEfh1W",
-                        "stake": "96608509421037438882028377566",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "nodemeister.pool.f863973.m0",
-                        "public_key": "ed25519:85EMyaNGMFuHK2RDH7KHno6fVYBR6iykUXHPPmFTGuTB",
-                        "stake": "47021543808070096585479049932",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "nala.pool.f863973.m0",
-                        "public_key": "ed25519:Fzwndob2h5PFdEuwo9eRFJV3BLLurcNaw2SGob5rMPEn",
-                        "stake": "44766587364445748049092546945",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "happystake.pool.f863973.m0",
-                        "public_key": "ed25519:3APqZiwzeZLzgfkJyGGTfepDYHA2d8NF1wZi4mCpZnaJ",
-                        "stake": "43959988855512773720415910025",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "ibb.pool.f863973.m0",
-                        "public_key": "ed25519:7gvdHhcMcXT1jMZoxDKy7yXnRiPVX1tAFTa7HWTHbe8C",
-                        "stake": "42001690004861681144621857517",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "mateennala.pool.f863973.m0",
-                        "public_key": "ed25519:9kNpQKUKzhc1AiFSEoZcTNapTnywjbXBPngH3EDpD1tw",
-                        "stake": "40056014128143748170300000000",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "wolfedge-capital-testnet.pool.f863973.m0",
-                        "public_key": "ed25519:CQEMcPQz6sqhAgoBm9ka9UeVcXj5NpNpRtDYYGkPggvg",
-                        "stake": "37464905110868615156797728096",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "jstaking.pool.f863973.m0",
-                        "public_key": "ed25519:fui1E5XwnAWGYDBSQ3168aDfsW1KDFH8A7nBHvZiqGv",
-                        "stake": "36368375383183646876651257216",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "dariya.pool.f863973.m0",
-                        "public_key": "ed25519:A5Rx38TsNKWXzF5o18HpaRrPeBzv3riqur51bqhU1Qbp",
-                        "stake": "36211347514033914937590010268",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "4ire-pool.pool.f863973.m0",
-                        "public_key": "ed25519:EWPSvYN9pGPMmCLjVxx96stWdqksXNSGnfnuWYn9iiE5",
-                        "stake": "33869896086305183386478534323",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "lionstake.pool.f863973.m0",
-                        "public_key": "ed25519:Fy6quR4nBhrEnDyEuPWoAdBP5tzNbuEZsEd91Q5pQnXB",
-                        "stake": "33765876364623459491244697143",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "zentriav2.factory.colorpalette.testnet",
-                        "public_key": "ed25519:4rCwSFzJ2e6suD5Yi7pgLidcAJ8Zt9BXieLzVedJDwmE",
-                        "stake": "30596434283244809799848018489",
-                        "validator_stake_struct_version": "V1"
-                    },
-                    {
-                        "account_id": "lastnode.pool.f863973.m0",
-                        "public_key": "ed25519:811gesxXYdYeThry96ZiWn8chgWYNyreiScMkmxg4U9u",
-                        "stake": "24146328727357015429360981746",
-                        "validator_stake_struct_version": "V1"
-                    }
-                ],
-                "prev_block_hash": "4E2VN7cUVSb8ek761H4cRo57ERTWBKbcB9uEBDS2cWhD"
-            },
-            "id": "idontcare"
-        }
-        "#;
+		const CLIENT_BLOCK_VIEW: &str =
+			include_str!("../../fixtures/testnet/next_light_client_block/86456070.json");
 		let client_block_near_view_next_epoch =
 			get_client_near_block_view(CLIENT_BLOCK_VIEW).unwrap();
 
@@ -961,10 +1022,67 @@ mod tests {
 			bs58::decode("ydgzeXHJ5Xyt7M1gXLxqLBW1Ejx6scNV5Nx2pxFM8su").into_vec().unwrap();
 		assert_eq!(
 			NearPublicKey::try_from_slice(
-				PublicKey::from_raw(&public_key_encoded).try_to_vec().unwrap().as_ref()
+				PublicKey::try_from(public_key_encoded.as_slice())
+					.unwrap()
+					.try_to_vec()
+					.unwrap()
+					.as_ref()
 			)
 			.unwrap(),
 			NearPublicKey::from_str("ydgzeXHJ5Xyt7M1gXLxqLBW1Ejx6scNV5Nx2pxFM8su").unwrap()
 		);
 	}
+
+	#[test]
+	fn test_newtypes_are_encoded_as_their_inner_value() {
+		let hash = CryptoHash([7; 32]);
+		assert_eq!(BlockHeight(42).try_to_vec().unwrap(), 42u64.try_to_vec().unwrap());
+		assert_eq!(Nonce(42).try_to_vec().unwrap(), 42u64.try_to_vec().unwrap());
+		assert_eq!(EpochId(hash).try_to_vec().unwrap(), hash.try_to_vec().unwrap());
+		assert_eq!(EpochId::from_str(&hash.to_string()).unwrap(), EpochId(hash));
+		assert_eq!(BlockHeight(10).blocks_since(BlockHeight(12)), 0);
+		assert_eq!(BlockHeight(u64::MAX).checked_add(1), None);
+	}
+
+	#[test]
+	fn test_validator_stake_view_versions() {
+		let v1 = ValidatorStakeViewV1 {
+			account_id: "node0.near".into(),
+			public_key: PublicKey([1; 32]),
+			stake: 42,
+		};
+		let v2 = ValidatorStakeView::V2(ValidatorStakeViewV2 {
+			account_id: v1.account_id.clone(),
+			public_key: v1.public_key.clone(),
+			stake: v1.stake,
+			is_chunk_only: true,
+		});
+		// the version tag, then the fields
+		let encoded = v2.try_to_vec().unwrap();
+		assert_eq!(encoded[0], 1);
+		assert_eq!(encoded[1..encoded.len() - 1], v1.try_to_vec().unwrap());
+		assert_eq!(encoded.last(), Some(&1));
+		assert_eq!(
+			ValidatorStakeView::try_from_slice(&encoded).unwrap().try_to_vec().unwrap(),
+			encoded
+		);
+		assert_eq!(ValidatorStakeView::V1(v1.clone()).try_to_vec().unwrap()[0], 0);
+
+		assert_eq!(v2.account_id(), "node0.near");
+		assert_eq!(v2.public_key().0, [1; 32]);
+		assert_eq!(v2.stake(), 42);
+		assert!(v2.is_chunk_only());
+		assert!(!ValidatorStakeView::V1(v1.clone()).is_chunk_only());
+		assert_eq!(v2.into_validator_stake().try_to_vec().unwrap(), v1.try_to_vec().unwrap());
+	}
+
+	#[test]
+	fn test_inner_lite_is_hashed_without_timestamp_nanosec() {
+		let mut inner_lite = BlockHeaderInnerLiteView::new_for_test();
+		inner_lite.timestamp_nanosec = 7;
+		assert_eq!(
+			InnerLiteFinal(&inner_lite).try_to_vec().unwrap(),
+			BlockHeaderInnerLiteViewFinal::from(inner_lite).try_to_vec().unwrap()
+		);
+	}
 }
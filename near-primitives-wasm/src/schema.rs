@@ -0,0 +1,115 @@
+//! # Borsh schema
+//!
+//! Borsh is not self-descriptive, so external implementations (JS bridge services, auditors,
+//! other light clients) can use the schemas exported here to check that their byte layouts match
+//! the ones produced and consumed by this crate.
+
+use borsh::{
+	maybestd::collections::HashMap,
+	schema::{BorshSchemaContainer, Declaration, Definition, Fields},
+	BorshSchema, BorshSerialize,
+};
+use sp_std::{vec, vec::Vec};
+
+use crate::{
+	ApprovalInner, BlockHeaderInnerLiteView, BlockHeaderInnerLiteViewFinal, CryptoHash, Direction,
+	ExecutionOutcomeView, LightClientBlockView, MerklePathItem, OutcomeProof, PublicKey, Signature,
+	ValidatorStakeView,
+};
+
+/// Keys and signatures are prefixed by a one byte key type, as in `near-crypto`.
+fn add_key_type_prefixed_definition<T: BorshSchema, Data: BorshSchema>(
+	definitions: &mut HashMap<Declaration, Definition>,
+) {
+	let fields = Fields::NamedFields(vec![
+		("key_type".into(), u8::declaration()),
+		("data".into(), Data::declaration()),
+	]);
+	T::add_definition(T::declaration(), Definition::Struct { fields }, definitions);
+	u8::add_definitions_recursively(definitions);
+	Data::add_definitions_recursively(definitions);
+}
+
+impl BorshSchema for PublicKey {
+	fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+		add_key_type_prefixed_definition::<Self, [u8; PublicKey::LEN]>(definitions);
+	}
+
+	fn declaration() -> Declaration {
+		"PublicKey".into()
+	}
+}
+
+impl BorshSchema for Signature {
+	fn add_definitions_recursively(definitions: &mut HashMap<Declaration, Definition>) {
+		add_key_type_prefixed_definition::<Self, [u8; Signature::LEN]>(definitions);
+	}
+
+	fn declaration() -> Declaration {
+		"Signature".into()
+	}
+}
+
+/// Schema containers of every type that crosses the wire.
+pub fn schema_containers() -> Vec<BorshSchemaContainer> {
+	vec![
+		LightClientBlockView::schema_container(),
+		BlockHeaderInnerLiteView::schema_container(),
+		BlockHeaderInnerLiteViewFinal::schema_container(),
+		ApprovalInner::schema_container(),
+		ValidatorStakeView::schema_container(),
+		OutcomeProof::schema_container(),
+		ExecutionOutcomeView::schema_container(),
+		MerklePathItem::schema_container(),
+		Direction::schema_container(),
+		CryptoHash::schema_container(),
+		PublicKey::schema_container(),
+		Signature::schema_container(),
+	]
+}
+
+/// Borsh encoding of [`schema_containers`], which is itself described by the schema of
+/// `Vec<BorshSchemaContainer>`.
+pub fn export_schema_containers() -> Result<Vec<u8>, borsh::maybestd::io::Error> {
+	schema_containers().try_to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use borsh::BorshDeserialize;
+
+	#[test]
+	fn test_light_client_block_view_schema() {
+		let container = LightClientBlockView::schema_container();
+		assert_eq!(container.declaration, "LightClientBlockView");
+		for declaration in [
+			"CryptoHash",
+			"BlockHeaderInnerLiteView",
+			"Option<Vec<ValidatorStakeView>>",
+			"Vec<Option<Signature>>",
+			"ValidatorStakeViewV1",
+			"ValidatorStakeViewV2",
+			"PublicKey",
+			"Signature",
+		] {
+			assert!(container.definitions.contains_key(declaration), "{} is missing", declaration);
+		}
+		assert_eq!(
+			container.definitions["Signature"],
+			Definition::Struct {
+				fields: Fields::NamedFields(vec![
+					("key_type".into(), "u8".into()),
+					("data".into(), "Array<u8, 64>".into()),
+				])
+			}
+		);
+	}
+
+	#[test]
+	fn test_exported_schema_roundtrip() {
+		let exported = export_schema_containers().unwrap();
+		let decoded = Vec::<BorshSchemaContainer>::try_from_slice(&exported).unwrap();
+		assert_eq!(decoded, schema_containers());
+	}
+}
@@ -0,0 +1,340 @@
+//! # Near lite client pallet
+//!
+//! Runs the light client on chain: relayers submit NEAR headers to move its head forward and
+//! outcome proofs to have them verified against it, while governance sets the checkpoint it
+//! starts from. The state of the client is kept in the storage of the pallet, see
+//! [`storage::RuntimeStorage`].
+//!
+//! Headers and proofs are passed borsh encoded, as the NEAR RPC returns them once converted, and
+//! decoded within the [`Config::MaxBlockProducers`] and [`Config::MaxProofDepth`] bounds that the
+//! weights of the calls are computed with.
+//!
+//! The calls are memoized by the hash of their arguments, see [`SubmissionMemo`]: a header or a
+//! proof that was already accepted is accepted again without being verified nor emitting events,
+//! as relayers retry their submissions and several of them may submit the same ones.
+//!
+//! [`SubmissionMemo`]: near_lite_client::SubmissionMemo
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+pub mod storage;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use borsh::{BorshDeserialize, BorshSerialize};
+	use codec::Encode;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{EnsureOrigin, UnixTime},
+		transactional,
+	};
+	use frame_system::pallet_prelude::*;
+	use near_lite_client::{
+		submission_id, weights::WeightInfo, DecodeLimits, LightClient, LightClientBuilder,
+		LiteClientResult, NearLiteClientError, OutcomeId, RetentionPolicy, SubmissionMemo,
+		SubmissionOutcome, TimestampPolicy, TrustedCheckpoint, TypedOutcomeProof,
+		VerificationFailure,
+	};
+	use near_primitives_wasm::{BlockHeight, CryptoHash, EpochId, HostFunctions};
+	use sp_std::vec::Vec;
+
+	use crate::storage::{self, RuntimeClock, RuntimeStorage};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to reset the client to a new checkpoint.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+		type HostFunctions: HostFunctions;
+		/// Headers timestamped ahead of it are rejected, and it tells how old the head is.
+		type UnixTime: UnixTime;
+		type WeightInfo: WeightInfo;
+		/// Upper bound of the block producers of an epoch, and so of the approvals of a header.
+		#[pallet::constant]
+		type MaxBlockProducers: Get<u32>;
+		/// Upper bound of the items of each merkle path of an outcome proof.
+		#[pallet::constant]
+		type MaxProofDepth: Get<u32>;
+		/// How many epochs of block producers are kept, at least 2.
+		#[pallet::constant]
+		type RetainedEpochs: Get<u32>;
+		/// How far ahead of [`Config::UnixTime`], in nanoseconds, a header may be timestamped.
+		#[pallet::constant]
+		type MaxClockDrift: Get<u64>;
+		/// How old the head may get, in nanoseconds, for outcome proofs to be verified against
+		/// it; proofs are always verified if `None`.
+		#[pallet::constant]
+		type MaxHeadAge: Get<Option<u64>>;
+		/// How many heights below the head the hashes of the accepted heads are kept for, along
+		/// with the block producers of the epochs that may have blocks in that window; the hashes
+		/// of every head are kept if `None`.
+		#[pallet::constant]
+		type RetainedHeights: Get<Option<u64>>;
+		/// How many of the latest submissions, headers and proofs alike, are memoized.
+		#[pallet::constant]
+		type MaxMemoizedSubmissions: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The head of the client, borsh encoded.
+	#[pallet::storage]
+	pub type Head<T> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Ids of the epochs in [`EpochBlockProducers`], from the oldest to the newest.
+	#[pallet::storage]
+	pub type Epochs<T> = StorageValue<_, Vec<EpochId>, ValueQuery>;
+
+	/// Height of the head when each epoch of [`Epochs`] was inserted, which the retention of
+	/// [`Config::RetainedHeights`] goes by.
+	#[pallet::storage]
+	pub type EpochHeights<T> = StorageValue<_, Vec<(EpochId, BlockHeight)>, ValueQuery>;
+
+	/// Block producers of the retained epochs, borsh encoded.
+	#[pallet::storage]
+	pub type EpochBlockProducers<T> =
+		StorageMap<_, Blake2_128Concat, EpochId, Vec<u8>, OptionQuery>;
+
+	/// Hashes of the heads accepted by the client, by height.
+	#[pallet::storage]
+	pub type BlockHashes<T> = StorageMap<_, Twox64Concat, BlockHeight, CryptoHash, OptionQuery>;
+
+	/// Lowest and highest heights in [`BlockHashes`].
+	#[pallet::storage]
+	pub type BlockHashBounds<T> = StorageValue<_, (BlockHeight, BlockHeight), OptionQuery>;
+
+	/// Next height in [`BlockHashes`] after each of them but the highest, so that pruning goes
+	/// up from the lowest one instead of going through every hash.
+	#[pallet::storage]
+	pub type NextBlockHashHeight<T> =
+		StorageMap<_, Twox64Concat, BlockHeight, BlockHeight, OptionQuery>;
+
+	/// Outcomes of the latest submissions, a borsh encoded [`SubmissionMemo`].
+	#[pallet::storage]
+	pub type Submissions<T> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A header became the head of the client.
+		HeadUpdated { height: BlockHeight, block_hash: CryptoHash, epoch_changed: bool },
+		/// The outcome `id` was proven to lead to the block outcome root.
+		TransactionVerified { id: CryptoHash, block_outcome_root: CryptoHash },
+		/// The client was reset to a checkpoint.
+		CheckpointSet { height: BlockHeight, block_hash: CryptoHash },
+		/// The history below `below_height` was dropped from the storage.
+		Pruned { below_height: BlockHeight },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No checkpoint was set yet.
+		NotInitialized,
+		/// An argument could not be decoded, or is over the bounds of the config.
+		Malformed,
+		/// The header is not ahead of the head.
+		StaleHeader,
+		/// The header or the outcome proof failed verification.
+		VerificationFailed,
+		/// The head is older than [`Config::MaxHeadAge`], headers must be submitted first.
+		HeadTooOld,
+		/// The state of the client or its configuration is invalid.
+		InvalidState,
+	}
+
+	impl<T> From<NearLiteClientError> for Error<T> {
+		// the string errors of `near-merkle-proofs` still convert into `ProofVerificationError`
+		#[allow(deprecated)]
+		fn from(err: NearLiteClientError) -> Self {
+			match err {
+				NearLiteClientError::Borsh(_) |
+				NearLiteClientError::Conversion(_) |
+				NearLiteClientError::Serialization { .. } |
+				NearLiteClientError::CapacityExceeded(_) => Self::Malformed,
+				NearLiteClientError::StaleHeader { .. } => Self::StaleHeader,
+				NearLiteClientError::Verification(VerificationFailure::HeadTooOld { .. }) =>
+					Self::HeadTooOld,
+				NearLiteClientError::Verification(_) |
+				NearLiteClientError::ProofVerificationError(_) |
+				NearLiteClientError::InvalidLiteBlock(_) |
+				NearLiteClientError::SignatureVerification(_) |
+				NearLiteClientError::MerkleRootMismatch { .. } => Self::VerificationFailed,
+				_ => Self::InvalidState,
+			}
+		}
+	}
+
+	// Resuming the client writes back what it read, and may prune it, before a header or a proof
+	// is verified: every call is transactional so that none of it is kept when they fail.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Validates a borsh encoded `LightClientBlockView` and makes it the head. Submitting an
+		/// accepted header again succeeds without an event.
+		#[pallet::weight(
+			T::WeightInfo::validate_head(T::MaxBlockProducers::get())
+				.saturating_add(Pallet::<T>::storage_weight())
+		)]
+		#[transactional]
+		pub fn submit_header(origin: OriginFor<T>, block_view: Vec<u8>) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(Head::<T>::exists(), Error::<T>::NotInitialized);
+			let accepted = Self::memoized(submission_id::<T::HostFunctions>(&block_view), || {
+				let block_view = Self::decode_limits().decode_block_view(&block_view)?;
+				let mut client = Self::builder().build()?;
+				let summary = client.validate_and_update_head(block_view)?;
+				Ok((summary, client.head_hash()))
+			})?;
+			let (summary, block_hash) = match accepted {
+				Some(accepted) => accepted,
+				None => return Ok(()),
+			};
+			if !summary.already_validated {
+				Self::deposit_event(Event::HeadUpdated {
+					height: summary.new_height,
+					block_hash,
+					epoch_changed: summary.epoch_changed,
+				});
+			}
+			if let Some(below_height) = summary.pruned_below {
+				Self::deposit_event(Event::Pruned { below_height });
+			}
+			Ok(())
+		}
+
+		/// Verifies a borsh encoded `OutcomeProof` of `outcome_id` and its borsh encoded outcome
+		/// root proof against `expected_block_outcome_root`: the outcome must be the one of the
+		/// transaction or receipt `outcome_id`, executed by the account it names.
+		#[pallet::weight(
+			T::WeightInfo::verify_outcome(T::MaxProofDepth::get())
+				.saturating_add(Pallet::<T>::storage_weight())
+		)]
+		#[transactional]
+		pub fn submit_transaction_proof(
+			origin: OriginFor<T>,
+			outcome_id: OutcomeId,
+			outcome_proof: Vec<u8>,
+			outcome_root_proof: Vec<u8>,
+			expected_block_outcome_root: CryptoHash,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(Head::<T>::exists(), Error::<T>::NotInitialized);
+			let payload =
+				(&outcome_id, &outcome_proof, &outcome_root_proof, expected_block_outcome_root)
+					.encode();
+			let verified = Self::memoized(submission_id::<T::HostFunctions>(&payload), || {
+				let limits = Self::decode_limits();
+				let outcome_proof = limits.decode_outcome_proof(&outcome_proof)?;
+				let outcome_root_proof = limits.decode_merkle_path(&outcome_root_proof)?;
+				let outcome = TypedOutcomeProof::new(outcome_id, outcome_proof)?;
+				Self::builder().build()?.validate_outcome(
+					&outcome,
+					&outcome_root_proof,
+					expected_block_outcome_root,
+				)?;
+				Ok(outcome.outcome_proof().id)
+			})?;
+			if let Some(id) = verified {
+				Self::deposit_event(Event::TransactionVerified {
+					id,
+					block_outcome_root: expected_block_outcome_root,
+				});
+			}
+			Ok(())
+		}
+
+		/// Drops the state of the client and restarts it from a borsh encoded
+		/// `LightClientBlockView`, trusted as is.
+		#[pallet::weight(Pallet::<T>::storage_weight())]
+		#[transactional]
+		pub fn force_set_checkpoint(origin: OriginFor<T>, checkpoint: Vec<u8>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let checkpoint = Self::decode_limits()
+				.decode_block_view(&checkpoint)
+				.map_err(Error::<T>::from)?;
+			storage::clear::<T>();
+			let client = Self::builder()
+				.checkpoint(TrustedCheckpoint(checkpoint))
+				.build()
+				.map_err(Error::<T>::from)?;
+			Self::deposit_event(Event::CheckpointSet {
+				height: client.head_height(),
+				block_hash: client.head_hash(),
+			});
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The client, resumed from the storage of the pallet.
+		pub fn client() -> Result<LightClient<T::HostFunctions>, Error<T>> {
+			ensure!(Head::<T>::exists(), Error::<T>::NotInitialized);
+			Self::builder().build().map_err(Error::<T>::from)
+		}
+
+		fn builder() -> LightClientBuilder<T::HostFunctions> {
+			let mut builder = LightClientBuilder::new()
+				.storage(RuntimeStorage::<T>::default())
+				.retained_epochs(T::RetainedEpochs::get() as usize)
+				.clock(RuntimeClock::<T>::default())
+				.timestamp_policy(TimestampPolicy {
+					max_drift: T::MaxClockDrift::get(),
+					monotonic: false,
+				});
+			if let Some(heights) = T::RetainedHeights::get() {
+				builder = builder.retention(RetentionPolicy::Heights(heights));
+			}
+			match T::MaxHeadAge::get() {
+				Some(max_age) => builder.max_head_age(max_age),
+				None => builder,
+			}
+		}
+
+		/// Runs `process` unless the submission `submission_id` was accepted before, in which case
+		/// it succeeds again with `None`. Only acceptances are memoized, as the storage of a failed
+		/// call is rolled back.
+		fn memoized<R>(
+			submission_id: CryptoHash,
+			process: impl FnOnce() -> LiteClientResult<R>,
+		) -> Result<Option<R>, Error<T>> {
+			let capacity = T::MaxMemoizedSubmissions::get();
+			let mut memo = Submissions::<T>::get()
+				.and_then(|encoded| SubmissionMemo::try_from_slice(&encoded).ok())
+				// a memo of another capacity is from before an upgrade, start over
+				.filter(|memo| memo.capacity() == capacity)
+				.unwrap_or_else(|| SubmissionMemo::new(capacity));
+			if memo.get(&submission_id) == Some(&SubmissionOutcome::Accepted) {
+				return Ok(None)
+			}
+			let accepted = process().map_err(Error::<T>::from)?;
+			memo.record(submission_id, SubmissionOutcome::Accepted);
+			Submissions::<T>::put(memo.try_to_vec().map_err(|_| Error::<T>::InvalidState)?);
+			Ok(Some(accepted))
+		}
+
+		fn decode_limits() -> DecodeLimits {
+			DecodeLimits {
+				max_approvals: T::MaxBlockProducers::get() as usize,
+				max_block_producers: T::MaxBlockProducers::get() as usize,
+				max_merkle_path_len: T::MaxProofDepth::get() as usize,
+			}
+		}
+
+		/// Resuming the client reads, and writes back, the head, every retained epoch plus one kept
+		/// for the retained heights, the heights they were inserted at and the memo of the
+		/// submissions; the calls write one more epoch at most. Indexing the hash of the head
+		/// touches the bounds of the hashes and one link, and pruning one hash for every hash
+		/// indexed, on average, the same.
+		fn storage_weight() -> Weight {
+			let items = T::RetainedEpochs::get() as u64 + 9;
+			T::DbWeight::get().reads_writes(items, items + 1)
+		}
+	}
+}
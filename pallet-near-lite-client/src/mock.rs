@@ -0,0 +1,94 @@
+use core::time::Duration;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Everything, UnixTime},
+};
+use frame_system::EnsureRoot;
+use near_lite_client::Sha256HostFunctions;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+use crate as pallet_near_lite_client;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		NearLiteClient: pallet_near_lite_client::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// Well after the timestamps of the headers built in the tests.
+pub const NOW: u64 = 2_000_000_000_000_000_000;
+
+pub struct MockTime;
+
+impl UnixTime for MockTime {
+	fn now() -> Duration {
+		Duration::from_nanos(NOW)
+	}
+}
+
+parameter_types! {
+	pub const MaxHeadAge: Option<u64> = None;
+	pub const RetainedHeights: Option<u64> = Some(100);
+}
+
+impl pallet_near_lite_client::Config for Test {
+	type Event = Event;
+	type ForceOrigin = EnsureRoot<u64>;
+	type HostFunctions = Sha256HostFunctions;
+	type UnixTime = MockTime;
+	type WeightInfo = ();
+	type MaxBlockProducers = ConstU32<100>;
+	type MaxProofDepth = ConstU32<32>;
+	type RetainedEpochs = ConstU32<2>;
+	type MaxClockDrift = ConstU64<0>;
+	type MaxHeadAge = MaxHeadAge;
+	type RetainedHeights = RetainedHeights;
+	type MaxMemoizedSubmissions = ConstU32<16>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	// events are only deposited from the first block on
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,214 @@
+//! # Runtime storage
+//!
+//! The state of the light client lives in the storage items of the pallet: the head and the block
+//! producers of every retained epoch are kept borsh encoded, as they hash, and the epoch ids in
+//! the order they were inserted, which decides which epoch is dropped first, with the height of
+//! the head at the time. The hashes of the accepted heads are indexed by height, each height linked
+//! to the next one so that they are pruned from the lowest.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use codec::Encode;
+use core::marker::PhantomData;
+use frame_support::traits::UnixTime;
+use near_lite_client::{
+	Clock, LightClientStorage, LiteClientResult, NearLiteClientError, StorageStats,
+	ValidatorStakeView,
+};
+use near_primitives_wasm::{BlockHeight, CryptoHash, EpochId, LightClientBlockView};
+use sp_std::vec::Vec;
+
+use crate::{
+	BlockHashBounds, BlockHashes, Config, EpochBlockProducers, EpochHeights, Epochs, Head,
+	NextBlockHashHeight, Submissions,
+};
+
+pub struct RuntimeStorage<T>(PhantomData<T>);
+
+impl<T> Default for RuntimeStorage<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config> LightClientStorage for RuntimeStorage<T> {
+	fn head(&self) -> LiteClientResult<Option<LightClientBlockView>> {
+		Head::<T>::get().map(|encoded| decode(&encoded)).transpose()
+	}
+
+	fn set_head(&mut self, head: &LightClientBlockView) -> LiteClientResult<()> {
+		Head::<T>::put(encode(head)?);
+		Ok(())
+	}
+
+	fn epoch_block_producers(&self) -> LiteClientResult<Vec<(EpochId, Vec<ValidatorStakeView>)>> {
+		Epochs::<T>::get()
+			.into_iter()
+			.map(|epoch_id| {
+				let encoded = EpochBlockProducers::<T>::get(epoch_id).ok_or_else(|| {
+					NearLiteClientError::Serialization {
+						type_name: core::any::type_name::<Vec<ValidatorStakeView>>(),
+						reason: "missing from storage".into(),
+					}
+				})?;
+				Ok((epoch_id, decode(&encoded)?))
+			})
+			.collect()
+	}
+
+	fn insert_epoch_block_producers(
+		&mut self,
+		epoch_id: EpochId,
+		block_producers: &[ValidatorStakeView],
+	) -> LiteClientResult<()> {
+		EpochBlockProducers::<T>::insert(epoch_id, encode(block_producers)?);
+		Epochs::<T>::mutate(|epochs| {
+			epochs.retain(|known| *known != epoch_id);
+			epochs.push(epoch_id);
+		});
+		Ok(())
+	}
+
+	fn remove_epoch_block_producers(&mut self, epoch_id: &EpochId) -> LiteClientResult<()> {
+		EpochBlockProducers::<T>::remove(epoch_id);
+		Epochs::<T>::mutate(|epochs| epochs.retain(|known| known != epoch_id));
+		EpochHeights::<T>::mutate(|heights| heights.retain(|(known, _)| known != epoch_id));
+		Ok(())
+	}
+
+	fn epoch_heights(&self) -> LiteClientResult<Vec<(EpochId, BlockHeight)>> {
+		Ok(EpochHeights::<T>::get())
+	}
+
+	fn set_epoch_height(&mut self, epoch_id: EpochId, height: BlockHeight) -> LiteClientResult<()> {
+		EpochHeights::<T>::mutate(|heights| {
+			heights.retain(|(known, _)| *known != epoch_id);
+			heights.push((epoch_id, height));
+		});
+		Ok(())
+	}
+
+	fn block_hash(&self, height: BlockHeight) -> LiteClientResult<Option<CryptoHash>> {
+		Ok(BlockHashes::<T>::get(height))
+	}
+
+	fn insert_block_hash(&mut self, height: BlockHeight, hash: CryptoHash) -> LiteClientResult<()> {
+		if !BlockHashes::<T>::contains_key(height) {
+			link_block_hash_height::<T>(height);
+		}
+		BlockHashes::<T>::insert(height, hash);
+		Ok(())
+	}
+
+	/// Follows the heights up from the lowest one, so that only the pruned hashes are touched.
+	fn prune_block_hashes(&mut self, height: BlockHeight) -> LiteClientResult<usize> {
+		let (mut lowest, highest) = match BlockHashBounds::<T>::get() {
+			Some(bounds) => bounds,
+			None => return Ok(0),
+		};
+		let mut pruned = 0;
+		while lowest < height {
+			BlockHashes::<T>::remove(lowest);
+			pruned += 1;
+			match NextBlockHashHeight::<T>::take(lowest) {
+				Some(next) => lowest = next,
+				None => {
+					BlockHashBounds::<T>::kill();
+					return Ok(pruned)
+				},
+			}
+		}
+		if pruned > 0 {
+			BlockHashBounds::<T>::put((lowest, highest));
+		}
+		Ok(pruned)
+	}
+
+	/// Goes through every storage item, for RPCs and off-chain workers rather than extrinsics.
+	fn stats(&self) -> LiteClientResult<StorageStats> {
+		let mut stats = StorageStats::default();
+		if let Some(head) = Head::<T>::get() {
+			stats.head.add(head.len());
+		}
+		if Epochs::<T>::exists() {
+			stats.other.add(Epochs::<T>::get().encoded_size());
+		}
+		if EpochHeights::<T>::exists() {
+			stats.other.add(EpochHeights::<T>::get().encoded_size());
+		}
+		for block_producers in EpochBlockProducers::<T>::iter_values() {
+			stats.block_producers.add(block_producers.len());
+		}
+		for (height, hash) in BlockHashes::<T>::iter() {
+			stats.block_hashes.add(hash.encoded_size());
+			stats.oldest_height =
+				Some(stats.oldest_height.map_or(height, |oldest| oldest.min(height)));
+			stats.newest_height =
+				Some(stats.newest_height.map_or(height, |newest| newest.max(height)));
+		}
+		Ok(stats)
+	}
+}
+
+/// Empties the storage items, to start over from a new checkpoint. The memoized submissions go
+/// too, as they were accepted by the previous client.
+pub(crate) fn clear<T: Config>() {
+	for epoch_id in Epochs::<T>::take() {
+		EpochBlockProducers::<T>::remove(epoch_id);
+	}
+	EpochHeights::<T>::kill();
+	Head::<T>::kill();
+	let _ = BlockHashes::<T>::remove_all(None);
+	BlockHashBounds::<T>::kill();
+	let _ = NextBlockHashHeight::<T>::remove_all(None);
+	Submissions::<T>::kill();
+}
+
+/// Links `height`, which has no hash indexed yet, to the heights around it.
+fn link_block_hash_height<T: Config>(height: BlockHeight) {
+	let (lowest, highest) = match BlockHashBounds::<T>::get() {
+		Some(bounds) => bounds,
+		None => return BlockHashBounds::<T>::put((height, height)),
+	};
+	if height > highest {
+		NextBlockHashHeight::<T>::insert(highest, height);
+		BlockHashBounds::<T>::put((lowest, height));
+	} else if height < lowest {
+		NextBlockHashHeight::<T>::insert(height, lowest);
+		BlockHashBounds::<T>::put((height, highest));
+	} else {
+		// the client indexes its heads in increasing order, heights in between only come from
+		// other writers
+		let mut previous = lowest;
+		while let Some(next) = NextBlockHashHeight::<T>::get(previous) {
+			if next > height {
+				NextBlockHashHeight::<T>::insert(height, next);
+				break
+			}
+			previous = next;
+		}
+		NextBlockHashHeight::<T>::insert(previous, height);
+	}
+}
+
+/// The time of the current block, from the runtime's `UnixTime`.
+pub struct RuntimeClock<T>(PhantomData<T>);
+
+impl<T> Default for RuntimeClock<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config> Clock for RuntimeClock<T> {
+	fn now(&self) -> u64 {
+		u64::try_from(T::UnixTime::now().as_nanos()).unwrap_or(u64::MAX)
+	}
+}
+
+fn encode<V: BorshSerialize + ?Sized>(value: &V) -> LiteClientResult<Vec<u8>> {
+	value.try_to_vec().map_err(NearLiteClientError::Borsh)
+}
+
+fn decode<V: BorshDeserialize>(encoded: &[u8]) -> LiteClientResult<V> {
+	V::try_from_slice(encoded).map_err(NearLiteClientError::Borsh)
+}
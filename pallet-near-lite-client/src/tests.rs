@@ -0,0 +1,287 @@
+use borsh::BorshSerialize;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+use near_lite_client::{LightClientStorage, OutcomeId, Sha256HostFunctions};
+use near_lite_client_test_utils::{MockNearChain, OutcomeProofBuilder, SignedHeader, ValidatorSet};
+use near_primitives_wasm::{
+	BlockHeight, CryptoHash, Direction, EpochId, ExecutionOutcomeView, MerklePathItem, OutcomeProof,
+};
+
+use crate::{
+	mock::*, storage::RuntimeStorage, BlockHashBounds, BlockHashes, EpochBlockProducers,
+	EpochHeights, Epochs, Error, Event as PalletEvent, NextBlockHashHeight,
+};
+
+fn signed_header() -> SignedHeader {
+	signed_header_at(BlockHeight(10))
+}
+
+fn signed_header_at(height: BlockHeight) -> SignedHeader {
+	let validators = ValidatorSet::new(4);
+	let epochs = [1, 2, 3].map(|epoch| EpochId(CryptoHash([epoch; 32])));
+	SignedHeader::new::<Sha256HostFunctions>(height, epochs, &validators, &validators).unwrap()
+}
+
+fn encode<V: BorshSerialize>(value: &V) -> Vec<u8> {
+	value.try_to_vec().unwrap()
+}
+
+/// `OutcomeProof` is only decoded by the client, its fields are encoded in order here.
+fn encode_outcome_proof(outcome_proof: &OutcomeProof) -> Vec<u8> {
+	let outcome = &outcome_proof.outcome;
+	encode(&(
+		&outcome_proof.proof,
+		outcome_proof.block_hash,
+		outcome_proof.id,
+		&outcome.logs,
+		&outcome.receipt_ids,
+		outcome.gas_burnt,
+		outcome.tokens_burnt,
+		&outcome.executor_id,
+		&outcome.status,
+	))
+}
+
+#[test]
+fn test_calls_need_a_checkpoint_set_by_governance() {
+	new_test_ext().execute_with(|| {
+		let chain = signed_header();
+		assert_noop!(
+			NearLiteClient::submit_header(Origin::signed(1), encode(&chain.block_view)),
+			Error::<Test>::NotInitialized
+		);
+		assert_noop!(
+			NearLiteClient::force_set_checkpoint(Origin::signed(1), encode(&chain.head)),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(NearLiteClient::force_set_checkpoint(Origin::root(), encode(&chain.head)));
+		System::assert_last_event(
+			PalletEvent::CheckpointSet {
+				height: BlockHeight(10),
+				block_hash: chain.head.current_block_hash::<Sha256HostFunctions>(),
+			}
+			.into(),
+		);
+		assert_eq!(NearLiteClient::client().unwrap().head_height(), BlockHeight(10));
+	});
+}
+
+#[test]
+fn test_submit_header() {
+	new_test_ext().execute_with(|| {
+		let chain = signed_header();
+		assert_ok!(NearLiteClient::force_set_checkpoint(Origin::root(), encode(&chain.head)));
+
+		assert_ok!(NearLiteClient::submit_header(Origin::signed(1), encode(&chain.block_view)));
+		System::assert_last_event(
+			PalletEvent::HeadUpdated {
+				height: BlockHeight(11),
+				block_hash: chain.block_view.current_block_hash::<Sha256HostFunctions>(),
+				epoch_changed: true,
+			}
+			.into(),
+		);
+		let client = NearLiteClient::client().unwrap();
+		assert_eq!(client.head_height(), BlockHeight(11));
+		assert!(client.epoch_block_producers(&EpochId(CryptoHash([3; 32]))).is_some());
+		assert_eq!(
+			client.block_hash_at(BlockHeight(10)).unwrap(),
+			Some(chain.head.current_block_hash::<Sha256HostFunctions>())
+		);
+		let stats = client.storage_stats().unwrap().unwrap();
+		assert_eq!(stats.head.entries, 1);
+		assert_eq!(stats.block_hashes.entries, 2);
+		assert_eq!(stats.oldest_height, Some(BlockHeight(10)));
+		assert_eq!(stats.newest_height, Some(BlockHeight(11)));
+
+		// another relayer submitting the same header
+		let events = System::events().len();
+		assert_ok!(NearLiteClient::submit_header(Origin::signed(2), encode(&chain.block_view)));
+		assert_eq!(System::events().len(), events);
+
+		assert_noop!(
+			NearLiteClient::submit_header(Origin::signed(1), encode(&chain.head)),
+			Error::<Test>::StaleHeader
+		);
+		assert_noop!(
+			NearLiteClient::submit_header(Origin::signed(1), vec![0; 3]),
+			Error::<Test>::Malformed
+		);
+
+		// the accepted headers are forgotten along with the client that accepted them
+		assert_ok!(NearLiteClient::force_set_checkpoint(Origin::root(), encode(&chain.head)));
+		assert_ok!(NearLiteClient::submit_header(Origin::signed(1), encode(&chain.block_view)));
+		assert_eq!(NearLiteClient::client().unwrap().head_height(), BlockHeight(11));
+	});
+}
+
+#[test]
+fn test_failed_update_leaves_the_storage_unchanged() {
+	new_test_ext().execute_with(|| {
+		let chain = signed_header_at(BlockHeight(200));
+		assert_ok!(NearLiteClient::force_set_checkpoint(Origin::root(), encode(&chain.head)));
+		// a hash below the retained heights, as kept by a longer `RetainedHeights` before an
+		// upgrade, is pruned when the client is resumed, before the header is verified
+		RuntimeStorage::<Test>::default()
+			.insert_block_hash(BlockHeight(50), CryptoHash([1; 32]))
+			.unwrap();
+
+		let mut block_view = chain.block_view.clone();
+		block_view.approvals_after_next = vec![None; 4];
+		assert_noop!(
+			NearLiteClient::submit_header(Origin::signed(1), encode(&block_view)),
+			Error::<Test>::VerificationFailed
+		);
+		assert!(BlockHashes::<Test>::contains_key(BlockHeight(50)));
+
+		assert_ok!(NearLiteClient::submit_header(Origin::signed(1), encode(&chain.block_view)));
+		assert!(!BlockHashes::<Test>::contains_key(BlockHeight(50)));
+	});
+}
+
+#[test]
+fn test_old_epochs_are_pruned() {
+	new_test_ext().execute_with(|| {
+		let mut chain = MockNearChain::<Sha256HostFunctions>::new(4);
+		assert_ok!(NearLiteClient::force_set_checkpoint(
+			Origin::root(),
+			encode(&chain.checkpoint().0)
+		));
+		// epochs of 30 heights, so that those inserted more than 100 heights below the head go,
+		// while the client is resumed on every call
+		for _ in 0..8 {
+			let epoch_change = chain.skip_heights(29).epoch_change();
+			assert_ok!(NearLiteClient::submit_header(Origin::signed(1), encode(&epoch_change)));
+		}
+		assert_eq!(NearLiteClient::client().unwrap().head_height(), BlockHeight(241));
+
+		let epochs = (5..=9).map(|epoch| chain.epoch_id(epoch)).collect::<Vec<_>>();
+		assert_eq!(Epochs::<Test>::get(), epochs);
+		assert_eq!(EpochBlockProducers::<Test>::iter_keys().count(), epochs.len());
+		let heights = EpochHeights::<Test>::get();
+		assert_eq!(heights.iter().map(|(epoch_id, _)| *epoch_id).collect::<Vec<_>>(), epochs);
+		// inserted with the head before the one that announced them
+		assert_eq!(heights.first(), Some(&(chain.epoch_id(5), BlockHeight(91))));
+	});
+}
+
+#[test]
+fn test_block_hashes_are_pruned_from_the_lowest() {
+	new_test_ext().execute_with(|| {
+		let mut chain = MockNearChain::<Sha256HostFunctions>::new(4);
+		assert_ok!(NearLiteClient::force_set_checkpoint(
+			Origin::root(),
+			encode(&chain.checkpoint().0)
+		));
+		for _ in 0..8 {
+			let epoch_change = chain.skip_heights(29).epoch_change();
+			assert_ok!(NearLiteClient::submit_header(Origin::signed(1), encode(&epoch_change)));
+		}
+
+		// the heads at 1, 31, ..., 241, the hashes of those at most 100 heights below are kept
+		let heights = [151, 181, 211, 241].map(BlockHeight);
+		let mut indexed = BlockHashes::<Test>::iter_keys().collect::<Vec<_>>();
+		indexed.sort();
+		assert_eq!(indexed, heights);
+		assert_eq!(BlockHashBounds::<Test>::get(), Some((heights[0], heights[3])));
+		for pair in heights.windows(2) {
+			assert_eq!(NextBlockHashHeight::<Test>::get(pair[0]), Some(pair[1]));
+		}
+		assert_eq!(NextBlockHashHeight::<Test>::iter().count(), 3);
+
+		// a height in between is linked where it belongs
+		let mut storage = RuntimeStorage::<Test>::default();
+		storage.insert_block_hash(BlockHeight(190), CryptoHash([1; 32])).unwrap();
+		assert_eq!(NextBlockHashHeight::<Test>::get(heights[1]), Some(BlockHeight(190)));
+		assert_eq!(NextBlockHashHeight::<Test>::get(BlockHeight(190)), Some(heights[2]));
+		assert_eq!(storage.prune_block_hashes(BlockHeight(200)).unwrap(), 3);
+		assert_eq!(BlockHashBounds::<Test>::get(), Some((heights[2], heights[3])));
+		assert_eq!(storage.prune_block_hashes(BlockHeight(300)).unwrap(), 2);
+		assert_eq!(BlockHashBounds::<Test>::get(), None);
+		assert_eq!(BlockHashes::<Test>::iter_keys().count(), 0);
+		assert_eq!(NextBlockHashHeight::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn test_submit_transaction_proof() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(NearLiteClient::force_set_checkpoint(
+			Origin::root(),
+			encode(&signed_header().head)
+		));
+
+		let path = (0..4)
+			.map(|index| MerklePathItem {
+				hash: CryptoHash([index; 32]),
+				direction: if index % 2 == 0 { Direction::Left } else { Direction::Right },
+			})
+			.collect::<Vec<_>>();
+		let outcome = ExecutionOutcomeView {
+			logs: Vec::new(),
+			receipt_ids: vec![CryptoHash([5; 32])],
+			gas_burnt: 1,
+			tokens_burnt: 1,
+			executor_id: "bridge.near".into(),
+			status: Vec::new(),
+		};
+		let (outcome_proof, outcome_root_proof, block_outcome_root) =
+			OutcomeProofBuilder::new(CryptoHash([7; 32]), outcome)
+				.proof(path.clone())
+				.outcome_root_proof(path)
+				.build::<Sha256HostFunctions>()
+				.unwrap();
+		let transaction = OutcomeId::Transaction {
+			transaction_hash: CryptoHash([7; 32]),
+			sender_id: "bridge.near".into(),
+		};
+
+		assert_ok!(NearLiteClient::submit_transaction_proof(
+			Origin::signed(1),
+			transaction.clone(),
+			encode_outcome_proof(&outcome_proof),
+			encode(&outcome_root_proof),
+			block_outcome_root,
+		));
+		System::assert_last_event(
+			PalletEvent::TransactionVerified { id: CryptoHash([7; 32]), block_outcome_root }.into(),
+		);
+
+		// a retry is accepted without being verified again
+		let events = System::events().len();
+		assert_ok!(NearLiteClient::submit_transaction_proof(
+			Origin::signed(2),
+			transaction.clone(),
+			encode_outcome_proof(&outcome_proof),
+			encode(&outcome_root_proof),
+			block_outcome_root,
+		));
+		assert_eq!(System::events().len(), events);
+
+		assert_noop!(
+			NearLiteClient::submit_transaction_proof(
+				Origin::signed(1),
+				transaction,
+				encode_outcome_proof(&outcome_proof),
+				encode(&outcome_root_proof),
+				CryptoHash::default(),
+			),
+			Error::<Test>::VerificationFailed
+		);
+		// the outcome of the transaction does not pass for the one of a receipt of another account
+		let receipt = OutcomeId::Receipt {
+			receipt_id: CryptoHash([7; 32]),
+			receiver_id: "alice.near".into(),
+		};
+		assert_noop!(
+			NearLiteClient::submit_transaction_proof(
+				Origin::signed(1),
+				receipt,
+				encode_outcome_proof(&outcome_proof),
+				encode(&outcome_root_proof),
+				block_outcome_root,
+			),
+			Error::<Test>::VerificationFailed
+		);
+	});
+}